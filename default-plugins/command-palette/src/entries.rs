@@ -0,0 +1,493 @@
+use clap::{Command, FromArgMatches, Subcommand};
+use std::collections::BTreeMap;
+use std::path::Path;
+use zellij_tile::prelude::actions::Action;
+use zellij_tile::prelude::plugin_api::action::ProtobufAction;
+use zellij_tile::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Category {
+    Action,
+    Keybind,
+    Command,
+    Tab,
+    Pane,
+    Session,
+}
+
+impl Category {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Category::Action => "action",
+            Category::Keybind => "keybind",
+            Category::Command => "command",
+            Category::Tab => "tab",
+            Category::Pane => "pane",
+            Category::Session => "session",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Target {
+    // usage is only set for entries that require arguments
+    CliAction { name: String, usage: Option<String> },
+    Actions(Vec<Action>),
+    RegisteredCommand { name: String, usage: Option<String> },
+    Tab(usize), // tab position
+    Pane(PaneId),
+    Session(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub id: String, // stable across palette instances, used to remember recently used entries
+    pub category: Category,
+    pub title: String,
+    pub description: String,
+    pub target: Target,
+}
+
+impl Entry {
+    pub fn searchable_text(&self) -> String {
+        if self.description.is_empty() {
+            format!("{}: {}", self.category.label(), self.title)
+        } else {
+            format!(
+                "{}: {} - {}",
+                self.category.label(),
+                self.title,
+                self.description
+            )
+        }
+    }
+    pub fn usage(&self) -> Option<&String> {
+        match &self.target {
+            Target::CliAction { usage, .. } | Target::RegisteredCommand { usage, .. } => {
+                usage.as_ref()
+            },
+            _ => None,
+        }
+    }
+    pub fn run(&self, arguments: &str, cwd: &Path) -> Result<(), String> {
+        match &self.target {
+            Target::CliAction { name, .. } => {
+                let actions = cli_action_to_actions(name, arguments, cwd)?;
+                close_self();
+                run_actions(actions);
+            },
+            Target::Actions(actions) => {
+                close_self();
+                run_actions(actions.clone());
+            },
+            Target::RegisteredCommand { name, .. } => {
+                let args = parse_command_arguments(arguments)?;
+                close_self();
+                invoke_registered_command(name, args);
+            },
+            Target::Tab(position) => {
+                close_self();
+                go_to_tab(*position as u32);
+            },
+            Target::Pane(pane_id) => {
+                close_self();
+                focus_pane_with_id(*pane_id, true);
+            },
+            Target::Session(session_name) => {
+                close_self();
+                switch_session(Some(session_name));
+            },
+        }
+        Ok(())
+    }
+}
+
+pub fn cli_action_entries() -> Vec<Entry> {
+    let mut entries = vec![];
+    for subcommand in cli_actions_command().get_subcommands_mut() {
+        if subcommand.is_hide_set() {
+            continue;
+        }
+        let name = subcommand.get_name().to_owned();
+        let description = subcommand
+            .get_about()
+            .map(|about| about.trim_end_matches('.').to_owned())
+            .unwrap_or_default();
+        let requires_arguments = subcommand.get_arguments().any(|arg| arg.is_required_set());
+        let usage = if requires_arguments {
+            Some(
+                subcommand
+                    .render_usage()
+                    .lines()
+                    .last()
+                    .map(|usage| usage.trim().to_owned())
+                    .unwrap_or_default(),
+            )
+        } else {
+            None
+        };
+        entries.push(Entry {
+            id: format!("action:{}", name),
+            category: Category::Action,
+            title: name.clone(),
+            description,
+            target: Target::CliAction { name, usage },
+        });
+    }
+    entries
+}
+
+pub fn keybind_entries(mode_info: &ModeInfo) -> Vec<Entry> {
+    // the same binding is often shared between modes, so we list it once with all its modes
+    let mut bindings: BTreeMap<(String, String), (Vec<Action>, Vec<InputMode>)> = BTreeMap::new();
+    for (mode, keybinds) in &mode_info.keybinds {
+        for (key, actions) in keybinds {
+            let actions_description = describe_actions(actions);
+            if actions_description.is_empty() || !can_be_run_from_plugin(actions) {
+                continue;
+            }
+            bindings
+                .entry((actions_description, key.to_string()))
+                .or_insert_with(|| (actions.clone(), vec![]))
+                .1
+                .push(*mode);
+        }
+    }
+    let mode_count = mode_info.keybinds.len();
+    bindings
+        .into_iter()
+        .map(|((actions_description, key), (actions, modes))| {
+            let modes = if modes.len() == mode_count {
+                "all modes".to_owned()
+            } else {
+                modes
+                    .iter()
+                    .map(|mode| format!("{:?}", mode))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            Entry {
+                id: format!("keybind:{}", actions_description),
+                category: Category::Keybind,
+                title: actions_description,
+                description: format!("<{}> in {}", key, modes),
+                target: Target::Actions(actions),
+            }
+        })
+        .collect()
+}
+
+pub fn registered_command_entries(registered_commands: &[RegisteredCommand]) -> Vec<Entry> {
+    registered_commands
+        .iter()
+        .map(|registered_command| {
+            let command = &registered_command.command;
+            let usage = if command.args.iter().any(|arg| arg.required) {
+                Some(
+                    command
+                        .args
+                        .iter()
+                        .map(|arg| {
+                            if arg.required {
+                                format!("{}=<{}>", arg.name, arg.name.to_uppercase())
+                            } else {
+                                format!("[{}=<{}>]", arg.name, arg.name.to_uppercase())
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                )
+            } else {
+                None
+            };
+            Entry {
+                id: format!("command:{}", command.name),
+                category: Category::Command,
+                title: command.name.clone(),
+                description: command.description.clone(),
+                target: Target::RegisteredCommand {
+                    name: command.name.clone(),
+                    usage,
+                },
+            }
+        })
+        .collect()
+}
+
+pub fn tab_entries(tabs: &[TabInfo]) -> Vec<Entry> {
+    tabs.iter()
+        .map(|tab| Entry {
+            id: format!("tab:{}", tab.name),
+            category: Category::Tab,
+            title: tab.name.clone(),
+            description: format!("#{}", tab.position + 1),
+            target: Target::Tab(tab.position),
+        })
+        .collect()
+}
+
+pub fn pane_entries(
+    pane_manifest: &PaneManifest,
+    tabs: &[TabInfo],
+    own_plugin_id: u32,
+) -> Vec<Entry> {
+    let mut entries = vec![];
+    for (tab_position, panes) in &pane_manifest.panes {
+        let tab_name = tabs
+            .iter()
+            .find(|tab| tab.position == *tab_position)
+            .map(|tab| tab.name.clone())
+            .unwrap_or_else(|| format!("Tab #{}", tab_position + 1));
+        for pane in panes {
+            if !pane.is_selectable || (pane.is_plugin && pane.id == own_plugin_id) {
+                continue;
+            }
+            let pane_id = if pane.is_plugin {
+                PaneId::Plugin(pane.id)
+            } else {
+                PaneId::Terminal(pane.id)
+            };
+            entries.push(Entry {
+                id: format!("pane:{}", pane.title),
+                category: Category::Pane,
+                title: pane.title.clone(),
+                description: tab_name.clone(),
+                target: Target::Pane(pane_id),
+            });
+        }
+    }
+    entries
+}
+
+pub fn session_entries(
+    live_sessions: &[SessionInfo],
+    resurrectable_sessions: &[(String, std::time::Duration)],
+) -> Vec<Entry> {
+    let live_sessions = live_sessions
+        .iter()
+        .filter(|session| !session.is_current_session)
+        .map(|session| Entry {
+            id: format!("session:{}", session.name),
+            category: Category::Session,
+            title: session.name.clone(),
+            description: format!("{} connected", session.connected_clients),
+            target: Target::Session(session.name.clone()),
+        });
+    let resurrectable_sessions = resurrectable_sessions.iter().map(|(name, _)| Entry {
+        id: format!("session:{}", name),
+        category: Category::Session,
+        title: name.clone(),
+        description: "exited, resurrect".to_owned(),
+        target: Target::Session(name.clone()),
+    });
+    live_sessions.chain(resurrectable_sessions).collect()
+}
+
+pub fn sort_by_recency(entries: &mut [Entry], recently_used: &[String]) {
+    // recently used entries come first, the rest keep their category order
+    entries.sort_by_key(|entry| {
+        recently_used
+            .iter()
+            .position(|id| id == &entry.id)
+            .unwrap_or(recently_used.len())
+    });
+}
+
+fn cli_actions_command() -> Command<'static> {
+    CliAction::augment_subcommands(Command::new("action").no_binary_name(true))
+}
+
+fn cli_action_to_actions(name: &str, arguments: &str, cwd: &Path) -> Result<Vec<Action>, String> {
+    let arguments = shell_words::split(arguments).map_err(|e| e.to_string())?;
+    let matches = cli_actions_command()
+        .try_get_matches_from(std::iter::once(name.to_owned()).chain(arguments))
+        .map_err(clap_error_message)?;
+    let cli_action = CliAction::from_arg_matches(&matches).map_err(clap_error_message)?;
+    let cwd = cwd.to_path_buf();
+    let actions = Action::actions_from_cli(cli_action, Box::new(move || cwd.clone()), None)?;
+    if !can_be_run_from_plugin(&actions) {
+        return Err(format!("{} cannot be run from the command palette", name));
+    }
+    Ok(actions)
+}
+
+fn can_be_run_from_plugin(actions: &[Action]) -> bool {
+    // plugins send actions to zellij over protobuf, which does not support all of them
+    actions
+        .iter()
+        .all(|action| ProtobufAction::try_from(action.clone()).is_ok())
+}
+
+fn clap_error_message(error: clap::Error) -> String {
+    // the first line holds the actual error, the rest is usage information
+    error
+        .to_string()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("error: ")
+        .to_owned()
+}
+
+fn parse_command_arguments(arguments: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut args = BTreeMap::new();
+    for argument in shell_words::split(arguments).map_err(|e| e.to_string())? {
+        match argument.split_once('=') {
+            Some((key, value)) => {
+                args.insert(key.to_owned(), value.to_owned());
+            },
+            None => {
+                return Err(format!(
+                    "Malformed argument: {}, expecting the form key=value",
+                    argument
+                ))
+            },
+        }
+    }
+    Ok(args)
+}
+
+fn describe_actions(actions: &[Action]) -> String {
+    actions
+        .iter()
+        .filter_map(|action| action.to_kdl())
+        .map(|node| {
+            node.to_string()
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry_with_id(id: &str) -> Entry {
+        Entry {
+            id: id.to_owned(),
+            category: Category::Action,
+            title: id.to_owned(),
+            description: String::new(),
+            target: Target::Actions(vec![]),
+        }
+    }
+
+    #[test]
+    fn cli_action_is_parsed_with_its_arguments() {
+        let actions = cli_action_to_actions("go-to-tab", "3", Path::new("/"));
+        assert_eq!(actions, Ok(vec![Action::GoToTab(3)]));
+    }
+
+    #[test]
+    fn cli_action_arguments_are_split_like_a_shell() {
+        let actions = cli_action_to_actions("rename-tab", "'my tab'", Path::new("/"));
+        assert_eq!(
+            actions,
+            Ok(vec![
+                Action::TabNameInput(vec![0]),
+                Action::TabNameInput("my tab".as_bytes().to_vec()),
+            ])
+        );
+    }
+
+    #[test]
+    fn cli_action_missing_a_required_argument_is_an_error() {
+        let actions = cli_action_to_actions("go-to-tab", "", Path::new("/"));
+        assert!(actions.is_err());
+    }
+
+    #[test]
+    fn cli_action_with_unbalanced_quotes_is_an_error() {
+        let actions = cli_action_to_actions("rename-tab", "'my tab", Path::new("/"));
+        assert!(actions.is_err());
+    }
+
+    #[test]
+    fn cli_action_that_plugins_cannot_run_is_an_error() {
+        let actions = cli_action_to_actions("dump-layout", "", Path::new("/"));
+        assert_eq!(
+            actions,
+            Err("dump-layout cannot be run from the command palette".to_owned())
+        );
+    }
+
+    #[test]
+    fn command_arguments_are_parsed_as_key_value_pairs() {
+        let args = parse_command_arguments("title=\"my note\" folder=work").unwrap();
+        let expected: BTreeMap<String, String> = [
+            ("title".to_owned(), "my note".to_owned()),
+            ("folder".to_owned(), "work".to_owned()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn command_argument_without_a_value_is_an_error() {
+        let args = parse_command_arguments("title=note folder");
+        assert_eq!(
+            args,
+            Err("Malformed argument: folder, expecting the form key=value".to_owned())
+        );
+    }
+
+    #[test]
+    fn keybinds_shared_between_modes_are_listed_once() {
+        let fullscreen_key = KeyWithModifier::new(BareKey::Char('f')).with_alt_modifier();
+        let quit_key = KeyWithModifier::new(BareKey::Char('q')).with_ctrl_modifier();
+        let fullscreen = (fullscreen_key.clone(), vec![Action::ToggleFocusFullscreen]);
+        let quit = (quit_key.clone(), vec![Action::Quit]);
+        let mode_info = ModeInfo {
+            keybinds: vec![
+                (InputMode::Normal, vec![fullscreen.clone(), quit.clone()]),
+                (InputMode::Locked, vec![fullscreen.clone()]),
+                (InputMode::Pane, vec![fullscreen, quit]),
+            ],
+            ..Default::default()
+        };
+        let entries = keybind_entries(&mode_info);
+        let descriptions: Vec<String> = entries
+            .iter()
+            .map(|entry| entry.description.clone())
+            .collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                format!("<{}> in Normal, Pane", quit_key),
+                format!("<{}> in all modes", fullscreen_key),
+            ]
+        );
+    }
+
+    #[test]
+    fn keybinds_that_plugins_cannot_run_are_not_listed() {
+        let mode_info = ModeInfo {
+            keybinds: vec![(
+                InputMode::Normal,
+                vec![(
+                    KeyWithModifier::new(BareKey::Char('c')).with_ctrl_modifier(),
+                    vec![Action::Copy],
+                )],
+            )],
+            ..Default::default()
+        };
+        assert!(keybind_entries(&mode_info).is_empty());
+    }
+
+    #[test]
+    fn recently_used_entries_come_first_in_recency_order() {
+        let mut entries = vec![
+            entry_with_id("action:a"),
+            entry_with_id("action:b"),
+            entry_with_id("tab:c"),
+            entry_with_id("pane:d"),
+        ];
+        sort_by_recency(&mut entries, &["pane:d".to_owned(), "action:b".to_owned()]);
+        let ids: Vec<&str> = entries.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["pane:d", "action:b", "action:a", "tab:c"]);
+    }
+}
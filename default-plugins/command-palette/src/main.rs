@@ -0,0 +1,452 @@
+mod entries;
+
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use zellij_tile::prelude::*;
+
+use entries::{
+    cli_action_entries, keybind_entries, pane_entries, registered_command_entries, session_entries,
+    sort_by_recency, tab_entries, Entry,
+};
+
+// remembered between sessions in the plugin store, newline separated entry ids
+const RECENTLY_USED_STORE_KEY: &str = "recently_used";
+const MAX_RECENTLY_USED: usize = 10;
+
+struct SearchResult {
+    entry_index: usize,
+    indices: Vec<usize>,
+}
+
+struct ArgumentPrompt {
+    entry_index: usize,
+    arguments: String,
+}
+
+#[derive(Default)]
+struct State {
+    own_plugin_id: u32,
+    cwd: PathBuf,
+    cli_actions: Vec<Entry>,
+    keybinds: Vec<Entry>,
+    registered_commands: Vec<Entry>,
+    tabs: Vec<TabInfo>,
+    pane_manifest: PaneManifest,
+    sessions: Vec<Entry>,
+    entries: Vec<Entry>,
+    recently_used: Vec<String>, // entry ids, most recent first
+    search_term: String,
+    search_results: Vec<SearchResult>,
+    selected_index: usize,
+    argument_prompt: Option<ArgumentPrompt>,
+    error: Option<String>,
+}
+
+register_plugin!(State);
+
+impl ZellijPlugin for State {
+    fn load(&mut self, _configuration: BTreeMap<String, String>) {
+        let plugin_ids = get_plugin_ids();
+        self.own_plugin_id = plugin_ids.plugin_id;
+        self.cwd = plugin_ids.initial_cwd;
+        self.recently_used = store_get(RECENTLY_USED_STORE_KEY, StoreScope::Global)
+            .ok()
+            .flatten()
+            .map(|recently_used| recently_used.lines().map(|id| id.to_owned()).collect())
+            .unwrap_or_default();
+        self.cli_actions = cli_action_entries();
+        subscribe(&[
+            EventType::ModeUpdate,
+            EventType::TabUpdate,
+            EventType::PaneUpdate,
+            EventType::SessionUpdate,
+            EventType::RegisteredCommandsUpdate,
+            EventType::Key,
+        ]);
+        list_registered_commands();
+        rename_plugin_pane(self.own_plugin_id, "Command Palette");
+        self.update_entries();
+    }
+    fn update(&mut self, event: Event) -> bool {
+        let mut should_render = false;
+        match event {
+            Event::ModeUpdate(mode_info) => {
+                // this is also sent when the configuration changes at runtime, so the listed
+                // keybindings are always the live ones
+                self.keybinds = keybind_entries(&mode_info);
+                self.update_entries();
+                should_render = true;
+            },
+            Event::TabUpdate(tabs) => {
+                self.tabs = tabs;
+                self.update_entries();
+                should_render = true;
+            },
+            Event::PaneUpdate(pane_manifest) => {
+                self.pane_manifest = pane_manifest;
+                self.update_entries();
+                should_render = true;
+            },
+            Event::SessionUpdate(live_sessions, resurrectable_sessions) => {
+                self.sessions = session_entries(&live_sessions, &resurrectable_sessions);
+                self.update_entries();
+                should_render = true;
+            },
+            Event::RegisteredCommandsUpdate(registered_commands) => {
+                self.registered_commands = registered_command_entries(&registered_commands);
+                self.update_entries();
+                should_render = true;
+            },
+            Event::Key(key) => {
+                should_render = if self.argument_prompt.is_some() {
+                    self.handle_argument_prompt_key(key)
+                } else {
+                    self.handle_search_key(key)
+                };
+            },
+            _ => {},
+        }
+        should_render
+    }
+    fn render(&mut self, rows: usize, cols: usize) {
+        match &self.argument_prompt {
+            Some(argument_prompt) => self.render_argument_prompt(argument_prompt, cols),
+            None => self.render_search(cols),
+        }
+        let list_y = 2;
+        let max_list_items = rows.saturating_sub(4); // 2 top padding, 2 bottom padding
+        let (more_above, more_below, list) = self.render_list(cols, max_list_items);
+        self.render_more_indication(more_above, more_below, cols, list_y, list.len());
+        print_nested_list_with_coordinates(list, 0, list_y, Some(cols), None);
+        let help_y = rows.saturating_sub(1);
+        match &self.error {
+            Some(error) => self.render_error(error, help_y, cols),
+            None => self.render_help(help_y, cols),
+        }
+    }
+}
+
+impl State {
+    fn update_entries(&mut self) {
+        let selected_id = self.selected_entry().map(|entry| entry.id.clone());
+        let panes = pane_entries(&self.pane_manifest, &self.tabs, self.own_plugin_id);
+        let mut entries: Vec<Entry> = self
+            .cli_actions
+            .iter()
+            .chain(self.keybinds.iter())
+            .chain(self.registered_commands.iter())
+            .chain(tab_entries(&self.tabs).iter())
+            .chain(panes.iter())
+            .chain(self.sessions.iter())
+            .cloned()
+            .collect();
+        sort_by_recency(&mut entries, &self.recently_used);
+        self.entries = entries;
+        self.update_search_results();
+        if let Some(selected_index) = selected_id.and_then(|selected_id| {
+            self.search_results
+                .iter()
+                .position(|result| self.entries[result.entry_index].id == selected_id)
+        }) {
+            self.selected_index = selected_index;
+        }
+    }
+    fn update_search_results(&mut self) {
+        if self.search_term.is_empty() {
+            self.search_results = (0..self.entries.len())
+                .map(|entry_index| SearchResult {
+                    entry_index,
+                    indices: vec![],
+                })
+                .collect();
+        } else {
+            let matcher = SkimMatcherV2::default().use_cache(true);
+            let mut matches: Vec<(i64, SearchResult)> = self
+                .entries
+                .iter()
+                .enumerate()
+                .filter_map(|(entry_index, entry)| {
+                    matcher
+                        .fuzzy_indices(&entry.searchable_text(), &self.search_term)
+                        .map(|(score, indices)| {
+                            (
+                                score,
+                                SearchResult {
+                                    entry_index,
+                                    indices,
+                                },
+                            )
+                        })
+                })
+                .collect();
+            // entries are already sorted by recency, and this sort is stable
+            matches.sort_by(|(a, _), (b, _)| b.cmp(a));
+            self.search_results = matches.into_iter().map(|(_, result)| result).collect();
+        }
+        self.selected_index = self
+            .selected_index
+            .min(self.search_results.len().saturating_sub(1));
+    }
+    fn selected_entry(&self) -> Option<&Entry> {
+        self.search_results
+            .get(self.selected_index)
+            .and_then(|result| self.entries.get(result.entry_index))
+    }
+    fn remember_as_recently_used(&mut self, entry_id: &str) {
+        self.recently_used.retain(|id| id != entry_id);
+        self.recently_used.insert(0, entry_id.to_owned());
+        self.recently_used.truncate(MAX_RECENTLY_USED);
+        store_set(
+            RECENTLY_USED_STORE_KEY,
+            &self.recently_used.join("\n"),
+            StoreScope::Global,
+        );
+    }
+    fn run_entry(&mut self, entry_index: usize, arguments: &str) {
+        let Some(entry) = self.entries.get(entry_index).cloned() else {
+            return;
+        };
+        match entry.run(arguments, &self.cwd) {
+            Ok(()) => self.remember_as_recently_used(&entry.id),
+            Err(e) => self.error = Some(e),
+        }
+    }
+    fn handle_search_key(&mut self, key: KeyWithModifier) -> bool {
+        let mut should_render = true;
+        self.error = None;
+        match key.bare_key {
+            BareKey::Char(character) if key.has_no_modifiers() => {
+                self.search_term.push(character);
+                self.selected_index = 0;
+                self.update_search_results();
+            },
+            BareKey::Backspace if key.has_no_modifiers() => {
+                self.search_term.pop();
+                self.selected_index = 0;
+                self.update_search_results();
+            },
+            BareKey::Down if key.has_no_modifiers() => {
+                if self.selected_index + 1 < self.search_results.len() {
+                    self.selected_index += 1;
+                } else {
+                    self.selected_index = 0;
+                }
+            },
+            BareKey::Up if key.has_no_modifiers() => {
+                if self.selected_index > 0 {
+                    self.selected_index -= 1;
+                } else {
+                    self.selected_index = self.search_results.len().saturating_sub(1);
+                }
+            },
+            BareKey::Enter if key.has_no_modifiers() => {
+                if let Some(entry_index) = self
+                    .search_results
+                    .get(self.selected_index)
+                    .map(|result| result.entry_index)
+                {
+                    if self.entries[entry_index].usage().is_some() {
+                        self.argument_prompt = Some(ArgumentPrompt {
+                            entry_index,
+                            arguments: String::new(),
+                        });
+                    } else {
+                        self.run_entry(entry_index, "");
+                    }
+                }
+            },
+            BareKey::Esc if key.has_no_modifiers() => {
+                if self.search_term.is_empty() {
+                    close_self();
+                } else {
+                    self.search_term.clear();
+                    self.selected_index = 0;
+                    self.update_search_results();
+                }
+            },
+            BareKey::Char('c') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                close_self();
+            },
+            _ => should_render = false,
+        }
+        should_render
+    }
+    fn handle_argument_prompt_key(&mut self, key: KeyWithModifier) -> bool {
+        let mut should_render = true;
+        self.error = None;
+        match key.bare_key {
+            BareKey::Char(character) if key.has_no_modifiers() => {
+                if let Some(argument_prompt) = self.argument_prompt.as_mut() {
+                    argument_prompt.arguments.push(character);
+                }
+            },
+            BareKey::Backspace if key.has_no_modifiers() => {
+                if let Some(argument_prompt) = self.argument_prompt.as_mut() {
+                    argument_prompt.arguments.pop();
+                }
+            },
+            BareKey::Enter if key.has_no_modifiers() => {
+                if let Some(argument_prompt) = self.argument_prompt.take() {
+                    self.run_entry(argument_prompt.entry_index, &argument_prompt.arguments);
+                    if self.error.is_some() {
+                        // keep the prompt open so the arguments can be fixed
+                        self.argument_prompt = Some(argument_prompt);
+                    }
+                }
+            },
+            BareKey::Esc if key.has_no_modifiers() => {
+                self.argument_prompt = None;
+            },
+            BareKey::Char('c') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                close_self();
+            },
+            _ => should_render = false,
+        }
+        should_render
+    }
+    fn render_search(&self, cols: usize) {
+        let prompt = " SEARCH: ";
+        let search_term = truncate_string_start(
+            &self.search_term,
+            cols.saturating_sub(prompt.chars().count() + 1), // 1 for the cursor
+        );
+        let text = Text::new(format!("{}{}_", prompt, search_term))
+            .color_range(2, ..prompt.chars().count())
+            .color_range(3, prompt.chars().count()..);
+        print_text_with_coordinates(text, 0, 0, None, None);
+    }
+    fn render_argument_prompt(&self, argument_prompt: &ArgumentPrompt, cols: usize) {
+        let Some(entry) = self.entries.get(argument_prompt.entry_index) else {
+            return;
+        };
+        let prompt = format!(" {} ", entry.title);
+        let arguments = truncate_string_start(
+            &argument_prompt.arguments,
+            cols.saturating_sub(prompt.chars().count() + 1), // 1 for the cursor
+        );
+        let text = Text::new(format!("{}{}_", prompt, arguments))
+            .color_range(2, ..prompt.chars().count())
+            .color_range(3, prompt.chars().count()..);
+        print_text_with_coordinates(text, 0, 0, None, None);
+        if let Some(usage) = entry.usage() {
+            let usage = format!(" Usage: {}", usage);
+            let usage_len = usage.chars().count();
+            let text = Text::new(usage).color_range(1, ..usage_len.min(8));
+            print_text_with_coordinates(text, 0, 1, Some(cols), None);
+        }
+    }
+    fn render_list(
+        &self,
+        cols: usize,
+        max_list_items: usize,
+    ) -> (usize, usize, Vec<NestedListItem>) {
+        let list_start = if self.search_results.len() > max_list_items {
+            self.selected_index
+                .saturating_sub(max_list_items / 2)
+                .min(self.search_results.len().saturating_sub(max_list_items))
+        } else {
+            0
+        };
+        let list_end = (list_start + max_list_items).min(self.search_results.len());
+        let max_item_len = cols.saturating_sub(3); // 3 for the bulletin
+        let mut items = vec![];
+        for (i, search_result) in self.search_results[list_start..list_end].iter().enumerate() {
+            let entry = &self.entries[search_result.entry_index];
+            let is_selected = list_start + i == self.selected_index;
+            items.push(self.render_entry(entry, &search_result.indices, is_selected, max_item_len));
+        }
+        let more_above = list_start;
+        let more_below = self.search_results.len().saturating_sub(list_end);
+        (more_above, more_below, items)
+    }
+    fn render_entry(
+        &self,
+        entry: &Entry,
+        indices: &[usize],
+        is_selected: bool,
+        max_item_len: usize,
+    ) -> NestedListItem {
+        let text = entry.searchable_text();
+        let text: String = text.chars().take(max_item_len).collect();
+        let text_len = text.chars().count();
+        let category_len = entry.category.label().chars().count() + 1; // 1 for the colon
+        let title_end = category_len + 1 + entry.title.chars().count();
+        let mut item = NestedListItem::new(text)
+            .color_range(2, ..category_len.min(text_len))
+            .color_range(0, category_len.min(text_len)..title_end.min(text_len))
+            .color_range(1, title_end.min(text_len)..)
+            .color_indices(
+                3,
+                indices.iter().copied().filter(|i| *i < text_len).collect(),
+            );
+        if is_selected {
+            item = item.selected();
+        }
+        item
+    }
+    fn render_more_indication(
+        &self,
+        more_above: usize,
+        more_below: usize,
+        cols: usize,
+        list_y: usize,
+        list_len: usize,
+    ) {
+        if more_above > 0 {
+            let text = format!("↑ [+{}]", more_above);
+            let text_len = text.chars().count();
+            print_text_with_coordinates(
+                Text::new(text).color_range(1, ..),
+                cols.saturating_sub(text_len),
+                list_y.saturating_sub(1),
+                None,
+                None,
+            );
+        }
+        if more_below > 0 {
+            let text = format!("↓ [+{}]", more_below);
+            let text_len = text.chars().count();
+            print_text_with_coordinates(
+                Text::new(text).color_range(1, ..),
+                cols.saturating_sub(text_len),
+                list_y + list_len,
+                None,
+                None,
+            );
+        }
+    }
+    fn render_error(&self, error: &str, y: usize, cols: usize) {
+        let text = Text::new(format!("ERROR: {}", error)).color_range(3, ..=5);
+        print_text_with_coordinates(text, 0, y, Some(cols), None);
+    }
+    fn render_help(&self, y: usize, cols: usize) {
+        let (text, ranges): (&str, &[std::ops::RangeInclusive<usize>]) =
+            if self.argument_prompt.is_some() {
+                (
+                    "Help: <ENTER> - Run, <ESC> - Back, <Ctrl c> - Exit",
+                    &[6..=12, 21..=25, 35..=42],
+                )
+            } else {
+                (
+                    "Help: <↓↑> - Navigate, <ENTER> - Run, <ESC> - Clear/Exit",
+                    &[6..=9, 23..=29, 38..=42],
+                )
+            };
+        let mut text = Text::new(text);
+        for range in ranges {
+            text = text.color_range(3, range.clone());
+        }
+        print_text_with_coordinates(text, 0, y, Some(cols), None);
+    }
+}
+
+fn truncate_string_start(string_to_truncate: &str, max_len: usize) -> String {
+    let mut truncated_string = string_to_truncate.to_owned();
+    let count_to_remove = truncated_string.chars().count().saturating_sub(max_len) + 5;
+    if truncated_string.chars().count() > max_len {
+        truncated_string.replace_range(0..count_to_remove, "[...]");
+    }
+    truncated_string
+}
@@ -479,6 +479,12 @@ impl KeybindProcessor {
                             Action::SearchToggleOption(actions::SearchOption::WholeWord)
                         )
                     },
+                    |action: &Action| {
+                        matches!(
+                            action,
+                            Action::SearchToggleOption(actions::SearchOption::Regex)
+                        )
+                    },
                 ];
                 Self::find_predetermined_actions(mode_info, mode, ordered_predicates)
             },
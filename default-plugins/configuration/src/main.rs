@@ -20,6 +20,11 @@ pub static POSSIBLE_MODIFIERS: [KeyModifier; 4] = [
     KeyModifier::Shift,
 ];
 
+// the last screen the user was on is remembered in the plugin store
+const SCREEN_STORE_KEY: &str = "screen";
+const PRESETS_SCREEN: &str = "presets";
+const REBIND_LEADERS_SCREEN: &str = "rebind_leaders";
+
 #[derive(Debug)]
 enum Screen {
     RebindLeaders(RebindLeadersScreen),
@@ -109,6 +114,14 @@ impl ZellijPlugin for State {
             resize_focused_pane(Resize::Increase);
             resize_focused_pane(Resize::Increase);
         } else {
+            if store_get(SCREEN_STORE_KEY, StoreScope::Global)
+                .ok()
+                .flatten()
+                .as_deref()
+                == Some(PRESETS_SCREEN)
+            {
+                self.current_screen = Screen::Presets(Default::default());
+            }
             rename_plugin_pane(own_plugin_id, "Configuration");
         }
     }
@@ -215,11 +228,13 @@ impl State {
         match &self.current_screen {
             Screen::RebindLeaders(_) => {
                 self.current_screen = Screen::Presets(Default::default());
+                store_set(SCREEN_STORE_KEY, PRESETS_SCREEN, StoreScope::Global);
             },
             Screen::Presets(_) => {
                 self.current_screen = Screen::RebindLeaders(
                     RebindLeadersScreen::default().with_mode_info(self.latest_mode_info.clone()),
                 );
+                store_set(SCREEN_STORE_KEY, REBIND_LEADERS_SCREEN, StoreScope::Global);
             },
         }
         if let Some(mode_info) = &self.latest_mode_info {
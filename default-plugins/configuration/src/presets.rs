@@ -104,6 +104,7 @@ keybinds clear-defaults=true {{
         bind "c" {{ SearchToggleOption "CaseSensitivity"; }}
         bind "w" {{ SearchToggleOption "Wrap"; }}
         bind "o" {{ SearchToggleOption "WholeWord"; }}
+        bind "r" {{ SearchToggleOption "Regex"; }}
     }}
     entersearch {{
         bind "Ctrl c" "Esc" {{ SwitchToMode "Scroll"; }}
@@ -155,6 +156,13 @@ keybinds clear-defaults=true {{
             }};
             SwitchToMode "Locked"
         }}
+        bind "m" {{
+            LaunchOrFocusPlugin "command-palette" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Locked"
+        }}
     }}
     shared_except "locked" "renametab" "renamepane" {{
         bind "{primary_modifier} g" {{ SwitchToMode "Locked"; }}
@@ -319,6 +327,7 @@ keybinds clear-defaults=true {{
         bind "c" {{ SearchToggleOption "CaseSensitivity"; }}
         bind "w" {{ SearchToggleOption "Wrap"; }}
         bind "o" {{ SearchToggleOption "WholeWord"; }}
+        bind "r" {{ SearchToggleOption "Regex"; }}
     }}
     entersearch {{
         bind "Ctrl c" "Esc" {{ SwitchToMode "Scroll"; }}
@@ -371,6 +380,13 @@ keybinds clear-defaults=true {{
             }};
             SwitchToMode "Normal"
         }}
+        bind "m" {{
+            LaunchOrFocusPlugin "command-palette" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
     }}
     tmux {{
         bind "[" {{ SwitchToMode "Scroll"; }}
@@ -537,6 +553,7 @@ keybinds clear-defaults=true {{
         bind "c" {{ SearchToggleOption "CaseSensitivity"; }}
         bind "w" {{ SearchToggleOption "Wrap"; }}
         bind "o" {{ SearchToggleOption "WholeWord"; }}
+        bind "r" {{ SearchToggleOption "Regex"; }}
     }}
     entersearch {{
         bind "Ctrl c" "Esc" {{ SwitchToMode "Scroll"; }}
@@ -587,6 +604,13 @@ keybinds clear-defaults=true {{
             }};
             SwitchToMode "Normal"
         }}
+        bind "m" {{
+            LaunchOrFocusPlugin "command-palette" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
     }}
     tmux {{
         bind "[" {{ SwitchToMode "Scroll"; }}
@@ -732,6 +756,7 @@ keybinds clear-defaults=true {{
         bind "c" {{ SearchToggleOption "CaseSensitivity"; }}
         bind "w" {{ SearchToggleOption "Wrap"; }}
         bind "o" {{ SearchToggleOption "WholeWord"; }}
+        bind "r" {{ SearchToggleOption "Regex"; }}
     }}
     entersearch {{
         bind "Ctrl c" "Esc" {{ SwitchToMode "Scroll"; }}
@@ -784,6 +809,13 @@ keybinds clear-defaults=true {{
             }};
             SwitchToMode "Normal"
         }}
+        bind "m" {{
+            LaunchOrFocusPlugin "command-palette" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
     }}
     tmux {{
         bind "[" {{ SwitchToMode "Scroll"; }}
@@ -931,6 +963,7 @@ keybinds clear-defaults=true {{
         bind "c" {{ SearchToggleOption "CaseSensitivity"; }}
         bind "w" {{ SearchToggleOption "Wrap"; }}
         bind "o" {{ SearchToggleOption "WholeWord"; }}
+        bind "r" {{ SearchToggleOption "Regex"; }}
     }}
     entersearch {{
         bind "Ctrl c" "Esc" {{ SwitchToMode "Scroll"; }}
@@ -981,6 +1014,13 @@ keybinds clear-defaults=true {{
             }};
             SwitchToMode "Normal"
         }}
+        bind "m" {{
+            LaunchOrFocusPlugin "command-palette" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
     }}
     tmux {{
         bind "[" {{ SwitchToMode "Scroll"; }}
@@ -1121,6 +1161,7 @@ keybinds clear-defaults=true {{
         bind "c" {{ SearchToggleOption "CaseSensitivity"; }}
         bind "w" {{ SearchToggleOption "Wrap"; }}
         bind "o" {{ SearchToggleOption "WholeWord"; }}
+        bind "r" {{ SearchToggleOption "Regex"; }}
     }}
     entersearch {{
         bind "Ctrl c" "Esc" {{ SwitchToMode "Scroll"; }}
@@ -1173,6 +1214,13 @@ keybinds clear-defaults=true {{
             }};
             SwitchToMode "Normal"
         }}
+        bind "m" {{
+            LaunchOrFocusPlugin "command-palette" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
     }}
     tmux {{
         bind "[" {{ SwitchToMode "Scroll"; }}
@@ -15,6 +15,7 @@ struct State {
     received_payload: Option<String>,
     configuration: BTreeMap<String, String>,
     message_to_plugin_payload: Option<String>,
+    handed_over_state: Option<String>,
 }
 
 #[derive(Default, Serialize, Deserialize)]
@@ -59,6 +60,7 @@ impl ZellijPlugin for State {
             PermissionType::ReadCliPipes,
             PermissionType::MessageAndLaunchOtherPlugins,
             PermissionType::Reconfigure,
+            PermissionType::FullHdAccess,
         ]);
         self.configuration = configuration;
         subscribe(&[
@@ -559,6 +561,7 @@ impl ZellijPlugin for State {
         should_render
     }
     fn pipe(&mut self, pipe_message: PipeMessage) -> bool {
+        let pipe_source = format!("{:?}", pipe_message.source);
         let input_pipe_id = match pipe_message.source {
             PipeSource::Cli(id) => id.clone(),
             PipeSource::Plugin(id) => format!("{}", id),
@@ -578,11 +581,100 @@ impl ZellijPlugin for State {
             );
         } else if name == "message_to_plugin" {
             self.message_to_plugin_payload = payload.clone();
+        } else if name == "subscribe_to_pane_content_changes" {
+            subscribe(&[EventType::PaneContentChanged]);
+        } else if name == "unsubscribe_from_pane_content_changes" {
+            unsubscribe(&[EventType::PaneContentChanged]);
+        } else if name == "call_plugin" {
+            subscribe(&[EventType::PluginCallResult]);
+            let args = pipe_message.args;
+            call_plugin(
+                args.get("destination")
+                    .map(|d| d.as_str())
+                    .unwrap_or("zellij:OWN_URL"),
+                args.get("method").map(|m| m.as_str()).unwrap_or_default(),
+                payload,
+                args.get("timeout_ms")
+                    .and_then(|t| t.parse().ok())
+                    .map(std::time::Duration::from_millis),
+            );
+        } else if name == "register_commands" {
+            subscribe(&[EventType::RegisteredCommandsUpdate]);
+            register_commands(vec![CommandSpec {
+                name: "fixture_command".to_owned(),
+                description: "A command registered by the fixture plugin".to_owned(),
+                args: vec![CommandArgSpec {
+                    name: "required_arg".to_owned(),
+                    description: "An argument the command cannot do without".to_owned(),
+                    required: true,
+                }],
+            }]);
+        } else if name == "fixture_command" {
+            self.message_to_plugin_payload = Some(format!(
+                "fixture_command from {} with {:?} on client {}",
+                pipe_source,
+                pipe_message.args,
+                get_plugin_ids().client_id
+            ));
+        } else if name == "subscribe_to_watch_events" {
+            subscribe(&[EventType::WatchedPathsChanged, EventType::WatchPathsFailed]);
+        } else if name == "watch_paths" {
+            subscribe(&[EventType::WatchedPathsChanged, EventType::WatchPathsFailed]);
+            let path = std::path::PathBuf::from(payload.unwrap_or_default());
+            let count: usize = pipe_message
+                .args
+                .get("count")
+                .and_then(|c| c.parse().ok())
+                .unwrap_or(1);
+            for _ in 0..count {
+                watch_paths(
+                    vec![path.clone()],
+                    WatchOptions {
+                        debounce_ms: Some(50),
+                        ..Default::default()
+                    },
+                );
+            }
+        } else if name == "unwatch_paths" {
+            if let Some(watch_id) = payload.and_then(|p| p.parse().ok()) {
+                unwatch_paths(watch_id);
+            }
+        } else if name == "hand_over_state" {
+            self.handed_over_state = payload;
+        } else if name == "show_handed_over_state" {
+            self.message_to_plugin_payload =
+                Some(format!("handed over state: {:?}", self.handed_over_state));
+        } else if name == "loop_forever" {
+            loop {
+                std::hint::spin_loop();
+            }
+        } else if name == "allocate_memory_mb" {
+            let megabytes: usize = payload.and_then(|p| p.parse().ok()).unwrap_or(0);
+            let allocation = vec![1_u8; megabytes * 1024 * 1024];
+            std::hint::black_box(allocation);
         }
         let should_render = true;
         should_render
     }
 
+    fn handle_call(&mut self, call: PluginCall) -> Option<Result<String, String>> {
+        match call.method.as_str() {
+            "get_client_id" => Some(Ok(format!(
+                "handled by client {}",
+                get_plugin_ids().client_id
+            ))),
+            _ => None, // never respond, so that the call times out
+        }
+    }
+
+    fn save_state(&self) -> Option<String> {
+        self.handed_over_state.clone()
+    }
+
+    fn restore_state(&mut self, state: String) {
+        self.handed_over_state = Some(state);
+    }
+
     fn render(&mut self, rows: usize, cols: usize) {
         if let Some(payload) = self.received_payload.as_ref() {
             println!("Payload from worker: {:?}", payload);
@@ -495,6 +495,7 @@ struct State {
     search_term: String,
     new_plugin_screen: Option<NewPluginScreen>,
     colors: Styling,
+    stored_data: HashMap<String, Vec<(String, String, StoreScope)>>, // plugin location -> (key, value, scope)
 }
 
 register_plugin!(State);
@@ -742,6 +743,23 @@ impl State {
                     items.push(self.render_config_line(config_key, config_val, cols))
                 }
             }
+            if let Some(stored_data) = self
+                .stored_data
+                .get(&plugin_info.location)
+                .filter(|s| !s.is_empty())
+            {
+                let stored_data_line = NestedListItem::new(format!("Stored data:"))
+                    .color_range(2, ..=11)
+                    .indent(1);
+                items.push(stored_data_line);
+                for (key, value, scope) in stored_data {
+                    let key = match scope {
+                        StoreScope::Global => key.to_owned(),
+                        StoreScope::Session => format!("{} (session)", key),
+                    };
+                    items.push(self.render_config_line(&key, value, cols))
+                }
+            }
         }
         items
     }
@@ -867,11 +885,11 @@ impl State {
         tab_line
     }
     pub fn render_help(&self, y: usize, cols: usize) {
-        let full_text = "Help: <←↓↑→> - Navigate/Expand, <ENTER> - focus, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <ESC> - Exit";
+        let full_text = "Help: <←↓↑→> - Navigate/Expand, <ENTER> - focus, <TAB> - Reload, <Del> - Close, <Ctrl x> - Clear data, <Ctrl a> - New, <ESC> - Exit";
         let middle_text =
-            "Help: <←↓↑→/ENTER> - Navigate, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <ESC> - Exit";
+            "Help: <←↓↑→/ENTER> - Navigate, <TAB> - Reload, <Del> - Close, <Ctrl x> - Clear data, <Ctrl a> - New, <ESC> - Exit";
         let short_text =
-            "<←↓↑→/ENTER/TAB/Del> - Navigate/Expand/Reload/Close, <Ctrl a> - New, <ESC> - Exit";
+            "<←↓↑→/ENTER/TAB/Del> - Navigate/Expand/Reload/Close, <Ctrl x> - Clear data, <Ctrl a> - New, <ESC> - Exit";
         if cols >= full_text.chars().count() {
             let text = Text::new(full_text)
                 .color_range(3, 5..=11)
@@ -879,7 +897,8 @@ impl State {
                 .color_range(3, 49..=53)
                 .color_range(3, 65..=69)
                 .color_range(3, 80..=87)
-                .color_range(3, 96..=100);
+                .color_range(3, 103..=110)
+                .color_range(3, 119..=123);
             print_text_with_coordinates(text, 0, y, Some(cols), None);
         } else if cols >= middle_text.chars().count() {
             let text = Text::new(middle_text)
@@ -887,13 +906,15 @@ impl State {
                 .color_range(3, 31..=35)
                 .color_range(3, 47..=51)
                 .color_range(3, 62..=69)
-                .color_range(3, 78..=82);
+                .color_range(3, 85..=92)
+                .color_range(3, 101..=105);
             print_text_with_coordinates(text, 0, y, Some(cols), None);
         } else {
             let text = Text::new(short_text)
                 .color_range(3, ..=21)
                 .color_range(3, 53..=60)
-                .color_range(3, 69..=73);
+                .color_range(3, 76..=83)
+                .color_range(3, 92..=96);
             print_text_with_coordinates(text, 0, y, Some(cols), None);
         }
     }
@@ -923,6 +944,16 @@ impl State {
             close_plugin_pane(selected_plugin_id);
         }
     }
+    pub fn clear_stored_data_of_selected(&mut self) {
+        if let Some(plugin_location) = self
+            .selected_plugin_id()
+            .and_then(|plugin_id| self.plugins.get(&plugin_id))
+            .map(|plugin_info| plugin_info.location.clone())
+        {
+            clear_plugin_store(&plugin_location);
+            self.stored_data.remove(&plugin_location);
+        }
+    }
     pub fn reset_selection(&mut self) {
         self.selected_index = None;
         self.expanded_indices.clear();
@@ -931,6 +962,19 @@ impl State {
         if let Some(selected_index) = &self.selected_index {
             self.expanded_indices.push(*selected_index);
         }
+        if let Some(plugin_location) = self
+            .selected_plugin_id()
+            .and_then(|plugin_id| self.plugins.get(&plugin_id))
+            .map(|plugin_info| plugin_info.location.clone())
+        {
+            let mut stored_data = vec![];
+            for scope in [StoreScope::Global, StoreScope::Session] {
+                if let Ok(entries) = list_plugin_store(&plugin_location, scope) {
+                    stored_data.extend(entries.into_iter().map(|(key, value)| (key, value, scope)));
+                }
+            }
+            self.stored_data.insert(plugin_location, stored_data);
+        }
     }
     pub fn collapse_selected(&mut self) {
         if let Some(selected_index) = &self.selected_index {
@@ -1026,6 +1070,10 @@ impl State {
             BareKey::Delete if key.has_no_modifiers() => {
                 self.close_selected();
             },
+            BareKey::Char('x') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                self.clear_stored_data_of_selected();
+                should_render = true;
+            },
             BareKey::Esc if key.has_no_modifiers() => {
                 if !self.search_term.is_empty() {
                     self.search_term.clear();
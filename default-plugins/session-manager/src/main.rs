@@ -19,6 +19,9 @@ use ui::{
 use resurrectable_sessions::ResurrectableSessions;
 use session_list::SessionList;
 
+// remembered between sessions in the plugin store
+const NEW_SESSION_FOLDER_STORE_KEY: &str = "new_session_folder";
+
 #[derive(Clone, Debug, Copy)]
 enum ActiveScreen {
     NewSession,
@@ -60,6 +63,11 @@ impl ZellijPlugin for State {
         if self.is_welcome_screen {
             self.active_screen = ActiveScreen::NewSession;
         }
+        self.new_session_info.new_session_folder =
+            store_get(NEW_SESSION_FOLDER_STORE_KEY, StoreScope::Global)
+                .ok()
+                .flatten()
+                .map(std::path::PathBuf::from);
         subscribe(&[
             EventType::ModeUpdate,
             EventType::SessionUpdate,
@@ -75,6 +83,7 @@ impl ZellijPlugin for State {
                     match self.request_ids.iter().position(|p| p == request_id) {
                         Some(request_id_position) => {
                             self.request_ids.remove(request_id_position);
+                            store_set(NEW_SESSION_FOLDER_STORE_KEY, &payload, StoreScope::Global);
                             let new_session_folder = std::path::PathBuf::from(payload);
                             self.new_session_info.new_session_folder = Some(new_session_folder);
                         },
@@ -256,6 +265,7 @@ impl State {
             },
             BareKey::Char('c') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
                 self.new_session_info.new_session_folder = None;
+                store_delete(NEW_SESSION_FOLDER_STORE_KEY, StoreScope::Global);
                 should_render = true;
             },
             BareKey::Esc if key.has_no_modifiers() => {
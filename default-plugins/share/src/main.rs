@@ -7,12 +7,20 @@ use std::net::IpAddr;
 use zellij_tile::prelude::*;
 
 use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
 
 use main_screen::MainScreen;
 use token_management_screen::TokenManagementScreen;
 use token_screen::TokenScreen;
 
 static WEB_SERVER_QUERY_DURATION: f64 = 0.4; // Doherty threshold
+static TOKEN_EXPIRATION_OPTIONS: [Option<u64>; 5] = [
+    None,
+    Some(60 * 60),
+    Some(24 * 60 * 60),
+    Some(7 * 24 * 60 * 60),
+    Some(30 * 24 * 60 * 60),
+];
 
 #[derive(Debug, Default)]
 struct App {
@@ -205,7 +213,7 @@ impl App {
 
     fn handle_token_action(&mut self) {
         if self.tokens.list.is_empty() {
-            self.generate_new_token(None);
+            self.generate_new_token(None, WebTokenScope::default());
         } else {
             self.change_to_manage_tokens_screen();
         }
@@ -244,6 +252,16 @@ impl App {
                 self.revoke_all_tokens();
                 true
             },
+            BareKey::Char('s') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                let session_name = self.state.session_name.clone();
+                self.tokens.toggle_new_token_session(session_name)
+            },
+            BareKey::Char('r') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                self.tokens.toggle_new_token_read_only()
+            },
+            BareKey::Char('e') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                self.tokens.cycle_new_token_expiration()
+            },
             _ => false,
         }
     }
@@ -258,8 +276,8 @@ impl App {
     }
 
     fn handle_enter_key(&mut self) -> bool {
-        if let Some(token_name) = self.tokens.finish_new_token_input() {
-            self.generate_new_token(token_name);
+        if let Some((token_name, scope)) = self.tokens.finish_new_token_input() {
+            self.generate_new_token(token_name, scope);
             return true;
         }
 
@@ -271,8 +289,8 @@ impl App {
         false
     }
 
-    fn generate_new_token(&mut self, name: Option<String>) {
-        match generate_web_login_token(name) {
+    fn generate_new_token(&mut self, name: Option<String>, scope: WebTokenScope) {
+        match generate_scoped_web_login_token(name, scope) {
             Ok(token) => self.change_to_token_screen(token),
             Err(e) => self.web_server.error = Some(e),
         }
@@ -453,6 +471,7 @@ impl App {
             self.tokens.selected_index,
             &self.tokens.renaming_token,
             &self.tokens.entering_new_name,
+            &self.tokens.new_token_scope,
             &self.web_server.error,
             &self.state.info,
             rows,
@@ -499,15 +518,16 @@ impl UIState {
 
 #[derive(Debug, Default)]
 struct TokenManager {
-    list: Vec<(String, String)>,
+    list: Vec<(String, String, WebTokenScope)>,
     selected_index: Option<usize>,
     entering_new_name: Option<String>,
+    new_token_scope: WebTokenScope,
     renaming_token: Option<String>,
 }
 
 impl TokenManager {
     fn retrieve_list(&mut self) -> Result<(), String> {
-        match list_web_login_tokens() {
+        match list_web_login_tokens_with_scope() {
             Ok(tokens) => {
                 self.list = tokens;
                 Ok(())
@@ -516,7 +536,7 @@ impl TokenManager {
         }
     }
 
-    fn get_selected_token(&self) -> Option<&(String, String)> {
+    fn get_selected_token(&self) -> Option<&(String, String, WebTokenScope)> {
         self.selected_index.and_then(|i| self.list.get(i))
     }
 
@@ -558,6 +578,42 @@ impl TokenManager {
 
     fn start_new_token_input(&mut self) {
         self.entering_new_name = Some(String::new());
+        self.new_token_scope = WebTokenScope::default();
+    }
+
+    fn toggle_new_token_session(&mut self, session_name: Option<String>) -> bool {
+        if self.entering_new_name.is_none() {
+            return false;
+        }
+        if self.new_token_scope.restricts_sessions() {
+            self.new_token_scope.allowed_sessions.clear();
+        } else if let Some(session_name) = session_name {
+            self.new_token_scope.allowed_sessions = vec![session_name];
+        }
+        true
+    }
+
+    fn toggle_new_token_read_only(&mut self) -> bool {
+        if self.entering_new_name.is_none() {
+            return false;
+        }
+        self.new_token_scope.read_only = !self.new_token_scope.read_only;
+        true
+    }
+
+    fn cycle_new_token_expiration(&mut self) -> bool {
+        if self.entering_new_name.is_none() {
+            return false;
+        }
+        let current_secs = self.new_token_scope.expires_in.map(|e| e.as_secs());
+        let current_index = TOKEN_EXPIRATION_OPTIONS
+            .iter()
+            .position(|o| *o == current_secs)
+            .unwrap_or(0);
+        let next_index = (current_index + 1) % TOKEN_EXPIRATION_OPTIONS.len();
+        self.new_token_scope.expires_in =
+            TOKEN_EXPIRATION_OPTIONS[next_index].map(Duration::from_secs);
+        true
     }
 
     fn start_rename_input(&mut self) {
@@ -591,10 +647,11 @@ impl TokenManager {
         false
     }
 
-    fn finish_new_token_input(&mut self) -> Option<Option<String>> {
+    fn finish_new_token_input(&mut self) -> Option<(Option<String>, WebTokenScope)> {
+        let scope = std::mem::take(&mut self.new_token_scope);
         self.entering_new_name
             .take()
-            .map(|name| if name.is_empty() { None } else { Some(name) })
+            .map(|name| (if name.is_empty() { None } else { Some(name) }, scope))
     }
 
     fn finish_rename_input(&mut self) -> Option<String> {
@@ -31,10 +31,11 @@ struct ColumnWidths {
 }
 
 pub struct TokenManagementScreen<'a> {
-    token_list: &'a Vec<(String, String)>,
+    token_list: &'a Vec<(String, String, WebTokenScope)>,
     selected_list_index: Option<usize>,
     renaming_token: &'a Option<String>,
     entering_new_token_name: &'a Option<String>,
+    new_token_scope: &'a WebTokenScope,
     error: &'a Option<String>,
     info: &'a Option<String>,
     rows: usize,
@@ -43,10 +44,11 @@ pub struct TokenManagementScreen<'a> {
 
 impl<'a> TokenManagementScreen<'a> {
     pub fn new(
-        token_list: &'a Vec<(String, String)>,
+        token_list: &'a Vec<(String, String, WebTokenScope)>,
         selected_list_index: Option<usize>,
         renaming_token: &'a Option<String>,
         entering_new_token_name: &'a Option<String>,
+        new_token_scope: &'a WebTokenScope,
         error: &'a Option<String>,
         info: &'a Option<String>,
         rows: usize,
@@ -57,6 +59,7 @@ impl<'a> TokenManagementScreen<'a> {
             selected_list_index,
             renaming_token,
             entering_new_token_name,
+            new_token_scope,
             error,
             info,
             rows,
@@ -234,10 +237,14 @@ impl<'a> TokenManagementScreen<'a> {
         max_width = std::cmp::max(max_width, title_text.len());
 
         let mut items = vec![];
-        for (i, (token, created_at)) in self.token_list.iter().enumerate() {
+        for (i, (token, created_at, scope)) in self.token_list.iter().enumerate() {
             let is_selected = Some(i) == self.selected_list_index;
+            let token = match scope.description() {
+                Some(scope_description) => format!("{} [{}]", token, scope_description),
+                None => token.clone(),
+            };
             let (row_text, row_items) =
-                self.create_token_item(token, created_at, is_selected, &column_widths);
+                self.create_token_item(&token, created_at, is_selected, &column_widths);
             max_width = std::cmp::max(max_width, row_text.chars().count());
             items.push(row_items);
         }
@@ -476,13 +483,18 @@ impl<'a> TokenManagementScreen<'a> {
             let truncated_name =
                 self.truncate_token_name(name, column_widths.token.saturating_sub(1)); // -1 for cursor
             let text = format!("{}_", truncated_name);
+            let scope_text = self
+                .new_token_scope
+                .description()
+                .map(|d| self.format_date(&format!("[{}]", d), column_widths.date, false))
+                .unwrap_or(date_placeholder);
             let item = vec![
                 Text::new(&text).color_range(3, ..),
-                Text::new(&date_placeholder),
+                Text::new(&scope_text).color_range(1, ..),
                 Text::new(&controls_placeholder),
             ];
             (
-                format!("{} {} {}", text, date_placeholder, controls_placeholder),
+                format!("{} {} {}", text, scope_text, controls_placeholder),
                 item,
             )
         } else {
@@ -509,12 +521,10 @@ impl<'a> TokenManagementScreen<'a> {
     }
 
     fn create_help_line(&self) -> (String, Text) {
-        let (text, highlight_range) = if self.entering_new_token_name.is_some() {
-            (
-                "Help: Enter optional name for new token, <Enter> to submit",
-                41..=47,
-            )
-        } else if self.renaming_token.is_some() {
+        if self.entering_new_token_name.is_some() {
+            return self.create_new_token_help_line();
+        }
+        let (text, highlight_range) = if self.renaming_token.is_some() {
             (
                 "Help: Enter new name for this token, <Enter> to submit",
                 39..=45,
@@ -529,13 +539,31 @@ impl<'a> TokenManagementScreen<'a> {
         let mut help_line = Text::new(text).color_range(3, highlight_range);
 
         // Add second highlight for the back option
-        if self.entering_new_token_name.is_none() && self.renaming_token.is_none() {
+        if self.renaming_token.is_none() {
             help_line = help_line.color_range(3, 36..=40);
         }
 
         (text.to_string(), help_line)
     }
 
+    fn create_new_token_help_line(&self) -> (String, Text) {
+        let text = "Help: optional name, <Enter> submit, <Ctrl s> this session only, <Ctrl r> read-only, <Ctrl e> expiration";
+        let mut help_line = Text::new(text);
+        let mut key_start = None;
+        for (i, c) in text.chars().enumerate() {
+            match c {
+                '<' => key_start = Some(i),
+                '>' => {
+                    if let Some(start) = key_start.take() {
+                        help_line = help_line.color_range(3, start..=i);
+                    }
+                },
+                _ => {},
+            }
+        }
+        (text.to_string(), help_line)
+    }
+
     fn create_status_message(&self) -> Option<(String, Text)> {
         if let Some(error) = &self.error {
             Some((error.clone(), Text::new(error).color_range(3, ..)))
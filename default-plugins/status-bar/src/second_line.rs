@@ -242,6 +242,8 @@ fn get_keys_and_hints(mi: &ModeInfo) -> Vec<(String, String, Vec<KeyWithModifier
             action_key(&km, &[A::SearchToggleOption(SOpt::Wrap)])),
         (s("Whole words"), s("Whole"),
             action_key(&km, &[A::SearchToggleOption(SOpt::WholeWord)])),
+        (s("Regex"), s("Regex"),
+            action_key(&km, &[A::SearchToggleOption(SOpt::Regex)])),
     ]} else if mi.mode == IM::Session { vec![
         (s("Detach"), s("Detach"), action_key(&km, &[Action::Detach])),
         (s("Session Manager"), s("Manager"), action_key(&km, &[A::LaunchOrFocusPlugin(Default::default(), true, true, false, false), TO_NORMAL])), // not entirely accurate
@@ -372,6 +374,28 @@ pub fn system_clipboard_error(palette: &Styling) -> LinePart {
     }
 }
 
+pub fn read_only_hint(palette: &Styling) -> LinePart {
+    let hint = " READ-ONLY: you are watching this session, your input is not sent to it";
+    let orange_color = palette_match!(palette.text_unselected.emphasis_0);
+    LinePart {
+        part: Style::new().fg(orange_color).bold().paint(hint).to_string(),
+        len: hint.len(),
+    }
+}
+
+pub fn read_only_indicator(palette: &Styling) -> LinePart {
+    let indicator = " READ-ONLY ";
+    let orange_color = palette_match!(palette.text_unselected.emphasis_0);
+    LinePart {
+        part: Style::new()
+            .fg(orange_color)
+            .bold()
+            .paint(indicator)
+            .to_string(),
+        len: indicator.len(),
+    }
+}
+
 pub fn fullscreen_panes_to_hide(palette: &Styling, panes_to_hide: usize) -> LinePart {
     let text_color = palette_match!(palette.text_unselected.base);
     let green_color = palette_match!(palette.text_unselected.emphasis_2);
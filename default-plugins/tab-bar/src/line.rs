@@ -204,6 +204,19 @@ fn tab_line_prefix(session_name: Option<&str>, palette: Styling, cols: usize) ->
     parts
 }
 
+fn read_only_indicator(palette: Styling) -> LinePart {
+    let indicator_text = "READ-ONLY ".to_string();
+    let indicator_len = indicator_text.chars().count();
+    let text_color = palette.text_unselected.emphasis_0;
+    let bg_color = palette.text_unselected.background;
+    let indicator_styled_text = style!(text_color, bg_color).bold().paint(indicator_text);
+    LinePart {
+        part: indicator_styled_text.to_string(),
+        len: indicator_len,
+        tab_index: None,
+    }
+}
+
 pub fn tab_separator(capabilities: PluginCapabilities) -> &'static str {
     if !capabilities.arrow_fonts {
         ARROW_SEPARATOR
@@ -236,6 +249,12 @@ pub fn tab_line(
         true => tab_line_prefix(None, palette, cols),
         false => tab_line_prefix(session_name, palette, cols),
     };
+    if mode_info.is_read_only == Some(true) {
+        let read_only_indicator = read_only_indicator(palette);
+        if get_current_title_len(&prefix) + read_only_indicator.len <= cols {
+            prefix.push(read_only_indicator);
+        }
+    }
 
     let mut swap_layout_indicator = if hide_swap_layout_indicator {
         None
@@ -11,6 +11,7 @@ use zellij_client::{
         config_yaml_to_config_kdl, convert_old_yaml_files, layout_yaml_to_layout_kdl,
     },
     os_input_output::get_client_os_input,
+    replay::start_replay,
     start_client as start_client_impl, ClientInfo,
 };
 use zellij_utils::sessions::{
@@ -37,12 +38,14 @@ use zellij_server::{os_input_output::get_server_os_input, start_server as start_
 use zellij_utils::{
     cli::{CliArgs, Command, SessionCommand, Sessions},
     data::{ConnectToSession, LayoutInfo},
+    downloader::lock_remote_plugins,
     envs,
     input::{
         actions::Action,
         config::{Config, ConfigError},
         layout::Layout,
         options::Options,
+        plugins::PluginLock,
     },
     setup::{find_default_config_dir, get_layout_dir, Setup},
 };
@@ -306,7 +309,15 @@ pub(crate) fn list_auth_tokens() -> Result<Vec<String>, String> {
         .map(|tokens| {
             let mut res = vec![];
             for t in tokens {
-                res.push(format!("{}: created at {}", t.name, t.created_at))
+                res.push(format!(
+                    "{}: created at {}{}",
+                    t.name,
+                    t.created_at,
+                    t.scope
+                        .description()
+                        .map(|d| format!(" ({})", d))
+                        .unwrap_or_default()
+                ))
             }
             res
         })
@@ -430,6 +441,53 @@ pub(crate) fn send_action_to_session(
         },
     };
 }
+pub(crate) fn replay_recording(recording: PathBuf) {
+    let os_input = get_os_input(get_client_os_input);
+    start_replay(Box::new(os_input), recording);
+}
+
+pub(crate) fn lock_plugins(opts: &CliArgs) {
+    let config = match Config::try_from(opts) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("There was an error in the config file:\n{}", e);
+            process::exit(1);
+        },
+    };
+    let Some(config_file_path) = Config::config_file_path(opts) else {
+        eprintln!("Could not find a configuration directory to place the lockfile in");
+        process::exit(1);
+    };
+    let remote_plugin_urls = config.remote_plugin_urls();
+    if remote_plugin_urls.is_empty() {
+        println!(
+            "No remote plugins found in {}, nothing to lock.",
+            config_file_path.display()
+        );
+        return;
+    }
+    let plugin_lock = match lock_remote_plugins(remote_plugin_urls) {
+        Ok(plugin_lock) => plugin_lock,
+        Err(e) => {
+            eprintln!("Failed to lock plugins: {}", e);
+            process::exit(2);
+        },
+    };
+    let lock_file_path = PluginLock::path_for_config_file(&config_file_path);
+    if let Err(e) = std::fs::write(&lock_file_path, plugin_lock.to_string()) {
+        eprintln!(
+            "Failed to write lockfile to {}: {}",
+            lock_file_path.display(),
+            e
+        );
+        process::exit(2);
+    }
+    for (url, sha256) in &plugin_lock.hashes {
+        println!("{} {}", sha256, url);
+    }
+    println!("Wrote {}", lock_file_path.display());
+}
+
 pub(crate) fn convert_old_config_file(old_config_file: PathBuf) {
     match File::open(&old_config_file) {
         Ok(mut handle) => {
@@ -638,6 +696,14 @@ pub(crate) fn start_client(opts: CliArgs) {
                 &opts,
             );
             if reconnect_to_session.name.is_some() {
+                // a read-only client stays read-only when switching sessions
+                let read_only = matches!(
+                    opts.command,
+                    Some(Command::Sessions(Sessions::Attach {
+                        read_only: true,
+                        ..
+                    }))
+                );
                 opts.command = Some(Command::Sessions(Sessions::Attach {
                     session_name: reconnect_to_session.name.clone(),
                     create: true,
@@ -645,6 +711,7 @@ pub(crate) fn start_client(opts: CliArgs) {
                     force_run_commands: false,
                     index: None,
                     options: None,
+                    read_only,
                 }));
             } else {
                 opts.command = None;
@@ -714,6 +781,7 @@ pub(crate) fn start_client(opts: CliArgs) {
             force_run_commands,
             index,
             options,
+            ..
         })) = opts.command.clone()
         {
             let config_options = match options.as_deref() {
@@ -4,7 +4,7 @@ mod tests;
 
 use clap::Parser;
 use zellij_utils::{
-    cli::{CliAction, CliArgs, Command, Sessions},
+    cli::{CliAction, CliArgs, Command, PluginSubcommand, Sessions},
     consts::{create_config_and_cache_folders, VERSION},
     envs,
     input::config::Config,
@@ -28,6 +28,7 @@ fn main() {
             command,
             direction,
             cwd,
+            env,
             floating,
             in_place,
             name,
@@ -48,6 +49,7 @@ fn main() {
                 plugin: None,
                 direction,
                 cwd,
+                env,
                 floating,
                 in_place,
                 name,
@@ -66,6 +68,15 @@ fn main() {
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::Plugin {
+            command: Some(PluginSubcommand::Lock),
+            ..
+        })) = opts.command
+        {
+            commands::lock_plugins(&opts);
+            std::process::exit(0);
+        }
+        if let Some(Command::Sessions(Sessions::Plugin {
+            command: _,
             url,
             floating,
             in_place,
@@ -82,9 +93,10 @@ fn main() {
             let stacked = false;
             let command_cli_action = CliAction::NewPane {
                 command: vec![],
-                plugin: Some(url),
+                plugin: url,
                 direction: None,
                 cwd,
+                env: vec![],
                 floating,
                 in_place,
                 name: None,
@@ -151,6 +163,10 @@ fn main() {
             commands::convert_old_theme_file(old_theme_file);
             std::process::exit(0);
         }
+        if let Some(Command::Sessions(Sessions::Replay { recording })) = opts.command {
+            commands::replay_recording(recording);
+            std::process::exit(0);
+        }
         if let Some(Command::Sessions(Sessions::Pipe {
             name,
             payload,
@@ -216,6 +232,7 @@ fn main() {
             let new_layout_cli_action = CliAction::NewTab {
                 layout: Some(layout.clone()),
                 layout_dir: options.as_ref().and_then(|o| o.layout_dir.clone()),
+                layout_arg: opts.layout_arg.clone(),
                 name: None,
                 cwd: options.as_ref().and_then(|o| o.default_cwd.clone()),
             };
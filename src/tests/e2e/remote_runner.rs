@@ -462,6 +462,7 @@ impl RemoteRunner {
             stacked: None,
             is_pinned: false,
             logical_position: None,
+            size_bounds: Default::default(),
         };
         setup_remote_environment(&mut channel, win_size);
         start_zellij(&mut channel);
@@ -500,6 +501,7 @@ impl RemoteRunner {
             stacked: None,
             is_pinned: false,
             logical_position: None,
+            size_bounds: Default::default(),
         };
         setup_remote_environment(&mut channel, win_size);
         start_zellij_mirrored_session(&mut channel);
@@ -538,6 +540,7 @@ impl RemoteRunner {
             stacked: None,
             is_pinned: false,
             logical_position: None,
+            size_bounds: Default::default(),
         };
         setup_remote_environment(&mut channel, win_size);
         start_zellij_mirrored_session_with_layout(&mut channel, layout_file_name);
@@ -579,6 +582,7 @@ impl RemoteRunner {
             stacked: None,
             is_pinned: false,
             logical_position: None,
+            size_bounds: Default::default(),
         };
         setup_remote_environment(&mut channel, win_size);
         start_zellij_mirrored_session_with_layout_and_viewport_serialization(
@@ -627,6 +631,7 @@ impl RemoteRunner {
             stacked: None,
             is_pinned: false,
             logical_position: None,
+            size_bounds: Default::default(),
         };
         setup_remote_environment(&mut channel, win_size);
         start_zellij_in_session(&mut channel, session_name, mirrored);
@@ -665,6 +670,7 @@ impl RemoteRunner {
             stacked: None,
             is_pinned: false,
             logical_position: None,
+            size_bounds: Default::default(),
         };
         setup_remote_environment(&mut channel, win_size);
         attach_to_existing_session(&mut channel, session_name);
@@ -703,6 +709,7 @@ impl RemoteRunner {
             stacked: None,
             is_pinned: false,
             logical_position: None,
+            size_bounds: Default::default(),
         };
         setup_remote_environment(&mut channel, win_size);
         start_zellij_without_frames(&mut channel);
@@ -742,6 +749,7 @@ impl RemoteRunner {
             stacked: None,
             is_pinned: false,
             logical_position: None,
+            size_bounds: Default::default(),
         };
         setup_remote_environment(&mut channel, win_size);
         start_zellij_with_config(&mut channel, &remote_path.to_string_lossy());
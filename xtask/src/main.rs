@@ -78,6 +78,10 @@ fn workspace_members() -> &'static Vec<WorkspaceMember> {
                 crate_name: "default-plugins/share",
                 build: true,
             },
+            WorkspaceMember {
+                crate_name: "default-plugins/command-palette",
+                build: true,
+            },
             WorkspaceMember {
                 crate_name: "zellij-utils",
                 build: false,
@@ -277,6 +277,10 @@ impl InputHandler {
                 self.os_input
                     .send_to_server(ClientToServerMsg::ColorRegisters(color_registers));
             },
+            AnsiStdinInstruction::KittyGraphics => {
+                self.os_input
+                    .send_to_server(ClientToServerMsg::TerminalSupportsKittyGraphics);
+            },
             AnsiStdinInstruction::SynchronizedOutput(enabled) => {
                 self.send_client_instructions
                     .send(ClientInstruction::SetSynchronizedOutput(enabled))
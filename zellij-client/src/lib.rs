@@ -5,6 +5,7 @@ mod command_is_executing;
 mod input_handler;
 mod keyboard_parser;
 pub mod old_config_converter;
+pub mod replay;
 mod stdin_ansi_parser;
 mod stdin_handler;
 #[cfg(feature = "web_server_capability")]
@@ -40,7 +41,10 @@ use zellij_utils::{
     ipc::{ClientAttributes, ClientToServerMsg, ExitReason, ServerToClientMsg},
     pane_size::Size,
 };
-use zellij_utils::{cli::CliArgs, input::layout::Layout};
+use zellij_utils::{
+    cli::{CliArgs, Command as CliCommand, Sessions},
+    input::layout::Layout,
+};
 
 #[cfg(windows)]
 use windows_sys::Win32::System::Console::{
@@ -78,6 +82,9 @@ impl From<ServerToClientMsg> for ClientInstruction {
         match instruction {
             ServerToClientMsg::Exit(e) => ClientInstruction::Exit(e),
             ServerToClientMsg::Render(buffer) => ClientInstruction::Render(buffer),
+            ServerToClientMsg::RenderDiff(render_diff) => {
+                ClientInstruction::Render(render_diff.to_vte())
+            },
             ServerToClientMsg::UnblockInputThread => ClientInstruction::UnblockInputThread,
             ServerToClientMsg::Connected => ClientInstruction::Connected,
             ServerToClientMsg::Log(log_lines) => ClientInstruction::Log(log_lines),
@@ -251,6 +258,7 @@ pub fn start_client(
         .map(|e| !e)
         .unwrap_or(false);
     let should_start_web_server = config_options.web_server.map(|w| w).unwrap_or(false);
+    let diff_rendering = config_options.diff_rendering.unwrap_or(false);
     let mut reconnect_to_session = None;
     let clear_client_terminal_attributes = "\u{1b}[?1l\u{1b}=\u{1b}[r\u{1b}[?1000l\u{1b}[?1002l\u{1b}[?1003l\u{1b}[?1005l\u{1b}[?1006l\u{1b}[?12l";
     let take_snapshot = "\u{1b}[?1049h";
@@ -317,6 +325,13 @@ pub fn start_client(
             os_input.update_session_name(name);
             let ipc_pipe = create_ipc_pipe();
             let is_web_client = false;
+            let is_read_only = matches!(
+                opts.command,
+                Some(CliCommand::Sessions(Sessions::Attach {
+                    read_only: true,
+                    ..
+                }))
+            );
 
             (
                 ClientToServerMsg::AttachClient(
@@ -326,6 +341,7 @@ pub fn start_client(
                     tab_position_to_focus,
                     pane_id_to_focus,
                     is_web_client,
+                    is_read_only,
                 ),
                 ipc_pipe,
             )
@@ -372,6 +388,9 @@ pub fn start_client(
 
     os_input.connect_to_server(&*ipc_pipe);
     os_input.send_to_server(first_msg);
+    if diff_rendering {
+        os_input.send_to_server(ClientToServerMsg::EnableDiffRendering);
+    }
 
     let mut command_is_executing = CommandIsExecuting::new();
 
@@ -0,0 +1,294 @@
+//! Plays back session recordings (asciicast v2 files, eg. made with `zellij action
+//! start-recording`) in the current terminal, or the current pane when running inside Zellij.
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use std::{fs, process, thread};
+
+use crate::os_input_output::ClientOsApi;
+use zellij_utils::asciicast::AsciicastRecording;
+use zellij_utils::errors::prelude::*;
+
+const SEEK_SECONDS: f64 = 5.0;
+const ENTER_ALTERNATE_SCREEN: &str = "\u{1b}[?1049h";
+const LEAVE_ALTERNATE_SCREEN: &str = "\u{1b}[m\u{1b}[?25h\u{1b}[?1049l";
+const CLEAR_SCREEN: &str = "\u{1b}[H\u{1b}[2J\u{1b}[m";
+// the modes a recording starts with: full scroll region, no origin mode, line wrapping and a
+// visible cursor
+const INITIAL_MODES: &str = "\u{1b}[r\u{1b}[?6l\u{1b}[?7h\u{1b}[?25h";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReplayInput {
+    TogglePause,
+    SeekBackwards,
+    SeekForwards,
+    Quit,
+}
+
+fn parse_replay_input(bytes: &[u8]) -> Vec<ReplayInput> {
+    let mut replay_input = vec![];
+    let mut index = 0;
+    while index < bytes.len() {
+        match &bytes[index..] {
+            [b'\x1b', b'[', b'D', ..] | [b'\x1b', b'O', b'D', ..] => {
+                replay_input.push(ReplayInput::SeekBackwards);
+                index += 3;
+                continue;
+            },
+            [b'\x1b', b'[', b'C', ..] | [b'\x1b', b'O', b'C', ..] => {
+                replay_input.push(ReplayInput::SeekForwards);
+                index += 3;
+                continue;
+            },
+            [b' ', ..] => replay_input.push(ReplayInput::TogglePause),
+            [b'h', ..] => replay_input.push(ReplayInput::SeekBackwards),
+            [b'l', ..] => replay_input.push(ReplayInput::SeekForwards),
+            [b'q', ..] | [3, ..] => replay_input.push(ReplayInput::Quit), // 3 => Ctrl-c
+            _ => {},
+        }
+        index += 1;
+    }
+    replay_input
+}
+
+/// The output events of a recording and how far into them we've played
+#[derive(Debug, Clone)]
+struct Replay {
+    events: Vec<(f64, String)>, // time in seconds, output
+    next_event: usize,
+    playhead: f64,
+}
+
+impl Replay {
+    pub fn new(recording: AsciicastRecording) -> Self {
+        let events = recording
+            .events
+            .into_iter()
+            .filter(|event| event.is_output())
+            .map(|event| (event.time(), event.data().to_owned()))
+            .collect();
+        Replay {
+            events,
+            next_event: 0,
+            playhead: 0.0,
+        }
+    }
+    pub fn duration(&self) -> f64 {
+        self.events.last().map(|(time, _)| *time).unwrap_or(0.0)
+    }
+    pub fn is_done(&self) -> bool {
+        self.next_event >= self.events.len()
+    }
+    pub fn time_of_next_event(&self) -> Option<f64> {
+        self.events.get(self.next_event).map(|(time, _)| *time)
+    }
+    /// The output to write in order to advance the replay to this point in time
+    pub fn play_until(&mut self, time: f64) -> String {
+        let mut output = String::new();
+        while let Some((event_time, event_output)) = self.events.get(self.next_event) {
+            if *event_time > time {
+                break;
+            }
+            output.push_str(event_output);
+            self.next_event += 1;
+        }
+        self.playhead = time;
+        output
+    }
+    /// The output to write in order to move the replay to this point in time, we can't undo
+    /// what was written to the terminal so going backwards clears the screen and replays
+    /// everything from the start
+    pub fn seek(&mut self, time: f64) -> String {
+        let time = time.max(0.0).min(self.duration());
+        if time < self.playhead {
+            self.next_event = 0;
+            // we clear inside the alternate screen rather than resetting the terminal, which
+            // would also leave it
+            let mut output = format!("{}{}", CLEAR_SCREEN, INITIAL_MODES);
+            output.push_str(&self.play_until(time));
+            output
+        } else {
+            self.play_until(time)
+        }
+    }
+}
+
+pub fn start_replay(mut os_input: Box<dyn ClientOsApi>, recording_path: PathBuf) {
+    let recording = match fs::read_to_string(&recording_path)
+        .map_err(anyError::from)
+        .and_then(|recording| AsciicastRecording::parse(&recording))
+    {
+        Ok(recording) => recording,
+        Err(e) => {
+            eprintln!(
+                "Failed to read recording {}: {:#}",
+                recording_path.display(),
+                e
+            );
+            process::exit(1);
+        },
+    };
+    let mut replay = Replay::new(recording);
+
+    let (send_input, receive_input) = channel();
+    let stdin_os_input = os_input.clone();
+    thread::Builder::new()
+        .name("replay_stdin".to_string())
+        .spawn(move || {
+            let mut stdin = stdin_os_input.get_stdin_reader();
+            loop {
+                let read_bytes = match stdin.fill_buf() {
+                    Ok(buf) if !buf.is_empty() => buf.to_vec(),
+                    _ => break,
+                };
+                stdin.consume(read_bytes.len());
+                for replay_input in parse_replay_input(&read_bytes) {
+                    if send_input.send(replay_input).is_err() {
+                        return;
+                    }
+                }
+            }
+        })
+        .unwrap();
+
+    // without raw mode we would only get the controls once enter is pressed
+    let stdin_is_terminal = os_input.stdin_is_terminal();
+    if stdin_is_terminal {
+        os_input.set_raw_mode(0);
+    }
+    let mut stdout = os_input.get_stdout_writer();
+    let mut write_to_terminal = |output: &str| {
+        let _ = stdout.write_all(output.as_bytes());
+        let _ = stdout.flush();
+    };
+    write_to_terminal(&format!(
+        "{}{}{}",
+        ENTER_ALTERNATE_SCREEN, CLEAR_SCREEN, INITIAL_MODES
+    ));
+
+    // when paused, the playhead is where we paused, otherwise it's where we were when we
+    // started playing plus the time that passed since
+    let mut started_playing_at = Some(Instant::now());
+    let mut playhead_when_started = 0.0;
+    let current_time = |started_playing_at: Option<Instant>, playhead_when_started: f64| {
+        started_playing_at
+            .map(|s| playhead_when_started + s.elapsed().as_secs_f64())
+            .unwrap_or(playhead_when_started)
+    };
+    loop {
+        let now = current_time(started_playing_at, playhead_when_started);
+        write_to_terminal(&replay.play_until(now));
+        if replay.is_done() && started_playing_at.is_some() {
+            // we stay paused at the end until asked to quit, so that the replay can still be
+            // seeked backwards
+            started_playing_at = None;
+            playhead_when_started = now;
+        }
+        let next_replay_input = match (started_playing_at, replay.time_of_next_event()) {
+            (Some(_), Some(time_of_next_event)) => {
+                let wait = Duration::from_secs_f64((time_of_next_event - now).max(0.0));
+                match receive_input.recv_timeout(wait) {
+                    Ok(replay_input) => Some(replay_input),
+                    Err(RecvTimeoutError::Timeout) => None,
+                    Err(RecvTimeoutError::Disconnected) => {
+                        thread::sleep(wait);
+                        None
+                    },
+                }
+            },
+            (Some(_), None) => None,
+            (None, _) => match receive_input.recv() {
+                Ok(replay_input) => Some(replay_input),
+                Err(_) => Some(ReplayInput::Quit),
+            },
+        };
+        let now = current_time(started_playing_at, playhead_when_started);
+        match next_replay_input {
+            Some(ReplayInput::TogglePause) => match started_playing_at.take() {
+                Some(_) => playhead_when_started = now,
+                None => started_playing_at = Some(Instant::now()),
+            },
+            Some(ReplayInput::SeekBackwards) => {
+                write_to_terminal(&replay.seek(now - SEEK_SECONDS));
+                playhead_when_started = replay.playhead;
+                started_playing_at = started_playing_at.map(|_| Instant::now());
+            },
+            Some(ReplayInput::SeekForwards) => {
+                write_to_terminal(&replay.seek(now + SEEK_SECONDS));
+                playhead_when_started = replay.playhead;
+                started_playing_at = started_playing_at.map(|_| Instant::now());
+            },
+            Some(ReplayInput::Quit) => break,
+            None => {},
+        }
+    }
+
+    write_to_terminal(LEAVE_ALTERNATE_SCREEN);
+    if stdin_is_terminal {
+        let _ = os_input.unset_raw_mode(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zellij_utils::asciicast::{AsciicastEvent, AsciicastHeader};
+
+    fn replay(events: &[(f64, &str)]) -> Replay {
+        Replay::new(AsciicastRecording {
+            header: AsciicastHeader {
+                version: 2,
+                width: 80,
+                height: 24,
+                timestamp: None,
+                title: None,
+            },
+            events: events
+                .iter()
+                .map(|(time, data)| AsciicastEvent(*time, "o".to_owned(), data.to_string()))
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn replay_plays_events_in_order() {
+        let mut replay = replay(&[(0.5, "a"), (1.0, "b"), (2.0, "c")]);
+        assert_eq!(replay.play_until(0.1), "");
+        assert_eq!(replay.play_until(1.0), "ab");
+        assert_eq!(replay.time_of_next_event(), Some(2.0));
+        assert_eq!(replay.play_until(5.0), "c");
+        assert!(replay.is_done());
+    }
+
+    #[test]
+    fn replay_seeks_forwards_and_backwards() {
+        let mut replay = replay(&[(0.5, "a"), (1.0, "b"), (6.0, "c"), (12.0, "d")]);
+        assert_eq!(replay.play_until(1.0), "ab");
+        assert_eq!(
+            replay.seek(6.0),
+            "c",
+            "seeking forwards plays what was skipped"
+        );
+        assert_eq!(
+            replay.seek(1.0),
+            format!("{}{}ab", CLEAR_SCREEN, INITIAL_MODES),
+            "seeking backwards plays everything again from the start"
+        );
+        assert_eq!(replay.seek(100.0), "cd", "seeking stops at the end");
+        assert_eq!(replay.playhead, 12.0);
+    }
+
+    #[test]
+    fn replay_input_is_parsed() {
+        assert_eq!(
+            parse_replay_input(b" \x1b[D\x1b[Cxq"),
+            vec![
+                ReplayInput::TogglePause,
+                ReplayInput::SeekBackwards,
+                ReplayInput::SeekForwards,
+                ReplayInput::Quit
+            ]
+        );
+    }
+}
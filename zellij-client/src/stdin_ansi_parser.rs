@@ -1,6 +1,8 @@
 use std::time::{Duration, Instant};
 
 const STARTUP_PARSE_DEADLINE_MS: u64 = 500;
+// the image id we query the terminal's kitty graphics support with
+const KITTY_GRAPHICS_QUERY_IMAGE_ID: u32 = 31;
 use lazy_static::lazy_static;
 use regex::Regex;
 use zellij_utils::{
@@ -69,6 +71,13 @@ impl StdinAnsiParser {
             "\u{1b}[14t\u{1b}[16t\u{1b}]11;?\u{1b}\u{5c}\u{1b}]10;?\u{1b}\u{5c}\u{1b}[?2026$p",
         );
 
+        // query kitty graphics protocol support by asking the terminal to validate (but not
+        // store) a 1x1 image, terminals that support it reply with <ESC>_Gi=31;OK<ESC>\
+        query_string.push_str(&format!(
+            "\u{1b}_Gi={},s=1,v=1,a=q,t=d,f=24;AAAA\u{1b}\u{5c}",
+            KITTY_GRAPHICS_QUERY_IMAGE_ID
+        ));
+
         // query colors
         // eg. <ESC>]4;5;?<ESC>\ => query color register number 5
         for i in 0..256 {
@@ -151,6 +160,11 @@ impl StdinAnsiParser {
             if let Ok(ansi_sequence) = AnsiStdinInstruction::bg_or_fg_from_bytes(&self.raw_buffer) {
                 self.pending_events.push(ansi_sequence);
                 self.raw_buffer.clear();
+            } else if let Some(ansi_sequence) =
+                AnsiStdinInstruction::kitty_graphics_from_bytes(&self.raw_buffer)
+            {
+                self.pending_events.push(ansi_sequence);
+                self.raw_buffer.clear();
             } else if let Ok((color_register, color_sequence)) =
                 color_sequence_from_bytes(&self.raw_buffer)
             {
@@ -181,6 +195,7 @@ pub enum AnsiStdinInstruction {
     ForegroundColor(String),
     ColorRegisters(Vec<(usize, String)>),
     SynchronizedOutput(Option<SyncOutput>),
+    KittyGraphics,
 }
 
 impl AnsiStdinInstruction {
@@ -254,6 +269,22 @@ impl AnsiStdinInstruction {
             Err("invalid_instruction")
         }
     }
+    pub fn kitty_graphics_from_bytes(bytes: &[u8]) -> Option<Self> {
+        // eg. <ESC>_Gi=31;OK<ESC>\
+        lazy_static! {
+            static ref RE: Regex = Regex::new(&format!(
+                r"\u{{1b}}_Gi={};OK\u{{1b}}\\$",
+                KITTY_GRAPHICS_QUERY_IMAGE_ID
+            ))
+            .unwrap();
+        }
+        let key_string = String::from_utf8_lossy(bytes);
+        if RE.is_match(&key_string) {
+            Some(AnsiStdinInstruction::KittyGraphics)
+        } else {
+            None
+        }
+    }
     pub fn color_registers_from_bytes(color_sequences: &mut Vec<(usize, String)>) -> Option<Self> {
         if color_sequences.is_empty() {
             return None;
@@ -1,11 +1,21 @@
 use crate::web_client::utils::parse_cookies;
 use axum::body::Body;
 use axum::http::header::SET_COOKIE;
-use axum::{extract::Request, http::StatusCode, middleware::Next, response::Response};
+use axum::{
+    extract::{MatchedPath, Path as AxumPath, Request},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+};
 use axum_extra::extract::cookie::{Cookie, SameSite};
-use zellij_utils::web_authentication_tokens::validate_session_token;
+use zellij_utils::{data::WebTokenScope, web_authentication_tokens::get_session_token_scope};
 
-pub async fn auth_middleware(request: Request, next: Next) -> Result<Response, StatusCode> {
+pub async fn auth_middleware(
+    matched_path: Option<MatchedPath>,
+    session_name: Option<AxumPath<String>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
     let cookies = parse_cookies(&request);
 
     let session_token = match cookies.get("session_token") {
@@ -13,12 +23,22 @@ pub async fn auth_middleware(request: Request, next: Next) -> Result<Response, S
         None => return Err(StatusCode::UNAUTHORIZED),
     };
 
-    match validate_session_token(&session_token) {
-        Ok(true) => {
+    match get_session_token_scope(&session_token) {
+        Ok(Some(token_scope)) => {
+            let is_terminal_route = matched_path
+                .as_ref()
+                .map(|p| p.as_str().starts_with("/ws/terminal"))
+                .unwrap_or(false);
+            if is_terminal_route
+                && !token_scope_allows_session(&token_scope, session_name.as_ref().map(|s| &s.0))
+            {
+                return Err(StatusCode::FORBIDDEN);
+            }
+            request.extensions_mut().insert(token_scope);
             let response = next.run(request).await;
             Ok(response)
         },
-        Ok(false) | Err(_) => {
+        Ok(None) | Err(_) => {
             // revoke session_token as if it exists it's no longer valid
             let clear_cookie = Cookie::build(("session_token", ""))
                 .http_only(true)
@@ -41,3 +61,15 @@ pub async fn auth_middleware(request: Request, next: Next) -> Result<Response, S
         },
     }
 }
+
+pub(super) fn token_scope_allows_session(
+    token_scope: &WebTokenScope,
+    session_name: Option<&String>,
+) -> bool {
+    match session_name {
+        Some(session_name) => token_scope.allows_session(session_name),
+        // without a session name clients get the welcome screen, from which they can create or
+        // attach to any session
+        None => !token_scope.restricts_sessions() && !token_scope.read_only,
+    }
+}
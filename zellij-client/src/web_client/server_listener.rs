@@ -1,6 +1,6 @@
 use crate::os_input_output::ClientOsApi;
 use crate::web_client::control_message::WebServerToWebClientControlMessage;
-use crate::web_client::session_management::build_initial_connection;
+use crate::web_client::session_management::{build_initial_connection, check_token_scope};
 use crate::web_client::types::{ClientConnectionBus, ConnectionTable, SessionManager};
 use crate::web_client::utils::terminal_init_messages;
 
@@ -10,7 +10,7 @@ use std::{
 };
 use zellij_utils::{
     cli::CliArgs,
-    data::Style,
+    data::{Style, WebTokenScope},
     input::{config::Config, options::Options},
     ipc::{ClientToServerMsg, ExitReason, ServerToClientMsg},
     sessions::generate_unique_session_name,
@@ -26,6 +26,7 @@ pub fn zellij_server_listener(
     config_file_path: Option<PathBuf>,
     web_client_id: String,
     session_manager: Arc<dyn SessionManager>,
+    token_scope: WebTokenScope,
 ) {
     let _server_listener_thread = std::thread::Builder::new()
         .name("server_listener".to_string())
@@ -33,14 +34,22 @@ pub fn zellij_server_listener(
             move || {
                 let mut client_connection_bus =
                     ClientConnectionBus::new(&web_client_id, &connection_table);
-                let (mut reconnect_to_session, is_welcome_screen) =
-                    match build_initial_connection(session_name, &config) {
-                        Ok(initial_session_connection) => initial_session_connection,
-                        Err(e) => {
-                            log::error!("{}", e);
-                            return;
-                        },
-                    };
+                let (mut reconnect_to_session, is_welcome_screen) = match build_initial_connection(
+                    session_name,
+                    &config,
+                    &token_scope,
+                    &*session_manager,
+                ) {
+                    Ok(initial_session_connection) => initial_session_connection,
+                    Err(e) => {
+                        log::error!("{}", e);
+                        handle_exit_reason(
+                            &mut client_connection_bus,
+                            ExitReason::Error(e.to_owned()),
+                        );
+                        return;
+                    },
+                };
                 'reconnect_loop: loop {
                     let reconnect_info = reconnect_to_session.take();
                     let path = {
@@ -81,6 +90,19 @@ pub fn zellij_server_listener(
                         .unwrap()
                         .to_owned();
 
+                    // sessions switched to from inside zellij (eg. through the session-manager)
+                    // are only checked here
+                    if let Err(e) =
+                        check_token_scope(&session_name, &token_scope, &*session_manager)
+                    {
+                        log::error!("{}", e);
+                        handle_exit_reason(
+                            &mut client_connection_bus,
+                            ExitReason::Error(e.to_owned()),
+                        );
+                        return;
+                    }
+
                     let is_web_client = true;
                     let (first_message, zellij_ipc_pipe) = session_manager.spawn_session_if_needed(
                         &session_name,
@@ -89,6 +111,7 @@ pub fn zellij_server_listener(
                         &config,
                         &config_options,
                         is_web_client,
+                        token_scope.read_only,
                         os_input.clone(),
                         reconnect_info.as_ref().and_then(|r| r.layout.clone()),
                         is_welcome_screen,
@@ -96,6 +119,9 @@ pub fn zellij_server_listener(
 
                     os_input.connect_to_server(&zellij_ipc_pipe);
                     os_input.send_to_server(first_message);
+                    if config_options.diff_rendering.unwrap_or(false) {
+                        os_input.send_to_server(ClientToServerMsg::EnableDiffRendering);
+                    }
 
                     client_connection_bus.send_control(
                         WebServerToWebClientControlMessage::SwitchedSession {
@@ -112,13 +138,18 @@ pub fn zellij_server_listener(
                                 break;
                             },
                             Some((ServerToClientMsg::Render(bytes), _)) => {
-                                if !sent_init_messages {
-                                    for message in terminal_init_messages() {
-                                        client_connection_bus.send_stdout(message.to_owned())
-                                    }
-                                    sent_init_messages = true;
-                                }
-                                client_connection_bus.send_stdout(bytes);
+                                send_render_to_web_client(
+                                    &mut client_connection_bus,
+                                    &mut sent_init_messages,
+                                    bytes,
+                                );
+                            },
+                            Some((ServerToClientMsg::RenderDiff(render_diff), _)) => {
+                                send_render_to_web_client(
+                                    &mut client_connection_bus,
+                                    &mut sent_init_messages,
+                                    render_diff.to_vte(),
+                                );
                             },
                             Some((ServerToClientMsg::SwitchSession(connect_to_session), _)) => {
                                 reconnect_to_session = Some(connect_to_session);
@@ -164,6 +195,20 @@ pub fn zellij_server_listener(
         });
 }
 
+fn send_render_to_web_client(
+    client_connection_bus: &mut ClientConnectionBus,
+    sent_init_messages: &mut bool,
+    bytes: String,
+) {
+    if !*sent_init_messages {
+        for message in terminal_init_messages() {
+            client_connection_bus.send_stdout(message.to_owned())
+        }
+        *sent_init_messages = true;
+    }
+    client_connection_bus.send_stdout(bytes);
+}
+
 fn handle_exit_reason(client_connection_bus: &mut ClientConnectionBus, exit_reason: ExitReason) {
     match exit_reason {
         ExitReason::WebClientsForbidden => {
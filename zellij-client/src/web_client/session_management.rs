@@ -1,10 +1,11 @@
 use crate::os_input_output::ClientOsApi;
 use crate::spawn_server;
+use crate::web_client::types::SessionManager;
 
 use std::{fs, path::PathBuf};
 use zellij_utils::{
     cli::CliArgs,
-    data::{ConnectToSession, LayoutInfo, WebSharing},
+    data::{ConnectToSession, LayoutInfo, WebSharing, WebTokenScope},
     envs,
     input::{
         config::{Config, ConfigError},
@@ -19,9 +20,20 @@ use zellij_utils::{
 pub fn build_initial_connection(
     session_name: Option<String>,
     config: &Config,
+    token_scope: &WebTokenScope,
+    session_manager: &dyn SessionManager,
 ) -> Result<(Option<ConnectToSession>, bool), &'static str> {
     // bool -> is_welcome_screen
     let should_start_with_welcome_screen = session_name.is_none();
+    if should_start_with_welcome_screen
+        && (token_scope.restricts_sessions() || token_scope.read_only)
+    {
+        // the welcome screen can create and attach to any session
+        return Err("This login token can only be used with a session name.");
+    }
+    if let Some(session_name) = &session_name {
+        check_token_scope(session_name, token_scope, session_manager)?;
+    }
     let default_layout_from_config =
         LayoutInfo::from_config(&config.options.layout_dir, &config.options.default_layout);
     if should_start_with_welcome_screen {
@@ -59,6 +71,24 @@ pub fn build_initial_connection(
     }
 }
 
+pub fn check_token_scope(
+    session_name: &str,
+    token_scope: &WebTokenScope,
+    session_manager: &dyn SessionManager,
+) -> Result<(), &'static str> {
+    if !token_scope.allows_session(session_name) {
+        return Err("This login token does not allow access to this session.");
+    }
+    if token_scope.read_only
+        && !session_manager
+            .session_exists(session_name)
+            .unwrap_or(false)
+    {
+        return Err("Read-only login tokens cannot create new sessions.");
+    }
+    Ok(())
+}
+
 fn layout_for_new_session(
     config: &Config,
     requested_layout: Option<LayoutInfo>,
@@ -98,6 +128,7 @@ pub fn spawn_session_if_needed(
     config: &Config,
     config_options: &Options,
     is_web_client: bool,
+    is_read_only: bool,
     os_input: Box<dyn ClientOsApi>,
     requested_layout: Option<LayoutInfo>,
     is_welcome_screen: bool,
@@ -109,6 +140,7 @@ pub fn spawn_session_if_needed(
             &config,
             &config_options,
             is_web_client,
+            is_read_only,
         )
     } else {
         let force_run_commands = false;
@@ -205,6 +237,7 @@ fn ipc_pipe_and_first_message_for_existing_session(
     config: &Config,
     config_options: &Options,
     is_web_client: bool,
+    is_read_only: bool,
 ) -> (ClientToServerMsg, PathBuf) {
     let zellij_ipc_pipe: PathBuf = {
         let mut sock_dir = zellij_utils::consts::ZELLIJ_SOCK_DIR.clone();
@@ -220,6 +253,7 @@ fn ipc_pipe_and_first_message_for_existing_session(
         None,
         None,
         is_web_client,
+        is_read_only,
     );
     (first_message, zellij_ipc_pipe)
 }
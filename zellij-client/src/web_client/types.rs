@@ -42,6 +42,7 @@ pub trait SessionManager: Send + Sync + std::fmt::Debug {
         config: &Config,
         config_options: &Options,
         is_web_client: bool,
+        is_read_only: bool,
         os_input: Box<dyn ClientOsApi>,
         requested_layout: Option<LayoutInfo>,
         is_welcome_screen: bool,
@@ -74,6 +75,7 @@ impl SessionManager for RealSessionManager {
         config: &Config,
         config_options: &Options,
         is_web_client: bool,
+        is_read_only: bool,
         os_input: Box<dyn ClientOsApi>,
         requested_layout: Option<LayoutInfo>,
         is_welcome_screen: bool,
@@ -85,6 +87,7 @@ impl SessionManager for RealSessionManager {
             config,
             config_options,
             is_web_client,
+            is_read_only,
             os_input,
             requested_layout,
             is_welcome_screen,
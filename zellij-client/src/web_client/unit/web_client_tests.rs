@@ -1295,6 +1295,7 @@ impl SessionManager for MockSessionManager {
         config: &Config,
         config_options: &Options,
         is_web_client: bool,
+        is_read_only: bool,
         _os_input: Box<dyn ClientOsApi>,
         _requested_layout: Option<LayoutInfo>,
         _is_welcome_screen: bool,
@@ -1308,6 +1309,7 @@ impl SessionManager for MockSessionManager {
             None,
             None,
             is_web_client,
+            is_read_only,
         );
 
         (first_message, mock_ipc_path)
@@ -1404,3 +1406,77 @@ impl ClientOsApi for MockClientOsApi {
         crate::os_input_output::StdinPoller::default()
     }
 }
+
+mod token_scope_tests {
+    use super::*;
+    use crate::web_client::authentication::token_scope_allows_session;
+    use crate::web_client::session_management::check_token_scope;
+    use zellij_utils::data::WebTokenScope;
+
+    fn scope(allowed_sessions: &[&str], read_only: bool) -> WebTokenScope {
+        WebTokenScope {
+            allowed_sessions: allowed_sessions.iter().map(|s| s.to_string()).collect(),
+            read_only,
+            expires_in: None,
+        }
+    }
+
+    fn session_manager_with(existing_sessions: &[&str]) -> MockSessionManager {
+        let mut session_manager = MockSessionManager::new();
+        for session_name in existing_sessions {
+            session_manager
+                .mock_sessions
+                .insert(session_name.to_string(), true);
+        }
+        session_manager
+    }
+
+    #[test]
+    fn unrestricted_token_allows_any_session() {
+        let scope = scope(&[], false);
+        let session_manager = session_manager_with(&["existing"]);
+        assert!(check_token_scope("existing", &scope, &session_manager).is_ok());
+        assert!(check_token_scope("new-session", &scope, &session_manager).is_ok());
+        assert!(token_scope_allows_session(
+            &scope,
+            Some(&"new-session".to_owned())
+        ));
+        assert!(token_scope_allows_session(&scope, None));
+    }
+
+    #[test]
+    fn session_restricted_token_only_allows_its_sessions() {
+        let scope = scope(&["allowed"], false);
+        let session_manager = session_manager_with(&["allowed", "other"]);
+        assert!(check_token_scope("allowed", &scope, &session_manager).is_ok());
+        assert!(check_token_scope("other", &scope, &session_manager).is_err());
+        assert!(token_scope_allows_session(
+            &scope,
+            Some(&"allowed".to_owned())
+        ));
+        assert!(!token_scope_allows_session(
+            &scope,
+            Some(&"other".to_owned())
+        ));
+        assert!(
+            !token_scope_allows_session(&scope, None),
+            "the welcome screen allows attaching to any session"
+        );
+    }
+
+    #[test]
+    fn read_only_token_cannot_create_sessions() {
+        let scope = scope(&[], true);
+        let session_manager = session_manager_with(&["existing"]);
+        assert!(check_token_scope("existing", &scope, &session_manager).is_ok());
+        assert!(check_token_scope("new-session", &scope, &session_manager).is_err());
+        assert!(token_scope_allows_session(
+            &scope,
+            Some(&"existing".to_owned())
+        ));
+        assert!(
+            !token_scope_allows_session(&scope, None),
+            "the welcome screen allows creating sessions"
+        );
+    }
+}
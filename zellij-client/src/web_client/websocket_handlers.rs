@@ -14,10 +14,11 @@ use axum::{
         Path as AxumPath, Query, State,
     },
     response::IntoResponse,
+    Extension,
 };
 use futures::StreamExt;
 use tokio_util::sync::CancellationToken;
-use zellij_utils::{input::mouse::MouseEvent, ipc::ClientToServerMsg};
+use zellij_utils::{data::WebTokenScope, input::mouse::MouseEvent, ipc::ClientToServerMsg};
 
 pub async fn ws_handler_control(
     ws: WebSocketUpgrade,
@@ -31,9 +32,12 @@ pub async fn ws_handler_terminal(
     ws: WebSocketUpgrade,
     session_name: Option<AxumPath<String>>,
     Query(params): Query<TerminalParams>,
+    Extension(token_scope): Extension<WebTokenScope>,
     State(state): State<AppState>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_ws_terminal(socket, session_name, params, state))
+    ws.on_upgrade(move |socket| {
+        handle_ws_terminal(socket, session_name, params, token_scope, state)
+    })
 }
 
 async fn handle_ws_control(socket: WebSocket, state: AppState) {
@@ -110,6 +114,7 @@ async fn handle_ws_terminal(
     socket: WebSocket,
     session_name: Option<AxumPath<String>>,
     params: TerminalParams,
+    token_scope: WebTokenScope,
     state: AppState,
 ) {
     let web_client_id = params.web_client_id;
@@ -141,6 +146,7 @@ async fn handle_ws_terminal(
         Some(state.config_file_path.clone()),
         web_client_id.clone(),
         state.session_manager.clone(),
+        token_scope,
     );
 
     let terminal_channel_cancellation_token = CancellationToken::new();
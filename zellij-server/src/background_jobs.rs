@@ -12,7 +12,7 @@ use isahc::AsyncReadResponseExt;
 use isahc::{config::RedirectPolicy, HttpClient, Request};
 use zellij_utils::is_socket;
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::io::Write;
 #[cfg(unix)]
@@ -60,6 +60,7 @@ pub enum BackgroundJob {
     HighlightPanesWithMessage(Vec<PaneId>, String),
     RenderToClients,
     QueryZellijWebServerStatus,
+    ReportPaneContentChanged(PaneId),
     Exit,
 }
 
@@ -86,6 +87,9 @@ impl From<&BackgroundJob> for BackgroundJobContext {
             BackgroundJob::QueryZellijWebServerStatus => {
                 BackgroundJobContext::QueryZellijWebServerStatus
             },
+            BackgroundJob::ReportPaneContentChanged(..) => {
+                BackgroundJobContext::ReportPaneContentChanged
+            },
             BackgroundJob::Exit => BackgroundJobContext::Exit,
         }
     }
@@ -97,6 +101,7 @@ static PLUGIN_ANIMATION_OFFSET_DURATION_MD: u64 = 500;
 static SESSION_READ_DURATION: u64 = 1000;
 static DEFAULT_SERIALIZATION_INTERVAL: u64 = 60000;
 static REPAINT_DELAY_MS: u64 = 10;
+static PANE_CONTENT_CHANGED_INTERVAL_MS: u64 = 100;
 
 pub(crate) fn background_jobs_main(
     bus: Bus<BackgroundJob>,
@@ -116,6 +121,8 @@ pub(crate) fn background_jobs_main(
     let serialization_interval = serialization_interval.map(|s| s * 1000); // convert to
                                                                            // milliseconds
     let last_render_request: Arc<Mutex<Option<Instant>>> = Arc::new(Mutex::new(None));
+    let panes_with_pending_content_change: Arc<Mutex<HashSet<PaneId>>> =
+        Arc::new(Mutex::new(HashSet::new()));
 
     let http_client = HttpClient::builder()
         // TODO: timeout?
@@ -508,6 +515,36 @@ pub(crate) fn background_jobs_main(
                     });
                 }
             },
+            BackgroundJob::ReportPaneContentChanged(pane_id) => {
+                // if a report is already pending for this pane, it will be sent after the
+                // interval and so also include this change, otherwise we schedule a new one
+                let should_run_task = panes_with_pending_content_change
+                    .lock()
+                    .unwrap()
+                    .insert(pane_id);
+                if should_run_task {
+                    task::spawn({
+                        let senders = bus.senders.clone();
+                        let panes_with_pending_content_change =
+                            panes_with_pending_content_change.clone();
+                        async move {
+                            task::sleep(std::time::Duration::from_millis(
+                                PANE_CONTENT_CHANGED_INTERVAL_MS,
+                            ))
+                            .await;
+                            panes_with_pending_content_change
+                                .lock()
+                                .unwrap()
+                                .remove(&pane_id);
+                            let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                                None,
+                                None,
+                                Event::PaneContentChanged(pane_id.into()),
+                            )]));
+                        }
+                    });
+                }
+            },
             BackgroundJob::HighlightPanesWithMessage(pane_ids, text) => {
                 if job_already_running(job, &mut running_jobs) {
                     continue;
@@ -38,7 +38,10 @@ use wasmtime::{Config as WasmtimeConfig, Engine, Strategy};
 
 use crate::{
     os_input_output::ServerOsApi,
-    plugins::{plugin_thread_main, PluginInstruction},
+    plugins::{
+        plugin_thread_main, PluginInstruction, DEFAULT_PLUGIN_EXECUTION_TIMEOUT_MS,
+        PLUGIN_EPOCH_TICK_MS,
+    },
     pty::{get_default_shell, pty_thread_main, Pty, PtyInstruction},
     screen::{screen_thread_main, ScreenInstruction},
     thread_bus::{Bus, ThreadSenders},
@@ -61,9 +64,9 @@ use zellij_utils::{
         keybinds::Keybinds,
         layout::{FloatingPaneLayout, Layout, PluginAlias, Run, RunPluginOrAlias},
         options::Options,
-        plugins::PluginAliases,
+        plugins::{PluginAliases, PluginLimits},
     },
-    ipc::{ClientAttributes, ExitReason, ServerToClientMsg},
+    ipc::{ClientAttributes, ExitReason, RenderDiff, ServerToClientMsg},
     shared::{default_palette, web_server_base_url},
 };
 
@@ -85,6 +88,7 @@ pub enum ServerInstruction {
         ClientId,
     ),
     Render(Option<HashMap<ClientId, String>>),
+    RenderDiff(HashMap<ClientId, RenderDiff>),
     UnblockInputThread,
     ClientExit(ClientId),
     RemoveClient(ClientId),
@@ -98,6 +102,7 @@ pub enum ServerInstruction {
         Option<usize>,       // tab position to focus
         Option<(u32, bool)>, // (pane_id, is_plugin) => pane_id to focus
         bool,                // is_web_client
+        bool,                // is_read_only
         ClientId,
     ),
     ConnStatus(ClientId),
@@ -139,6 +144,7 @@ impl From<&ServerInstruction> for ServerContext {
         match *server_instruction {
             ServerInstruction::NewClient(..) => ServerContext::NewClient,
             ServerInstruction::Render(..) => ServerContext::Render,
+            ServerInstruction::RenderDiff(..) => ServerContext::RenderDiff,
             ServerInstruction::UnblockInputThread => ServerContext::UnblockInputThread,
             ServerInstruction::ClientExit(..) => ServerContext::ClientExit,
             ServerInstruction::RemoveClient(..) => ServerContext::RemoveClient,
@@ -402,6 +408,10 @@ impl SessionMetaData {
                         .options
                         .advanced_mouse_actions
                         .unwrap_or(true),
+                    forward_pane_notifications: new_config
+                        .options
+                        .forward_pane_notifications
+                        .unwrap_or(false),
                 })
                 .unwrap();
             self.senders
@@ -484,6 +494,7 @@ macro_rules! send_to_client {
 pub(crate) struct SessionState {
     clients: HashMap<ClientId, Option<(Size, bool)>>, // bool -> is_web_client
     pipes: HashMap<String, ClientId>,                 // String => pipe_id
+    read_only_clients: HashSet<ClientId>,
 }
 
 impl SessionState {
@@ -491,6 +502,7 @@ impl SessionState {
         SessionState {
             clients: HashMap::new(),
             pipes: HashMap::new(),
+            read_only_clients: HashSet::new(),
         }
     }
     pub fn new_client(&mut self) -> ClientId {
@@ -512,6 +524,7 @@ impl SessionState {
     pub fn remove_client(&mut self, client_id: ClientId) {
         self.clients.remove(&client_id);
         self.pipes.retain(|_p_id, c_id| c_id != &client_id);
+        self.read_only_clients.remove(&client_id);
     }
     pub fn set_client_size(&mut self, client_id: ClientId, size: Size) {
         self.clients
@@ -523,23 +536,40 @@ impl SessionState {
     pub fn set_client_data(&mut self, client_id: ClientId, size: Size, is_web_client: bool) {
         self.clients.insert(client_id, Some((size, is_web_client)));
     }
+    pub fn set_client_read_only(&mut self, client_id: ClientId, is_read_only: bool) {
+        if is_read_only {
+            self.read_only_clients.insert(client_id);
+        } else {
+            self.read_only_clients.remove(&client_id);
+        }
+    }
+    pub fn is_read_only_client(&self, client_id: &ClientId) -> bool {
+        self.read_only_clients.contains(client_id)
+    }
     pub fn min_client_terminal_size(&self) -> Option<Size> {
         // None if there are no client sizes
-        let mut rows: Vec<usize> = self
+        // read-only clients do not get to resize the session, unless they are the only ones
+        // attached to it
+        let sized_clients: Vec<(&ClientId, Size)> = self
             .clients
-            .values()
-            .filter_map(|size_and_is_web_client| {
-                size_and_is_web_client.map(|(size, _is_web_client)| size.rows)
+            .iter()
+            .filter_map(|(client_id, size_and_is_web_client)| {
+                size_and_is_web_client.map(|(size, _is_web_client)| (client_id, size))
             })
             .collect();
-        rows.sort_unstable();
-        let mut cols: Vec<usize> = self
-            .clients
-            .values()
-            .filter_map(|size_and_is_web_client| {
-                size_and_is_web_client.map(|(size, _is_web_client)| size.cols)
+        let only_read_only_clients_are_attached = sized_clients
+            .iter()
+            .all(|(client_id, _)| self.read_only_clients.contains(client_id));
+        let client_sizes: Vec<Size> = sized_clients
+            .into_iter()
+            .filter(|(client_id, _)| {
+                only_read_only_clients_are_attached || !self.read_only_clients.contains(client_id)
             })
+            .map(|(_, size)| size)
             .collect();
+        let mut rows: Vec<usize> = client_sizes.iter().map(|size| size.rows).collect();
+        rows.sort_unstable();
+        let mut cols: Vec<usize> = client_sizes.iter().map(|size| size.cols).collect();
         cols.sort_unstable();
         let min_rows = rows.first();
         let min_cols = cols.first();
@@ -700,6 +730,17 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     client_attributes.size,
                     is_web_client,
                 );
+                session_data
+                    .read()
+                    .unwrap()
+                    .as_ref()
+                    .unwrap()
+                    .senders
+                    .send_to_screen(ScreenInstruction::ClientTerminalResize(
+                        client_id,
+                        client_attributes.size,
+                    ))
+                    .unwrap();
 
                 let default_shell = runtime_config_options.default_shell.map(|shell| {
                     TerminalAction::RunCommand(RunCommand {
@@ -800,6 +841,7 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 tab_position_to_focus,
                 pane_id_to_focus,
                 is_web_client,
+                is_read_only,
                 client_id,
             ) => {
                 let mut rlock = session_data.write().unwrap();
@@ -824,6 +866,10 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     attrs.size,
                     is_web_client,
                 );
+                session_state
+                    .write()
+                    .unwrap()
+                    .set_client_read_only(client_id, is_read_only);
                 let min_size = session_state
                     .read()
                     .unwrap()
@@ -833,11 +879,18 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .senders
                     .send_to_screen(ScreenInstruction::TerminalResize(min_size))
                     .unwrap();
+                session_data
+                    .senders
+                    .send_to_screen(ScreenInstruction::ClientTerminalResize(
+                        client_id, attrs.size,
+                    ))
+                    .unwrap();
                 session_data
                     .senders
                     .send_to_screen(ScreenInstruction::AddClient(
                         client_id,
                         is_web_client,
+                        is_read_only,
                         tab_position_to_focus,
                         pane_id_to_focus,
                     ))
@@ -847,7 +900,7 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .send_to_plugin(PluginInstruction::AddClient(client_id))
                     .unwrap();
                 let default_mode = config.options.default_mode.unwrap_or_default();
-                let mode_info = get_mode_info(
+                let mut mode_info = get_mode_info(
                     default_mode,
                     &attrs,
                     session_data.capabilities,
@@ -856,6 +909,7 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                         .get_client_keybinds(&client_id),
                     Some(default_mode),
                 );
+                mode_info.is_read_only = Some(is_read_only);
                 session_data
                     .senders
                     .send_to_screen(ScreenInstruction::ChangeMode(mode_info.clone(), client_id))
@@ -1105,6 +1159,16 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     break;
                 }
             },
+            ServerInstruction::RenderDiff(render_diffs) => {
+                for (client_id, render_diff) in render_diffs {
+                    send_to_client!(
+                        client_id,
+                        os_input,
+                        ServerToClientMsg::RenderDiff(render_diff),
+                        session_state
+                    );
+                }
+            },
             ServerInstruction::Error(backtrace) => {
                 let client_ids = session_state.read().unwrap().client_ids();
                 for client_id in client_ids {
@@ -1580,6 +1644,15 @@ fn init_session(
             let capabilities = capabilities.clone();
             let layout_dir = config_options.layout_dir.clone();
             let background_plugins = config.background_plugins.clone();
+            let plugin_lock = config.plugin_lock.clone();
+            let plugin_limits = PluginLimits::new(
+                config_options.plugin_max_memory_mb,
+                Some(
+                    config_options
+                        .plugin_execution_timeout_ms
+                        .unwrap_or(DEFAULT_PLUGIN_EXECUTION_TIMEOUT_MS),
+                ),
+            );
             move || {
                 plugin_thread_main(
                     plugin_bus,
@@ -1596,6 +1669,8 @@ fn init_session(
                     default_mode,
                     default_keybinds,
                     background_plugins,
+                    plugin_limits,
+                    plugin_lock,
                     client_id,
                 )
                 .fatal()
@@ -1755,11 +1830,36 @@ fn should_show_startup_tip(
 #[cfg(not(feature = "singlepass"))]
 fn get_engine() -> Engine {
     log::info!("Compiling plugins using Cranelift");
-    Engine::new(WasmtimeConfig::new().strategy(Strategy::Cranelift)).unwrap()
+    let engine = Engine::new(
+        WasmtimeConfig::new()
+            .strategy(Strategy::Cranelift)
+            .epoch_interruption(true),
+    )
+    .unwrap();
+    start_epoch_ticker(engine.clone());
+    engine
 }
 
 #[cfg(feature = "singlepass")]
 fn get_engine() -> Engine {
     log::info!("Compiling plugins using Singlepass");
-    Engine::new(WasmtimeConfig::new().strategy(Strategy::Winch)).unwrap()
+    let engine = Engine::new(
+        WasmtimeConfig::new()
+            .strategy(Strategy::Winch)
+            .epoch_interruption(true),
+    )
+    .unwrap();
+    start_epoch_ticker(engine.clone());
+    engine
+}
+
+fn start_epoch_ticker(engine: Engine) {
+    // plugins get a deadline (in epoch ticks) before each call into them, this is what moves the
+    // clock forward so that a plugin stuck in a loop is interrupted once it passes its deadline
+    let _ = thread::Builder::new()
+        .name("wasm_epoch".to_string())
+        .spawn(move || loop {
+            thread::sleep(std::time::Duration::from_millis(PLUGIN_EPOCH_TICK_MS));
+            engine.increment_epoch();
+        });
 }
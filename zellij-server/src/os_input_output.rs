@@ -205,6 +205,7 @@ fn handle_openpty(
             }
             command
                 .args(&cmd.args)
+                .envs(&cmd.env)
                 .env("ZELLIJ_PANE_ID", &format!("{}", terminal_id))
                 .pre_exec(move || -> std::io::Result<()> {
                     if libc::login_tty(pid_secondary) != 0 {
@@ -413,6 +414,7 @@ fn spawn_terminal(
                 cwd: payload.cwd,
                 hold_on_close: false,
                 hold_on_start: false,
+                env: payload.env,
                 ..Default::default()
             }
         },
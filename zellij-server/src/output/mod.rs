@@ -1,9 +1,15 @@
+mod render_diff;
+
 use std::collections::VecDeque;
 
+use render_diff::ClientRenderInput;
+pub use render_diff::{InvalidatedRegion, RenderDiffState};
+
 use crate::panes::selection::Selection;
 use crate::panes::Row;
 
 use crate::{
+    panes::kitty_graphics::{KittyGraphicsOutputState, KittyImage},
     panes::sixel::SixelImageStore,
     panes::terminal_character::{AnsiCode, CharacterStyles},
     panes::{LinkHandler, TerminalCharacter, DEFAULT_STYLES, EMPTY_TERMINAL_CHARACTER},
@@ -17,6 +23,7 @@ use std::{
     str,
 };
 use zellij_utils::errors::prelude::*;
+use zellij_utils::ipc::RenderDiff;
 use zellij_utils::pane_size::PaneGeom;
 use zellij_utils::pane_size::SizeInPixels;
 
@@ -90,42 +97,56 @@ fn serialize_chunks_with_newlines(
     let mut vte_output = String::new();
     let link_handler = link_handler.map(|l_h| l_h.borrow());
     for character_chunk in character_chunks {
-        let chunk_changed_colors = character_chunk.changed_colors();
-        let mut character_styles = DEFAULT_STYLES.enable_styled_underlines(styled_underlines);
         vte_output.push_str("\n\r");
-        let mut chunk_width = character_chunk.x;
-        for t_character in character_chunk.terminal_characters.iter() {
-            let current_character_styles = adjust_styles_for_possible_selection(
-                character_chunk.selection_and_colors(),
-                *t_character.styles,
-                character_chunk.y,
-                chunk_width,
-            );
-            write_changed_styles(
-                &mut character_styles,
-                current_character_styles,
-                chunk_changed_colors,
-                link_handler.as_ref(),
-                &mut vte_output,
-            )
-            .with_context(err_context)?;
-            chunk_width += t_character.width();
-            vte_output.push(t_character.character);
-        }
+        serialize_chunk_characters(
+            &character_chunk,
+            link_handler.as_ref(),
+            styled_underlines,
+            &mut vte_output,
+        )
+        .with_context(err_context)?;
     }
     Ok(vte_output)
 }
+fn serialize_chunk_characters(
+    character_chunk: &CharacterChunk,
+    link_handler: Option<&std::cell::Ref<LinkHandler>>,
+    styled_underlines: bool,
+    vte_output: &mut String,
+) -> Result<()> {
+    let chunk_changed_colors = character_chunk.changed_colors();
+    let mut character_styles = DEFAULT_STYLES.enable_styled_underlines(styled_underlines);
+    let mut chunk_width = character_chunk.x;
+    for t_character in character_chunk.terminal_characters.iter() {
+        let current_character_styles = adjust_styles_for_possible_selection(
+            character_chunk.selection_and_colors(),
+            *t_character.styles,
+            character_chunk.y,
+            chunk_width,
+        );
+        write_changed_styles(
+            &mut character_styles,
+            current_character_styles,
+            chunk_changed_colors,
+            link_handler,
+            vte_output,
+        )?;
+        chunk_width += t_character.width();
+        vte_output.push(t_character.character);
+    }
+    Ok(())
+}
 fn serialize_chunks(
     character_chunks: Vec<CharacterChunk>,
     sixel_chunks: Option<&Vec<SixelImageChunk>>,
     link_handler: Option<&mut Rc<RefCell<LinkHandler>>>,
     sixel_image_store: Option<&mut SixelImageStore>,
+    kitty_vte: Option<String>,
     styled_underlines: bool,
 ) -> Result<String> {
     let err_context = || "failed to serialize input chunks".to_string();
 
     let mut vte_output = String::new();
-    let mut sixel_vte: Option<String> = None;
     let link_handler = link_handler.map(|l_h| l_h.borrow());
     for character_chunk in character_chunks {
         let chunk_changed_colors = character_chunk.changed_colors();
@@ -152,6 +173,22 @@ fn serialize_chunks(
             vte_output.push(t_character.character);
         }
     }
+    vte_output.push_str(
+        &serialize_image_chunks(sixel_chunks, sixel_image_store, kitty_vte)
+            .with_context(err_context)?,
+    );
+    Ok(vte_output)
+}
+
+fn serialize_image_chunks(
+    sixel_chunks: Option<&Vec<SixelImageChunk>>,
+    sixel_image_store: Option<&mut SixelImageStore>,
+    kitty_vte: Option<String>,
+) -> Result<String> {
+    let err_context = || "failed to serialize image chunks".to_string();
+
+    let mut vte_output = String::new();
+    let mut sixel_vte: Option<String> = None;
     if let Some(sixel_image_store) = sixel_image_store {
         if let Some(sixel_chunks) = sixel_chunks {
             for sixel_chunk in sixel_chunks {
@@ -181,9 +218,26 @@ fn serialize_chunks(
         vte_output.push_str(sixel_vte);
         vte_output.push_str(restore_cursor_position);
     }
+    if let Some(kitty_vte) = kitty_vte.filter(|kitty_vte| !kitty_vte.is_empty()) {
+        // kitty images are not overwritten by text, so unlike sixel images they are placed (and
+        // moved or deleted) regardless of which lines changed
+        let save_cursor_position = "\u{1b}[s";
+        let restore_cursor_position = "\u{1b}[u";
+        vte_output.push_str(save_cursor_position);
+        vte_output.push_str(&kitty_vte);
+        vte_output.push_str(restore_cursor_position);
+    }
     Ok(vte_output)
 }
 
+fn div_ceil(numerator: usize, denominator: usize) -> usize {
+    if denominator == 0 {
+        0
+    } else {
+        (numerator + denominator - 1) / denominator
+    }
+}
+
 type AbsoluteMiddleStart = usize;
 type AbsoluteMiddleEnd = usize;
 type PadLeftEndBy = usize;
@@ -241,21 +295,28 @@ pub struct Output {
     post_vte_instructions: HashMap<ClientId, Vec<String>>,
     client_character_chunks: HashMap<ClientId, Vec<CharacterChunk>>,
     sixel_chunks: HashMap<ClientId, Vec<SixelImageChunk>>,
+    kitty_image_chunks: HashMap<ClientId, Vec<KittyImageChunk>>,
     link_handler: Option<Rc<RefCell<LinkHandler>>>,
     sixel_image_store: Rc<RefCell<SixelImageStore>>,
+    kitty_graphics_output_state: Rc<RefCell<KittyGraphicsOutputState>>,
     character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
     floating_panes_stack: Option<FloatingPanesStack>,
     styled_underlines: bool,
+    // these are only used for clients that opted into diff rendering
+    cleared_display_clients: HashSet<ClientId>,
+    invalidated_regions: HashMap<ClientId, Vec<InvalidatedRegion>>,
 }
 
 impl Output {
     pub fn new(
         sixel_image_store: Rc<RefCell<SixelImageStore>>,
+        kitty_graphics_output_state: Rc<RefCell<KittyGraphicsOutputState>>,
         character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
         styled_underlines: bool,
     ) -> Self {
         Output {
             sixel_image_store,
+            kitty_graphics_output_state,
             character_cell_size,
             styled_underlines,
             ..Default::default()
@@ -354,6 +415,35 @@ impl Output {
             .or_insert_with(Vec::new);
         entry.push(String::from(vte_instruction));
     }
+    pub fn add_clear_display_instruction_to_multiple_clients(
+        &mut self,
+        client_ids: impl Iterator<Item = ClientId>,
+    ) {
+        let clear_display = "\u{1b}[2J";
+        for client_id in client_ids {
+            self.add_pre_vte_instruction_to_client(client_id, clear_display);
+            self.cleared_display_clients.insert(client_id);
+        }
+    }
+    pub fn invalidate_region_for_client(
+        &mut self,
+        client_id: ClientId,
+        invalidated_region: InvalidatedRegion,
+    ) {
+        self.invalidated_regions
+            .entry(client_id)
+            .or_insert_with(Vec::new)
+            .push(invalidated_region);
+    }
+    pub fn invalidate_region_for_multiple_clients(
+        &mut self,
+        client_ids: impl Iterator<Item = ClientId>,
+        invalidated_region: InvalidatedRegion,
+    ) {
+        for client_id in client_ids {
+            self.invalidate_region_for_client(client_id, invalidated_region);
+        }
+    }
     pub fn add_sixel_image_chunks_to_client(
         &mut self,
         client_id: ClientId,
@@ -396,12 +486,91 @@ impl Output {
             }
         }
     }
+    pub fn add_kitty_image_chunks_to_multiple_clients(
+        &mut self,
+        kitty_image_chunks: Vec<KittyImageChunk>,
+        client_ids: impl Iterator<Item = ClientId>,
+        z_index: Option<usize>,
+    ) {
+        // these are all the images currently visible in the pane, clients that support kitty
+        // graphics are sent only the difference from what they already display
+        let visible_kitty_image_chunks =
+            self.visible_kitty_image_chunks(kitty_image_chunks, z_index);
+        for client_id in client_ids {
+            if self
+                .kitty_graphics_output_state
+                .borrow()
+                .client_supports_kitty_graphics(client_id)
+            {
+                self.kitty_image_chunks
+                    .entry(client_id)
+                    .or_insert_with(Vec::new)
+                    .append(&mut visible_kitty_image_chunks.clone());
+            }
+        }
+    }
+    pub fn add_kitty_image_placeholders_to_multiple_clients(
+        &mut self,
+        kitty_image_chunks: Vec<KittyImageChunk>,
+        client_ids: impl Iterator<Item = ClientId>,
+        z_index: Option<usize>,
+    ) -> Result<()> {
+        // the terminals of these clients can't display the images, so we cover their area with a
+        // placeholder instead
+        let visible_kitty_image_chunks =
+            self.visible_kitty_image_chunks(kitty_image_chunks, z_index);
+        let placeholder_chunks: Vec<CharacterChunk> = visible_kitty_image_chunks
+            .iter()
+            .flat_map(|chunk| chunk.placeholder_character_chunks())
+            .collect();
+        if placeholder_chunks.is_empty() {
+            return Ok(());
+        }
+        for client_id in client_ids {
+            if !self
+                .kitty_graphics_output_state
+                .borrow()
+                .client_supports_kitty_graphics(client_id)
+            {
+                self.add_character_chunks_to_client(client_id, placeholder_chunks.clone(), z_index)
+                    .context("failed to add kitty image placeholders")?;
+            }
+        }
+        Ok(())
+    }
+    fn visible_kitty_image_chunks(
+        &self,
+        kitty_image_chunks: Vec<KittyImageChunk>,
+        z_index: Option<usize>,
+    ) -> Vec<KittyImageChunk> {
+        match &self.floating_panes_stack {
+            Some(floating_panes_stack) => {
+                floating_panes_stack.visible_kitty_image_chunks(kitty_image_chunks, z_index)
+            },
+            None => kitty_image_chunks,
+        }
+    }
+    fn kitty_graphics_have_changes(&self) -> bool {
+        let kitty_graphics_output_state = self.kitty_graphics_output_state.borrow();
+        self.client_character_chunks.keys().any(|client_id| {
+            kitty_graphics_output_state.client_supports_kitty_graphics(*client_id)
+                && kitty_graphics_output_state.has_changes_for_client(
+                    *client_id,
+                    self.kitty_image_chunks
+                        .get(client_id)
+                        .map(|c| c.as_slice())
+                        .unwrap_or(&[]),
+                )
+        })
+    }
     pub fn serialize(&mut self) -> Result<HashMap<ClientId, String>> {
         let err_context = || "failed to serialize output to clients".to_string();
 
         let mut serialized_render_instructions = HashMap::new();
 
-        for (client_id, client_character_chunks) in self.client_character_chunks.drain() {
+        let client_character_chunks: Vec<(ClientId, Vec<CharacterChunk>)> =
+            self.client_character_chunks.drain().collect();
+        for (client_id, client_character_chunks) in client_character_chunks {
             let mut client_serialized_render_instructions = String::new();
 
             // append pre-vte instructions for this client
@@ -413,6 +582,8 @@ impl Output {
                 }
             }
 
+            let kitty_vte = self.kitty_vte_for_client(client_id);
+
             // append the actual vte
             client_serialized_render_instructions.push_str(
                 &serialize_chunks(
@@ -420,6 +591,7 @@ impl Output {
                     self.sixel_chunks.get(&client_id),
                     self.link_handler.as_mut(),
                     Some(&mut self.sixel_image_store.borrow_mut()),
+                    kitty_vte,
                     self.styled_underlines,
                 )
                 .with_context(err_context)?,
@@ -437,11 +609,116 @@ impl Output {
         }
         Ok(serialized_render_instructions)
     }
+    pub fn serialize_render_diffs(
+        &mut self,
+        render_diff_state: &mut RenderDiffState,
+    ) -> Result<HashMap<ClientId, RenderDiff>> {
+        // this removes the clients that opted into diff rendering from the output, so that
+        // serialize() only handles the rest of them
+        let err_context = || "failed to serialize render diffs to clients".to_string();
+
+        let diff_client_ids: Vec<ClientId> = self
+            .client_character_chunks
+            .keys()
+            .copied()
+            .filter(|client_id| render_diff_state.has_client(*client_id))
+            .collect();
+        let mut render_inputs = HashMap::new();
+        let mut render_diffs = HashMap::new();
+        for client_id in diff_client_ids {
+            let character_chunks = self
+                .client_character_chunks
+                .remove(&client_id)
+                .unwrap_or_default();
+            let pre_vte = self
+                .pre_vte_instructions
+                .remove(&client_id)
+                .unwrap_or_default()
+                .concat();
+            let sixel_chunks = self.sixel_chunks.remove(&client_id).unwrap_or_default();
+            let mut invalidated_regions = self
+                .invalidated_regions
+                .remove(&client_id)
+                .unwrap_or_default();
+            if let Some(character_cell_size) = *self.character_cell_size.borrow() {
+                // sixel images are drawn over the cells below them
+                invalidated_regions.extend(sixel_chunks.iter().map(|sixel_chunk| {
+                    InvalidatedRegion {
+                        x: sixel_chunk.cell_x,
+                        y: sixel_chunk.cell_y,
+                        columns: div_ceil(
+                            sixel_chunk.sixel_image_pixel_width,
+                            character_cell_size.width,
+                        ),
+                        rows: div_ceil(
+                            sixel_chunk.sixel_image_pixel_height,
+                            character_cell_size.height,
+                        ),
+                    }
+                }));
+            }
+            let kitty_vte = self.kitty_vte_for_client(client_id);
+            let mut post_vte = serialize_image_chunks(
+                Some(&sixel_chunks),
+                Some(&mut self.sixel_image_store.borrow_mut()),
+                kitty_vte,
+            )
+            .with_context(err_context)?;
+            if let Some(post_vte_instructions_for_client) =
+                self.post_vte_instructions.remove(&client_id)
+            {
+                post_vte.push_str(&post_vte_instructions_for_client.concat());
+            }
+            render_inputs.insert(
+                client_id,
+                ClientRenderInput {
+                    character_chunks,
+                    invalidated_regions,
+                    display_cleared: self.cleared_display_clients.remove(&client_id),
+                },
+            );
+            render_diffs.insert(
+                client_id,
+                RenderDiff {
+                    pre_vte,
+                    lines: vec![],
+                    post_vte,
+                },
+            );
+        }
+        if render_inputs.is_empty() {
+            return Ok(render_diffs);
+        }
+        let link_handler = self.link_handler.as_ref().map(|l_h| l_h.borrow());
+        let diff_lines = render_diff_state
+            .diff_lines_for_clients(render_inputs, link_handler.as_ref(), self.styled_underlines)
+            .with_context(err_context)?;
+        for (client_id, lines) in diff_lines {
+            if let Some(render_diff) = render_diffs.get_mut(&client_id) {
+                render_diff.lines = lines;
+            }
+        }
+        render_diffs.retain(|_client_id, render_diff| render_diff != &RenderDiff::default());
+        Ok(render_diffs)
+    }
+    fn kitty_vte_for_client(&mut self, client_id: ClientId) -> Option<String> {
+        let mut kitty_graphics_output_state = self.kitty_graphics_output_state.borrow_mut();
+        if kitty_graphics_output_state.client_supports_kitty_graphics(client_id) {
+            let kitty_image_chunks = self
+                .kitty_image_chunks
+                .remove(&client_id)
+                .unwrap_or_default();
+            Some(kitty_graphics_output_state.serialize_for_client(client_id, &kitty_image_chunks))
+        } else {
+            None
+        }
+    }
     pub fn is_dirty(&self) -> bool {
         !self.pre_vte_instructions.is_empty()
             || !self.post_vte_instructions.is_empty()
             || self.client_character_chunks.values().any(|c| !c.is_empty())
             || self.sixel_chunks.values().any(|c| !c.is_empty())
+            || self.kitty_graphics_have_changes()
     }
     pub fn has_rendered_assets(&self) -> bool {
         // pre_vte and post_vte are not considered rendered assets as they should not be visible
@@ -528,6 +805,24 @@ impl FloatingPanesStack {
         }
         chunks_to_check
     }
+    pub fn visible_kitty_image_chunks(
+        &self,
+        mut kitty_image_chunks: Vec<KittyImageChunk>,
+        z_index: Option<usize>,
+    ) -> Vec<KittyImageChunk> {
+        let z_index = z_index.unwrap_or(0);
+        let mut chunks_to_check: Vec<KittyImageChunk> = kitty_image_chunks.drain(..).collect();
+        let panes_to_check = self.layers.iter().skip(z_index);
+        for pane_geom in panes_to_check {
+            let chunks_to_check_against_this_pane: Vec<KittyImageChunk> =
+                chunks_to_check.drain(..).collect();
+            for k_chunk in chunks_to_check_against_this_pane {
+                let mut uncovered_chunks = self.remove_covered_kitty_parts(pane_geom, &k_chunk);
+                chunks_to_check.append(&mut uncovered_chunks);
+            }
+        }
+        chunks_to_check
+    }
     fn remove_covered_parts(
         &self,
         pane_geom: &PaneGeom,
@@ -749,6 +1044,70 @@ impl FloatingPanesStack {
         }
         uncovered_chunks
     }
+    fn remove_covered_kitty_parts(
+        &self,
+        pane_geom: &PaneGeom,
+        k_chunk: &KittyImageChunk,
+    ) -> Vec<KittyImageChunk> {
+        // unlike sixel images, kitty images are placed on whole cells - so we can cut them into
+        // (up to four) rectangles around the pane without worrying about pixels
+        let pane_top_edge = pane_geom.y;
+        let pane_bottom_edge = pane_geom.y + pane_geom.rows.as_usize();
+        let pane_left_edge = pane_geom.x;
+        let pane_right_edge = pane_geom.x + pane_geom.cols.as_usize();
+        let k_chunk_top_edge = k_chunk.cell_y;
+        let k_chunk_bottom_edge = k_chunk.cell_y + k_chunk.rows;
+        let k_chunk_left_edge = k_chunk.cell_x;
+        let k_chunk_right_edge = k_chunk.cell_x + k_chunk.columns;
+        let pane_intersects_with_chunk = pane_top_edge < k_chunk_bottom_edge
+            && pane_bottom_edge > k_chunk_top_edge
+            && pane_left_edge < k_chunk_right_edge
+            && pane_right_edge > k_chunk_left_edge;
+        if !pane_intersects_with_chunk {
+            return vec![k_chunk.clone()];
+        }
+        let covered_rows_top = std::cmp::max(pane_top_edge, k_chunk_top_edge) - k_chunk_top_edge;
+        let covered_row_count = std::cmp::min(pane_bottom_edge, k_chunk_bottom_edge)
+            - std::cmp::max(pane_top_edge, k_chunk_top_edge);
+        let mut uncovered_chunks = vec![];
+        if pane_top_edge > k_chunk_top_edge {
+            // above the pane
+            uncovered_chunks.push(k_chunk.crop(
+                0,
+                0,
+                k_chunk.columns,
+                pane_top_edge - k_chunk_top_edge,
+            ));
+        }
+        if pane_bottom_edge < k_chunk_bottom_edge {
+            // below the pane
+            uncovered_chunks.push(k_chunk.crop(
+                0,
+                pane_bottom_edge - k_chunk_top_edge,
+                k_chunk.columns,
+                k_chunk_bottom_edge - pane_bottom_edge,
+            ));
+        }
+        if pane_left_edge > k_chunk_left_edge {
+            // to the left of the pane
+            uncovered_chunks.push(k_chunk.crop(
+                0,
+                covered_rows_top,
+                pane_left_edge - k_chunk_left_edge,
+                covered_row_count,
+            ));
+        }
+        if pane_right_edge < k_chunk_right_edge {
+            // to the right of the pane
+            uncovered_chunks.push(k_chunk.crop(
+                pane_right_edge - k_chunk_left_edge,
+                covered_rows_top,
+                k_chunk_right_edge - pane_right_edge,
+                covered_row_count,
+            ));
+        }
+        uncovered_chunks.into_iter().flatten().collect()
+    }
     pub fn cursor_is_visible(&self, cursor_x: usize, cursor_y: usize) -> bool {
         let z_index = 0; // TODO: receive z_index
         let panes_to_check = self.layers.iter().skip(z_index);
@@ -769,7 +1128,7 @@ impl FloatingPanesStack {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct CharacterChunk {
     pub terminal_characters: Vec<TerminalCharacter>,
     pub x: usize,
@@ -789,6 +1148,73 @@ pub struct SixelImageChunk {
     pub sixel_image_id: usize,
 }
 
+#[derive(Debug, Clone)]
+pub struct KittyImageChunk {
+    pub image: Rc<KittyImage>,
+    pub cell_x: usize,
+    pub cell_y: usize,
+    pub columns: usize,
+    pub rows: usize,
+    // the part of the image (in pixels) that is scaled into the above cells
+    pub source_x: usize,
+    pub source_y: usize,
+    pub source_width: usize,
+    pub source_height: usize,
+    pub z_index: i32,
+}
+
+impl KittyImageChunk {
+    pub fn crop(
+        &self,
+        column_offset: usize,
+        row_offset: usize,
+        columns: usize,
+        rows: usize,
+    ) -> Option<KittyImageChunk> {
+        // returns the part of this chunk in the given cells (relative to the chunk), along with
+        // the part of the image that is displayed in them
+        let columns = std::cmp::min(columns, self.columns.saturating_sub(column_offset));
+        let rows = std::cmp::min(rows, self.rows.saturating_sub(row_offset));
+        if columns == 0 || rows == 0 {
+            return None;
+        }
+        let source_x = self.source_x + self.source_width * column_offset / self.columns;
+        let source_y = self.source_y + self.source_height * row_offset / self.rows;
+        let source_width = std::cmp::max(self.source_width * columns / self.columns, 1);
+        let source_height = std::cmp::max(self.source_height * rows / self.rows, 1);
+        Some(KittyImageChunk {
+            image: self.image.clone(),
+            cell_x: self.cell_x + column_offset,
+            cell_y: self.cell_y + row_offset,
+            columns,
+            rows,
+            source_x,
+            source_y,
+            source_width,
+            source_height,
+            z_index: self.z_index,
+        })
+    }
+    pub fn placeholder_character_chunks(&self) -> Vec<CharacterChunk> {
+        let placeholder_text = "[image]";
+        (0..self.rows)
+            .map(|row| {
+                let terminal_characters = (0..self.columns)
+                    .map(|column| {
+                        let character = if row == 0 {
+                            placeholder_text.chars().nth(column).unwrap_or(' ')
+                        } else {
+                            ' '
+                        };
+                        TerminalCharacter::new_singlewidth(character)
+                    })
+                    .collect();
+                CharacterChunk::new(terminal_characters, self.cell_x, self.cell_y + row)
+            })
+            .collect()
+    }
+}
+
 impl CharacterChunk {
     pub fn new(terminal_characters: Vec<TerminalCharacter>, x: usize, y: usize) -> Self {
         CharacterChunk {
@@ -948,6 +1374,19 @@ impl OutputBuffer {
         }
         serialize_chunks_with_newlines(chunks, None, None, self.styled_underlines)
     }
+    /// A single line with its styles, without moving the cursor before or after it
+    pub fn serialize_line(&self, line: &Row) -> Result<String> {
+        let terminal_characters = line.columns.iter().cloned().collect();
+        let mut vte_output = String::new();
+        serialize_chunk_characters(
+            &CharacterChunk::new(terminal_characters, 0, 0),
+            None,
+            self.styled_underlines,
+            &mut vte_output,
+        )
+        .context("failed to serialize line")?;
+        Ok(vte_output)
+    }
     pub fn changed_chunks_in_viewport(
         &self,
         viewport: &[Row],
@@ -1059,3 +1498,11 @@ impl OutputBuffer {
         changed_rects
     }
 }
+
+#[cfg(test)]
+#[path = "./unit/render_diff_tests.rs"]
+mod render_diff_tests;
+
+#[cfg(test)]
+#[path = "./unit/kitty_graphics_output_tests.rs"]
+mod kitty_graphics_output_tests;
@@ -0,0 +1,245 @@
+use std::cell::Ref;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::{adjust_styles_for_possible_selection, write_changed_styles, CharacterChunk};
+use crate::{
+    panes::terminal_character::{AnsiCode, CharacterStyles},
+    panes::{LinkHandler, DEFAULT_STYLES},
+    ClientId,
+};
+use zellij_utils::errors::prelude::*;
+use zellij_utils::ipc::{RenderDiffLine, RenderDiffSpan};
+
+// unchanged cells between two changed ones are sent again rather than starting a new line if
+// there are at most this many of them, since going to a new position costs about as much
+const MAX_UNCHANGED_CELLS_IN_RUN: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RenderedCell {
+    // we don't know what the client displays here (eg. before the first render or after raw VTE
+    // was written to it), so it will always be sent
+    Unknown,
+    Character(char, CharacterStyles),
+    WideCharacterContinuation,
+}
+
+/// What we last sent to a client, cell by cell
+#[derive(Debug, Clone, Default)]
+struct RenderedFrame {
+    lines: Vec<Vec<RenderedCell>>,
+}
+
+impl RenderedFrame {
+    fn cell(&self, x: usize, y: usize) -> RenderedCell {
+        self.lines
+            .get(y)
+            .and_then(|line| line.get(x))
+            .copied()
+            .unwrap_or(RenderedCell::Unknown)
+    }
+    fn set_cell(&mut self, x: usize, y: usize, cell: RenderedCell) {
+        if self.lines.len() <= y {
+            self.lines.resize_with(y + 1, Vec::new);
+        }
+        let line = &mut self.lines[y];
+        if line.len() <= x {
+            line.resize(x + 1, RenderedCell::Unknown);
+        }
+        line[x] = cell;
+    }
+    fn invalidate_region(&mut self, region: &InvalidatedRegion) {
+        for line in self.lines.iter_mut().skip(region.y).take(region.rows) {
+            for cell in line.iter_mut().skip(region.x).take(region.columns) {
+                *cell = RenderedCell::Unknown;
+            }
+        }
+    }
+    fn diff_character_chunks(
+        &mut self,
+        character_chunks: &[CharacterChunk],
+        link_handler: Option<&Ref<LinkHandler>>,
+        styled_underlines: bool,
+    ) -> Result<Vec<RenderDiffLine>> {
+        let mut lines = vec![];
+        for character_chunk in character_chunks {
+            let chunk_changed_colors = character_chunk.changed_colors();
+            let mut cells = Vec::with_capacity(character_chunk.terminal_characters.len());
+            let mut changed_cell_indices = vec![];
+            let mut x = character_chunk.x;
+            let y = character_chunk.y;
+            for t_character in character_chunk.terminal_characters.iter() {
+                let character_styles = adjust_styles_for_possible_selection(
+                    character_chunk.selection_and_colors(),
+                    *t_character.styles,
+                    y,
+                    x,
+                );
+                let width = t_character.width();
+                // changed colors are applied when serializing, so we can't tell what the client
+                // ends up displaying
+                let rendered_cell = if chunk_changed_colors.is_some() {
+                    RenderedCell::Unknown
+                } else {
+                    RenderedCell::Character(t_character.character, character_styles)
+                };
+                let cell_changed = rendered_cell == RenderedCell::Unknown
+                    || self.cell(x, y) != rendered_cell
+                    || (1..width)
+                        .any(|i| self.cell(x + i, y) != RenderedCell::WideCharacterContinuation);
+                self.set_cell(x, y, rendered_cell);
+                for i in 1..width {
+                    self.set_cell(x + i, y, RenderedCell::WideCharacterContinuation);
+                }
+                if cell_changed {
+                    changed_cell_indices.push(cells.len());
+                }
+                cells.push((x, t_character.character, character_styles));
+                x += width;
+            }
+            let mut runs: Vec<(usize, usize)> = vec![];
+            for index in changed_cell_indices {
+                match runs.last_mut() {
+                    Some((_run_start, run_end))
+                        if index - *run_end <= MAX_UNCHANGED_CELLS_IN_RUN + 1 =>
+                    {
+                        *run_end = index;
+                    },
+                    _ => runs.push((index, index)),
+                }
+            }
+            for (run_start, run_end) in runs {
+                lines.push(
+                    render_diff_line(
+                        &cells[run_start..=run_end],
+                        y,
+                        chunk_changed_colors,
+                        link_handler,
+                        styled_underlines,
+                    )
+                    .context("failed to diff character chunks")?,
+                );
+            }
+        }
+        Ok(lines)
+    }
+}
+
+fn render_diff_line(
+    cells: &[(usize, char, CharacterStyles)],
+    y: usize,
+    chunk_changed_colors: Option<[Option<AnsiCode>; 256]>,
+    link_handler: Option<&Ref<LinkHandler>>,
+    styled_underlines: bool,
+) -> Result<RenderDiffLine> {
+    // the client resets the styles at the start of every line
+    let mut character_styles = DEFAULT_STYLES.enable_styled_underlines(styled_underlines);
+    let mut spans: Vec<RenderDiffSpan> = vec![];
+    for (_x, character, current_character_styles) in cells {
+        let mut changed_styles = String::new();
+        write_changed_styles(
+            &mut character_styles,
+            *current_character_styles,
+            chunk_changed_colors,
+            link_handler,
+            &mut changed_styles,
+        )?;
+        match spans.last_mut() {
+            Some(span) if changed_styles.is_empty() => span.text.push(*character),
+            _ => spans.push(RenderDiffSpan {
+                styles: changed_styles,
+                text: character.to_string(),
+            }),
+        }
+    }
+    Ok(RenderDiffLine {
+        x: cells.first().map(|(x, _, _)| *x).unwrap_or(0),
+        y,
+        spans,
+    })
+}
+
+/// An area of the client's terminal that was drawn over by something other than character chunks
+/// (eg. raw VTE or images), so it has to be sent again on the next render
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidatedRegion {
+    pub x: usize,
+    pub y: usize,
+    pub columns: usize,
+    pub rows: usize,
+}
+
+/// Everything that goes into a diff render for a single client
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClientRenderInput {
+    pub character_chunks: Vec<CharacterChunk>,
+    pub invalidated_regions: Vec<InvalidatedRegion>,
+    pub display_cleared: bool,
+}
+
+/// Keeps track of what was sent to each client that opted into diff rendering.
+///
+/// Clients that were sent the same frames share it, so that the diff for them is computed once
+/// as long as they keep being sent the same thing (eg. when they are focused on the same tab).
+#[derive(Debug, Default)]
+pub struct RenderDiffState {
+    rendered_frames: HashMap<ClientId, Rc<RenderedFrame>>,
+    empty_frame: Rc<RenderedFrame>,
+}
+
+impl RenderDiffState {
+    pub fn add_client(&mut self, client_id: ClientId) {
+        self.rendered_frames
+            .insert(client_id, self.empty_frame.clone());
+    }
+    pub fn remove_client(&mut self, client_id: ClientId) {
+        self.rendered_frames.remove(&client_id);
+    }
+    pub fn has_client(&self, client_id: ClientId) -> bool {
+        self.rendered_frames.contains_key(&client_id)
+    }
+    pub fn diff_lines_for_clients(
+        &mut self,
+        render_inputs: HashMap<ClientId, ClientRenderInput>,
+        link_handler: Option<&Ref<LinkHandler>>,
+        styled_underlines: bool,
+    ) -> Result<HashMap<ClientId, Vec<RenderDiffLine>>> {
+        let err_context = || "failed to diff render for clients".to_string();
+
+        // clients that were last sent the same frame and are now sent the same input get the
+        // same diff
+        let mut client_groups: Vec<(Rc<RenderedFrame>, ClientRenderInput, Vec<ClientId>)> = vec![];
+        for (client_id, render_input) in render_inputs {
+            let previous_frame = match self.rendered_frames.remove(&client_id) {
+                Some(previous_frame) if !render_input.display_cleared => previous_frame,
+                _ => self.empty_frame.clone(),
+            };
+            match client_groups.iter_mut().find(|(frame, input, _)| {
+                Rc::ptr_eq(frame, &previous_frame) && input == &render_input
+            }) {
+                Some((_, _, client_ids)) => client_ids.push(client_id),
+                None => client_groups.push((previous_frame, render_input, vec![client_id])),
+            }
+        }
+        let mut diff_lines = HashMap::new();
+        for (mut frame, render_input, client_ids) in client_groups {
+            // the frame is only copied if it is still shared with clients outside this group
+            let rendered_frame = Rc::make_mut(&mut frame);
+            let lines = rendered_frame
+                .diff_character_chunks(
+                    &render_input.character_chunks,
+                    link_handler,
+                    styled_underlines,
+                )
+                .with_context(err_context)?;
+            for region in &render_input.invalidated_regions {
+                rendered_frame.invalidate_region(region);
+            }
+            for client_id in client_ids {
+                self.rendered_frames.insert(client_id, frame.clone());
+                diff_lines.insert(client_id, lines.clone());
+            }
+        }
+        Ok(diff_lines)
+    }
+}
@@ -0,0 +1,129 @@
+use super::*;
+use crate::panes::kitty_graphics::{KittyGraphicsCommand, KittyGrid};
+use zellij_utils::pane_size::{Dimension, Size};
+
+// a 10x10 pixel image placed on 10x10 cells at the top left of the screen
+fn kitty_image_chunk() -> KittyImageChunk {
+    let mut kitty_grid = KittyGrid::default();
+    let payload = base64::encode(vec![0; 10 * 10 * 3]);
+    let command = KittyGraphicsCommand::parse(
+        format!("a=T,q=2,f=24,s=10,v=10,c=10,r=10;{}", payload).as_bytes(),
+    )
+    .unwrap();
+    let pane_size = Size { rows: 50, cols: 50 };
+    kitty_grid.handle_command(command, 0, 0, None, pane_size);
+    kitty_grid
+        .image_chunks_in_viewport(pane_size.cols, pane_size.rows, 0, 0, 0)
+        .remove(0)
+}
+
+fn pane_geom(x: usize, y: usize, cols: usize, rows: usize) -> PaneGeom {
+    PaneGeom {
+        x,
+        y,
+        cols: Dimension::fixed(cols),
+        rows: Dimension::fixed(rows),
+        ..Default::default()
+    }
+}
+
+// (cell_x, cell_y, columns, rows, source_x, source_y, source_width, source_height)
+fn chunk_geometry(
+    chunk: &KittyImageChunk,
+) -> (usize, usize, usize, usize, usize, usize, usize, usize) {
+    (
+        chunk.cell_x,
+        chunk.cell_y,
+        chunk.columns,
+        chunk.rows,
+        chunk.source_x,
+        chunk.source_y,
+        chunk.source_width,
+        chunk.source_height,
+    )
+}
+
+#[test]
+fn kitty_image_is_cut_around_a_pane_covering_its_middle() {
+    let floating_panes_stack = FloatingPanesStack {
+        layers: vec![pane_geom(3, 3, 4, 4)],
+    };
+    let visible_chunks =
+        floating_panes_stack.visible_kitty_image_chunks(vec![kitty_image_chunk()], None);
+    let visible_chunks: Vec<_> = visible_chunks.iter().map(chunk_geometry).collect();
+    assert_eq!(
+        visible_chunks,
+        vec![
+            (0, 0, 10, 3, 0, 0, 10, 3), // above the pane
+            (0, 7, 10, 3, 0, 7, 10, 3), // below the pane
+            (0, 3, 3, 4, 0, 3, 3, 4),   // to the left of the pane
+            (7, 3, 3, 4, 7, 3, 3, 4),   // to the right of the pane
+        ]
+    );
+}
+
+#[test]
+fn kitty_image_partly_covered_by_a_pane_is_cropped() {
+    let floating_panes_stack = FloatingPanesStack {
+        layers: vec![pane_geom(5, 0, 20, 20)],
+    };
+    let visible_chunks =
+        floating_panes_stack.visible_kitty_image_chunks(vec![kitty_image_chunk()], None);
+    let visible_chunks: Vec<_> = visible_chunks.iter().map(chunk_geometry).collect();
+    assert_eq!(visible_chunks, vec![(0, 0, 5, 10, 0, 0, 5, 10)]);
+}
+
+#[test]
+fn kitty_image_outside_of_a_pane_is_not_cut() {
+    let floating_panes_stack = FloatingPanesStack {
+        layers: vec![pane_geom(10, 10, 5, 5)],
+    };
+    let visible_chunks =
+        floating_panes_stack.visible_kitty_image_chunks(vec![kitty_image_chunk()], None);
+    let visible_chunks: Vec<_> = visible_chunks.iter().map(chunk_geometry).collect();
+    assert_eq!(visible_chunks, vec![(0, 0, 10, 10, 0, 0, 10, 10)]);
+}
+
+#[test]
+fn kitty_image_fully_covered_by_a_pane_is_hidden() {
+    let floating_panes_stack = FloatingPanesStack {
+        layers: vec![pane_geom(0, 0, 10, 10)],
+    };
+    let visible_chunks =
+        floating_panes_stack.visible_kitty_image_chunks(vec![kitty_image_chunk()], None);
+    assert!(visible_chunks.is_empty());
+}
+
+#[test]
+fn kitty_image_placeholder_is_sent_to_clients_without_kitty_graphics() {
+    let kitty_graphics_output_state = Rc::new(RefCell::new(KittyGraphicsOutputState::default()));
+    kitty_graphics_output_state
+        .borrow_mut()
+        .add_supported_client(1);
+    let mut output = Output::new(
+        Rc::new(RefCell::new(SixelImageStore::default())),
+        kitty_graphics_output_state,
+        Rc::new(RefCell::new(None)),
+        true,
+    );
+    let client_ids: HashSet<ClientId> = [1, 2].into_iter().collect();
+    output.add_clients(&client_ids, Rc::new(RefCell::new(LinkHandler::new())), None);
+    let chunks = vec![kitty_image_chunk()];
+    output.add_kitty_image_chunks_to_multiple_clients(
+        chunks.clone(),
+        client_ids.iter().copied(),
+        None,
+    );
+    output
+        .add_kitty_image_placeholders_to_multiple_clients(chunks, client_ids.iter().copied(), None)
+        .unwrap();
+    let serialized_output = output.serialize().unwrap();
+
+    let supported_client_output = serialized_output.get(&1).unwrap();
+    assert!(supported_client_output.contains("\u{1b}_Ga=p,"));
+    assert!(!supported_client_output.contains("[image]"));
+
+    let unsupported_client_output = serialized_output.get(&2).unwrap();
+    assert!(!unsupported_client_output.contains("\u{1b}_G"));
+    assert!(unsupported_client_output.contains("[image]"));
+}
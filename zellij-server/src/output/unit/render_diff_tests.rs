@@ -0,0 +1,179 @@
+use super::*;
+use zellij_utils::ipc::{RenderDiffLine, RenderDiffSpan};
+
+fn create_output() -> Output {
+    Output::new(
+        Rc::new(RefCell::new(SixelImageStore::default())),
+        Rc::new(RefCell::new(KittyGraphicsOutputState::default())),
+        Rc::new(RefCell::new(None)),
+        true,
+    )
+}
+
+fn render_line(
+    render_diff_state: &mut RenderDiffState,
+    client_ids: &[ClientId],
+    line: &str,
+    x: usize,
+    y: usize,
+) -> (HashMap<ClientId, RenderDiff>, HashMap<ClientId, String>) {
+    let mut output = create_output();
+    let client_ids: HashSet<ClientId> = client_ids.iter().copied().collect();
+    output.add_clients(&client_ids, Rc::new(RefCell::new(LinkHandler::new())), None);
+    let terminal_characters = line.chars().map(TerminalCharacter::new).collect();
+    output
+        .add_character_chunks_to_multiple_clients(
+            vec![CharacterChunk::new(terminal_characters, x, y)],
+            client_ids.iter().copied(),
+            None,
+        )
+        .unwrap();
+    let render_diffs = output.serialize_render_diffs(render_diff_state).unwrap();
+    let serialized_output = output.serialize().unwrap();
+    (render_diffs, serialized_output)
+}
+
+fn unstyled_line(x: usize, y: usize, text: &str) -> RenderDiffLine {
+    RenderDiffLine {
+        x,
+        y,
+        spans: vec![RenderDiffSpan {
+            styles: String::new(),
+            text: text.to_owned(),
+        }],
+    }
+}
+
+#[test]
+pub fn render_diff_sends_whole_chunk_on_first_render() {
+    let mut render_diff_state = RenderDiffState::default();
+    render_diff_state.add_client(1);
+    let (render_diffs, serialized_output) =
+        render_line(&mut render_diff_state, &[1], "hello", 2, 3);
+    assert_eq!(
+        render_diffs.get(&1).map(|r| r.lines.clone()),
+        Some(vec![unstyled_line(2, 3, "hello")]),
+        "whole chunk sent to client without a previous frame"
+    );
+    assert!(
+        serialized_output.is_empty(),
+        "diff client is not sent a full render"
+    );
+}
+
+#[test]
+pub fn render_diff_skips_unchanged_cells() {
+    let mut render_diff_state = RenderDiffState::default();
+    render_diff_state.add_client(1);
+    render_line(&mut render_diff_state, &[1], "hello world", 0, 0);
+    let (render_diffs, _) = render_line(&mut render_diff_state, &[1], "hello world", 0, 0);
+    assert!(render_diffs.is_empty(), "nothing sent when nothing changed");
+    let (render_diffs, _) = render_line(&mut render_diff_state, &[1], "hello_world", 0, 0);
+    assert_eq!(
+        render_diffs.get(&1).map(|r| r.lines.clone()),
+        Some(vec![unstyled_line(5, 0, "_")]),
+        "only the changed cell is sent"
+    );
+    let (render_diffs, _) = render_line(&mut render_diff_state, &[1], "jello_worlD", 0, 0);
+    assert_eq!(
+        render_diffs.get(&1).map(|r| r.lines.clone()),
+        Some(vec![unstyled_line(0, 0, "j"), unstyled_line(10, 0, "D")]),
+        "changed cells that are far apart are sent separately"
+    );
+    let (render_diffs, _) = render_line(&mut render_diff_state, &[1], "jelLo_WorlD", 0, 0);
+    assert_eq!(
+        render_diffs.get(&1).map(|r| r.lines.clone()),
+        Some(vec![unstyled_line(3, 0, "Lo_W")]),
+        "changed cells that are close to each other are sent together"
+    );
+}
+
+#[test]
+pub fn render_diff_is_shared_between_clients_with_the_same_view() {
+    let mut render_diff_state = RenderDiffState::default();
+    render_diff_state.add_client(1);
+    render_diff_state.add_client(2);
+    render_line(&mut render_diff_state, &[1, 2], "hello world", 0, 0);
+    let (render_diffs, serialized_output) =
+        render_line(&mut render_diff_state, &[1, 2, 3], "hello_world", 0, 0);
+    assert_eq!(
+        render_diffs.get(&1),
+        render_diffs.get(&2),
+        "both clients get the same diff"
+    );
+    assert_eq!(
+        render_diffs.get(&1).map(|r| r.lines.clone()),
+        Some(vec![unstyled_line(5, 0, "_")]),
+    );
+    assert!(
+        !render_diffs.contains_key(&3) && serialized_output.contains_key(&3),
+        "client that did not opt in gets a full render"
+    );
+}
+
+#[test]
+pub fn render_diff_sends_everything_after_display_is_cleared() {
+    let mut render_diff_state = RenderDiffState::default();
+    render_diff_state.add_client(1);
+    render_line(&mut render_diff_state, &[1], "hello", 0, 0);
+
+    let mut output = create_output();
+    output.add_clients(
+        &HashSet::from([1]),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        None,
+    );
+    output.add_clear_display_instruction_to_multiple_clients([1].into_iter());
+    let terminal_characters = "hello".chars().map(TerminalCharacter::new).collect();
+    output
+        .add_character_chunks_to_client(
+            1,
+            vec![CharacterChunk::new(terminal_characters, 0, 0)],
+            None,
+        )
+        .unwrap();
+    let render_diffs = output
+        .serialize_render_diffs(&mut render_diff_state)
+        .unwrap();
+    assert_eq!(
+        render_diffs.get(&1),
+        Some(&RenderDiff {
+            pre_vte: "\u{1b}[2J".to_owned(),
+            lines: vec![unstyled_line(0, 0, "hello")],
+            post_vte: String::new(),
+        }),
+    );
+}
+
+#[test]
+pub fn render_diff_resends_invalidated_regions() {
+    let mut render_diff_state = RenderDiffState::default();
+    render_diff_state.add_client(1);
+    render_line(&mut render_diff_state, &[1], "hello world", 0, 0);
+
+    let mut output = create_output();
+    output.add_clients(
+        &HashSet::from([1]),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        None,
+    );
+    output.invalidate_region_for_client(
+        1,
+        InvalidatedRegion {
+            x: 2,
+            y: 0,
+            columns: 2,
+            rows: 1,
+        },
+    );
+    output
+        .serialize_render_diffs(&mut render_diff_state)
+        .unwrap();
+
+    let (render_diffs, _) = render_line(&mut render_diff_state, &[1], "hello world", 0, 0);
+    assert_eq!(
+        render_diffs.get(&1).map(|r| r.lines.clone()),
+        Some(vec![unstyled_line(2, 0, "ll")]),
+        "cells drawn over by something else are sent again"
+    );
+}
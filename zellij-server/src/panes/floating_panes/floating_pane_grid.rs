@@ -859,6 +859,7 @@ pub fn half_size_middle_geom(space: &Viewport, offset: usize) -> PaneGeom {
         stacked: None,
         is_pinned: false,
         logical_position: None,
+        size_bounds: Default::default(),
     };
     geom.cols.set_inner(space.cols / 2);
     geom.rows.set_inner(space.rows / 2);
@@ -874,6 +875,7 @@ fn half_size_top_left_geom(space: &Viewport, offset: usize) -> PaneGeom {
         stacked: None,
         is_pinned: false,
         logical_position: None,
+        size_bounds: Default::default(),
     };
     geom.cols.set_inner(space.cols / 3);
     geom.rows.set_inner(space.rows / 3);
@@ -889,6 +891,7 @@ fn half_size_top_right_geom(space: &Viewport, offset: usize) -> PaneGeom {
         stacked: None,
         is_pinned: false,
         logical_position: None,
+        size_bounds: Default::default(),
     };
     geom.cols.set_inner(space.cols / 3);
     geom.rows.set_inner(space.rows / 3);
@@ -904,6 +907,7 @@ fn half_size_bottom_left_geom(space: &Viewport, offset: usize) -> PaneGeom {
         stacked: None,
         is_pinned: false,
         logical_position: None,
+        size_bounds: Default::default(),
     };
     geom.cols.set_inner(space.cols / 3);
     geom.rows.set_inner(space.rows / 3);
@@ -919,6 +923,7 @@ fn half_size_bottom_right_geom(space: &Viewport, offset: usize) -> PaneGeom {
         stacked: None,
         is_pinned: false,
         logical_position: None,
+        size_bounds: Default::default(),
     };
     geom.cols.set_inner(space.cols / 3);
     geom.rows.set_inner(space.rows / 3);
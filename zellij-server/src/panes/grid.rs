@@ -1,3 +1,4 @@
+use super::kitty_graphics::{KittyGraphicsParser, KittyGrid};
 use super::sixel::{PixelRect, SixelGrid, SixelImageStore};
 use std::borrow::Cow;
 use std::cell::RefCell;
@@ -10,15 +11,17 @@ use std::{
     cmp::Ordering,
     collections::{BTreeSet, VecDeque},
     fmt::{self, Debug, Formatter},
+    path::{Path, PathBuf},
     str,
 };
 
+use url::Url;
 use vte;
 use zellij_utils::{
     consts::{DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE},
-    data::{Palette, PaletteColor, Styling},
+    data::{Palette, PaletteColor, PaneScrollback, ScrollbackRange, Styling},
     input::mouse::{MouseEvent, MouseEventType},
-    pane_size::SizeInPixels,
+    pane_size::{Size, SizeInPixels},
     position::Position,
 };
 
@@ -28,10 +31,11 @@ pub const MAX_TITLE_STACK_SIZE: usize = 1000;
 use vte::{Params, Perform};
 use zellij_utils::{consts::VERSION, shared::version_number};
 
-use crate::output::{CharacterChunk, OutputBuffer, SixelImageChunk};
+use crate::output::{CharacterChunk, KittyImageChunk, OutputBuffer, SixelImageChunk};
 use crate::panes::alacritty_functions::{parse_number, xparse_color};
 use crate::panes::hyperlink_tracker::HyperlinkTracker;
 use crate::panes::link_handler::LinkHandler;
+use crate::panes::scrollback_store::ScrollbackStore;
 use crate::panes::search::SearchResult;
 use crate::panes::selection::Selection;
 use crate::panes::terminal_character::{
@@ -120,6 +124,7 @@ fn transfer_rows_from_lines_above_to_viewport(
     lines_above: &mut VecDeque<Row>,
     viewport: &mut Vec<Row>,
     sixel_grid: &mut SixelGrid,
+    kitty_grid: &mut KittyGrid,
     count: usize,
     max_viewport_width: usize,
 ) -> usize {
@@ -151,7 +156,7 @@ fn transfer_rows_from_lines_above_to_viewport(
     }
     if !next_lines.is_empty() {
         let excess_row = Row::from_rows(next_lines);
-        bounded_push(lines_above, sixel_grid, excess_row);
+        bounded_push(lines_above, sixel_grid, kitty_grid, excess_row);
     }
     match usize::try_from(lines_added_to_viewport) {
         Ok(n) => n,
@@ -163,6 +168,7 @@ fn transfer_rows_from_viewport_to_lines_above(
     viewport: &mut Vec<Row>,
     lines_above: &mut VecDeque<Row>,
     sixel_grid: &mut SixelGrid,
+    kitty_grid: &mut KittyGrid,
     count: usize,
     max_viewport_width: usize,
 ) -> isize {
@@ -178,7 +184,12 @@ fn transfer_rows_from_viewport_to_lines_above(
             next_lines.append(&mut bottom_canonical_row_and_wraps_in_dst);
         }
         next_lines.push(next_line);
-        let dropped_line_width = bounded_push(lines_above, sixel_grid, Row::from_rows(next_lines));
+        let dropped_line_width = bounded_push(
+            lines_above,
+            sixel_grid,
+            kitty_grid,
+            Row::from_rows(next_lines),
+        );
         if let Some(width) = dropped_line_width {
             transferred_rows_count -=
                 calculate_row_display_height(width, max_viewport_width) as isize;
@@ -227,12 +238,18 @@ fn transfer_rows_from_lines_below_to_viewport(
     }
 }
 
-fn bounded_push(vec: &mut VecDeque<Row>, sixel_grid: &mut SixelGrid, value: Row) -> Option<usize> {
+fn bounded_push(
+    vec: &mut VecDeque<Row>,
+    sixel_grid: &mut SixelGrid,
+    kitty_grid: &mut KittyGrid,
+    value: Row,
+) -> Option<usize> {
     let mut dropped_line_width = None;
     if vec.len() >= *SCROLL_BUFFER_SIZE.get().unwrap() {
         let line = vec.pop_front();
         if let Some(line) = line {
             sixel_grid.offset_grid_top();
+            kitty_grid.offset_grid_top();
             dropped_line_width = Some(line.width());
         }
     }
@@ -287,6 +304,18 @@ macro_rules! dump_screen {
     }};
 }
 
+fn parse_osc_7_uri(uri: &str) -> Option<PathBuf> {
+    // file://hostname/path/to/dir - the hostname is ignored since the shell might be running on a
+    // remote machine (eg. over ssh) and we still want to know the path it is in
+    let url = Url::parse(uri).ok()?;
+    if url.scheme() != "file" {
+        return None;
+    }
+    let mut local_url = Url::parse("file:///").ok()?;
+    local_url.set_path(url.path());
+    local_url.to_file_path().ok()
+}
+
 fn utf8_mouse_coordinates(column: usize, line: isize) -> Vec<u8> {
     let mut coordinates = vec![];
     let mouse_pos_encode = |pos: usize| -> Vec<u8> {
@@ -328,6 +357,8 @@ pub struct Grid {
     title_stack: Vec<String>,
     character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
     sixel_grid: SixelGrid,
+    kitty_grid: KittyGrid,
+    kitty_graphics_parser: KittyGraphicsParser,
     pub changed_colors: Option<[Option<AnsiCode>; 256]>,
     pub should_render: bool,
     pub lock_renders: bool,
@@ -353,6 +384,8 @@ pub struct Grid {
     pub focus_event_tracking: bool,
     pub search_results: SearchResult,
     pub pending_clipboard_update: Option<String>,
+    pub pending_cwd_update: Option<PathBuf>,
+    pub pending_notifications: Vec<(Option<String>, String)>, // (title, body)
     ui_component_bytes: Option<Vec<u8>>,
     style: Style,
     debug: bool,
@@ -463,6 +496,30 @@ impl Debug for Grid {
             }
         }
 
+        // display kitty graphics placeholder
+        let kitty_indication_character = |x| {
+            let kitty_indication_word = "Kitty";
+            kitty_indication_word
+                .chars()
+                .nth(x % kitty_indication_word.len())
+                .unwrap()
+        };
+        for image_coordinates in self
+            .kitty_grid
+            .image_cell_coordinates_in_viewport(self.height, self.lines_above.len())
+        {
+            let (image_top_edge, image_bottom_edge, image_left_edge, image_right_edge) =
+                image_coordinates;
+            for y in image_top_edge..image_bottom_edge {
+                let row = buffer.get_mut(y).unwrap();
+                for x in image_left_edge..image_right_edge {
+                    let fake_kitty_terminal_character =
+                        TerminalCharacter::new_singlewidth(kitty_indication_character(x));
+                    row.add_character_at(fake_kitty_terminal_character, x);
+                }
+            }
+        }
+
         // display terminal characters with stripped styles
         for (i, row) in buffer.iter().enumerate() {
             let mut cow_row = Cow::Borrowed(row);
@@ -540,7 +597,11 @@ impl Grid {
             character_cell_size,
             search_results: Default::default(),
             sixel_grid,
+            kitty_grid: KittyGrid::default(),
+            kitty_graphics_parser: KittyGraphicsParser::default(),
             pending_clipboard_update: None,
+            pending_cwd_update: None,
+            pending_notifications: vec![],
             ui_component_bytes: None,
             style,
             debug,
@@ -718,6 +779,7 @@ impl Grid {
                 &mut self.lines_above,
                 &mut self.viewport,
                 &mut self.sixel_grid,
+                &mut self.kitty_grid,
                 1,
                 self.width,
             );
@@ -747,7 +809,7 @@ impl Grid {
             } else {
                 match self.lines_above.pop_back() {
                     Some(mut last_line_above) => {
-                        last_line_above.append(&mut line_to_push_up.columns);
+                        last_line_above.append_row(&mut line_to_push_up);
                         last_line_above
                     },
                     None => {
@@ -758,8 +820,12 @@ impl Grid {
                 }
             };
 
-            let dropped_line_width =
-                bounded_push(&mut self.lines_above, &mut self.sixel_grid, line_to_push_up);
+            let dropped_line_width = bounded_push(
+                &mut self.lines_above,
+                &mut self.sixel_grid,
+                &mut self.kitty_grid,
+                line_to_push_up,
+            );
             if let Some(width) = dropped_line_width {
                 let dropped_line_height = calculate_row_display_height(width, self.width);
 
@@ -831,7 +897,7 @@ impl Grid {
                     && !self.lines_above.is_empty()
                 {
                     let mut first_line_above = self.lines_above.pop_back().unwrap();
-                    first_line_above.append(&mut row.columns);
+                    first_line_above.append_row(&mut row);
                     viewport_canonical_lines.push(first_line_above);
                     cursor_canonical_line_index += 1;
                 } else if row.is_canonical {
@@ -839,7 +905,7 @@ impl Grid {
                 } else {
                     match viewport_canonical_lines.last_mut() {
                         Some(last_line) => {
-                            last_line.append(&mut row.columns);
+                            last_line.append_row(&mut row);
                         },
                         None => {
                             // the state is corrupted somehow
@@ -979,6 +1045,7 @@ impl Grid {
                         &mut self.lines_above,
                         &mut self.viewport,
                         &mut self.sixel_grid,
+                        &mut self.kitty_grid,
                         row_count_to_transfer,
                         new_columns,
                     );
@@ -1006,6 +1073,7 @@ impl Grid {
                         &mut self.viewport,
                         &mut self.lines_above,
                         &mut self.sixel_grid,
+                        &mut self.kitty_grid,
                         row_count_to_transfer,
                         new_columns,
                     );
@@ -1091,6 +1159,56 @@ impl Grid {
 
         (changed_character_chunks, changed_sixel_image_chunks)
     }
+    pub fn max_viewer_scroll(&self) -> usize {
+        self.lines_above.len()
+    }
+    pub fn render_scrolled_back(
+        &self,
+        lines_back: usize,
+        content_x: usize,
+        content_y: usize,
+    ) -> Vec<CharacterChunk> {
+        // renders the viewport as it looks lines_back lines up the scrollback, without touching
+        // the shared scroll position (used for clients that scroll only their own view)
+        let lines_back = lines_back.min(self.max_viewer_scroll());
+        let end = self.lines_above.len() + self.viewport.len() - lines_back;
+        let start = end.saturating_sub(self.height);
+        let rows: Vec<Row> = self
+            .lines_above
+            .iter()
+            .chain(self.viewport.iter())
+            .skip(start)
+            .take(end - start)
+            .cloned()
+            .collect();
+        let mut output_buffer = self.output_buffer.clone();
+        output_buffer.update_all_lines();
+        let mut character_chunks = output_buffer.changed_chunks_in_viewport(
+            &rows,
+            self.width,
+            self.height,
+            content_x,
+            content_y,
+        );
+        for character_chunk in character_chunks.iter_mut() {
+            character_chunk.add_changed_colors(self.changed_colors);
+        }
+        character_chunks
+    }
+    pub fn kitty_image_chunks(&self, x_offset: usize, y_offset: usize) -> Vec<KittyImageChunk> {
+        // unlike sixel images, these are re-sent to the clients as placements and diffed per
+        // client in the output, so we always return all the ones visible in the viewport
+        if !self.kitty_grid.has_placements() {
+            return vec![];
+        }
+        self.kitty_grid.image_chunks_in_viewport(
+            self.width,
+            self.height,
+            self.lines_above.len(),
+            x_offset,
+            y_offset,
+        )
+    }
     pub fn serialize(&self, scrollback_lines_to_serialize: Option<usize>) -> Option<String> {
         match scrollback_lines_to_serialize {
             Some(scrollback_lines_to_serialize) => {
@@ -1113,6 +1231,65 @@ impl Grid {
             None => self.output_buffer.serialize(&self.viewport).ok(),
         }
     }
+    pub fn persist_scrollback(
+        &self,
+        scrollback_store: &mut ScrollbackStore,
+        folder: &Path,
+        scrollback_lines_to_persist: usize,
+    ) -> Result<()> {
+        if self.alternate_screen_state.is_some() {
+            // the scrollback of the main screen is put aside until we leave the alternate screen,
+            // so we leave it as it was last persisted
+            return Ok(());
+        }
+        let first_index = if scrollback_lines_to_persist == 0 {
+            0
+        } else {
+            self.lines_above
+                .len()
+                .saturating_sub(scrollback_lines_to_persist)
+        };
+        let lines: Vec<&Row> = self.lines_above.iter().skip(first_index).collect();
+        scrollback_store.persist(folder, &lines, |line| {
+            self.output_buffer.serialize_line(line)
+        })
+    }
+    /// Places lines (eg. read from a `ScrollbackStore`) above the viewport, this is meant to be
+    /// done before anything else is written to the grid
+    pub fn restore_scrollback(&mut self, lines: Vec<String>) {
+        if lines.is_empty() {
+            return;
+        }
+        let mut vte_parser = vte::Parser::new();
+        for (index, line) in lines.iter().enumerate() {
+            if index > 0 {
+                for byte in "\r\n".bytes() {
+                    vte_parser.advance(self, byte);
+                }
+            }
+            // every line is serialized starting from the default styles
+            for byte in "\u{1b}[m".bytes().chain(line.bytes()) {
+                vte_parser.advance(self, byte);
+            }
+        }
+        for byte in "\u{1b}[m".bytes() {
+            vte_parser.advance(self, byte);
+        }
+        let restored_rows = self.cursor.y + 1;
+        transfer_rows_from_viewport_to_lines_above(
+            &mut self.viewport,
+            &mut self.lines_above,
+            &mut self.sixel_grid,
+            &mut self.kitty_grid,
+            restored_rows,
+            self.width,
+        );
+        self.viewport = vec![Row::new().canonical()];
+        self.cursor.x = 0;
+        self.cursor.y = 0;
+        self.scrollback_buffer_lines = self.recalculate_scrollback_buffer_count();
+        self.output_buffer.update_all_lines();
+    }
     pub fn render(
         &mut self,
         content_x: usize,
@@ -1224,6 +1401,152 @@ impl Grid {
         scrollback.push_str(&viewport);
         scrollback
     }
+    /// All the rows of the grid (lines above, viewport and lines below) grouped by the unwrapped
+    /// line they belong to
+    pub(crate) fn logical_lines(&self) -> Vec<Vec<&Row>> {
+        let mut lines: Vec<Vec<&Row>> = vec![];
+        for row in self
+            .lines_above
+            .iter()
+            .chain(self.viewport.iter())
+            .chain(self.lines_below.iter())
+        {
+            match lines.last_mut() {
+                Some(line) if !row.is_canonical => line.push(row),
+                _ => lines.push(vec![row]),
+            }
+        }
+        lines
+    }
+    /// Returns the unwrapped lines in the given range, either as plain text or with their styles
+    /// serialized as ANSI escape sequences
+    pub fn pane_scrollback(&self, range: ScrollbackRange, with_styles: bool) -> PaneScrollback {
+        let lines = self.logical_lines();
+        let total_lines = lines.len();
+        let (start, end) = match range {
+            ScrollbackRange::Viewport => {
+                // the lines that have at least one of their rows in the viewport
+                let first_viewport_row = self.lines_above.len();
+                let viewport_end_row = first_viewport_row + self.viewport.len();
+                let mut row_index = 0;
+                let mut start = None;
+                let mut end = 0;
+                for (line_index, line) in lines.iter().enumerate() {
+                    let line_end_row = row_index + line.len();
+                    if line_end_row > first_viewport_row && row_index < viewport_end_row {
+                        start.get_or_insert(line_index);
+                        end = line_index + 1;
+                    }
+                    row_index = line_end_row;
+                }
+                (start.unwrap_or(end), end)
+            },
+            ScrollbackRange::Full => (0, total_lines),
+            ScrollbackRange::Lines { start, end } => {
+                let end = end.min(total_lines);
+                (start.min(end), end)
+            },
+            ScrollbackRange::Last(line_count) => {
+                (total_lines.saturating_sub(line_count), total_lines)
+            },
+        };
+        let lines = lines[start..end]
+            .iter()
+            .map(|line| {
+                if with_styles {
+                    line.iter()
+                        .filter_map(|row| self.output_buffer.serialize_line(row).ok())
+                        .collect()
+                } else {
+                    let line: String = line
+                        .iter()
+                        .flat_map(|row| row.columns.iter().map(|c| c.character))
+                        .collect();
+                    line.trim_end().to_owned()
+                }
+            })
+            .collect();
+        PaneScrollback {
+            lines,
+            first_line_index: start,
+            total_lines,
+            with_styles,
+        }
+    }
+    fn add_prompt_mark(&mut self, prompt_mark: PromptMark) {
+        self.pad_lines_until(self.cursor.y, EMPTY_TERMINAL_CHARACTER);
+        if let Some(row) = self.viewport.get_mut(self.cursor.y) {
+            row.prompt_marks.push(prompt_mark);
+        }
+    }
+    pub fn scroll_to_previous_prompt(&mut self) -> bool {
+        // returns true if there was a prompt to scroll to
+        if !self.lines_above.iter().any(|row| row.starts_prompt()) {
+            return false;
+        }
+        loop {
+            let lines_below_count = self.lines_below.len();
+            self.scroll_up_one_line();
+            let scrolled = self.lines_below.len() != lines_below_count;
+            let prompt_at_top = self.viewport.first().map(|row| row.starts_prompt());
+            if !scrolled || prompt_at_top.unwrap_or(false) {
+                break;
+            }
+        }
+        self.output_buffer.update_all_lines();
+        true
+    }
+    pub fn scroll_to_next_prompt(&mut self) -> bool {
+        // returns true if there was a prompt to scroll to
+        let has_next_prompt = self
+            .viewport
+            .iter()
+            .skip(1)
+            .chain(self.lines_below.iter())
+            .any(|row| row.starts_prompt());
+        if !has_next_prompt {
+            return false;
+        }
+        loop {
+            let lines_below_count = self.lines_below.len();
+            self.scroll_down_one_line();
+            let scrolled = self.lines_below.len() != lines_below_count;
+            let prompt_at_top = self.viewport.first().map(|row| row.starts_prompt());
+            if !scrolled || prompt_at_top.unwrap_or(false) {
+                break;
+            }
+        }
+        self.output_buffer.update_all_lines();
+        true
+    }
+    pub fn last_command_output(&self) -> Option<String> {
+        // the lines between the last OSC 133 output mark and the end of that command
+        let rows: Vec<&Row> = self
+            .lines_above
+            .iter()
+            .chain(self.viewport.iter())
+            .chain(self.lines_below.iter())
+            .collect();
+        let output_start = rows
+            .iter()
+            .rposition(|row| row.prompt_marks.contains(&PromptMark::OutputStart))?;
+        let ends_on_same_line = rows[output_start]
+            .prompt_marks
+            .iter()
+            .skip_while(|mark| **mark != PromptMark::OutputStart)
+            .any(|mark| mark.ends_output());
+        if ends_on_same_line {
+            return Some(String::new());
+        }
+        let output_end = rows
+            .iter()
+            .skip(output_start + 1)
+            .position(|row| row.prompt_marks.iter().any(|mark| mark.ends_output()))
+            .map(|index| output_start + 1 + index)
+            .unwrap_or(rows.len());
+        let output_rows = rows[output_start..output_end].to_vec();
+        Some(dump_screen!(output_rows))
+    }
     pub fn move_viewport_up(&mut self, count: usize) {
         for _ in 0..count {
             self.scroll_up_one_line();
@@ -1754,6 +2077,7 @@ impl Grid {
         if let Some(images_to_reap) = self.sixel_grid.clear() {
             self.sixel_grid.reap_images(images_to_reap);
         }
+        self.kitty_grid.clear();
     }
     fn set_preceding_character(&mut self, terminal_character: TerminalCharacter) {
         self.preceding_char = Some(terminal_character);
@@ -2041,6 +2365,7 @@ impl Grid {
             &mut self.viewport,
             &mut self.lines_above,
             &mut self.sixel_grid,
+            &mut self.kitty_grid,
             count,
             self.width,
         );
@@ -2073,6 +2398,39 @@ impl Grid {
             None
         }
     }
+    pub fn handle_kitty_graphics_byte(&mut self, byte: u8) {
+        let command = match self.kitty_graphics_parser.advance(byte) {
+            Some(command) => command,
+            None => return,
+        };
+        let cursor_line = (self.lines_above.len() + self.cursor.y) as isize;
+        let character_cell_size = *self.character_cell_size.borrow();
+        let result = self.kitty_grid.handle_command(
+            command,
+            self.cursor.x,
+            cursor_line,
+            character_cell_size,
+            Size {
+                rows: self.height,
+                cols: self.width,
+            },
+        );
+        if let Some(response) = result.response {
+            self.pending_messages_to_pty.push(response.into_bytes());
+        }
+        if let Some((columns, rows)) = result.cursor_movement {
+            // the cursor is placed after the bottom right corner of the image
+            let cursor_x = self.cursor.x;
+            for _ in 0..rows.saturating_sub(1) {
+                self.add_canonical_line();
+            }
+            self.cursor.x = cursor_x;
+            self.move_cursor_forward_until_edge(columns);
+        }
+        if result.should_render {
+            self.render_full_viewport();
+        }
+    }
     fn create_sixel_image(&mut self) {
         if let Some((x_pixel_coordinates, y_pixel_coordinates)) =
             self.current_cursor_pixel_coordinates()
@@ -2590,6 +2948,21 @@ impl Perform for Grid {
                 }
             },
 
+            // Report current working directory.
+            b"7" => {
+                if params.len() < 2 {
+                    return;
+                }
+                let uri = params[1..]
+                    .iter()
+                    .flat_map(|x| str::from_utf8(x))
+                    .collect::<Vec<&str>>()
+                    .join(";");
+                if let Some(cwd) = parse_osc_7_uri(&uri) {
+                    self.pending_cwd_update = Some(cwd);
+                }
+            },
+
             // define hyperlink
             b"8" => {
                 if params.len() < 3 {
@@ -2600,6 +2973,23 @@ impl Perform for Grid {
                 })
             },
 
+            // Desktop notification (iTerm2 style): OSC 9 ; body
+            b"9" => {
+                if params.len() < 2 {
+                    return;
+                }
+                if params.len() > 2 && params[1].iter().all(|b| b.is_ascii_digit()) {
+                    // ConEmu style sub-commands (eg. OSC 9 ; 4 for progress) are not notifications
+                    return;
+                }
+                let body = params[1..]
+                    .iter()
+                    .flat_map(|x| str::from_utf8(x))
+                    .collect::<Vec<&str>>()
+                    .join(";");
+                self.pending_notifications.push((None, body));
+            },
+
             // Get/set Foreground (b"10") or background (b"11") colors
             b"10" | b"11" => {
                 if params.len() >= 2 {
@@ -2644,6 +3034,25 @@ impl Perform for Grid {
                 // get/set cursor color currently unimplemented
             },
 
+            // Shell integration (semantic prompt) marks.
+            b"133" => {
+                let prompt_mark = match params.get(1).and_then(|p| p.get(0)) {
+                    Some(b'A') => Some(PromptMark::PromptStart),
+                    Some(b'B') => Some(PromptMark::CommandStart),
+                    Some(b'C') => Some(PromptMark::OutputStart),
+                    Some(b'D') => Some(PromptMark::CommandEnd(
+                        params
+                            .get(2)
+                            .and_then(|exit_code| str::from_utf8(exit_code).ok())
+                            .and_then(|exit_code| exit_code.parse().ok()),
+                    )),
+                    _ => None,
+                };
+                if let Some(prompt_mark) = prompt_mark {
+                    self.add_prompt_mark(prompt_mark);
+                }
+            },
+
             // Set cursor style.
             b"50" => {
                 if params.len() >= 2
@@ -2683,6 +3092,20 @@ impl Perform for Grid {
                 }
             },
 
+            // Desktop notification (urxvt style): OSC 777 ; notify ; title ; body
+            b"777" => {
+                if params.len() < 3 || params[1] != b"notify" {
+                    return;
+                }
+                let title = str::from_utf8(params[2]).ok().map(|t| t.to_owned());
+                let body = params[3..]
+                    .iter()
+                    .flat_map(|x| str::from_utf8(x))
+                    .collect::<Vec<&str>>()
+                    .join(";");
+                self.pending_notifications.push((title, body));
+            },
+
             // Reset color index.
             b"104" => {
                 // Reset all color indexes when no parameters are given.
@@ -2792,11 +3215,13 @@ impl Perform for Grid {
                     if let Some(images_to_reap) = self.sixel_grid.clear() {
                         self.sixel_grid.reap_images(images_to_reap);
                     }
+                    self.kitty_grid.clear();
                 } else if clear_type == 3 {
                     self.clear_lines_above();
                     if let Some(images_to_reap) = self.sixel_grid.clear() {
                         self.sixel_grid.reap_images(images_to_reap);
                     }
+                    self.kitty_grid.clear();
                 }
             };
         } else if c == 'H' || c == 'f' {
@@ -2847,6 +3272,7 @@ impl Perform for Grid {
                                     &mut self.viewport,
                                     &mut self.cursor,
                                     &mut self.sixel_grid,
+                                    &mut self.kitty_grid,
                                     &mut self.supports_kitty_keyboard_protocol,
                                 );
                             }
@@ -2950,11 +3376,14 @@ impl Perform for Grid {
                                 &mut self.sixel_grid,
                                 SixelGrid::new(self.character_cell_size.clone(), sixel_image_store),
                             );
+                            let current_kitty_grid =
+                                std::mem::replace(&mut self.kitty_grid, KittyGrid::default());
                             self.alternate_screen_state = Some(AlternateScreenState::new(
                                 current_lines_above,
                                 current_viewport,
                                 current_cursor,
                                 alternate_sixelgrid,
+                                current_kitty_grid,
                                 current_supports_kitty_keyboard_protocol,
                             ));
                             self.clear_viewport_before_rendering = true;
@@ -3427,6 +3856,7 @@ pub struct AlternateScreenState {
     viewport: Vec<Row>,
     cursor: Cursor,
     sixel_grid: SixelGrid,
+    kitty_grid: KittyGrid,
     supports_kitty_keyboard_protocol: bool,
 }
 impl AlternateScreenState {
@@ -3435,6 +3865,7 @@ impl AlternateScreenState {
         viewport: Vec<Row>,
         cursor: Cursor,
         sixel_grid: SixelGrid,
+        kitty_grid: KittyGrid,
         supports_kitty_keyboard_protocol: bool,
     ) -> Self {
         AlternateScreenState {
@@ -3442,6 +3873,7 @@ impl AlternateScreenState {
             viewport,
             cursor,
             sixel_grid,
+            kitty_grid,
             supports_kitty_keyboard_protocol,
         }
     }
@@ -3451,12 +3883,14 @@ impl AlternateScreenState {
         viewport: &mut Vec<Row>,
         cursor: &mut Cursor,
         sixel_grid: &mut SixelGrid,
+        kitty_grid: &mut KittyGrid,
         supports_kitty_keyboard_protocol: &mut bool,
     ) {
         std::mem::swap(&mut self.lines_above, lines_above);
         std::mem::swap(&mut self.viewport, viewport);
         std::mem::swap(&mut self.cursor, cursor);
         std::mem::swap(&mut self.sixel_grid, sixel_grid);
+        std::mem::swap(&mut self.kitty_grid, kitty_grid);
         std::mem::swap(
             &mut self.supports_kitty_keyboard_protocol,
             supports_kitty_keyboard_protocol,
@@ -3464,10 +3898,26 @@ impl AlternateScreenState {
     }
 }
 
+/// Shell integration (OSC 133) marks, recorded on the line they were received on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptMark {
+    PromptStart,
+    CommandStart,
+    OutputStart,
+    CommandEnd(Option<i32>), // exit code
+}
+
+impl PromptMark {
+    pub fn ends_output(&self) -> bool {
+        matches!(self, PromptMark::PromptStart | PromptMark::CommandEnd(_))
+    }
+}
+
 #[derive(Clone)]
 pub struct Row {
     pub columns: VecDeque<TerminalCharacter>,
     pub is_canonical: bool,
+    pub prompt_marks: Vec<PromptMark>,
     width: Option<usize>,
 }
 
@@ -3485,6 +3935,7 @@ impl Row {
         Row {
             columns: VecDeque::new(),
             is_canonical: false,
+            prompt_marks: vec![],
             width: None,
         }
     }
@@ -3492,6 +3943,7 @@ impl Row {
         Row {
             columns,
             is_canonical: false,
+            prompt_marks: vec![],
             width: None,
         }
     }
@@ -3501,7 +3953,7 @@ impl Row {
         } else {
             let mut first_row = rows.remove(0);
             for row in &mut rows {
-                first_row.append(&mut row.columns);
+                first_row.append_row(row);
             }
             first_row
         }
@@ -3718,6 +4170,13 @@ impl Row {
         self.columns.append(to_append);
         self.width = None;
     }
+    pub fn append_row(&mut self, row: &mut Row) {
+        self.append(&mut row.columns);
+        self.prompt_marks.append(&mut row.prompt_marks);
+    }
+    pub fn starts_prompt(&self) -> bool {
+        self.prompt_marks.contains(&PromptMark::PromptStart)
+    }
     pub fn drain_until(&mut self, x: usize) -> VecDeque<TerminalCharacter> {
         let mut drained_part_len = 0;
         let mut split_pos = 0;
@@ -3792,6 +4251,9 @@ impl Row {
                 part.is_canonical = true;
             }
         }
+        if let Some(part) = parts.get_mut(0) {
+            part.prompt_marks = std::mem::take(&mut self.prompt_marks);
+        }
         if parts.is_empty() {
             parts.push(self.clone());
         }
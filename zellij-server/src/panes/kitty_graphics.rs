@@ -0,0 +1,975 @@
+use crate::output::KittyImageChunk;
+use crate::ClientId;
+
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::fmt::{self, Debug, Formatter};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use zellij_utils::pane_size::{Size, SizeInPixels};
+
+// this is the limit kitty itself uses for a single image
+const MAX_IMAGE_DATA_SIZE: usize = 400_000_000;
+// the largest chunk kitty allows applications to send in one escape sequence
+const TRANSMISSION_CHUNK_SIZE: usize = 4096;
+
+// images are re-transmitted to the clients with ids that are unique across all panes
+static NEXT_IMAGE_ID: AtomicU32 = AtomicU32::new(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KittyImageFormat {
+    Rgb,
+    Rgba,
+    Png,
+}
+
+impl KittyImageFormat {
+    fn from_control_value(value: u32) -> Option<Self> {
+        match value {
+            24 => Some(KittyImageFormat::Rgb),
+            32 => Some(KittyImageFormat::Rgba),
+            100 => Some(KittyImageFormat::Png),
+            _ => None,
+        }
+    }
+    fn control_value(&self) -> u32 {
+        match self {
+            KittyImageFormat::Rgb => 24,
+            KittyImageFormat::Rgba => 32,
+            KittyImageFormat::Png => 100,
+        }
+    }
+}
+
+pub struct KittyImage {
+    pub id: u32,
+    pub format: KittyImageFormat,
+    pub pixel_width: usize,
+    pub pixel_height: usize,
+    pub is_compressed: bool, // zlib, we pass it through to the clients as is
+    data: Vec<u8>,
+}
+
+impl Debug for KittyImage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // we don't want to print the image data
+        f.debug_struct("KittyImage")
+            .field("id", &self.id)
+            .field("format", &self.format)
+            .field("pixel_width", &self.pixel_width)
+            .field("pixel_height", &self.pixel_height)
+            .field("is_compressed", &self.is_compressed)
+            .field("data_len", &self.data.len())
+            .finish()
+    }
+}
+
+impl KittyImage {
+    fn new(
+        format: KittyImageFormat,
+        pixel_width: usize,
+        pixel_height: usize,
+        is_compressed: bool,
+        data: Vec<u8>,
+    ) -> Result<Self, &'static str> {
+        let (pixel_width, pixel_height) = match format {
+            KittyImageFormat::Png => png_dimensions(&data).ok_or("EBADPNG:invalid PNG data")?,
+            _ => (pixel_width, pixel_height),
+        };
+        if pixel_width == 0 || pixel_height == 0 {
+            return Err("EINVAL:missing image dimensions");
+        }
+        if !is_compressed {
+            let bytes_per_pixel = match format {
+                KittyImageFormat::Rgb => Some(3),
+                KittyImageFormat::Rgba => Some(4),
+                KittyImageFormat::Png => None,
+            };
+            if let Some(bytes_per_pixel) = bytes_per_pixel {
+                if data.len() < pixel_width * pixel_height * bytes_per_pixel {
+                    return Err("ENODATA:insufficient image data");
+                }
+            }
+        }
+        Ok(KittyImage {
+            id: NEXT_IMAGE_ID.fetch_add(1, Ordering::Relaxed),
+            format,
+            pixel_width,
+            pixel_height,
+            is_compressed,
+            data,
+        })
+    }
+    fn serialize_transmission(&self, vte_output: &mut String) {
+        let encoded = base64::encode(&self.data);
+        let chunk_count = encoded.len().div_ceil(TRANSMISSION_CHUNK_SIZE);
+        for (i, chunk) in encoded
+            .as_bytes()
+            .chunks(TRANSMISSION_CHUNK_SIZE)
+            .enumerate()
+        {
+            let more_chunks = if i + 1 < chunk_count { 1 } else { 0 };
+            if i == 0 {
+                vte_output.push_str(&format!(
+                    "\u{1b}_Ga=t,t=d,q=2,i={},f={},s={},v={}{},m={};",
+                    self.id,
+                    self.format.control_value(),
+                    self.pixel_width,
+                    self.pixel_height,
+                    if self.is_compressed { ",o=z" } else { "" },
+                    more_chunks,
+                ));
+            } else {
+                vte_output.push_str(&format!("\u{1b}_Gm={};", more_chunks));
+            }
+            // base64 is always valid utf8
+            vte_output.push_str(std::str::from_utf8(chunk).unwrap_or(""));
+            vte_output.push_str("\u{1b}\\");
+        }
+    }
+}
+
+fn read_image_file(path: &Path) -> Result<Vec<u8>, &'static str> {
+    // we check the file before reading it so that eg. a path to a device or to a huge file can't
+    // have us read without bound
+    let metadata = std::fs::metadata(path).map_err(|_| "EBADF:failed to read file")?;
+    if !metadata.is_file() {
+        return Err("EINVAL:not a regular file");
+    }
+    if metadata.len() > MAX_IMAGE_DATA_SIZE as u64 {
+        return Err("EFBIG:image data too large");
+    }
+    let mut data = vec![];
+    std::fs::File::open(path)
+        .and_then(|file| {
+            // the file might have grown since we checked it
+            file.take(MAX_IMAGE_DATA_SIZE as u64 + 1)
+                .read_to_end(&mut data)
+        })
+        .map_err(|_| "EBADF:failed to read file")?;
+    Ok(data)
+}
+
+fn png_dimensions(data: &[u8]) -> Option<(usize, usize)> {
+    // the IHDR chunk always comes first, right after the 8 byte signature
+    const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    if data.len() < 24 || data[..8] != PNG_SIGNATURE || &data[12..16] != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes([data[16], data[17], data[18], data[19]]);
+    let height = u32::from_be_bytes([data[20], data[21], data[22], data[23]]);
+    Some((width as usize, height as usize))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KittyGraphicsCommand {
+    action: Option<char>,              // a
+    quiet: u32,                        // q
+    format: Option<u32>,               // f
+    transmission_medium: Option<char>, // t
+    compression: Option<char>,         // o
+    pixel_width: usize,                // s
+    pixel_height: usize,               // v
+    more_chunks: bool,                 // m
+    image_id: Option<u32>,             // i
+    image_number: Option<u32>,         // I
+    placement_id: Option<u32>,         // p
+    source_x: usize,                   // x
+    source_y: usize,                   // y
+    source_width: usize,               // w
+    source_height: usize,              // h
+    columns: usize,                    // c
+    rows: usize,                       // r
+    cursor_movement: u32,              // C
+    unicode_placeholder: bool,         // U
+    z_index: i32,                      // z
+    delete_target: Option<char>,       // d
+    payload: Vec<u8>,
+}
+
+impl KittyGraphicsCommand {
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        // eg. a=T,f=100,i=1;<base64 payload>
+        let (control_data, payload) = match bytes.iter().position(|b| *b == b';') {
+            Some(separator) => (&bytes[..separator], &bytes[separator + 1..]),
+            None => (bytes, &[][..]),
+        };
+        let control_data = std::str::from_utf8(control_data).ok()?;
+        let mut command = KittyGraphicsCommand {
+            payload: payload.to_vec(),
+            ..Default::default()
+        };
+        for key_value in control_data.split(',').filter(|k_v| !k_v.is_empty()) {
+            let (key, value) = key_value.split_once('=')?;
+            let first_char = value.chars().next();
+            let number = || value.parse::<u32>().ok();
+            match key {
+                "a" => command.action = first_char,
+                "q" => command.quiet = number()?,
+                "f" => command.format = number(),
+                "t" => command.transmission_medium = first_char,
+                "o" => command.compression = first_char,
+                "s" => command.pixel_width = number()? as usize,
+                "v" => command.pixel_height = number()? as usize,
+                "m" => command.more_chunks = number()? == 1,
+                "i" => command.image_id = number(),
+                "I" => command.image_number = number(),
+                "p" => command.placement_id = number(),
+                "x" => command.source_x = number()? as usize,
+                "y" => command.source_y = number()? as usize,
+                "w" => command.source_width = number()? as usize,
+                "h" => command.source_height = number()? as usize,
+                "c" => command.columns = number()? as usize,
+                "r" => command.rows = number()? as usize,
+                "C" => command.cursor_movement = number()?,
+                "U" => command.unicode_placeholder = number()? == 1,
+                "z" => command.z_index = value.parse::<i32>().ok()?,
+                "d" => command.delete_target = first_char,
+                _ => {}, // keys we don't support (eg. animation related ones) are ignored
+            }
+        }
+        Some(command)
+    }
+    fn response(&self, message: &str) -> Option<String> {
+        // kitty only responds to commands that specify an image id or number
+        let is_error = message != "OK";
+        if self.quiet >= 2 || (self.quiet == 1 && !is_error) {
+            return None;
+        }
+        let mut keys = vec![];
+        if let Some(image_id) = self.image_id {
+            keys.push(format!("i={}", image_id));
+        }
+        if let Some(image_number) = self.image_number {
+            keys.push(format!("I={}", image_number));
+        }
+        if keys.is_empty() {
+            return None;
+        }
+        if let Some(placement_id) = self.placement_id {
+            keys.push(format!("p={}", placement_id));
+        }
+        Some(format!("\u{1b}_G{};{}\u{1b}\\", keys.join(","), message))
+    }
+    fn decoded_payload(&self) -> Result<Vec<u8>, &'static str> {
+        base64::decode(&self.payload).map_err(|_| "EINVAL:invalid base64 payload")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+enum KittyApcState {
+    #[default]
+    Ground,
+    Escape,
+    ApcStart,
+    Collecting,
+    CollectingEscape,
+}
+
+// vte ignores APC sequences (ESC _ ... ESC \) completely, so we pick the graphics commands
+// (ESC _ G ... ESC \) out of the byte stream ourselves before it reaches the vte parser
+#[derive(Debug, Clone, Default)]
+pub struct KittyGraphicsParser {
+    state: KittyApcState,
+    buffer: Vec<u8>,
+}
+
+impl KittyGraphicsParser {
+    pub fn advance(&mut self, byte: u8) -> Option<KittyGraphicsCommand> {
+        match (self.state, byte) {
+            (KittyApcState::Ground, 0x1b) => self.state = KittyApcState::Escape,
+            (KittyApcState::Ground, _) => {},
+            (KittyApcState::Escape, b'_') => self.state = KittyApcState::ApcStart,
+            (KittyApcState::Escape, 0x1b) => {},
+            (KittyApcState::Escape, _) => self.state = KittyApcState::Ground,
+            (KittyApcState::ApcStart, b'G') => {
+                self.buffer.clear();
+                self.state = KittyApcState::Collecting;
+            },
+            (KittyApcState::ApcStart, 0x1b) => self.state = KittyApcState::Escape,
+            (KittyApcState::ApcStart, _) => self.state = KittyApcState::Ground,
+            (KittyApcState::Collecting, 0x1b) => self.state = KittyApcState::CollectingEscape,
+            (KittyApcState::Collecting, 0x18) | (KittyApcState::Collecting, 0x1a) => {
+                // CAN and SUB cancel the sequence
+                self.buffer.clear();
+                self.state = KittyApcState::Ground;
+            },
+            (KittyApcState::Collecting, _) => {
+                if self.buffer.len() < MAX_IMAGE_DATA_SIZE {
+                    self.buffer.push(byte);
+                } else {
+                    self.buffer.clear();
+                    self.state = KittyApcState::Ground;
+                }
+            },
+            (KittyApcState::CollectingEscape, b'\\') => {
+                self.state = KittyApcState::Ground;
+                let command = KittyGraphicsCommand::parse(&self.buffer);
+                self.buffer = vec![];
+                return command;
+            },
+            (KittyApcState::CollectingEscape, _) => {
+                self.buffer.clear();
+                self.state = if byte == 0x1b {
+                    KittyApcState::Escape
+                } else {
+                    KittyApcState::Ground
+                };
+            },
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+struct KittyPlacement {
+    image_id: u32, // as chosen by the application
+    placement_id: u32,
+    image: Rc<KittyImage>,
+    x: usize, // in cells, relative to the pane
+    y: isize, // in lines, relative to the top of the scrollbuffer
+    columns: usize,
+    rows: usize,
+    source_x: usize,
+    source_y: usize,
+    source_width: usize,
+    source_height: usize,
+    z_index: i32,
+}
+
+impl KittyPlacement {
+    fn intersects_cell(&self, x: usize, y: isize) -> bool {
+        x >= self.x && x < self.x + self.columns && y >= self.y && y < self.y + self.rows as isize
+    }
+}
+
+// the outcome of a command as far as the grid is concerned
+#[derive(Debug, Default)]
+pub struct KittyCommandResult {
+    pub response: Option<String>,
+    pub cursor_movement: Option<(usize, usize)>, // columns, rows
+    pub should_render: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KittyGrid {
+    images: HashMap<u32, Rc<KittyImage>>, // application image id => image
+    image_numbers: HashMap<u32, u32>,     // application image number => application image id
+    placements: Vec<KittyPlacement>,
+    pending_transmission: Option<(KittyGraphicsCommand, Vec<u8>)>, // first command, payload so far
+    next_generated_image_id: u32,
+}
+
+impl KittyGrid {
+    pub fn handle_command(
+        &mut self,
+        mut command: KittyGraphicsCommand,
+        cursor_x: usize,
+        cursor_line: isize, // relative to the top of the scrollbuffer
+        character_cell_size: Option<SizeInPixels>,
+        pane_size: Size,
+    ) -> KittyCommandResult {
+        if let Some((mut first_command, mut payload)) = self.pending_transmission.take() {
+            // continuation chunks only carry the m (and possibly q) keys, everything else comes
+            // from the first chunk
+            if payload.len() + command.payload.len() > MAX_IMAGE_DATA_SIZE {
+                return KittyCommandResult {
+                    response: first_command.response("EFBIG:image data too large"),
+                    ..Default::default()
+                };
+            }
+            payload.append(&mut command.payload);
+            if command.more_chunks {
+                self.pending_transmission = Some((first_command, payload));
+                return KittyCommandResult::default();
+            }
+            first_command.payload = payload;
+            first_command.more_chunks = false;
+            command = first_command;
+        } else if command.more_chunks {
+            let payload = std::mem::take(&mut command.payload);
+            self.pending_transmission = Some((command, payload));
+            return KittyCommandResult::default();
+        }
+        match command.action.unwrap_or('t') {
+            't' | 'T' | 'q' => self.transmit(
+                command,
+                cursor_x,
+                cursor_line,
+                character_cell_size,
+                pane_size,
+            ),
+            'p' => self.display(
+                command,
+                cursor_x,
+                cursor_line,
+                character_cell_size,
+                pane_size,
+            ),
+            'd' => self.delete(&command, cursor_x, cursor_line),
+            _ => KittyCommandResult {
+                response: command.response("EINVAL:unsupported action"),
+                ..Default::default()
+            },
+        }
+    }
+    fn transmit(
+        &mut self,
+        mut command: KittyGraphicsCommand,
+        cursor_x: usize,
+        cursor_line: isize,
+        character_cell_size: Option<SizeInPixels>,
+        pane_size: Size,
+    ) -> KittyCommandResult {
+        let is_query = command.action == Some('q');
+        let image = self.image_from_command(&command);
+        let image = match image {
+            Ok(image) => image,
+            Err(e) => {
+                return KittyCommandResult {
+                    response: command.response(e),
+                    ..Default::default()
+                };
+            },
+        };
+        if is_query {
+            return KittyCommandResult {
+                response: command.response("OK"),
+                ..Default::default()
+            };
+        }
+        let image_id = match (command.image_id, command.image_number) {
+            (Some(image_id), _) => image_id,
+            (None, Some(image_number)) => {
+                let image_id = self.generate_image_id();
+                self.image_numbers.insert(image_number, image_id);
+                command.image_id = Some(image_id);
+                image_id
+            },
+            (None, None) => {
+                // kitty doesn't respond to commands without an image id or number, this keeps it
+                // that way once we've given the image an id
+                let image_id = self.generate_image_id();
+                command.image_id = Some(image_id);
+                command.quiet = 2;
+                image_id
+            },
+        };
+        // re-transmitting an image replaces it along with all its placements
+        self.placements.retain(|p| p.image_id != image_id);
+        self.images.insert(image_id, Rc::new(image));
+        if command.action == Some('T') {
+            self.display(
+                command,
+                cursor_x,
+                cursor_line,
+                character_cell_size,
+                pane_size,
+            )
+        } else {
+            KittyCommandResult {
+                response: command.response("OK"),
+                ..Default::default()
+            }
+        }
+    }
+    fn image_from_command(
+        &self,
+        command: &KittyGraphicsCommand,
+    ) -> Result<KittyImage, &'static str> {
+        let format = KittyImageFormat::from_control_value(command.format.unwrap_or(32))
+            .ok_or("EINVAL:unsupported format")?;
+        let is_compressed = match command.compression {
+            Some('z') => true,
+            Some(_) => return Err("EINVAL:unsupported compression"),
+            None => false,
+        };
+        let payload = command.decoded_payload()?;
+        let data = match command.transmission_medium.unwrap_or('d') {
+            'd' => payload,
+            medium @ ('f' | 't') => {
+                let path = PathBuf::from(
+                    String::from_utf8(payload).map_err(|_| "EINVAL:invalid file path")?,
+                );
+                let data = read_image_file(&path)?;
+                if medium == 't'
+                    && path.starts_with(std::env::temp_dir())
+                    && path.to_string_lossy().contains("tty-graphics-protocol")
+                {
+                    // temporary files are deleted once read, as long as they're safe to delete
+                    let _ = std::fs::remove_file(&path);
+                }
+                data
+            },
+            _ => return Err("EINVAL:unsupported transmission medium"),
+        };
+        if data.len() > MAX_IMAGE_DATA_SIZE {
+            return Err("EFBIG:image data too large");
+        }
+        KittyImage::new(
+            format,
+            command.pixel_width,
+            command.pixel_height,
+            is_compressed,
+            data,
+        )
+    }
+    fn display(
+        &mut self,
+        command: KittyGraphicsCommand,
+        cursor_x: usize,
+        cursor_line: isize,
+        character_cell_size: Option<SizeInPixels>,
+        pane_size: Size,
+    ) -> KittyCommandResult {
+        let image_id = command.image_id.or_else(|| {
+            command
+                .image_number
+                .and_then(|image_number| self.image_numbers.get(&image_number).copied())
+        });
+        let Some((image_id, image)) =
+            image_id.and_then(|image_id| Some((image_id, self.images.get(&image_id)?.clone())))
+        else {
+            return KittyCommandResult {
+                response: command.response("ENOENT:image not found"),
+                ..Default::default()
+            };
+        };
+        if command.unicode_placeholder {
+            return KittyCommandResult {
+                response: command.response("EINVAL:unicode placeholders are not supported"),
+                ..Default::default()
+            };
+        }
+        let source_x = std::cmp::min(command.source_x, image.pixel_width);
+        let source_y = std::cmp::min(command.source_y, image.pixel_height);
+        let source_width = match command.source_width {
+            0 => image.pixel_width - source_x,
+            source_width => std::cmp::min(source_width, image.pixel_width - source_x),
+        };
+        let source_height = match command.source_height {
+            0 => image.pixel_height - source_y,
+            source_height => std::cmp::min(source_height, image.pixel_height - source_y),
+        };
+        let Some((columns, rows)) = placement_size_in_cells(
+            command.columns,
+            command.rows,
+            source_width,
+            source_height,
+            character_cell_size,
+        ) else {
+            return KittyCommandResult {
+                response: command.response("EINVAL:unknown character cell size"),
+                ..Default::default()
+            };
+        };
+        // placements are never larger than the pane, this also bounds the lines the grid adds
+        // to make room for them
+        let columns = std::cmp::min(columns, std::cmp::max(pane_size.cols, 1));
+        let rows = std::cmp::min(rows, std::cmp::max(pane_size.rows, 1));
+        let placement_id = command.placement_id.unwrap_or(0);
+        if placement_id != 0 {
+            self.placements
+                .retain(|p| !(p.image_id == image_id && p.placement_id == placement_id));
+        }
+        self.placements.push(KittyPlacement {
+            image_id,
+            placement_id,
+            image,
+            x: cursor_x,
+            y: cursor_line,
+            columns,
+            rows,
+            source_x,
+            source_y,
+            source_width,
+            source_height,
+            z_index: command.z_index,
+        });
+        KittyCommandResult {
+            response: command.response("OK"),
+            cursor_movement: if command.cursor_movement == 1 {
+                None
+            } else {
+                Some((columns, rows))
+            },
+            should_render: true,
+        }
+    }
+    fn delete(
+        &mut self,
+        command: &KittyGraphicsCommand,
+        cursor_x: usize,
+        cursor_line: isize,
+    ) -> KittyCommandResult {
+        let delete_target = command.delete_target.unwrap_or('a');
+        let free_images = delete_target.is_ascii_uppercase();
+        let placement_count = self.placements.len();
+        // x and y are 1 indexed cell coordinates for the p/x/y targets
+        let cell_x = command.source_x.saturating_sub(1);
+        let cell_y = command.source_y.saturating_sub(1) as isize;
+        let mut freed_image_ids = vec![];
+        match delete_target.to_ascii_lowercase() {
+            'a' => self.placements.clear(),
+            'i' => {
+                if let Some(image_id) = command.image_id {
+                    self.placements.retain(|p| {
+                        p.image_id != image_id
+                            || command
+                                .placement_id
+                                .map(|placement_id| p.placement_id != placement_id)
+                                .unwrap_or(false)
+                    });
+                    freed_image_ids.push(image_id);
+                }
+            },
+            'n' => {
+                if let Some(image_id) = command
+                    .image_number
+                    .and_then(|image_number| self.image_numbers.get(&image_number).copied())
+                {
+                    self.placements.retain(|p| p.image_id != image_id);
+                    freed_image_ids.push(image_id);
+                }
+            },
+            'c' => self
+                .placements
+                .retain(|p| !p.intersects_cell(cursor_x, cursor_line)),
+            'p' => {
+                // for this target, the y coordinate is relative to the cursor line's screen, which
+                // the grid resolves for us through the cursor line
+                let line = cursor_line + cell_y;
+                self.placements.retain(|p| !p.intersects_cell(cell_x, line));
+            },
+            'x' => self
+                .placements
+                .retain(|p| !(cell_x >= p.x && cell_x < p.x + p.columns)),
+            'y' => {
+                let line = cursor_line + cell_y;
+                self.placements
+                    .retain(|p| !(line >= p.y && line < p.y + p.rows as isize));
+            },
+            'z' => {
+                let z_index = command.z_index;
+                self.placements.retain(|p| p.z_index != z_index);
+            },
+            'r' => {
+                // x and y are the (inclusive) range of image ids here
+                let (first_id, last_id) = (command.source_x as u32, command.source_y as u32);
+                self.placements
+                    .retain(|p| p.image_id < first_id || p.image_id > last_id);
+            },
+            _ => {
+                return KittyCommandResult {
+                    response: command.response("EINVAL:unsupported delete target"),
+                    ..Default::default()
+                };
+            },
+        }
+        if free_images {
+            if freed_image_ids.is_empty() {
+                // free all images that are no longer displayed
+                let displayed_image_ids: HashSet<u32> =
+                    self.placements.iter().map(|p| p.image_id).collect();
+                freed_image_ids = self
+                    .images
+                    .keys()
+                    .filter(|image_id| !displayed_image_ids.contains(image_id))
+                    .copied()
+                    .collect();
+            }
+            for image_id in freed_image_ids {
+                if !self.placements.iter().any(|p| p.image_id == image_id) {
+                    self.images.remove(&image_id);
+                    self.image_numbers.retain(|_, id| *id != image_id);
+                }
+            }
+        }
+        KittyCommandResult {
+            response: None, // kitty does not respond to deletions
+            cursor_movement: None,
+            should_render: self.placements.len() != placement_count,
+        }
+    }
+    fn generate_image_id(&mut self) -> u32 {
+        // we count down from the top so as not to collide with ids chosen by the application
+        loop {
+            self.next_generated_image_id = self.next_generated_image_id.wrapping_sub(1);
+            if self.next_generated_image_id != 0
+                && !self.images.contains_key(&self.next_generated_image_id)
+            {
+                return self.next_generated_image_id;
+            }
+        }
+    }
+    pub fn has_placements(&self) -> bool {
+        !self.placements.is_empty()
+    }
+    pub fn offset_grid_top(&mut self) {
+        // a line was dropped from the top of the scrollbuffer
+        for placement in self.placements.iter_mut() {
+            placement.y -= 1;
+        }
+        self.placements.retain(|p| p.y + p.rows as isize > 0);
+    }
+    pub fn clear(&mut self) {
+        // placements are removed, image data stays around until the application deletes it
+        self.placements.clear();
+    }
+    pub fn image_cell_coordinates_in_viewport(
+        &self,
+        viewport_height: usize,
+        scrollback_height: usize,
+    ) -> Vec<(usize, usize, usize, usize)> {
+        // (top, bottom, left, right)
+        self.placements
+            .iter()
+            .filter_map(|placement| {
+                let top_edge = placement.y - scrollback_height as isize;
+                let bottom_edge = top_edge + placement.rows as isize;
+                if bottom_edge <= 0 || top_edge >= viewport_height as isize {
+                    return None;
+                }
+                Some((
+                    std::cmp::max(top_edge, 0) as usize,
+                    std::cmp::min(bottom_edge as usize, viewport_height),
+                    placement.x,
+                    placement.x + placement.columns,
+                ))
+            })
+            .collect()
+    }
+    pub fn image_chunks_in_viewport(
+        &self,
+        viewport_width: usize,
+        viewport_height: usize,
+        scrollback_height: usize,
+        viewport_x_offset: usize,
+        viewport_y_offset: usize,
+    ) -> Vec<KittyImageChunk> {
+        let mut placements: Vec<&KittyPlacement> = self.placements.iter().collect();
+        // stable sort, so that placements with the same z-index are drawn in the order they were
+        // placed
+        placements.sort_by_key(|p| p.z_index);
+        placements
+            .into_iter()
+            .filter_map(|placement| {
+                let top_edge = placement.y - scrollback_height as isize;
+                let bottom_edge = top_edge + placement.rows as isize;
+                if bottom_edge <= 0
+                    || top_edge >= viewport_height as isize
+                    || placement.x >= viewport_width
+                {
+                    return None;
+                }
+                let rows_above_viewport = if top_edge < 0 {
+                    top_edge.unsigned_abs()
+                } else {
+                    0
+                };
+                let chunk = KittyImageChunk {
+                    image: placement.image.clone(),
+                    cell_x: viewport_x_offset + placement.x,
+                    // the chunk might start above the viewport, so its place on screen is set
+                    // once it's been cropped
+                    cell_y: 0,
+                    columns: placement.columns,
+                    rows: placement.rows,
+                    source_x: placement.source_x,
+                    source_y: placement.source_y,
+                    source_width: placement.source_width,
+                    source_height: placement.source_height,
+                    z_index: placement.z_index,
+                };
+                // crop the parts that are above or below the viewport or to the right of it
+                let visible_rows = std::cmp::min(bottom_edge as usize, viewport_height)
+                    - std::cmp::max(top_edge, 0) as usize;
+                let visible_columns =
+                    std::cmp::min(placement.columns, viewport_width - placement.x);
+                chunk
+                    .crop(0, rows_above_viewport, visible_columns, visible_rows)
+                    .map(|mut chunk| {
+                        chunk.cell_y = viewport_y_offset + std::cmp::max(top_edge, 0) as usize;
+                        chunk
+                    })
+            })
+            .collect()
+    }
+}
+
+fn placement_size_in_cells(
+    columns: usize,
+    rows: usize,
+    source_width: usize,
+    source_height: usize,
+    character_cell_size: Option<SizeInPixels>,
+) -> Option<(usize, usize)> {
+    let ceil_div = |a: usize, b: usize| a.div_ceil(b.max(1));
+    match (columns, rows) {
+        (0, 0) => {
+            let character_cell_size = character_cell_size?;
+            Some((
+                std::cmp::max(ceil_div(source_width, character_cell_size.width), 1),
+                std::cmp::max(ceil_div(source_height, character_cell_size.height), 1),
+            ))
+        },
+        (columns, 0) => {
+            // keep the aspect ratio of the image
+            let character_cell_size = character_cell_size?;
+            let pixel_width = columns * character_cell_size.width;
+            let pixel_height = pixel_width * source_height / source_width.max(1);
+            Some((
+                columns,
+                std::cmp::max(ceil_div(pixel_height, character_cell_size.height), 1),
+            ))
+        },
+        (0, rows) => {
+            let character_cell_size = character_cell_size?;
+            let pixel_height = rows * character_cell_size.height;
+            let pixel_width = pixel_height * source_width / source_height.max(1);
+            Some((
+                std::cmp::max(ceil_div(pixel_width, character_cell_size.width), 1),
+                rows,
+            ))
+        },
+        (columns, rows) => Some((columns, rows)),
+    }
+}
+
+// the parts of an image chunk that identify a single placement on the client's terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct KittyPlacementKey {
+    image_id: u32,
+    cell_x: usize,
+    cell_y: usize,
+    columns: usize,
+    rows: usize,
+    source_x: usize,
+    source_y: usize,
+    source_width: usize,
+    source_height: usize,
+    z_index: i32,
+}
+
+impl From<&KittyImageChunk> for KittyPlacementKey {
+    fn from(chunk: &KittyImageChunk) -> Self {
+        KittyPlacementKey {
+            image_id: chunk.image.id,
+            cell_x: chunk.cell_x,
+            cell_y: chunk.cell_y,
+            columns: chunk.columns,
+            rows: chunk.rows,
+            source_x: chunk.source_x,
+            source_y: chunk.source_y,
+            source_width: chunk.source_width,
+            source_height: chunk.source_height,
+            z_index: chunk.z_index,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct KittyClientState {
+    transmitted_images: HashMap<u32, Weak<KittyImage>>,
+    placements: HashMap<KittyPlacementKey, u32>, // u32 is the placement id on the client terminal
+    next_placement_id: u32,
+}
+
+// unlike sixel images, kitty images are not overwritten by text - so we need to keep track of
+// what's displayed on each client's terminal in order to move and remove images as needed
+#[derive(Debug, Default)]
+pub struct KittyGraphicsOutputState {
+    supported_clients: HashSet<ClientId>,
+    clients: HashMap<ClientId, KittyClientState>,
+}
+
+impl KittyGraphicsOutputState {
+    pub fn add_supported_client(&mut self, client_id: ClientId) {
+        self.supported_clients.insert(client_id);
+    }
+    pub fn client_supports_kitty_graphics(&self, client_id: ClientId) -> bool {
+        self.supported_clients.contains(&client_id)
+    }
+    pub fn remove_client(&mut self, client_id: ClientId) {
+        self.supported_clients.remove(&client_id);
+        self.clients.remove(&client_id);
+    }
+    pub fn has_changes_for_client(&self, client_id: ClientId, chunks: &[KittyImageChunk]) -> bool {
+        let displayed_placements = self.clients.get(&client_id).map(|c| &c.placements);
+        match displayed_placements {
+            Some(displayed_placements) => {
+                displayed_placements.len() != chunks.len()
+                    || chunks
+                        .iter()
+                        .any(|c| !displayed_placements.contains_key(&KittyPlacementKey::from(c)))
+            },
+            None => !chunks.is_empty(),
+        }
+    }
+    pub fn serialize_for_client(
+        &mut self,
+        client_id: ClientId,
+        chunks: &[KittyImageChunk],
+    ) -> String {
+        let mut vte_output = String::new();
+        let client_state = self.clients.entry(client_id).or_default();
+        let new_placements: HashSet<KittyPlacementKey> =
+            chunks.iter().map(KittyPlacementKey::from).collect();
+
+        client_state
+            .placements
+            .retain(|placement_key, placement_id| {
+                let is_still_displayed = new_placements.contains(placement_key);
+                if !is_still_displayed {
+                    vte_output.push_str(&format!(
+                        "\u{1b}_Ga=d,d=i,q=2,i={},p={}\u{1b}\\",
+                        placement_key.image_id, placement_id
+                    ));
+                }
+                is_still_displayed
+            });
+        client_state.transmitted_images.retain(|image_id, image| {
+            // the image was deleted in its pane, so we free it on the client's terminal as well
+            let image_is_alive = image.upgrade().is_some();
+            if !image_is_alive {
+                vte_output.push_str(&format!("\u{1b}_Ga=d,d=I,q=2,i={}\u{1b}\\", image_id));
+            }
+            image_is_alive
+        });
+
+        for chunk in chunks {
+            let placement_key = KittyPlacementKey::from(chunk);
+            if client_state.placements.contains_key(&placement_key) {
+                continue;
+            }
+            if let Entry::Vacant(entry) = client_state.transmitted_images.entry(chunk.image.id) {
+                chunk.image.serialize_transmission(&mut vte_output);
+                entry.insert(Rc::downgrade(&chunk.image));
+            }
+            client_state.next_placement_id = client_state.next_placement_id.wrapping_add(1).max(1);
+            let placement_id = client_state.next_placement_id;
+            vte_output.push_str(&format!(
+                "\u{1b}[{};{}H\u{1b}_Ga=p,q=2,C=1,i={},p={},x={},y={},w={},h={},c={},r={},z={}\u{1b}\\",
+                chunk.cell_y + 1, // + 1 because VTE is 1 indexed
+                chunk.cell_x + 1,
+                chunk.image.id,
+                placement_id,
+                chunk.source_x,
+                chunk.source_y,
+                chunk.source_width,
+                chunk.source_height,
+                chunk.columns,
+                chunk.rows,
+                chunk.z_index,
+            ));
+            client_state.placements.insert(placement_key, placement_id);
+        }
+        vte_output
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/kitty_graphics_tests.rs"]
+mod kitty_graphics_tests;
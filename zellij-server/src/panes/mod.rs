@@ -1,6 +1,7 @@
 pub mod alacritty_functions;
 pub mod grid;
 pub mod hyperlink_tracker;
+pub mod kitty_graphics;
 pub mod link_handler;
 pub mod selection;
 pub mod sixel;
@@ -9,6 +10,7 @@ pub mod terminal_character;
 mod active_panes;
 pub mod floating_panes;
 mod plugin_pane;
+mod scrollback_store;
 mod search;
 pub mod terminal_pane;
 mod tiled_panes;
@@ -19,6 +21,7 @@ pub use floating_panes::*;
 pub use grid::*;
 pub use link_handler::*;
 pub(crate) use plugin_pane::*;
+pub(crate) use scrollback_store::*;
 pub use sixel::*;
 pub(crate) use terminal_character::*;
 pub use terminal_pane::*;
@@ -0,0 +1,264 @@
+//! Keeps a copy of the scrollback of a terminal pane on disk, so that it can be restored when the
+//! session is resurrected.
+//!
+//! The store file is an append-only log of changes to the list of persisted lines, so that
+//! persisting the scrollback of a pane only writes what changed since it was last persisted. Lines
+//! are stored as VTE (their characters along with the escape sequences for their styles). The file
+//! starts with a header (`SCROLLBACK_STORE_MAGIC` followed by the format version) and is then made
+//! of records, each starting with a single byte tag:
+//!
+//! - `LINE_RECORD`, followed by the length of the line (u32, little endian) and the line itself
+//! - `TRIM_RECORD`, followed by a count (u32, little endian) of lines to drop from the top
+//! - `TRUNCATE_RECORD`, followed by a count (u32, little endian) of lines to keep from the top
+//!
+//! Once the file holds a lot more records than there are persisted lines, it is rewritten from
+//! scratch.
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::panes::grid::Row;
+use zellij_utils::errors::prelude::*;
+
+const SCROLLBACK_STORE_MAGIC: &[u8] = b"ZJSB";
+const SCROLLBACK_STORE_VERSION: u8 = 1;
+
+const LINE_RECORD: u8 = 0;
+const TRIM_RECORD: u8 = 1;
+const TRUNCATE_RECORD: u8 = 2;
+
+// the file is not compacted before it has at least this many line records, so that panes with
+// little scrollback are not rewritten all the time
+const MIN_LINE_RECORDS_BEFORE_COMPACTION: usize = 1000;
+
+#[derive(Debug)]
+pub struct ScrollbackStore {
+    file_name: String,
+    // the folder we last persisted to, if it changes (eg. the session was renamed) we start over
+    folder: Option<PathBuf>,
+    // hashes of the lines that are currently persisted, in order
+    persisted_lines: VecDeque<u64>,
+    line_records_in_file: usize,
+}
+
+impl ScrollbackStore {
+    pub fn new(file_name: String) -> Self {
+        ScrollbackStore {
+            file_name,
+            folder: None,
+            persisted_lines: VecDeque::new(),
+            line_records_in_file: 0,
+        }
+    }
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+    /// Whether the store file exists and is up to date as of the last time we persisted
+    pub fn is_persisted(&self) -> bool {
+        self.folder.is_some()
+    }
+    pub fn persist(
+        &mut self,
+        folder: &Path,
+        lines: &[&Row],
+        serialize_line: impl Fn(&Row) -> Result<String>,
+    ) -> Result<()> {
+        let store_path = folder.join(&self.file_name);
+        let err_context = || format!("failed to persist scrollback to {}", store_path.display());
+
+        let line_hashes: Vec<u64> = lines.iter().map(|line| hash_line(line)).collect();
+        let should_rewrite_file = self.folder.as_deref() != Some(folder)
+            || self.line_records_in_file
+                > std::cmp::max(
+                    MIN_LINE_RECORDS_BEFORE_COMPACTION,
+                    line_hashes.len().saturating_mul(2),
+                );
+        let result = if should_rewrite_file {
+            self.rewrite_file(folder, lines, &line_hashes, serialize_line)
+        } else {
+            self.append_changes(folder, lines, &line_hashes, serialize_line)
+        };
+        if result.is_err() {
+            // we no longer know what's in the file, so it will be written from scratch next time
+            self.folder = None;
+        }
+        result.with_context(err_context)
+    }
+    /// Deletes the store file, if we persisted one (eg. because its pane was closed)
+    pub fn remove(&mut self) -> Result<()> {
+        match self.folder.take() {
+            Some(folder) => {
+                let store_path = folder.join(&self.file_name);
+                self.persisted_lines.clear();
+                self.line_records_in_file = 0;
+                match fs::remove_file(&store_path) {
+                    Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                        Err(e).with_context(|| {
+                            format!("failed to remove scrollback store {}", store_path.display())
+                        })
+                    },
+                    _ => Ok(()),
+                }
+            },
+            None => Ok(()),
+        }
+    }
+    fn rewrite_file(
+        &mut self,
+        folder: &Path,
+        lines: &[&Row],
+        line_hashes: &[u64],
+        serialize_line: impl Fn(&Row) -> Result<String>,
+    ) -> Result<()> {
+        fs::create_dir_all(folder)?;
+        let mut file = BufWriter::new(File::create(folder.join(&self.file_name))?);
+        file.write_all(SCROLLBACK_STORE_MAGIC)?;
+        file.write_all(&[SCROLLBACK_STORE_VERSION])?;
+        for line in lines {
+            write_line_record(&mut file, &serialize_line(line)?)?;
+        }
+        file.flush()?;
+        self.folder = Some(folder.to_path_buf());
+        self.persisted_lines = line_hashes.iter().copied().collect();
+        self.line_records_in_file = line_hashes.len();
+        Ok(())
+    }
+    fn append_changes(
+        &mut self,
+        folder: &Path,
+        lines: &[&Row],
+        line_hashes: &[u64],
+        serialize_line: impl Fn(&Row) -> Result<String>,
+    ) -> Result<()> {
+        // lines only ever leave the scrollback from the top (when it is full) or the bottom (eg.
+        // when the pane grows), so we look for where the current lines start in the persisted
+        // ones and keep whatever they have in common from there
+        let trimmed_line_count = match line_hashes.first() {
+            Some(first_line) => self
+                .persisted_lines
+                .iter()
+                .position(|persisted_line| persisted_line == first_line)
+                .unwrap_or(self.persisted_lines.len()),
+            None => self.persisted_lines.len(),
+        };
+        let unchanged_line_count = self
+            .persisted_lines
+            .iter()
+            .skip(trimmed_line_count)
+            .zip(line_hashes.iter())
+            .take_while(|(persisted_line, line)| persisted_line == line)
+            .count();
+        let truncate = trimmed_line_count + unchanged_line_count < self.persisted_lines.len();
+        if trimmed_line_count == 0 && !truncate && unchanged_line_count == lines.len() {
+            return Ok(());
+        }
+
+        let mut file = BufWriter::new(
+            OpenOptions::new()
+                .append(true)
+                .open(folder.join(&self.file_name))?,
+        );
+        if trimmed_line_count > 0 {
+            write_count_record(&mut file, TRIM_RECORD, trimmed_line_count)?;
+            self.persisted_lines.drain(..trimmed_line_count);
+        }
+        if truncate {
+            write_count_record(&mut file, TRUNCATE_RECORD, unchanged_line_count)?;
+            self.persisted_lines.truncate(unchanged_line_count);
+        }
+        for (line, line_hash) in lines.iter().zip(line_hashes).skip(unchanged_line_count) {
+            write_line_record(&mut file, &serialize_line(line)?)?;
+            self.persisted_lines.push_back(*line_hash);
+            self.line_records_in_file += 1;
+        }
+        file.flush()?;
+        Ok(())
+    }
+}
+
+/// Reads the lines in a scrollback store file, as VTE
+pub fn read_scrollback_store(path: &Path) -> Result<Vec<String>> {
+    let err_context = || format!("failed to read scrollback from {}", path.display());
+
+    let contents = fs::read(path).with_context(err_context)?;
+    parse_scrollback_store(&contents).with_context(err_context)
+}
+
+fn parse_scrollback_store(contents: &[u8]) -> Result<Vec<String>> {
+    let header_length = SCROLLBACK_STORE_MAGIC.len() + 1;
+    if contents.len() < header_length || !contents.starts_with(SCROLLBACK_STORE_MAGIC) {
+        return Err(anyhow!("not a scrollback store"));
+    }
+    let version = contents[SCROLLBACK_STORE_MAGIC.len()];
+    if version != SCROLLBACK_STORE_VERSION {
+        return Err(anyhow!("unsupported scrollback store version {}", version));
+    }
+    let mut lines = VecDeque::new();
+    let mut records = &contents[header_length..];
+    while let Some((tag, rest)) = records.split_first() {
+        let (count, rest) = match read_u32(rest) {
+            Some(count_and_rest) => count_and_rest,
+            // the last record was not fully written (eg. we crashed while writing it)
+            None => break,
+        };
+        records = match *tag {
+            LINE_RECORD => {
+                if rest.len() < count {
+                    break;
+                }
+                let (line, rest) = rest.split_at(count);
+                lines.push_back(String::from_utf8_lossy(line).into_owned());
+                rest
+            },
+            TRIM_RECORD => {
+                lines.drain(..std::cmp::min(count, lines.len()));
+                rest
+            },
+            TRUNCATE_RECORD => {
+                lines.truncate(count);
+                rest
+            },
+            _ => return Err(anyhow!("unknown scrollback store record {}", tag)),
+        };
+    }
+    Ok(lines.into())
+}
+
+fn read_u32(bytes: &[u8]) -> Option<(usize, &[u8])> {
+    if bytes.len() < 4 {
+        return None;
+    }
+    let (number, rest) = bytes.split_at(4);
+    let number = u32::from_le_bytes([number[0], number[1], number[2], number[3]]);
+    Some((number as usize, rest))
+}
+
+fn write_line_record(file: &mut impl Write, line: &str) -> Result<()> {
+    write_count_record(file, LINE_RECORD, line.len())?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+fn write_count_record(file: &mut impl Write, tag: u8, count: usize) -> Result<()> {
+    let count = u32::try_from(count).context("scrollback store record is too long")?;
+    file.write_all(&[tag])?;
+    file.write_all(&count.to_le_bytes())?;
+    Ok(())
+}
+
+fn hash_line(line: &Row) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    line.is_canonical.hash(&mut hasher);
+    for terminal_character in &line.columns {
+        terminal_character.character.hash(&mut hasher);
+        (*terminal_character.styles).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+#[path = "./unit/scrollback_store_tests.rs"]
+mod scrollback_store_tests;
@@ -1,8 +1,11 @@
 use crate::panes::selection::Selection;
 use crate::panes::terminal_character::TerminalCharacter;
 use crate::panes::{Grid, Row};
+use regex::{Regex, RegexBuilder};
 use std::borrow::Cow;
 use std::fmt::Debug;
+use zellij_utils::data::ScrollbackSearchMatch;
+use zellij_utils::errors::prelude::*;
 use zellij_utils::input::actions::SearchDirection;
 use zellij_utils::position::Position;
 
@@ -98,9 +101,49 @@ pub struct SearchResult {
     pub whole_word_only: bool, // TODO
     // Jump from the bottom to the top (or vice versa), if we run out of lines to search
     pub wrap_search: bool,
+    // Treat the needle as a regular expression
+    pub regex: bool,
+    // The needle compiled with the current options, if in regex mode and the needle is valid
+    compiled_regex: Option<Regex>,
+    // All the matches in the scrollback as they were last listed, until the search or the
+    // contents of the pane change
+    listed_matches: Option<Vec<ScrollbackSearchMatch>>,
 }
 
 impl SearchResult {
+    /// A search for all the matches of a regular expression, without any modifiers set
+    pub fn with_regex(pattern: &str) -> Result<Self> {
+        let compiled_regex =
+            Regex::new(pattern).with_context(|| format!("invalid search pattern {}", pattern))?;
+        Ok(SearchResult {
+            needle: pattern.to_owned(),
+            regex: true,
+            compiled_regex: Some(compiled_regex),
+            ..Default::default()
+        })
+    }
+
+    /// Has to be called whenever the needle or any of the options changes, so that the regex we
+    /// search with is up to date
+    pub(crate) fn update_regex(&mut self) {
+        self.listed_matches = None;
+        self.compiled_regex = if self.regex && !self.needle.is_empty() {
+            let pattern = if self.whole_word_only {
+                format!(r"\b(?:{})\b", self.needle)
+            } else {
+                self.needle.clone()
+            };
+            // while the needle is being typed it is often not a valid regex (yet), in which case
+            // we simply don't find anything
+            RegexBuilder::new(&pattern)
+                .case_insensitive(self.case_insensitive)
+                .build()
+                .ok()
+        } else {
+            None
+        };
+    }
+
     /// This is only used for Debug formatting Grid, which itself is only used
     /// for tests.
     #[allow(clippy::ptr_arg)]
@@ -141,7 +184,7 @@ impl SearchResult {
     }
 
     pub fn has_modifiers_set(&self) -> bool {
-        self.wrap_search || self.whole_word_only || self.case_insensitive
+        self.wrap_search || self.whole_word_only || self.case_insensitive || self.regex
     }
 
     fn check_if_haystack_char_matches_needle(
@@ -181,6 +224,11 @@ impl SearchResult {
         if self.needle.is_empty() || row.columns.is_empty() {
             return res;
         }
+        if self.regex {
+            let mut res = self.search_row_with_regex(ridx, row, tail);
+            reflow_selection_ends(&mut res, row.width());
+            return res;
+        }
 
         let mut tailit = tail.iter();
         let mut source = SearchSource::Main(row); // Where we currently get the haystack-characters from
@@ -267,15 +315,49 @@ impl SearchResult {
             }
         }
 
-        // The tail may have not been wrapped yet (when coming from lines_below),
-        // so it could be that the end extends across more characters than the row is wide.
-        // Therefore we need to reflow the end:
-        for s in res.iter_mut() {
-            while s.end.column() > row.width() {
-                s.end.column.0 -= row.width();
-                s.end.line.0 += 1;
+        reflow_selection_ends(&mut res, row.width());
+        res
+    }
+
+    /// Same as `search_row`, but matching the needle as a regex against the row and its tail
+    /// joined into a single line
+    fn search_row_with_regex(&self, ridx: usize, row: &Row, tail: &[&Row]) -> Vec<Selection> {
+        let mut res = Vec::new();
+        let regex = match &self.compiled_regex {
+            Some(regex) => regex,
+            None => return res,
+        };
+        let mut haystack = String::new();
+        // The byte offset of each character in the haystack along with its position in the grid
+        let mut positions = Vec::new();
+        for (line_offset, line) in std::iter::once(row).chain(tail.iter().copied()).enumerate() {
+            for (cidx, terminal_character) in line.columns.iter().enumerate() {
+                let position = Position::new((ridx + line_offset) as i32, cidx as u16);
+                positions.push((haystack.len(), position));
+                haystack.push(terminal_character.character);
             }
         }
+        let char_index =
+            |byte_offset: usize| positions.partition_point(|(offset, _)| *offset < byte_offset);
+        for found in regex.find_iter(&haystack) {
+            // Empty matches (eg. of "a*") have nothing we could highlight
+            if found.start() == found.end() {
+                continue;
+            }
+            let start = char_index(found.start());
+            if start >= row.columns.len() {
+                // Matches starting in the tail are found when searching the tail rows themselves
+                break;
+            }
+            let last = positions[char_index(found.end()) - 1].1;
+            let mut selection = Selection::default();
+            selection.start(positions[start].1);
+            selection.end(Position::new(
+                last.line() as i32,
+                (last.column() + 1) as u16,
+            ));
+            res.push(selection);
+        }
         res
     }
 
@@ -392,6 +474,18 @@ impl SearchResult {
     }
 }
 
+// The tail may have not been wrapped yet (when coming from lines_below),
+// so it could be that the end extends across more characters than the row is wide.
+// Therefore we need to reflow the end:
+fn reflow_selection_ends(selections: &mut [Selection], row_width: usize) {
+    for s in selections.iter_mut() {
+        while s.end.column() > row_width {
+            s.end.column.0 -= row_width;
+            s.end.line.0 += 1;
+        }
+    }
+}
+
 impl Grid {
     pub fn search_down(&mut self) {
         self.search_scrollbuffer(SearchDirection::Down);
@@ -412,6 +506,7 @@ impl Grid {
 
     pub fn set_search_string(&mut self, needle: &str) {
         self.search_results.needle = needle.to_string();
+        self.search_results.update_regex();
         self.search_viewport();
         // If the current viewport does not contain any hits,
         // we jump around until we find something. Starting
@@ -454,6 +549,7 @@ impl Grid {
 
     pub fn toggle_search_case_sensitivity(&mut self) {
         self.search_results.case_insensitive = !self.search_results.case_insensitive;
+        self.search_results.update_regex();
         for line in self.search_results.selections.drain(..) {
             self.output_buffer
                 .update_lines(line.start.line() as usize, line.end.line() as usize);
@@ -469,6 +565,7 @@ impl Grid {
 
     pub fn toggle_search_whole_words(&mut self) {
         self.search_results.whole_word_only = !self.search_results.whole_word_only;
+        self.search_results.update_regex();
         for line in self.search_results.selections.drain(..) {
             self.output_buffer
                 .update_lines(line.start.line() as usize, line.end.line() as usize);
@@ -479,6 +576,128 @@ impl Grid {
         self.search_results.unset_active_selection_if_nonexistent();
     }
 
+    pub fn toggle_search_regex(&mut self) {
+        self.search_results.regex = !self.search_results.regex;
+        self.search_results.update_regex();
+        for line in self.search_results.selections.drain(..) {
+            self.output_buffer
+                .update_lines(line.start.line() as usize, line.end.line() as usize);
+        }
+        self.search_results.active = None;
+        self.search_viewport();
+    }
+
+    /// All the matches of the current search, from the top of the scrollback to the bottom
+    pub fn search_all_matches(&mut self) -> Vec<ScrollbackSearchMatch> {
+        if let Some(listed_matches) = &self.search_results.listed_matches {
+            return listed_matches.clone();
+        }
+        let matches = self.find_all_matches(&self.search_results);
+        self.search_results.listed_matches = Some(matches.clone());
+        matches
+    }
+
+    /// Has to be called whenever the contents of the grid change, so that the matches we list
+    /// (and jump to) are searched for again
+    pub fn invalidate_listed_search_matches(&mut self) {
+        self.search_results.listed_matches = None;
+    }
+
+    /// All the matches of `pattern` (a regex), from the top of the scrollback to the bottom
+    pub fn search_scrollback(&self, pattern: &str) -> Result<Vec<ScrollbackSearchMatch>> {
+        let search = SearchResult::with_regex(pattern)?;
+        Ok(self.find_all_matches(&search))
+    }
+
+    fn find_all_matches(&self, search: &SearchResult) -> Vec<ScrollbackSearchMatch> {
+        // Rows that are wrapped (ie. are not canonical) are searched along with the line they
+        // belong to, so that the matches are reported by (unwrapped) line
+        let lines = self.logical_lines();
+        let mut matches = vec![];
+        for (line_index, line) in lines.iter().enumerate() {
+            let mut line_matches = vec![];
+            let mut row_start_column = 0;
+            for (ridx, row) in line.iter().enumerate() {
+                for selection in search.search_row(ridx, row, &line[ridx + 1..]) {
+                    // search_row also finds matches starting in the tail (when not searching
+                    // with a regex), those are found again once we get to their row
+                    if selection.start.line() as usize == ridx {
+                        line_matches.push(row_start_column + selection.start.column());
+                    }
+                }
+                row_start_column += row.columns.len();
+            }
+            if line_matches.is_empty() {
+                continue;
+            }
+            let line_text = line
+                .iter()
+                .flat_map(|row| row.columns.iter().map(|c| c.character))
+                .collect::<String>();
+            let line_text = line_text.trim_end();
+            for column in line_matches {
+                matches.push(ScrollbackSearchMatch {
+                    line_number: line_index + 1,
+                    column,
+                    line: line_text.to_owned(),
+                });
+            }
+        }
+        matches
+    }
+
+    /// Scrolls to the match with the given index (starting at 1) in `search_all_matches` and
+    /// makes it the active one. Returns false if there is no such match.
+    pub fn search_jump_to_match(&mut self, index: usize) -> bool {
+        let Some(target) = index
+            .checked_sub(1)
+            .and_then(|index| self.search_all_matches().get(index).cloned())
+        else {
+            return false;
+        };
+        // Scroll down from the top until the line of the match starts the viewport (or we reach
+        // the bottom), lines above the viewport are whole lines so we can count them directly
+        self.move_viewport_to_opposite_end(SearchDirection::Down);
+        while (self.lines_above.len() + 1 < target.line_number
+            || self.viewport.first().map_or(false, |row| !row.is_canonical))
+            && !self.lines_below.is_empty()
+        {
+            self.scroll_down_one_line();
+        }
+        // Then find the row of the match in the viewport and the column inside of that row
+        let mut viewport_row = target
+            .line_number
+            .checked_sub(self.lines_above.len() + 1)
+            .and_then(|nth_line| {
+                self.viewport
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, row)| row.is_canonical)
+                    .nth(nth_line)
+            })
+            .map_or(0, |(ridx, _)| ridx);
+        let mut target_column = target.column;
+        while viewport_row + 1 < self.viewport.len()
+            && !self.viewport[viewport_row + 1].is_canonical
+            && target_column >= self.viewport[viewport_row].columns.len()
+        {
+            target_column -= self.viewport[viewport_row].columns.len();
+            viewport_row += 1;
+        }
+        self.search_results.selections.clear();
+        self.search_viewport();
+        self.search_results.active = self
+            .search_results
+            .selections
+            .iter()
+            .find(|s| s.start.line() == viewport_row as isize && s.start.column() == target_column)
+            .or_else(|| self.search_results.selections.first())
+            .cloned();
+        self.is_scrolled = true;
+        self.output_buffer.update_all_lines();
+        true
+    }
+
     fn search_scrollbuffer(&mut self, dir: SearchDirection) {
         let first_sel = self.search_results.selections.first();
         let last_sel = self.search_results.selections.last();
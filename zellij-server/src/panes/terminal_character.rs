@@ -1,5 +1,6 @@
 use std::convert::From;
 use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::ops::{Index, IndexMut};
 use std::rc::Rc;
 use unicode_width::UnicodeWidthChar;
@@ -59,7 +60,7 @@ thread_local! {
         RcCharacterStyles::Rc(Rc::new(DEFAULT_STYLES));
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum AnsiCode {
     On,
     Reset,
@@ -69,7 +70,7 @@ pub enum AnsiCode {
     Underline(Option<AnsiStyledUnderline>),
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum AnsiStyledUnderline {
     Double,
     Undercurl,
@@ -86,7 +87,7 @@ impl From<PaletteColor> for AnsiCode {
     }
 }
 
-#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
 pub enum NamedColor {
     Black,
     Red,
@@ -244,6 +245,25 @@ impl PartialEq for CharacterStyles {
     }
 }
 
+// hashes the same fields that are compared above
+impl Hash for CharacterStyles {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.foreground.hash(state);
+        self.background.hash(state);
+        self.underline_color.hash(state);
+        self.strike.hash(state);
+        self.hidden.hash(state);
+        self.reverse.hash(state);
+        self.slow_blink.hash(state);
+        self.fast_blink.hash(state);
+        self.underline.hash(state);
+        self.bold.hash(state);
+        self.dim.hash(state);
+        self.italic.hash(state);
+        self.link_anchor.hash(state);
+    }
+}
+
 impl CharacterStyles {
     pub fn foreground(mut self, foreground_code: Option<AnsiCode>) -> Self {
         self.foreground = foreground_code;
@@ -767,7 +787,7 @@ impl From<StyleDeclaration> for CharacterStyles {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Hash)]
 pub enum LinkAnchor {
     Start(u16),
     End,
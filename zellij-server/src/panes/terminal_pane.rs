@@ -1,18 +1,20 @@
-use crate::output::{CharacterChunk, SixelImageChunk};
+use crate::output::{CharacterChunk, KittyImageChunk, SixelImageChunk};
 use crate::panes::sixel::SixelImageStore;
-use crate::panes::LinkHandler;
 use crate::panes::{
     grid::Grid,
     terminal_character::{render_first_run_banner, TerminalCharacter, EMPTY_TERMINAL_CHARACTER},
 };
+use crate::panes::{read_scrollback_store, LinkHandler, ScrollbackStore};
 use crate::pty::VteBytes;
 use crate::tab::{AdjustedInput, Pane};
 use crate::ClientId;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::time::{self, Instant};
+use uuid::Uuid;
 use vte;
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::input::mouse::{MouseEvent, MouseEventType};
@@ -20,7 +22,7 @@ use zellij_utils::pane_size::Offset;
 use zellij_utils::{
     data::{
         BareKey, InputMode, KeyWithModifier, Palette, PaletteColor, PaneId as ZellijUtilsPaneId,
-        Style, Styling,
+        PaneScrollback, ScrollbackRange, ScrollbackSearchMatch, Style, Styling,
     },
     errors::prelude::*,
     input::layout::Run,
@@ -138,8 +140,12 @@ pub struct TerminalPane {
     // held on startup and can possibly be used to display some errors
     pane_frame_color_override: Option<(PaletteColor, Option<String>)>,
     invoked_with: Option<Run>,
+    cwd: Option<PathBuf>, // as reported by the shell through OSC 7
     #[allow(dead_code)]
     arrow_fonts: bool,
+    scrollback_store: Option<ScrollbackStore>,
+    viewer_scroll: HashMap<ClientId, usize>, // lines each (read-only) client scrolled its own view back
+    viewers_to_render: HashSet<ClientId>,
 }
 
 impl Pane for TerminalPane {
@@ -190,7 +196,10 @@ impl Pane for TerminalPane {
     }
     fn handle_pty_bytes(&mut self, bytes: VteBytes) {
         self.set_should_render(true);
+        self.grid.invalidate_listed_search_matches();
         for &byte in &bytes {
+            // vte ignores APC sequences, so we look for kitty graphics commands ourselves
+            self.grid.handle_kitty_graphics_byte(byte);
             self.vte_parser.advance(&mut self.grid, byte);
         }
     }
@@ -306,8 +315,27 @@ impl Pane for TerminalPane {
     }
     fn render(
         &mut self,
-        _client_id: Option<ClientId>,
+        client_id: Option<ClientId>,
     ) -> Result<Option<(Vec<CharacterChunk>, Option<String>, Vec<SixelImageChunk>)>> {
+        let viewer_scroll = client_id.and_then(|c| {
+            self.viewer_scroll
+                .get(&c)
+                .map(|lines_back| (c, *lines_back))
+        });
+        if let Some((client_id, lines_back)) = viewer_scroll {
+            // this client scrolled its own view, so it gets rendered separately from the shared
+            // view and without marking the pane as rendered for the other clients
+            let viewer_needs_render = self.viewers_to_render.remove(&client_id);
+            if !self.should_render() && !viewer_needs_render {
+                return Ok(None);
+            }
+            let character_chunks = self.grid.render_scrolled_back(
+                lines_back,
+                self.get_content_x(),
+                self.get_content_y(),
+            );
+            return Ok(Some((character_chunks, None, vec![])));
+        }
         if self.should_render() {
             let content_x = self.get_content_x();
             let content_y = self.get_content_y();
@@ -367,6 +395,9 @@ impl Pane for TerminalPane {
                 if self.grid.search_results.wrap_search {
                     modifiers.push("w")
                 }
+                if self.grid.search_results.regex {
+                    modifiers.push("r")
+                }
                 modifier_text.push_str(&modifiers.join(", "));
                 modifier_text.push(']');
             }
@@ -543,6 +574,41 @@ impl Pane for TerminalPane {
     fn is_scrolled(&self) -> bool {
         self.grid.is_scrolled
     }
+    fn viewer_scroll_up(&mut self, count: usize, client_id: ClientId) {
+        let max_viewer_scroll = self.grid.max_viewer_scroll();
+        let lines_back = self.viewer_scroll.entry(client_id).or_insert(0);
+        *lines_back = lines_back.saturating_add(count).min(max_viewer_scroll);
+        self.viewers_to_render.insert(client_id);
+    }
+    fn viewer_scroll_down(&mut self, count: usize, client_id: ClientId) {
+        if let Some(lines_back) = self.viewer_scroll.get_mut(&client_id) {
+            *lines_back = lines_back.saturating_sub(count);
+            if *lines_back == 0 {
+                // back at the shared view
+                self.viewer_scroll.remove(&client_id);
+                self.render_full_viewport();
+                self.set_should_render(true);
+            } else {
+                self.viewers_to_render.insert(client_id);
+            }
+        }
+    }
+    fn has_viewer_scroll(&self, client_id: ClientId) -> bool {
+        self.viewer_scroll.contains_key(&client_id)
+    }
+    fn scroll_to_previous_prompt(&mut self, _client_id: ClientId) -> bool {
+        let scrolled = self.grid.scroll_to_previous_prompt();
+        self.set_should_render(true);
+        scrolled
+    }
+    fn scroll_to_next_prompt(&mut self, _client_id: ClientId) -> bool {
+        let scrolled = self.grid.scroll_to_next_prompt();
+        self.set_should_render(true);
+        scrolled
+    }
+    fn last_command_output(&self) -> Option<String> {
+        self.grid.last_command_output()
+    }
 
     fn active_at(&self) -> Instant {
         self.active_at
@@ -562,6 +628,27 @@ impl Pane for TerminalPane {
         self.grid.pending_clipboard_update.take()
     }
 
+    fn drain_cwd_update(&mut self) -> Option<PathBuf> {
+        let cwd_update = self.grid.pending_cwd_update.take();
+        if let Some(cwd) = &cwd_update {
+            self.cwd = Some(cwd.clone());
+        }
+        cwd_update
+    }
+
+    fn cwd(&self) -> Option<PathBuf> {
+        self.cwd.clone()
+    }
+
+    fn drain_notifications(&mut self) -> Vec<(Option<String>, String)> {
+        self.grid.pending_notifications.drain(..).collect()
+    }
+
+    fn kitty_image_chunks(&self) -> Vec<KittyImageChunk> {
+        self.grid
+            .kitty_image_chunks(self.get_content_x(), self.get_content_y())
+    }
+
     fn start_selection(&mut self, start: &Position, _client_id: ClientId) {
         self.grid.start_selection(start);
         self.set_should_render(true);
@@ -724,6 +811,30 @@ impl Pane for TerminalPane {
     fn toggle_search_wrap(&mut self) {
         self.grid.toggle_search_wrap();
     }
+    fn toggle_search_regex(&mut self) {
+        self.grid.toggle_search_regex();
+        self.set_should_render(true);
+    }
+    fn list_search_matches(&mut self) -> Vec<ScrollbackSearchMatch> {
+        if self.search_term.is_empty() {
+            return vec![];
+        }
+        self.grid.search_all_matches()
+    }
+    fn search_jump_to_match(&mut self, index: usize) -> bool {
+        if self.search_term.is_empty() {
+            return false;
+        }
+        let jumped = self.grid.search_jump_to_match(index);
+        self.set_should_render(true);
+        jumped
+    }
+    fn search_scrollback(&self, pattern: &str) -> Result<Vec<ScrollbackSearchMatch>> {
+        self.grid.search_scrollback(pattern)
+    }
+    fn pane_scrollback(&self, range: ScrollbackRange, with_styles: bool) -> Result<PaneScrollback> {
+        Ok(self.grid.pane_scrollback(range, with_styles))
+    }
     fn clear_search(&mut self) {
         self.grid.clear_search();
         self.search_term.clear();
@@ -804,6 +915,28 @@ impl Pane for TerminalPane {
     fn serialize(&self, scrollback_lines_to_serialize: Option<usize>) -> Option<String> {
         self.grid.serialize(scrollback_lines_to_serialize)
     }
+    fn persist_scrollback(
+        &mut self,
+        folder: &Path,
+        scrollback_lines_to_persist: usize,
+    ) -> Result<()> {
+        let scrollback_store = self
+            .scrollback_store
+            .get_or_insert_with(|| ScrollbackStore::new(format!("scrollback_{}", Uuid::new_v4())));
+        self.grid
+            .persist_scrollback(scrollback_store, folder, scrollback_lines_to_persist)
+    }
+    fn persisted_scrollback_file(&self) -> Option<String> {
+        self.scrollback_store
+            .as_ref()
+            .filter(|s| s.is_persisted())
+            .map(|s| s.file_name().to_owned())
+    }
+    fn remove_persisted_scrollback(&mut self) {
+        if let Some(scrollback_store) = self.scrollback_store.as_mut() {
+            scrollback_store.remove().non_fatal();
+        }
+    }
     fn rerun(&mut self) -> Option<RunCommand> {
         // if this is a command pane that has exited or is waiting to be rerun, will return its
         // RunCommand, otherwise it is safe to assume this is not the right sort of pane or that it
@@ -937,12 +1070,29 @@ impl TerminalPane {
             borderless: false,
             exclude_from_sync: false,
             fake_cursor_locations: HashSet::new(),
+            viewer_scroll: HashMap::new(),
+            viewers_to_render: HashSet::new(),
             search_term: String::new(),
             is_held: None,
             banner: None,
             pane_frame_color_override: None,
             invoked_with,
+            cwd: None,
             arrow_fonts,
+            scrollback_store: None,
+        }
+    }
+    pub fn restore_scrollback(&mut self, scrollback_store_path: &Path) {
+        match read_scrollback_store(scrollback_store_path) {
+            Ok(lines) => self.grid.restore_scrollback(lines),
+            Err(e) => log::error!("{:?}", e),
+        }
+        // we keep persisting to the same file name, so that the store stays tied to this pane in
+        // the serialized layout across resurrections
+        if let Some(file_name) = scrollback_store_path.file_name() {
+            self.scrollback_store = Some(ScrollbackStore::new(
+                file_name.to_string_lossy().into_owned(),
+            ));
         }
     }
     pub fn get_x(&self) -> usize {
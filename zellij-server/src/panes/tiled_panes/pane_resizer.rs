@@ -1,9 +1,9 @@
 use super::stacked_panes::StackedPanes;
 use crate::{panes::PaneId, tab::Pane};
 use cassowary::{
-    strength::{REQUIRED, STRONG},
+    strength::{self, REQUIRED, STRONG},
     Expression, Solver, Variable,
-    WeightedRelation::EQ,
+    WeightedRelation::{EQ, GE, LE},
 };
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -11,7 +11,7 @@ use std::rc::Rc;
 use zellij_utils::{
     errors::prelude::*,
     input::layout::SplitDirection,
-    pane_size::{Constraint, Dimension, PaneGeom},
+    pane_size::{Constraint, Dimension, PaneGeom, SizeBounds},
 };
 
 pub struct PaneResizer<'a> {
@@ -28,6 +28,7 @@ struct Span {
     direction: SplitDirection,
     pos: usize,
     size: Dimension,
+    size_bounds: SizeBounds,
     size_var: Variable,
 }
 
@@ -105,6 +106,8 @@ impl<'a> PaneResizer<'a> {
             if error < 0 {
                 flex_spans.reverse();
             }
+            // prefer not to push spans with min/max sizes over their bounds while rounding
+            flex_spans.sort_by_key(|s| !s.size_bounds.is_empty());
             for span in flex_spans {
                 rounded_sizes
                     .entry(span.size_var)
@@ -294,6 +297,7 @@ impl<'a> PaneResizer<'a> {
                 direction,
                 pos: position_and_size.x,
                 size: position_and_size.cols,
+                size_bounds: position_and_size.size_bounds,
                 size_var,
             }),
             SplitDirection::Vertical => Some(Span {
@@ -301,6 +305,7 @@ impl<'a> PaneResizer<'a> {
                 direction,
                 pos: position_and_size.y,
                 size: position_and_size.rows,
+                size_bounds: position_and_size.size_bounds,
                 size_var,
             }),
         }
@@ -334,6 +339,28 @@ fn constrain_spans(space: usize, spans: &[Span]) -> HashSet<cassowary::Constrain
         };
     }
 
+    // Keep flexible spans within their min/max sizes, this is stronger than their ratios but not
+    // required so that we can still lay out the panes if the screen is too small to honor them
+    let bound_strength = strength::create(100.0, 0.0, 0.0, 1.0);
+    for span in spans.iter().filter(|s| s.size.is_percent()) {
+        let (min_size, max_size) = match span.direction {
+            SplitDirection::Horizontal => (
+                span.size_bounds.min_cols(space),
+                span.size_bounds.max_cols(space),
+            ),
+            SplitDirection::Vertical => (
+                span.size_bounds.min_rows(space),
+                span.size_bounds.max_rows(space),
+            ),
+        };
+        if let Some(min_size) = min_size {
+            constraints.insert(span.size_var | GE(bound_strength) | min_size as f64);
+        }
+        if let Some(max_size) = max_size {
+            constraints.insert(span.size_var | LE(bound_strength) | max_size as f64);
+        }
+    }
+
     constraints
 }
 
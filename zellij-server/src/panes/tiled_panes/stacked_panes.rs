@@ -191,6 +191,7 @@ impl<'a> StackedPanes<'a> {
             x: first_pane_in_stack.x,
             cols: first_pane_in_stack.cols,
             rows,
+            size_bounds: first_pane_in_stack.size_bounds,
             stacked: None, // important because otherwise the minimum stack size will not be
             // respected
             ..Default::default()
@@ -302,6 +303,7 @@ impl<'a> StackedPanes<'a> {
         let mut new_flexible_pane_geom = new_full_stack_geom;
         new_flexible_pane_geom.stacked = flexible_pane.stacked;
         new_flexible_pane_geom.logical_position = flexible_pane.logical_position;
+        new_flexible_pane_geom.size_bounds = flexible_pane.size_bounds;
         new_flexible_pane_geom
             .rows
             .set_inner(new_rows_for_flexible_pane);
@@ -185,6 +185,12 @@ impl<'a> TiledPaneGrid<'a> {
             if direction.is_horizontal() {
                 match strategy.resize {
                     Resize::Increase => {
+                        if !self
+                            .can_increase_pane_width(pane_id, change_by.0 as f64)
+                            .with_context(err_context)?
+                        {
+                            return Ok(false);
+                        }
                         for id in pane_ids {
                             if !self
                                 .can_reduce_pane_width(&id, change_by.0 as f64)
@@ -195,13 +201,28 @@ impl<'a> TiledPaneGrid<'a> {
                         }
                         Ok(true)
                     },
-                    Resize::Decrease => self
-                        .can_reduce_pane_width(pane_id, change_by.0 as f64)
-                        .with_context(err_context),
+                    Resize::Decrease => {
+                        for id in pane_ids {
+                            if !self
+                                .can_increase_pane_width(&id, change_by.0 as f64)
+                                .with_context(err_context)?
+                            {
+                                return Ok(false);
+                            }
+                        }
+                        self.can_reduce_pane_width(pane_id, change_by.0 as f64)
+                            .with_context(err_context)
+                    },
                 }
             } else {
                 match strategy.resize {
                     Resize::Increase => {
+                        if !self
+                            .can_increase_pane_height(pane_id, change_by.1 as f64)
+                            .with_context(err_context)?
+                        {
+                            return Ok(false);
+                        }
                         for id in pane_ids {
                             if !self
                                 .can_reduce_pane_height(&id, change_by.1 as f64)
@@ -212,9 +233,18 @@ impl<'a> TiledPaneGrid<'a> {
                         }
                         Ok(true)
                     },
-                    Resize::Decrease => self
-                        .can_reduce_pane_height(pane_id, change_by.1 as f64)
-                        .with_context(err_context),
+                    Resize::Decrease => {
+                        for id in pane_ids {
+                            if !self
+                                .can_increase_pane_height(&id, change_by.1 as f64)
+                                .with_context(err_context)?
+                            {
+                                return Ok(false);
+                            }
+                        }
+                        self.can_reduce_pane_height(pane_id, change_by.1 as f64)
+                            .with_context(err_context)
+                    },
                 }
             }
         } else {
@@ -528,7 +558,12 @@ impl<'a> TiledPaneGrid<'a> {
             .with_context(err_context)?;
         let current_fixed_cols = pane.cols.as_usize();
         let will_reduce_by = ((self.display_area.cols as f64 / 100.0) * reduce_by) as usize;
-        if current_fixed_cols.saturating_sub(will_reduce_by) < MIN_TERMINAL_WIDTH {
+        let min_cols = pane
+            .size_bounds
+            .min_cols(self.display_area.cols)
+            .unwrap_or(0)
+            .max(MIN_TERMINAL_WIDTH);
+        if current_fixed_cols.saturating_sub(will_reduce_by) < min_cols {
             Ok(false)
         } else if let Some(cols) = pane.cols.as_percent() {
             Ok(cols - reduce_by >= RESIZE_PERCENT)
@@ -552,7 +587,12 @@ impl<'a> TiledPaneGrid<'a> {
         };
         let current_fixed_rows = pane.rows.as_usize();
         let will_reduce_by = ((self.display_area.rows as f64 / 100.0) * reduce_by) as usize;
-        if current_fixed_rows.saturating_sub(will_reduce_by) < min_terminal_height {
+        let min_rows = pane
+            .size_bounds
+            .min_rows(self.display_area.rows)
+            .unwrap_or(0)
+            .max(min_terminal_height);
+        if current_fixed_rows.saturating_sub(will_reduce_by) < min_rows {
             Ok(false)
         } else if let Some(rows) = pane.rows.as_percent() {
             Ok(rows - reduce_by >= RESIZE_PERCENT)
@@ -561,6 +601,39 @@ impl<'a> TiledPaneGrid<'a> {
         }
     }
 
+    fn can_increase_pane_width(&self, pane_id: &PaneId, increase_by: f64) -> Result<bool> {
+        let err_context = || {
+            format!("failed to determine if pane {pane_id:?} can increase width by {increase_by} %")
+        };
+
+        let pane = self
+            .get_pane_geom(pane_id)
+            .with_context(|| no_pane_id(pane_id))
+            .with_context(err_context)?;
+        let will_increase_by = ((self.display_area.cols as f64 / 100.0) * increase_by) as usize;
+        match pane.size_bounds.max_cols(self.display_area.cols) {
+            Some(max_cols) => Ok(pane.cols.as_usize() + will_increase_by <= max_cols),
+            None => Ok(true),
+        }
+    }
+    fn can_increase_pane_height(&self, pane_id: &PaneId, increase_by: f64) -> Result<bool> {
+        let err_context = || {
+            format!(
+                "failed to determine if pane {pane_id:?} can increase height by {increase_by} %"
+            )
+        };
+
+        let pane = self
+            .get_pane_geom(pane_id)
+            .with_context(|| no_pane_id(pane_id))
+            .with_context(err_context)?;
+        let will_increase_by = ((self.display_area.rows as f64 / 100.0) * increase_by) as usize;
+        match pane.size_bounds.max_rows(self.display_area.rows) {
+            Some(max_rows) => Ok(pane.rows.as_usize() + will_increase_by <= max_rows),
+            None => Ok(true),
+        }
+    }
+
     fn reduce_pane_height(&mut self, id: &PaneId, percent: f64) {
         if self.can_reduce_pane_height(id, percent).unwrap_or(false) {
             let current_pane_is_stacked = self
@@ -1275,21 +1348,60 @@ impl<'a> TiledPaneGrid<'a> {
         None
     }
     fn find_panes_to_grow(&self, id: PaneId) -> Option<(Vec<PaneId>, SplitDirection)> {
-        if let Some(panes) = self
-            .panes_to_the_left_between_aligning_borders(id)
-            .or_else(|| self.panes_to_the_right_between_aligning_borders(id))
-        {
-            return Some((panes, SplitDirection::Horizontal));
-        }
-
-        if let Some(panes) = self
-            .panes_above_between_aligning_borders(id)
-            .or_else(|| self.panes_below_between_aligning_borders(id))
-        {
-            return Some((panes, SplitDirection::Vertical));
-        }
+        let candidates: Vec<(Vec<PaneId>, SplitDirection)> = [
+            (
+                self.panes_to_the_left_between_aligning_borders(id),
+                SplitDirection::Horizontal,
+            ),
+            (
+                self.panes_to_the_right_between_aligning_borders(id),
+                SplitDirection::Horizontal,
+            ),
+            (
+                self.panes_above_between_aligning_borders(id),
+                SplitDirection::Vertical,
+            ),
+            (
+                self.panes_below_between_aligning_borders(id),
+                SplitDirection::Vertical,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(panes, direction)| panes.map(|panes| (panes, direction)))
+        .collect();
 
-        None
+        // prefer growing panes that would stay within their max size, but rather grow them past it
+        // than leave a hole in the layout
+        candidates
+            .iter()
+            .find(|(panes, direction)| self.panes_can_grow_over_pane(panes, *direction, &id))
+            .or_else(|| candidates.first())
+            .cloned()
+    }
+    fn panes_can_grow_over_pane(
+        &self,
+        panes: &[PaneId],
+        direction: SplitDirection,
+        id: &PaneId,
+    ) -> bool {
+        let Some(freed_space) = self.get_pane_geom(id) else {
+            return false;
+        };
+        panes
+            .iter()
+            .filter_map(|p| self.get_pane_geom(p))
+            .all(|geom| match direction {
+                SplitDirection::Horizontal => geom
+                    .size_bounds
+                    .max_cols(self.display_area.cols)
+                    .map(|max_cols| geom.cols.as_usize() + freed_space.cols.as_usize() <= max_cols)
+                    .unwrap_or(true),
+                SplitDirection::Vertical => geom
+                    .size_bounds
+                    .max_rows(self.display_area.rows)
+                    .map(|max_rows| geom.rows.as_usize() + freed_space.rows.as_usize() <= max_rows)
+                    .unwrap_or(true),
+            })
     }
     fn grow_panes(
         &mut self,
@@ -1365,8 +1477,16 @@ impl<'a> TiledPaneGrid<'a> {
                     * pane_to_check.cols();
                 let pane_can_be_split = pane_to_check.cols() >= MIN_TERMINAL_WIDTH
                     && pane_to_check.rows() >= MIN_TERMINAL_HEIGHT
-                    && ((pane_to_check.cols() > pane_to_check.min_width() * 2)
-                        || (pane_to_check.rows() > pane_to_check.min_height() * 2));
+                    && ((pane_to_check.cols() > pane_to_check.min_width() * 2
+                        && self.pane_keeps_min_size_when_split(
+                            &pane_to_check.current_geom(),
+                            SplitDirection::Vertical,
+                        ))
+                        || (pane_to_check.rows() > pane_to_check.min_height() * 2
+                            && self.pane_keeps_min_size_when_split(
+                                &pane_to_check.current_geom(),
+                                SplitDirection::Horizontal,
+                            )));
                 if pane_can_be_split && pane_size > current_largest_pane_size {
                     (pane_size, Some(*id_of_pane_to_check))
                 } else {
@@ -1378,13 +1498,17 @@ impl<'a> TiledPaneGrid<'a> {
             let Some(pane_to_split) = panes.get(t_id_to_split) else {
                 return None;
             };
+            let geom_to_split = pane_to_split.current_geom();
             let direction = if pane_to_split.rows()
                 * cursor_height_width_ratio.unwrap_or(DEFAULT_CURSOR_HEIGHT_WIDTH_RATIO)
                 > pane_to_split.cols()
                 && pane_to_split.rows() > pane_to_split.min_height() * 2
+                && self.pane_keeps_min_size_when_split(&geom_to_split, SplitDirection::Horizontal)
             {
                 Some(SplitDirection::Horizontal)
-            } else if pane_to_split.cols() > pane_to_split.min_width() * 2 {
+            } else if pane_to_split.cols() > pane_to_split.min_width() * 2
+                && self.pane_keeps_min_size_when_split(&geom_to_split, SplitDirection::Vertical)
+            {
                 Some(SplitDirection::Vertical)
             } else {
                 None
@@ -1405,13 +1529,17 @@ impl<'a> TiledPaneGrid<'a> {
         let Some(pane_to_split) = panes.get(active_pane_id) else {
             return None;
         };
+        let geom_to_split = pane_to_split.current_geom();
         let direction = if pane_to_split.rows()
             * cursor_height_width_ratio.unwrap_or(DEFAULT_CURSOR_HEIGHT_WIDTH_RATIO)
             > pane_to_split.cols()
             && pane_to_split.rows() > 10 * 2
+            && self.pane_keeps_min_size_when_split(&geom_to_split, SplitDirection::Horizontal)
         {
             Some(SplitDirection::Horizontal)
-        } else if pane_to_split.cols() > 30 * 2 {
+        } else if pane_to_split.cols() > 30 * 2
+            && self.pane_keeps_min_size_when_split(&geom_to_split, SplitDirection::Vertical)
+        {
             Some(SplitDirection::Vertical)
         } else {
             None
@@ -1419,6 +1547,20 @@ impl<'a> TiledPaneGrid<'a> {
 
         direction.map(|direction| (*active_pane_id, direction))
     }
+    fn pane_keeps_min_size_when_split(&self, geom: &PaneGeom, direction: SplitDirection) -> bool {
+        match direction {
+            SplitDirection::Vertical => geom
+                .size_bounds
+                .min_cols(self.display_area.cols)
+                .map(|min_cols| geom.cols.as_usize() / 2 >= min_cols)
+                .unwrap_or(true),
+            SplitDirection::Horizontal => geom
+                .size_bounds
+                .min_rows(self.display_area.rows)
+                .map(|min_rows| geom.rows.as_usize() / 2 >= min_rows)
+                .unwrap_or(true),
+        }
+    }
     pub fn has_room_for_new_stacked_pane(&self) -> bool {
         let panes = self.panes.borrow();
         let flexible_pane_in_stack: Vec<(&PaneId, &&mut Box<dyn Pane>)> = panes
@@ -2337,12 +2479,14 @@ pub fn split(direction: SplitDirection, rect: &PaneGeom) -> Option<(PaneGeom, Pa
                 x: first_rect.x + 1,
                 cols: first_rect.cols,
                 logical_position: None,
+                size_bounds: Default::default(),
                 ..*rect
             },
             SplitDirection::Horizontal => PaneGeom {
                 y: first_rect.y + 1,
                 rows: first_rect.rows,
                 logical_position: None,
+                size_bounds: Default::default(),
                 ..*rect
             },
         };
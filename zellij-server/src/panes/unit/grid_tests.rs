@@ -7,7 +7,7 @@ use std::collections::HashMap;
 use std::rc::Rc;
 use vte;
 use zellij_utils::{
-    data::{Palette, Style},
+    data::{Palette, ScrollbackRange, Style},
     pane_size::SizeInPixels,
     position::Position,
 };
@@ -3900,3 +3900,377 @@ fn cannot_escape_scroll_region() {
     }
     assert_snapshot!(format!("{:?}", grid));
 }
+
+#[test]
+fn last_command_output_from_osc_133_marks() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let content = "\u{1b}]133;A\u{7}$ \u{1b}]133;B\u{7}ls\r\n\u{1b}]133;C\u{7}file1\r\nfile2\r\n\u{1b}]133;D;0\u{7}\u{1b}]133;A\u{7}$ ".as_bytes();
+    for byte in content {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.last_command_output(),
+        Some(String::from("file1\nfile2")),
+        "output between the last OSC 133 C and D marks"
+    );
+}
+
+#[test]
+fn pane_scrollback_in_ranges() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let long_line = "0123456789".repeat(3);
+    let mut content = String::from("\u{1b}[31mline 1\u{1b}[m\r\n");
+    for i in 2..=14 {
+        content.push_str(&format!("line {}\r\n", i));
+    }
+    content.push_str(&format!("{}\r\nlast", long_line));
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+
+    let viewport = grid.pane_scrollback(ScrollbackRange::Viewport, false);
+    assert_eq!(viewport.total_lines, 16, "wrapped line counted once");
+    assert_eq!(
+        viewport.first_line_index, 7,
+        "viewport starts after the scrollback"
+    );
+    assert_eq!(
+        viewport.lines.len(),
+        9,
+        "all lines in the viewport returned"
+    );
+    assert_eq!(
+        viewport.lines[7], long_line,
+        "wrapped line returned unwrapped"
+    );
+    assert_eq!(
+        grid.pane_scrollback(ScrollbackRange::Full, false)
+            .lines
+            .len(),
+        16,
+        "full scrollback returned"
+    );
+    assert_eq!(
+        grid.pane_scrollback(ScrollbackRange::Last(2), false).lines,
+        vec![long_line.clone(), String::from("last")],
+        "last lines returned"
+    );
+    assert_eq!(
+        grid.pane_scrollback(ScrollbackRange::Lines { start: 1, end: 3 }, false)
+            .lines,
+        vec![String::from("line 2"), String::from("line 3")],
+        "range of lines returned"
+    );
+    let out_of_bounds = grid.pane_scrollback(
+        ScrollbackRange::Lines {
+            start: 15,
+            end: 100,
+        },
+        false,
+    );
+    assert_eq!(
+        (out_of_bounds.first_line_index, out_of_bounds.lines),
+        (15, vec![String::from("last")]),
+        "range clamped to the existing lines"
+    );
+    let plain = grid.pane_scrollback(ScrollbackRange::Lines { start: 0, end: 1 }, false);
+    let styled = grid.pane_scrollback(ScrollbackRange::Lines { start: 0, end: 1 }, true);
+    assert_eq!(
+        plain.lines,
+        vec![String::from("line 1")],
+        "plain line has no styles"
+    );
+    assert!(
+        styled.lines[0].contains("line 1") && styled.lines[0].contains("\u{1b}["),
+        "styled line includes its ANSI styles"
+    );
+}
+
+#[test]
+fn scroll_to_previous_and_next_prompt() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        5,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let mut content = String::new();
+    for command_index in 0..3 {
+        write!(
+            &mut content,
+            "\u{1b}]133;A\u{7}$ cmd{}\r\n\u{1b}]133;C\u{7}",
+            command_index
+        )
+        .unwrap();
+        for line_index in 0..5 {
+            write!(&mut content, "output{}\r\n", line_index).unwrap();
+        }
+        content.push_str("\u{1b}]133;D;0\u{7}");
+    }
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert!(
+        grid.scroll_to_previous_prompt(),
+        "scrolled to previous prompt"
+    );
+    assert_eq!(
+        grid.viewport[0]
+            .columns
+            .iter()
+            .map(|c| c.character)
+            .collect::<String>()
+            .trim_end(),
+        "$ cmd2",
+        "last prompt at the top of the viewport"
+    );
+    assert!(
+        grid.scroll_to_previous_prompt(),
+        "scrolled to previous prompt"
+    );
+    assert_eq!(
+        grid.viewport[0]
+            .columns
+            .iter()
+            .map(|c| c.character)
+            .collect::<String>()
+            .trim_end(),
+        "$ cmd1",
+        "second prompt at the top of the viewport"
+    );
+    assert!(grid.scroll_to_next_prompt(), "scrolled to next prompt");
+    assert_eq!(
+        grid.viewport[0]
+            .columns
+            .iter()
+            .map(|c| c.character)
+            .collect::<String>()
+            .trim_end(),
+        "$ cmd2",
+        "back to the last prompt"
+    );
+}
+
+#[test]
+fn osc_7_reports_cwd() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let content = "\u{1b}]7;file://remote-host/home/user/my%20project\u{1b}\\".as_bytes();
+    for byte in content {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.pending_cwd_update,
+        Some(std::path::PathBuf::from("/home/user/my project")),
+        "cwd parsed from OSC 7 regardless of hostname"
+    );
+}
+
+#[test]
+fn osc_9_and_osc_777_notifications() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let content = "\u{1b}]9;build done; 0 errors\u{1b}\\\u{1b}]9;4;1;50\u{1b}\\\u{1b}]777;notify;cargo;tests passed\u{1b}\\".as_bytes();
+    for byte in content {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.pending_notifications,
+        vec![
+            (None, "build done; 0 errors".to_owned()),
+            (Some("cargo".to_owned()), "tests passed".to_owned()),
+        ],
+        "OSC 9 and OSC 777 notifications collected, ConEmu progress reports ignored"
+    );
+}
+
+#[test]
+fn kitty_graphics_transmit_and_display() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    // a 1x1 RGB image sent in two chunks, displayed over 4x2 cells
+    let content =
+        "\u{1b}_Ga=T,f=24,s=1,v=1,i=5,c=4,r=2,m=1;AA\u{1b}\\\u{1b}_Gm=0;AA\u{1b}\\after".as_bytes();
+    for byte in content {
+        grid.handle_kitty_graphics_byte(*byte);
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.pending_messages_to_pty,
+        vec!["\u{1b}_Gi=5;OK\u{1b}\\".as_bytes().to_vec()],
+        "image transmission acknowledged"
+    );
+    let image_chunks = grid.kitty_image_chunks(1, 1);
+    assert_eq!(image_chunks.len(), 1, "image placed in viewport");
+    assert_eq!(
+        (
+            image_chunks[0].cell_x,
+            image_chunks[0].cell_y,
+            image_chunks[0].columns,
+            image_chunks[0].rows
+        ),
+        (1, 1, 4, 2),
+        "image placed at cursor position relative to the pane"
+    );
+    assert_eq!(
+        grid.cursor_coordinates(),
+        Some((9, 1)),
+        "cursor moved past the image and the text printed after it"
+    );
+}
+
+#[test]
+fn kitty_graphics_delete_placements() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    let mut grid = Grid::new(
+        10,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    );
+    let content = "\u{1b}_Ga=t,f=24,s=1,v=1,i=7,q=2;AAAA\u{1b}\\\u{1b}_Ga=p,i=7,c=2,r=1,q=2\u{1b}\\\u{1b}_Ga=p,i=7,c=2,r=1,q=2\u{1b}\\".as_bytes();
+    for byte in content {
+        grid.handle_kitty_graphics_byte(*byte);
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert!(
+        grid.pending_messages_to_pty.is_empty(),
+        "no responses when quiet"
+    );
+    assert_eq!(grid.kitty_image_chunks(0, 0).len(), 2, "image placed twice");
+    let content = "\u{1b}_Ga=d,d=i,i=7,q=2\u{1b}\\".as_bytes();
+    for byte in content {
+        grid.handle_kitty_graphics_byte(*byte);
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert!(
+        grid.kitty_image_chunks(0, 0).is_empty(),
+        "all placements of the image deleted"
+    );
+}
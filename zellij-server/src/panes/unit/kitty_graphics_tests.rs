@@ -0,0 +1,212 @@
+use super::*;
+
+const PANE_SIZE: Size = Size { rows: 20, cols: 40 };
+
+fn rgb_image(pixel_width: usize, pixel_height: usize) -> Rc<KittyImage> {
+    let data = vec![0; pixel_width * pixel_height * 3];
+    Rc::new(
+        KittyImage::new(
+            KittyImageFormat::Rgb,
+            pixel_width,
+            pixel_height,
+            false,
+            data,
+        )
+        .unwrap(),
+    )
+}
+
+fn rgb_image_command(
+    control_data: &str,
+    pixel_width: usize,
+    pixel_height: usize,
+) -> KittyGraphicsCommand {
+    let payload = base64::encode(vec![0; pixel_width * pixel_height * 3]);
+    KittyGraphicsCommand::parse(
+        format!(
+            "f=24,s={},v={},{};{}",
+            pixel_width, pixel_height, control_data, payload
+        )
+        .as_bytes(),
+    )
+    .unwrap()
+}
+
+fn image_chunk(image: &Rc<KittyImage>, cell_x: usize, cell_y: usize) -> KittyImageChunk {
+    KittyImageChunk {
+        image: image.clone(),
+        cell_x,
+        cell_y,
+        columns: 2,
+        rows: 2,
+        source_x: 0,
+        source_y: 0,
+        source_width: image.pixel_width,
+        source_height: image.pixel_height,
+        z_index: 0,
+    }
+}
+
+fn parse_bytes(parser: &mut KittyGraphicsParser, bytes: &[u8]) -> Vec<KittyGraphicsCommand> {
+    bytes
+        .iter()
+        .filter_map(|byte| parser.advance(*byte))
+        .collect()
+}
+
+#[test]
+fn apc_parser_picks_graphics_commands_out_of_the_byte_stream() {
+    let mut parser = KittyGraphicsParser::default();
+    let commands = parse_bytes(
+        &mut parser,
+        b"some text\x1b[31mred\x1b_Ga=T,f=100,i=3;AAAA\x1b\\more text",
+    );
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0].action, Some('T'));
+    assert_eq!(commands[0].format, Some(100));
+    assert_eq!(commands[0].image_id, Some(3));
+    assert_eq!(commands[0].payload, b"AAAA".to_vec());
+}
+
+#[test]
+fn apc_parser_ignores_other_apc_sequences() {
+    let mut parser = KittyGraphicsParser::default();
+    let commands = parse_bytes(&mut parser, b"\x1b_Xa=T,i=1\x1b\\\x1b_Ga=d\x1b\\");
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0].action, Some('d'));
+}
+
+#[test]
+fn apc_parser_drops_cancelled_and_interrupted_sequences() {
+    let mut parser = KittyGraphicsParser::default();
+    // CAN cancels the sequence
+    assert!(parse_bytes(&mut parser, b"\x1b_Ga=T,i=1\x18;AAAA\x1b\\").is_empty());
+    // an escape that doesn't terminate the sequence starts a new one
+    let commands = parse_bytes(&mut parser, b"\x1b_Ga=T,i=1\x1b\x1b_Ga=p,i=2\x1b\\");
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0].action, Some('p'));
+    assert_eq!(commands[0].image_id, Some(2));
+}
+
+#[test]
+fn apc_parser_drops_malformed_control_data() {
+    let mut parser = KittyGraphicsParser::default();
+    assert!(parse_bytes(&mut parser, b"\x1b_Ga=T,c=notanumber\x1b\\").is_empty());
+    assert!(parse_bytes(&mut parser, b"\x1b_Gnovalue\x1b\\").is_empty());
+}
+
+#[test]
+fn placement_is_clamped_to_pane_size() {
+    let mut kitty_grid = KittyGrid::default();
+    let result = kitty_grid.handle_command(
+        rgb_image_command("a=T,c=100000,r=100000", 2, 2),
+        0,
+        0,
+        None,
+        PANE_SIZE,
+    );
+    assert_eq!(
+        result.cursor_movement,
+        Some((PANE_SIZE.cols, PANE_SIZE.rows))
+    );
+    let chunks = kitty_grid.image_chunks_in_viewport(PANE_SIZE.cols, PANE_SIZE.rows, 0, 0, 0);
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].columns, PANE_SIZE.cols);
+    assert_eq!(chunks[0].rows, PANE_SIZE.rows);
+}
+
+#[test]
+fn placement_with_zero_cell_size_does_not_panic() {
+    let mut kitty_grid = KittyGrid::default();
+    let result = kitty_grid.handle_command(
+        rgb_image_command("a=T", 4, 4),
+        0,
+        0,
+        Some(SizeInPixels {
+            width: 0,
+            height: 0,
+        }),
+        PANE_SIZE,
+    );
+    assert_eq!(result.cursor_movement, Some((4, 4)));
+}
+
+#[test]
+fn file_transmission_only_reads_regular_files() {
+    let mut kitty_grid = KittyGrid::default();
+    let directory = base64::encode(std::env::temp_dir().to_string_lossy().as_bytes());
+    let command =
+        KittyGraphicsCommand::parse(format!("a=t,t=f,f=100,i=1;{}", directory).as_bytes()).unwrap();
+    let result = kitty_grid.handle_command(command, 0, 0, None, PANE_SIZE);
+    assert_eq!(
+        result.response,
+        Some("\u{1b}_Gi=1;EINVAL:not a regular file\u{1b}\\".to_owned())
+    );
+}
+
+#[test]
+fn output_state_only_sends_changed_placements() {
+    let client_id = 1;
+    let image = rgb_image(2, 2);
+    let chunk = image_chunk(&image, 0, 0);
+    let mut output_state = KittyGraphicsOutputState::default();
+    output_state.add_supported_client(client_id);
+
+    assert!(output_state.has_changes_for_client(client_id, &[chunk.clone()]));
+    let vte_output = output_state.serialize_for_client(client_id, &[chunk.clone()]);
+    assert!(vte_output.contains(&format!("\u{1b}_Ga=t,t=d,q=2,i={},", image.id)));
+    assert!(vte_output.contains(&format!("a=p,q=2,C=1,i={},p=1,", image.id)));
+
+    // nothing changed, so nothing is sent
+    assert!(!output_state.has_changes_for_client(client_id, &[chunk.clone()]));
+    assert_eq!(
+        output_state.serialize_for_client(client_id, &[chunk.clone()]),
+        ""
+    );
+
+    // the image moved, so the old placement is deleted and a new one is placed without
+    // re-transmitting the image
+    let moved_chunk = image_chunk(&image, 3, 1);
+    assert!(output_state.has_changes_for_client(client_id, &[moved_chunk.clone()]));
+    let vte_output = output_state.serialize_for_client(client_id, &[moved_chunk.clone()]);
+    assert!(vte_output.contains(&format!("\u{1b}_Ga=d,d=i,q=2,i={},p=1\u{1b}\\", image.id)));
+    assert!(vte_output.contains(&format!(
+        "\u{1b}[2;4H\u{1b}_Ga=p,q=2,C=1,i={},p=2,",
+        image.id
+    )));
+    assert!(!vte_output.contains("a=t,"));
+}
+
+#[test]
+fn output_state_frees_deleted_images_on_the_client() {
+    let client_id = 1;
+    let image = rgb_image(2, 2);
+    let image_id = image.id;
+    let mut output_state = KittyGraphicsOutputState::default();
+    output_state.add_supported_client(client_id);
+    output_state.serialize_for_client(client_id, &[image_chunk(&image, 0, 0)]);
+
+    drop(image);
+    let vte_output = output_state.serialize_for_client(client_id, &[]);
+    assert!(vte_output.contains(&format!("\u{1b}_Ga=d,d=i,q=2,i={},p=1\u{1b}\\", image_id)));
+    assert!(vte_output.contains(&format!("\u{1b}_Ga=d,d=I,q=2,i={}\u{1b}\\", image_id)));
+}
+
+#[test]
+fn output_state_is_tracked_per_client() {
+    let image = rgb_image(2, 2);
+    let chunk = image_chunk(&image, 0, 0);
+    let mut output_state = KittyGraphicsOutputState::default();
+    output_state.add_supported_client(1);
+    output_state.add_supported_client(2);
+    output_state.serialize_for_client(1, &[chunk.clone()]);
+
+    assert!(!output_state.has_changes_for_client(1, &[chunk.clone()]));
+    assert!(output_state.has_changes_for_client(2, &[chunk.clone()]));
+    let vte_output = output_state.serialize_for_client(2, &[chunk.clone()]);
+    assert!(vte_output.contains("a=t,"));
+
+    output_state.remove_client(2);
+    assert!(!output_state.client_supports_kitty_graphics(2));
+    assert!(output_state.client_supports_kitty_graphics(1));
+}
@@ -0,0 +1,164 @@
+use super::*;
+use crate::panes::grid::Grid;
+use crate::panes::link_handler::LinkHandler;
+use crate::panes::sixel::SixelImageStore;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use zellij_utils::data::{Palette, Style};
+
+fn create_grid(rows: usize, columns: usize) -> Grid {
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    Grid::new(
+        rows,
+        columns,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        explicitly_disable_kitty_keyboard_protocol,
+    )
+}
+
+fn write_to_grid(grid: &mut Grid, bytes: &str) {
+    let mut vte_parser = vte::Parser::new();
+    for byte in bytes.bytes() {
+        vte_parser.advance(grid, byte);
+    }
+}
+
+fn lines_above(grid: &Grid) -> Vec<String> {
+    grid.lines_above
+        .iter()
+        .map(|row| {
+            row.columns
+                .iter()
+                .map(|c| c.character)
+                .collect::<String>()
+                .trim_end()
+                .to_owned()
+        })
+        .collect()
+}
+
+fn persisted_lines(store_path: &Path) -> Vec<String> {
+    let mut grid = create_grid(2, 20);
+    grid.restore_scrollback(read_scrollback_store(store_path).unwrap());
+    lines_above(&grid)
+}
+
+#[test]
+fn scrollback_is_persisted_and_restored() {
+    let folder = tempfile::tempdir().unwrap();
+    let mut grid = create_grid(3, 20);
+    write_to_grid(
+        &mut grid,
+        "first\r\n\u{1b}[31msecond\u{1b}[m line\r\nthird line is longer than the pane\r\nfourth\r\nfifth\r\nsixth",
+    );
+    let mut scrollback_store = ScrollbackStore::new("scrollback_1".to_owned());
+    assert!(!scrollback_store.is_persisted());
+    grid.persist_scrollback(&mut scrollback_store, folder.path(), 0)
+        .unwrap();
+    assert!(scrollback_store.is_persisted());
+
+    let lines = read_scrollback_store(&folder.path().join("scrollback_1")).unwrap();
+    assert_eq!(lines.len(), 3);
+
+    let mut restored_grid = create_grid(3, 20);
+    restored_grid.restore_scrollback(lines);
+    assert_eq!(lines_above(&restored_grid), lines_above(&grid));
+    assert_eq!(
+        restored_grid.lines_above[1].columns[0].styles, grid.lines_above[1].columns[0].styles,
+        "styles are restored"
+    );
+    assert_eq!(
+        restored_grid.scrollback_position_and_length(),
+        grid.scrollback_position_and_length()
+    );
+}
+
+#[test]
+fn only_changes_are_appended_to_the_scrollback_store() {
+    let folder = tempfile::tempdir().unwrap();
+    let store_path = folder.path().join("scrollback_1");
+    let mut grid = create_grid(2, 20);
+    write_to_grid(&mut grid, "1\r\n2\r\n3\r\n4\r\n5");
+    let mut scrollback_store = ScrollbackStore::new("scrollback_1".to_owned());
+    grid.persist_scrollback(&mut scrollback_store, folder.path(), 0)
+        .unwrap();
+    let file_length = std::fs::metadata(&store_path).unwrap().len();
+
+    grid.persist_scrollback(&mut scrollback_store, folder.path(), 0)
+        .unwrap();
+    assert_eq!(
+        std::fs::metadata(&store_path).unwrap().len(),
+        file_length,
+        "nothing is written if nothing changed"
+    );
+
+    write_to_grid(&mut grid, "\r\n6");
+    grid.persist_scrollback(&mut scrollback_store, folder.path(), 0)
+        .unwrap();
+    let new_line = grid
+        .output_buffer
+        .serialize_line(grid.lines_above.back().unwrap())
+        .unwrap();
+    assert_eq!(
+        std::fs::metadata(&store_path).unwrap().len(),
+        file_length + 5 + new_line.len() as u64,
+        "only the new line is appended"
+    );
+    assert_eq!(persisted_lines(&store_path), vec!["1", "2", "3", "4"]);
+
+    // only the last 2 lines are kept from now on
+    grid.persist_scrollback(&mut scrollback_store, folder.path(), 2)
+        .unwrap();
+    assert_eq!(persisted_lines(&store_path), vec!["3", "4"]);
+
+    // scrolling up moves lines from the scrollback back into the viewport
+    grid.scroll_up_one_line();
+    grid.persist_scrollback(&mut scrollback_store, folder.path(), 2)
+        .unwrap();
+    assert_eq!(persisted_lines(&store_path), vec!["2", "3"]);
+}
+
+#[test]
+fn cut_short_scrollback_stores_are_read_up_to_the_last_full_line() {
+    let mut contents = SCROLLBACK_STORE_MAGIC.to_vec();
+    contents.push(SCROLLBACK_STORE_VERSION);
+    write_line_record(&mut contents, "full line").unwrap();
+    write_line_record(&mut contents, "cut short").unwrap();
+    contents.truncate(contents.len() - 3);
+    assert_eq!(
+        parse_scrollback_store(&contents).unwrap(),
+        vec!["full line".to_owned()]
+    );
+    assert!(parse_scrollback_store(b"not a scrollback store").is_err());
+}
+
+#[test]
+fn removing_a_scrollback_store_deletes_its_file() {
+    let folder = tempfile::tempdir().unwrap();
+    let store_path = folder.path().join("scrollback_1");
+    let mut grid = create_grid(2, 20);
+    write_to_grid(&mut grid, "1\r\n2\r\n3");
+    let mut scrollback_store = ScrollbackStore::new("scrollback_1".to_owned());
+    scrollback_store.remove().unwrap(); // nothing was persisted yet
+    grid.persist_scrollback(&mut scrollback_store, folder.path(), 0)
+        .unwrap();
+    assert!(store_path.exists());
+
+    scrollback_store.remove().unwrap();
+    assert!(!store_path.exists());
+    assert!(!scrollback_store.is_persisted());
+}
@@ -421,3 +421,132 @@ pub fn searching_whole_word_case_insensitive() {
         format!("{:?}", terminal_pane.grid)
     );
 }
+
+#[test]
+pub fn searching_with_regex() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term("tortor|quam");
+    assert!(
+        terminal_pane.grid.search_results.selections.is_empty(),
+        "needle is searched for literally before toggling regex mode"
+    );
+
+    terminal_pane.toggle_search_regex();
+    assert_snapshot!(
+        "grid_copy_tortor_or_quam_highlighted",
+        format!("{:?}", terminal_pane.grid)
+    );
+
+    terminal_pane.update_search_term("(");
+    assert!(
+        terminal_pane.grid.search_results.selections.is_empty(),
+        "invalid regex finds nothing"
+    );
+}
+
+#[test]
+pub fn searching_with_regex_across_line_wrap() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.handle_pty_bytes(
+        "a:--:aaaaaaaaa:--:--:--:aaaaaaaaaaa:--: :--: :--: aaa :--::--: aaa"
+            .as_bytes()
+            .to_vec(),
+    );
+    terminal_pane.grid.change_size(20, 5);
+    terminal_pane.update_search_term(":--:");
+    let mut plain_selections = terminal_pane.grid.search_results.selections.clone();
+    // plain searches find a match starting at the very beginning of the tail twice
+    plain_selections.dedup();
+    assert!(plain_selections
+        .iter()
+        .any(|selection| selection.start.line() != selection.end.line()));
+
+    terminal_pane.update_search_term("\0");
+    terminal_pane.update_search_term(":-{2}:");
+    terminal_pane.toggle_search_regex();
+    assert_eq!(
+        terminal_pane.grid.search_results.selections, plain_selections,
+        "regex matches wrap across lines the same way plain ones do"
+    );
+
+    terminal_pane.update_search_term("\0");
+    terminal_pane.update_search_term("a{3}");
+    terminal_pane.toggle_search_regex();
+    assert!(terminal_pane.grid.search_results.selections.len() > 2);
+    terminal_pane.toggle_search_whole_words();
+    assert_eq!(
+        terminal_pane.grid.search_results.selections.len(),
+        2,
+        "only the two standalone \"aaa\" words match"
+    );
+}
+
+// the line number and column of the active search match, as reported by list_search_matches
+fn active_search_match_position(terminal_pane: &TerminalPane) -> (usize, usize) {
+    let grid = &terminal_pane.grid;
+    let active = grid.search_results.active.unwrap();
+    let mut row_index = active.start.line() as usize;
+    let line_number = grid.lines_above.len()
+        + grid
+            .viewport
+            .iter()
+            .take(row_index + 1)
+            .filter(|row| row.is_canonical)
+            .count();
+    let mut column = active.start.column();
+    while row_index > 0 && !grid.viewport[row_index].is_canonical {
+        row_index -= 1;
+        column += grid.viewport[row_index].columns.len();
+    }
+    (line_number, column)
+}
+
+#[test]
+pub fn listing_and_jumping_to_search_matches() {
+    let mut terminal_pane = create_pane();
+    assert!(terminal_pane.list_search_matches().is_empty());
+
+    terminal_pane.update_search_term("tortor");
+    let search_matches = terminal_pane.list_search_matches();
+    assert_eq!(search_matches.len(), 7);
+    assert_eq!(
+        search_matches[0].line,
+        "Velit ut tortor pretium viverra suspendisse potenti nullam ac tortor. Adipiscing elit ut aliquam purus sit amet luctus venenatis."
+    );
+    assert_eq!(search_matches[0].column, 9);
+    assert_eq!(search_matches[1].line_number, search_matches[0].line_number);
+    assert_eq!(search_matches[1].column, 62);
+
+    for (index, search_match) in search_matches.iter().enumerate() {
+        assert!(terminal_pane.search_jump_to_match(index + 1));
+        assert_eq!(
+            active_search_match_position(&terminal_pane),
+            (search_match.line_number, search_match.column),
+            "jumped to match {}",
+            index + 1
+        );
+    }
+    assert!(!terminal_pane.search_jump_to_match(0));
+    assert!(!terminal_pane.search_jump_to_match(search_matches.len() + 1));
+
+    terminal_pane.handle_pty_bytes("\n\rone more tortor".as_bytes().to_vec());
+    assert_eq!(
+        terminal_pane.list_search_matches().len(),
+        search_matches.len() + 1,
+        "new output is searched as well"
+    );
+    assert!(terminal_pane.search_jump_to_match(search_matches.len() + 1));
+}
+
+#[test]
+pub fn searching_scrollback_for_a_pattern() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term("tortor");
+    let search_matches = terminal_pane.list_search_matches();
+    assert_eq!(
+        terminal_pane.search_scrollback("t[aeiou]rtor").unwrap(),
+        search_matches,
+        "the current search of the pane is not affected"
+    );
+    assert!(terminal_pane.search_scrollback("(").is_err());
+}
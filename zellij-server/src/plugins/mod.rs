@@ -1,6 +1,7 @@
 mod pipes;
 mod plugin_loader;
 mod plugin_map;
+mod plugin_store;
 mod plugin_worker;
 mod wasm_bridge;
 mod watch_filesystem;
@@ -25,16 +26,16 @@ use wasm_bridge::WasmBridge;
 use async_std::{channel, future::timeout, task};
 use zellij_utils::{
     data::{
-        ClientInfo, Event, EventType, FloatingPaneCoordinates, InputMode, MessageToPlugin,
-        PermissionStatus, PermissionType, PipeMessage, PipeSource, PluginCapabilities,
-        WebServerStatus,
+        ClientInfo, CommandSpec, Event, EventType, FloatingPaneCoordinates, InputMode,
+        MessageToPlugin, PermissionStatus, PermissionType, PipeMessage, PipeSource,
+        PluginCapabilities, WatchOptions, WebServerStatus,
     },
     errors::{prelude::*, ContextType, PluginContext},
     input::{
         command::TerminalAction,
         keybinds::Keybinds,
         layout::{FloatingPaneLayout, Layout, Run, RunPlugin, RunPluginOrAlias, TiledPaneLayout},
-        plugins::PluginAliases,
+        plugins::{PluginAliases, PluginLimits, PluginLock},
     },
     ipc::ClientAttributes,
     pane_size::Size,
@@ -43,6 +44,13 @@ use zellij_utils::{
 
 pub type PluginId = u32;
 
+/// The maximum time a plugin may spend in a single update/render/pipe call unless configured
+/// otherwise
+pub const DEFAULT_PLUGIN_EXECUTION_TIMEOUT_MS: u64 = 10000;
+/// How often the wasm engine's epoch is incremented, plugin execution deadlines are measured in
+/// these ticks
+pub const PLUGIN_EPOCH_TICK_MS: u64 = 10;
+
 #[derive(Clone, Debug)]
 pub enum PluginInstruction {
     Load(
@@ -71,6 +79,7 @@ pub enum PluginInstruction {
         Size,
     ),
     ReloadPluginWithId(u32),
+    PluginFileChanged(RunPlugin), // the .wasm file of a plugin watched for changes was rebuilt
     Resize(PluginId, usize, usize), // plugin_id, columns, rows
     AddClient(ClientId),
     RemoveClient(ClientId),
@@ -104,6 +113,7 @@ pub enum PluginInstruction {
         String, // serialized payload
     ),
     PluginSubscribedToEvents(PluginId, ClientId, HashSet<EventType>),
+    PluginUnsubscribedFromEvents(PluginId, ClientId, HashSet<EventType>),
     PermissionRequestResult(
         PluginId,
         Option<ClientId>,
@@ -166,6 +176,37 @@ pub enum PluginInstruction {
     ChangePluginHostDir(PathBuf, PluginId, ClientId),
     WebServerStarted(String), // String -> the base url of the web server
     FailedToStartWebServer(String),
+    CallPlugin {
+        caller_plugin_id: PluginId,
+        caller_client_id: ClientId,
+        call_id: u64,
+        destination: String,
+        method: String,
+        payload: Option<String>,
+        timeout: Option<Duration>,
+    },
+    PluginCallResponse(PluginId, u64, Result<String, String>), // the responding plugin, the id of the call
+    PluginCallTimedOut(u64, Duration),
+    WatchPaths {
+        plugin_id: PluginId,
+        client_id: ClientId,
+        watch_id: u32,
+        paths: Vec<PathBuf>,
+        watch_options: WatchOptions,
+    },
+    UnwatchPaths {
+        plugin_id: PluginId,
+        client_id: ClientId,
+        watch_id: u32,
+    },
+    RegisterCommands(PluginId, Vec<CommandSpec>),
+    ListRegisteredCommands(PluginId, ClientId),
+    InvokeRegisteredCommand {
+        name: String,
+        args: BTreeMap<String, String>,
+        client_id: ClientId,
+        source: PipeSource,
+    },
     Exit,
 }
 
@@ -178,6 +219,7 @@ impl From<&PluginInstruction> for PluginContext {
             PluginInstruction::Unload(..) => PluginContext::Unload,
             PluginInstruction::Reload(..) => PluginContext::Reload,
             PluginInstruction::ReloadPluginWithId(..) => PluginContext::ReloadPluginWithId,
+            PluginInstruction::PluginFileChanged(..) => PluginContext::PluginFileChanged,
             PluginInstruction::Resize(..) => PluginContext::Resize,
             PluginInstruction::Exit => PluginContext::Exit,
             PluginInstruction::AddClient(_) => PluginContext::AddClient,
@@ -194,6 +236,9 @@ impl From<&PluginInstruction> for PluginContext {
             PluginInstruction::PluginSubscribedToEvents(..) => {
                 PluginContext::PluginSubscribedToEvents
             },
+            PluginInstruction::PluginUnsubscribedFromEvents(..) => {
+                PluginContext::PluginUnsubscribedFromEvents
+            },
             PluginInstruction::PermissionRequestResult(..) => {
                 PluginContext::PermissionRequestResult
             },
@@ -215,6 +260,16 @@ impl From<&PluginInstruction> for PluginContext {
             PluginInstruction::ChangePluginHostDir(..) => PluginContext::ChangePluginHostDir,
             PluginInstruction::WebServerStarted(..) => PluginContext::WebServerStarted,
             PluginInstruction::FailedToStartWebServer(..) => PluginContext::FailedToStartWebServer,
+            PluginInstruction::CallPlugin { .. } => PluginContext::CallPlugin,
+            PluginInstruction::PluginCallResponse(..) => PluginContext::PluginCallResponse,
+            PluginInstruction::PluginCallTimedOut(..) => PluginContext::PluginCallTimedOut,
+            PluginInstruction::WatchPaths { .. } => PluginContext::WatchPaths,
+            PluginInstruction::UnwatchPaths { .. } => PluginContext::UnwatchPaths,
+            PluginInstruction::RegisterCommands(..) => PluginContext::RegisterCommands,
+            PluginInstruction::ListRegisteredCommands(..) => PluginContext::ListRegisteredCommands,
+            PluginInstruction::InvokeRegisteredCommand { .. } => {
+                PluginContext::InvokeRegisteredCommand
+            },
         }
     }
 }
@@ -234,6 +289,8 @@ pub(crate) fn plugin_thread_main(
     default_mode: InputMode,
     default_keybinds: Keybinds,
     background_plugins: HashSet<RunPluginOrAlias>,
+    plugin_limits: PluginLimits,
+    plugin_lock: PluginLock,
     // the client id that started the session,
     // we need it here because the thread's own list of connected clients might not yet be updated
     // on session start when we need to load the background plugins, and so we must have an
@@ -262,6 +319,8 @@ pub(crate) fn plugin_thread_main(
         layout_dir,
         default_mode,
         default_keybinds,
+        plugin_limits,
+        plugin_lock,
     );
 
     for run_plugin_or_alias in background_plugins {
@@ -352,6 +411,7 @@ pub(crate) fn plugin_thread_main(
             },
             PluginInstruction::Unload(pid) => {
                 wasm_bridge.unload_plugin(pid)?;
+                wasm_bridge.update_pane_content_changed_subscribers();
             },
             PluginInstruction::Reload(
                 should_float,
@@ -426,6 +486,10 @@ pub(crate) fn plugin_thread_main(
             PluginInstruction::ReloadPluginWithId(plugin_id) => {
                 wasm_bridge.reload_plugin_with_id(plugin_id).non_fatal();
             },
+            PluginInstruction::PluginFileChanged(run_plugin) => {
+                log::info!("{} changed, reloading", run_plugin.location);
+                wasm_bridge.reload_plugin(&run_plugin).non_fatal();
+            },
             PluginInstruction::Resize(pid, new_columns, new_rows) => {
                 wasm_bridge.resize_plugin(pid, new_columns, new_rows, shutdown_send.clone())?;
             },
@@ -526,6 +590,8 @@ pub(crate) fn plugin_thread_main(
                     done_receiving_permissions,
                     shutdown_send.clone(),
                 )?;
+                // a reloaded plugin might no longer be subscribed to what it was before
+                wasm_bridge.update_pane_content_changed_subscribers();
             },
             PluginInstruction::ApplyCachedWorkerMessages(plugin_id) => {
                 wasm_bridge.apply_cached_worker_messages(plugin_id)?;
@@ -551,9 +617,9 @@ pub(crate) fn plugin_thread_main(
                 )];
                 wasm_bridge.update_plugins(updates, shutdown_send.clone())?;
             },
-            PluginInstruction::PluginSubscribedToEvents(_plugin_id, _client_id, _events) => {
-                // no-op, there used to be stuff we did here - now there isn't, but we might want
-                // to add stuff here in the future
+            PluginInstruction::PluginSubscribedToEvents(_plugin_id, _client_id, _events)
+            | PluginInstruction::PluginUnsubscribedFromEvents(_plugin_id, _client_id, _events) => {
+                wasm_bridge.update_pane_content_changed_subscribers();
             },
             PluginInstruction::PermissionRequestResult(
                 plugin_id,
@@ -657,6 +723,7 @@ pub(crate) fn plugin_thread_main(
                         client_metadata.get_pane_id().into(),
                         client_metadata.stringify_command(&default_editor),
                         is_current_client,
+                        client_metadata.is_read_only(),
                     ));
                 }
                 let updates = vec![(
@@ -672,6 +739,10 @@ pub(crate) fn plugin_thread_main(
                     &wasm_bridge,
                     &plugin_aliases,
                 );
+                // plugin states are only stored for resurrection, not in layouts dumped for users
+                let plugin_saved_states = wasm_bridge
+                    .serializable_plugin_states(&session_layout_metadata.all_plugin_ids());
+                session_layout_metadata.update_plugin_saved_states(plugin_saved_states);
                 drop(
                     bus.senders
                         .send_to_pty(PtyInstruction::LogLayoutToHd(session_layout_metadata)),
@@ -961,6 +1032,80 @@ pub(crate) fn plugin_thread_main(
                     .update_plugins(updates, shutdown_send.clone())
                     .non_fatal();
             },
+            PluginInstruction::CallPlugin {
+                caller_plugin_id,
+                caller_client_id,
+                call_id,
+                destination,
+                method,
+                payload,
+                timeout,
+            } => {
+                wasm_bridge
+                    .call_plugin(
+                        caller_plugin_id,
+                        caller_client_id,
+                        call_id,
+                        destination,
+                        method,
+                        payload,
+                        timeout,
+                        &plugin_aliases,
+                        shutdown_send.clone(),
+                    )
+                    .non_fatal();
+            },
+            PluginInstruction::PluginCallResponse(responding_plugin_id, plugin_call_id, result) => {
+                wasm_bridge
+                    .respond_to_plugin_call(
+                        responding_plugin_id,
+                        plugin_call_id,
+                        result,
+                        shutdown_send.clone(),
+                    )
+                    .non_fatal();
+            },
+            PluginInstruction::PluginCallTimedOut(plugin_call_id, timeout) => {
+                wasm_bridge
+                    .plugin_call_timed_out(plugin_call_id, timeout, shutdown_send.clone())
+                    .non_fatal();
+            },
+            PluginInstruction::WatchPaths {
+                plugin_id,
+                client_id,
+                watch_id,
+                paths,
+                watch_options,
+            } => {
+                wasm_bridge
+                    .watch_paths(plugin_id, client_id, watch_id, paths, watch_options)
+                    .non_fatal();
+            },
+            PluginInstruction::UnwatchPaths {
+                plugin_id,
+                client_id,
+                watch_id,
+            } => {
+                wasm_bridge.unwatch_paths(plugin_id, client_id, watch_id);
+            },
+            PluginInstruction::RegisterCommands(plugin_id, commands) => {
+                wasm_bridge.register_commands(plugin_id, commands);
+            },
+            PluginInstruction::ListRegisteredCommands(plugin_id, client_id) => {
+                wasm_bridge
+                    .list_registered_commands(plugin_id, client_id, shutdown_send.clone())
+                    .non_fatal();
+            },
+            PluginInstruction::InvokeRegisteredCommand {
+                name,
+                args,
+                client_id,
+                source,
+            } => {
+                wasm_bridge
+                    .invoke_registered_command(name, args, client_id, source, shutdown_send.clone())
+                    .non_fatal();
+            },
             PluginInstruction::Exit => {
                 break;
             },
@@ -1,5 +1,5 @@
 use super::{PluginId, PluginInstruction};
-use crate::plugins::plugin_map::RunningPlugin;
+use crate::plugins::plugin_map::{call_plugin_with_limits, RunningPlugin};
 use crate::plugins::wasm_bridge::PluginRenderAsset;
 use crate::plugins::zellij_exports::{wasi_read_string, wasi_write_object};
 use std::collections::{HashMap, HashSet};
@@ -145,6 +145,9 @@ pub fn apply_pipe_message_to_plugin(
     plugin_render_assets: &mut Vec<PluginRenderAsset>,
     senders: &ThreadSenders,
 ) -> Result<()> {
+    if running_plugin.exceeded_limits() {
+        return Ok(());
+    }
     let instance = &running_plugin.instance;
     let rows = running_plugin.rows;
     let columns = running_plugin.columns;
@@ -161,15 +164,18 @@ pub fn apply_pipe_message_to_plugin(
                 &protobuf_pipe_message.encode_to_vec(),
             )
             .with_context(err_context)?;
-            let should_render = pipe
-                .call(&mut running_plugin.store, ())
+            let should_render = call_plugin_with_limits(&mut running_plugin.store, &pipe, ())
                 .with_context(err_context)?;
             let should_render = should_render == 1;
             if rows > 0 && columns > 0 && should_render {
                 let rendered_bytes = instance
                     .get_typed_func::<(i32, i32), ()>(&mut running_plugin.store, "render")
                     .and_then(|render| {
-                        render.call(&mut running_plugin.store, (rows as i32, columns as i32))
+                        call_plugin_with_limits(
+                            &mut running_plugin.store,
+                            &render,
+                            (rows as i32, columns as i32),
+                        )
                     })
                     .and_then(|_| wasi_read_string(running_plugin.store.data()))
                     .with_context(err_context)?;
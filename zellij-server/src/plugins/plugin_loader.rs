@@ -1,6 +1,8 @@
 use crate::plugins::plugin_map::{
     PluginEnv, PluginMap, RunningPlugin, VecDequeInputStream, WriteOutputStream,
+    UNLIMITED_EXECUTION_DEADLINE,
 };
+use crate::plugins::plugin_store::{PluginStore, PLUGIN_STORE_DIR_NAME};
 use crate::plugins::plugin_worker::{plugin_worker, RunningWorker};
 use crate::plugins::zellij_exports::{wasi_write_object, zellij_exports};
 use crate::plugins::PluginId;
@@ -855,6 +857,7 @@ impl<'a> PluginLoader<'a> {
             wasi_ctx,
             plugin_own_data_dir: self.plugin_own_data_dir.clone(),
             plugin_own_cache_dir: self.plugin_own_cache_dir.clone(),
+            plugin_store: PluginStore::new(self.plugin_dir.join(PLUGIN_STORE_DIR_NAME)),
             tab_index: self.tab_index,
             path_to_default_shell: self.path_to_default_shell.clone(),
             capabilities: self.capabilities.clone(),
@@ -869,10 +872,14 @@ impl<'a> PluginLoader<'a> {
             subscriptions: Arc::new(Mutex::new(HashSet::new())),
             keybinds: self.keybinds.clone(),
             intercepting_key_presses: false,
+            exceeded_limits: false,
             stdin_pipe,
             stdout_pipe,
         };
         let mut store = Store::new(&self.engine, plugin_env);
+        store.limiter(|plugin_env| plugin_env);
+        // execution deadlines are only set when calling into the plugin after it was loaded
+        store.set_epoch_deadline(UNLIMITED_EXECUTION_DEADLINE);
 
         let mut linker = Linker::new(&self.engine);
         wasmtime_wasi::preview1::add_to_linker_sync(&mut linker, |plugin_env: &mut PluginEnv| {
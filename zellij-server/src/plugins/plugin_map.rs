@@ -1,5 +1,6 @@
+use crate::plugins::plugin_store::PluginStore;
 use crate::plugins::plugin_worker::MessageToWorker;
-use crate::plugins::PluginId;
+use crate::plugins::{PluginId, PLUGIN_EPOCH_TICK_MS};
 use bytes::Bytes;
 use std::io::Write;
 use std::{
@@ -7,7 +8,7 @@ use std::{
     path::PathBuf,
     sync::{Arc, Mutex},
 };
-use wasmtime::{Instance, Store};
+use wasmtime::{Instance, ResourceLimiter, Store, Trap, TypedFunc, WasmParams, WasmResults};
 use wasmtime_wasi::preview1::WasiP1Ctx;
 use wasmtime_wasi::{
     HostInputStream, HostOutputStream, StdinStream, StdoutStream, StreamError, StreamResult,
@@ -27,7 +28,14 @@ use zellij_utils::{
     input::plugins::PluginConfig,
     ipc::ClientAttributes,
 };
-use zellij_utils::{data::PermissionType, errors::prelude::*};
+use zellij_utils::{
+    data::PermissionType,
+    errors::{prelude::*, ZellijError},
+};
+
+// far enough in the future to never be reached, but not so far that adding it to the current
+// epoch overflows
+pub const UNLIMITED_EXECUTION_DEADLINE: u64 = u64::MAX / 2;
 
 // the idea here is to provide atomicity when adding/removing plugins from the map (eg. when a new
 // client connects) but to also allow updates/renders not to block each other
@@ -260,6 +268,10 @@ impl PluginMap {
                         location: run_plugin_location,
                         configuration: run_plugin_configuration,
                         initial_cwd,
+                        saved_state: None,
+                        limit_overrides: plugin_config.limits.into(),
+                        sha256: None,
+                        watch_for_changes: false,
                     })
                 } else {
                     None
@@ -299,6 +311,7 @@ pub struct PluginEnv {
     #[allow(dead_code)]
     pub plugin_own_data_dir: PathBuf,
     pub plugin_own_cache_dir: PathBuf,
+    pub plugin_store: PluginStore,
     pub path_to_default_shell: PathBuf,
     pub capabilities: PluginCapabilities,
     pub client_attributes: ClientAttributes,
@@ -314,6 +327,66 @@ pub struct PluginEnv {
     pub stdout_pipe: Arc<Mutex<VecDeque<u8>>>,
     pub keybinds: Keybinds,
     pub intercepting_key_presses: bool,
+    pub exceeded_limits: bool,
+}
+
+impl ResourceLimiter for PluginEnv {
+    fn memory_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> Result<bool> {
+        match self.plugin.limits.max_memory_mb {
+            Some(max_memory_mb) if desired > max_memory_mb.saturating_mul(1024 * 1024) => {
+                Err(ZellijError::PluginMemoryLimitExceeded { max_memory_mb }.into())
+            },
+            _ => Ok(true),
+        }
+    }
+    fn table_growing(
+        &mut self,
+        _current: usize,
+        _desired: usize,
+        _maximum: Option<usize>,
+    ) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+/// Calls an exported plugin function (eg. `update`, `render` or `pipe`) within the plugin's
+/// execution deadline. If the plugin exceeds its time or memory limit, it is marked as such and
+/// should not be called again.
+pub fn call_plugin_with_limits<Params: WasmParams, Results: WasmResults>(
+    store: &mut Store<PluginEnv>,
+    func: &TypedFunc<Params, Results>,
+    params: Params,
+) -> Result<Results> {
+    let execution_deadline = match store.data().plugin.limits.execution_timeout_ms {
+        // we add a tick because the first one might come right after we set the deadline
+        Some(execution_timeout_ms) => execution_timeout_ms.div_ceil(PLUGIN_EPOCH_TICK_MS) + 1,
+        None => UNLIMITED_EXECUTION_DEADLINE,
+    };
+    store.set_epoch_deadline(execution_deadline);
+    func.call(&mut *store, params).map_err(|e| {
+        if e.downcast_ref::<Trap>() == Some(&Trap::Interrupt) {
+            store.data_mut().exceeded_limits = true;
+            let execution_timeout_ms = store
+                .data()
+                .plugin
+                .limits
+                .execution_timeout_ms
+                .unwrap_or_default();
+            e.context(ZellijError::PluginExecutionTimeout {
+                execution_timeout_ms,
+            })
+        } else {
+            if let Some(ZellijError::PluginMemoryLimitExceeded { .. }) = e.downcast_ref() {
+                store.data_mut().exceeded_limits = true;
+            }
+            e
+        }
+    })
 }
 
 #[derive(Clone)]
@@ -461,4 +534,7 @@ impl RunningPlugin {
     pub fn intercepting_key_presses(&self) -> bool {
         self.store.data().intercepting_key_presses
     }
+    pub fn exceeded_limits(&self) -> bool {
+        self.store.data().exceeded_limits
+    }
 }
@@ -0,0 +1,144 @@
+use lazy_static::lazy_static;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use zellij_utils::{data::StoreScope, envs, errors::prelude::*};
+
+/// The directory (under the plugin dir in the zellij data dir) holding the stores of all plugins
+pub const PLUGIN_STORE_DIR_NAME: &str = "store";
+const GLOBAL_STORE_FILE_NAME: &str = "global.json";
+const SESSION_STORES_DIR_NAME: &str = "sessions";
+
+lazy_static! {
+    // several instances of the same plugin (eg. one per client) share a store, so we serialize
+    // access to it across plugin threads
+    static ref STORE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// Persistent key/value storage for plugins, kept in the zellij data dir so that it survives
+/// clearing the cache and restarting zellij.
+///
+/// Each plugin location (eg. `zellij:session-manager` or a remote url) gets its own directory,
+/// holding one json file for the global scope and one for each session that used the session
+/// scope.
+#[derive(Debug, Clone)]
+pub struct PluginStore {
+    store_dir: PathBuf,
+}
+
+impl PluginStore {
+    pub fn new(store_dir: PathBuf) -> Self {
+        PluginStore { store_dir }
+    }
+    pub fn get(
+        &self,
+        plugin_location: &str,
+        key: &str,
+        scope: StoreScope,
+    ) -> Result<Option<String>> {
+        let _lock = STORE_LOCK.lock().to_anyhow()?;
+        let mut entries = self.read_entries(&self.scope_file(plugin_location, scope)?)?;
+        Ok(entries.remove(key))
+    }
+    pub fn set(
+        &self,
+        plugin_location: &str,
+        key: String,
+        value: String,
+        scope: StoreScope,
+    ) -> Result<()> {
+        let _lock = STORE_LOCK.lock().to_anyhow()?;
+        let scope_file = self.scope_file(plugin_location, scope)?;
+        let mut entries = self.read_entries(&scope_file)?;
+        entries.insert(key, value);
+        self.write_entries(&scope_file, &entries)
+    }
+    pub fn delete(&self, plugin_location: &str, key: &str, scope: StoreScope) -> Result<()> {
+        let _lock = STORE_LOCK.lock().to_anyhow()?;
+        let scope_file = self.scope_file(plugin_location, scope)?;
+        let mut entries = self.read_entries(&scope_file)?;
+        if entries.remove(key).is_some() {
+            self.write_entries(&scope_file, &entries)?;
+        }
+        Ok(())
+    }
+    pub fn list(
+        &self,
+        plugin_location: &str,
+        scope: StoreScope,
+    ) -> Result<BTreeMap<String, String>> {
+        let _lock = STORE_LOCK.lock().to_anyhow()?;
+        self.read_entries(&self.scope_file(plugin_location, scope)?)
+    }
+    /// Removes all the stored data of this plugin, in all scopes and for all sessions
+    pub fn clear(&self, plugin_location: &str) -> Result<()> {
+        let _lock = STORE_LOCK.lock().to_anyhow()?;
+        let plugin_store_dir = self.plugin_store_dir(plugin_location)?;
+        match fs::remove_dir_all(&plugin_store_dir) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e)
+                .with_context(|| format!("failed to clear plugin store at {plugin_store_dir:?}")),
+            _ => Ok(()),
+        }
+    }
+    fn plugin_store_dir(&self, plugin_location: &str) -> Result<PathBuf> {
+        if plugin_location.is_empty() || plugin_location == "." || plugin_location == ".." {
+            return Err(anyhow!("invalid plugin location: {plugin_location:?}"));
+        }
+        Ok(self.store_dir.join(encode_path_component(plugin_location)))
+    }
+    fn scope_file(&self, plugin_location: &str, scope: StoreScope) -> Result<PathBuf> {
+        let plugin_store_dir = self.plugin_store_dir(plugin_location)?;
+        Ok(match scope {
+            StoreScope::Global => plugin_store_dir.join(GLOBAL_STORE_FILE_NAME),
+            StoreScope::Session => plugin_store_dir.join(SESSION_STORES_DIR_NAME).join(format!(
+                "{}.json",
+                encode_path_component(&envs::get_session_name().unwrap_or_default())
+            )),
+        })
+    }
+    fn read_entries(&self, scope_file: &Path) -> Result<BTreeMap<String, String>> {
+        match fs::read_to_string(scope_file) {
+            Ok(stored) => serde_json::from_str(&stored)
+                .with_context(|| format!("failed to parse plugin store at {scope_file:?}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(e) => {
+                Err(e).with_context(|| format!("failed to read plugin store at {scope_file:?}"))
+            },
+        }
+    }
+    fn write_entries(&self, scope_file: &Path, entries: &BTreeMap<String, String>) -> Result<()> {
+        let err_context = || format!("failed to write plugin store at {scope_file:?}");
+        if let Some(parent) = scope_file.parent() {
+            fs::create_dir_all(parent).with_context(err_context)?;
+        }
+        // write to a temporary file first so that a crash mid-write does not corrupt the store
+        let tmp_file = scope_file.with_extension("json.tmp");
+        fs::write(
+            &tmp_file,
+            serde_json::to_string(entries).with_context(err_context)?,
+        )
+        .with_context(err_context)?;
+        fs::rename(&tmp_file, scope_file).with_context(err_context)
+    }
+}
+
+/// Percent-encodes everything but ascii alphanumerics, `-` and `_`, so that each plugin location
+/// maps to its own directory name that cannot be `.`, `..` or contain a path separator
+fn encode_path_component(component: &str) -> String {
+    let mut encoded = String::with_capacity(component.len());
+    for byte in component.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[path = "./unit/plugin_store_tests.rs"]
+#[cfg(test)]
+mod plugin_store_tests;
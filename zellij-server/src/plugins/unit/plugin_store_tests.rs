@@ -0,0 +1,182 @@
+use super::*;
+use tempfile::tempdir;
+use zellij_utils::envs::set_session_name;
+
+#[test]
+fn can_set_get_and_delete_values() {
+    let store_dir = tempdir().unwrap();
+    let plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    let plugin_location = "zellij:session-manager";
+    plugin_store
+        .set(
+            plugin_location,
+            "key".to_owned(),
+            "value".to_owned(),
+            StoreScope::Global,
+        )
+        .unwrap();
+    assert_eq!(
+        plugin_store
+            .get(plugin_location, "key", StoreScope::Global)
+            .unwrap(),
+        Some("value".to_owned())
+    );
+    plugin_store
+        .delete(plugin_location, "key", StoreScope::Global)
+        .unwrap();
+    assert_eq!(
+        plugin_store
+            .get(plugin_location, "key", StoreScope::Global)
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn stores_are_separate_per_plugin_location_and_scope() {
+    set_session_name("test".into());
+    let store_dir = tempdir().unwrap();
+    let plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    plugin_store
+        .set(
+            "zellij:session-manager",
+            "key".to_owned(),
+            "global".to_owned(),
+            StoreScope::Global,
+        )
+        .unwrap();
+    plugin_store
+        .set(
+            "zellij:session-manager",
+            "key".to_owned(),
+            "session".to_owned(),
+            StoreScope::Session,
+        )
+        .unwrap();
+    plugin_store
+        .set(
+            "file:/path/to/my-plugin.wasm",
+            "other_key".to_owned(),
+            "other".to_owned(),
+            StoreScope::Global,
+        )
+        .unwrap();
+    assert_eq!(
+        plugin_store
+            .list("zellij:session-manager", StoreScope::Global)
+            .unwrap(),
+        BTreeMap::from([("key".to_owned(), "global".to_owned())])
+    );
+    assert_eq!(
+        plugin_store
+            .list("zellij:session-manager", StoreScope::Session)
+            .unwrap(),
+        BTreeMap::from([("key".to_owned(), "session".to_owned())])
+    );
+    assert_eq!(
+        plugin_store
+            .list("file:/path/to/my-plugin.wasm", StoreScope::Global)
+            .unwrap(),
+        BTreeMap::from([("other_key".to_owned(), "other".to_owned())])
+    );
+}
+
+#[test]
+fn clearing_a_store_removes_all_of_its_scopes() {
+    set_session_name("test".into());
+    let store_dir = tempdir().unwrap();
+    let plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    let plugin_location = "zellij:configuration";
+    for scope in [StoreScope::Global, StoreScope::Session] {
+        plugin_store
+            .set(plugin_location, "key".to_owned(), "value".to_owned(), scope)
+            .unwrap();
+    }
+    plugin_store
+        .set(
+            "zellij:session-manager",
+            "key".to_owned(),
+            "value".to_owned(),
+            StoreScope::Global,
+        )
+        .unwrap();
+    plugin_store.clear(plugin_location).unwrap();
+    for scope in [StoreScope::Global, StoreScope::Session] {
+        assert!(plugin_store
+            .list(plugin_location, scope)
+            .unwrap()
+            .is_empty());
+    }
+    assert_eq!(
+        plugin_store
+            .get("zellij:session-manager", "key", StoreScope::Global)
+            .unwrap(),
+        Some("value".to_owned())
+    );
+    // clearing a store that does not exist is not an error
+    plugin_store.clear("zellij:nonexistent").unwrap();
+}
+
+#[test]
+fn plugin_locations_cannot_escape_the_store_directory() {
+    let data_dir = tempdir().unwrap();
+    let store_dir = data_dir.path().join("store");
+    let plugin_store = PluginStore::new(store_dir.clone());
+    plugin_store
+        .set(
+            "zellij:session-manager",
+            "key".to_owned(),
+            "value".to_owned(),
+            StoreScope::Global,
+        )
+        .unwrap();
+    for plugin_location in ["", ".", ".."] {
+        assert!(plugin_store.clear(plugin_location).is_err());
+        assert!(plugin_store
+            .list(plugin_location, StoreScope::Global)
+            .is_err());
+    }
+    plugin_store
+        .set(
+            "../../escaped",
+            "key".to_owned(),
+            "value".to_owned(),
+            StoreScope::Global,
+        )
+        .unwrap();
+    plugin_store.clear("../..").unwrap();
+    assert!(data_dir.path().exists());
+    assert!(!data_dir.path().join("escaped").exists());
+    assert_eq!(std::fs::read_dir(&store_dir).unwrap().count(), 2);
+    assert_eq!(
+        plugin_store
+            .get("zellij:session-manager", "key", StoreScope::Global)
+            .unwrap(),
+        Some("value".to_owned())
+    );
+}
+
+#[test]
+fn similar_plugin_locations_get_separate_stores() {
+    let store_dir = tempdir().unwrap();
+    let plugin_store = PluginStore::new(store_dir.path().to_path_buf());
+    let plugin_locations = ["file:/a/b.wasm", "file:/a_b.wasm", "file_/a/b.wasm"];
+    for plugin_location in plugin_locations {
+        plugin_store
+            .set(
+                plugin_location,
+                "key".to_owned(),
+                plugin_location.to_owned(),
+                StoreScope::Global,
+            )
+            .unwrap();
+    }
+    for plugin_location in plugin_locations {
+        assert_eq!(
+            plugin_store
+                .get(plugin_location, "key", StoreScope::Global)
+                .unwrap(),
+            Some(plugin_location.to_owned())
+        );
+    }
+}
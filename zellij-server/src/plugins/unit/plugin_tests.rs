@@ -8,8 +8,8 @@ use std::path::PathBuf;
 use tempfile::tempdir;
 use wasmtime::Engine;
 use zellij_utils::data::{
-    BareKey, Event, InputMode, KeyWithModifier, PermissionStatus, PermissionType,
-    PluginCapabilities,
+    BareKey, ClientId, Event, InputMode, KeyWithModifier, PermissionStatus, PermissionType,
+    PipeSource, PluginCapabilities,
 };
 use zellij_utils::errors::ErrorContext;
 use zellij_utils::input::keybinds::Keybinds;
@@ -17,7 +17,7 @@ use zellij_utils::input::layout::{
     Layout, PluginAlias, PluginUserConfiguration, RunPlugin, RunPluginLocation, RunPluginOrAlias,
 };
 use zellij_utils::input::permission::PermissionCache;
-use zellij_utils::input::plugins::PluginAliases;
+use zellij_utils::input::plugins::{PluginAliases, PluginLimits};
 use zellij_utils::ipc::ClientAttributes;
 use zellij_utils::pane_size::Size;
 
@@ -299,6 +299,38 @@ fn create_plugin_thread(
     SenderWithContext<PluginInstruction>,
     Receiver<(ScreenInstruction, ErrorContext)>,
     Box<dyn FnOnce()>,
+) {
+    let engine = Engine::new(wasmtime::Config::new().strategy(wasmtime::Strategy::Winch)).unwrap();
+    create_plugin_thread_with_engine_and_limits(zellij_cwd, engine, Default::default())
+}
+
+fn create_plugin_thread_with_limits(
+    zellij_cwd: Option<PathBuf>,
+    plugin_limits: PluginLimits,
+) -> (
+    SenderWithContext<PluginInstruction>,
+    Receiver<(ScreenInstruction, ErrorContext)>,
+    Box<dyn FnOnce()>,
+) {
+    // execution timeouts are enforced through epoch interruption, like in the real engine
+    let engine = Engine::new(
+        wasmtime::Config::new()
+            .strategy(wasmtime::Strategy::Winch)
+            .epoch_interruption(true),
+    )
+    .unwrap();
+    crate::start_epoch_ticker(engine.clone());
+    create_plugin_thread_with_engine_and_limits(zellij_cwd, engine, plugin_limits)
+}
+
+fn create_plugin_thread_with_engine_and_limits(
+    zellij_cwd: Option<PathBuf>,
+    engine: Engine,
+    plugin_limits: PluginLimits,
+) -> (
+    SenderWithContext<PluginInstruction>,
+    Receiver<(ScreenInstruction, ErrorContext)>,
+    Box<dyn FnOnce()>,
 ) {
     let zellij_cwd = zellij_cwd.unwrap_or_else(|| PathBuf::from("."));
     let initiating_client_id = 1;
@@ -333,7 +365,6 @@ fn create_plugin_thread(
         None,
     )
     .should_silently_fail();
-    let engine = Engine::new(wasmtime::Config::new().strategy(wasmtime::Strategy::Winch)).unwrap();
     let data_dir = PathBuf::from(tempdir().unwrap().path());
     let default_shell = PathBuf::from(".");
     let plugin_capabilities = PluginCapabilities::default();
@@ -369,6 +400,8 @@ fn create_plugin_thread(
                 InputMode::Normal,
                 Keybinds::default(),
                 Default::default(),
+                plugin_limits,
+                Default::default(),
                 initiating_client_id,
             )
             .expect("TEST")
@@ -453,6 +486,8 @@ fn create_plugin_thread_with_server_receiver(
                 InputMode::Normal,
                 Keybinds::default(),
                 Default::default(),
+                Default::default(),
+                Default::default(),
                 initiating_client_id,
             )
             .expect("TEST");
@@ -543,6 +578,8 @@ fn create_plugin_thread_with_pty_receiver(
                 InputMode::Normal,
                 Keybinds::default(),
                 Default::default(),
+                Default::default(),
+                Default::default(),
                 initiating_client_id,
             )
             .expect("TEST")
@@ -628,6 +665,8 @@ fn create_plugin_thread_with_background_jobs_receiver(
                 InputMode::Normal,
                 Keybinds::default(),
                 Default::default(),
+                Default::default(),
+                Default::default(),
                 initiating_client_id,
             )
             .expect("TEST")
@@ -6931,6 +6970,10 @@ pub fn run_plugin_in_specific_cwd() {
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
         configuration: Default::default(),
         initial_cwd: Some(plugin_initial_cwd.clone()),
+        saved_state: None,
+        limit_overrides: Default::default(),
+        sha256: None,
+        watch_for_changes: false,
     });
     let tab_index = 1;
     let client_id = 1;
@@ -9017,3 +9060,1072 @@ pub fn before_close_plugin_event() {
         .unwrap();
     assert_snapshot!(format!("{:#?}", sent_instruction));
 }
+
+#[test]
+#[ignore]
+pub fn pane_content_changes_are_only_reported_while_a_plugin_is_subscribed() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+        ..Default::default()
+    });
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread!(
+        received_screen_instructions,
+        ScreenInstruction::SetReportPaneContentChanges,
+        screen_receiver,
+        2,
+        &PermissionType::ReadCliPipes,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        plugin_title,
+        run_plugin,
+        Some(tab_index),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    for pipe_name in [
+        "subscribe_to_pane_content_changes",
+        "unsubscribe_from_pane_content_changes",
+    ] {
+        let _ = plugin_thread_sender.send(PluginInstruction::CliPipe {
+            pipe_id: "input_pipe_id".to_owned(),
+            name: pipe_name.to_owned(),
+            payload: None,
+            plugin: None, // broadcast
+            args: None,
+            configuration: None,
+            floating: None,
+            pane_id_to_replace: None,
+            pane_title: None,
+            cwd: None,
+            skip_cache: false,
+            cli_client_id: client_id,
+        });
+        std::thread::sleep(std::time::Duration::from_millis(500));
+    }
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let reported_subscription_changes: Vec<bool> = received_screen_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|i| match i {
+            ScreenInstruction::SetReportPaneContentChanges(report_pane_content_changes) => {
+                Some(*report_pane_content_changes)
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(reported_subscription_changes, vec![true, false]);
+}
+
+#[test]
+#[ignore]
+pub fn plugin_exceeding_its_execution_timeout_is_interrupted() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) = create_plugin_thread_with_limits(
+        Some(plugin_host_folder),
+        PluginLimits::new(None, Some(100)),
+    );
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+        ..Default::default()
+    });
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::ReadCliPipes,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        plugin_title,
+        run_plugin,
+        Some(tab_index),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(5000)); // the cache might be cold
+    let _ = plugin_thread_sender.send(PluginInstruction::CliPipe {
+        pipe_id: "input_pipe_id".to_owned(),
+        name: "loop_forever".to_owned(),
+        payload: None,
+        plugin: None, // broadcast
+        args: None,
+        configuration: None,
+        floating: None,
+        pane_id_to_replace: None,
+        pane_title: None,
+        cwd: None,
+        skip_cache: false,
+        cli_client_id: client_id,
+    });
+    std::thread::sleep(std::time::Duration::from_millis(1000));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let plugin_error = received_screen_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| match i {
+            ScreenInstruction::UpdatePluginLoadingStage(_, loading_indication)
+                if loading_indication.is_error() =>
+            {
+                Some(format!("{:?}", loading_indication))
+            },
+            _ => None,
+        })
+        .expect("plugin was not interrupted");
+    assert!(plugin_error.contains("did not finish handling an event within 100ms"));
+}
+
+#[test]
+#[ignore]
+pub fn plugin_exceeding_its_memory_limit_is_stopped() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) = create_plugin_thread_with_limits(
+        Some(plugin_host_folder),
+        PluginLimits::new(Some(64), None),
+    );
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+        ..Default::default()
+    });
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::ReadCliPipes,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        plugin_title,
+        run_plugin,
+        Some(tab_index),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(5000)); // the cache might be cold
+    let _ = plugin_thread_sender.send(PluginInstruction::CliPipe {
+        pipe_id: "input_pipe_id".to_owned(),
+        name: "allocate_memory_mb".to_owned(),
+        payload: Some("128".to_owned()),
+        plugin: None, // broadcast
+        args: None,
+        configuration: None,
+        floating: None,
+        pane_id_to_replace: None,
+        pane_title: None,
+        cwd: None,
+        skip_cache: false,
+        cli_client_id: client_id,
+    });
+    std::thread::sleep(std::time::Duration::from_millis(1000));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let plugin_error = received_screen_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| match i {
+            ScreenInstruction::UpdatePluginLoadingStage(_, loading_indication)
+                if loading_indication.is_error() =>
+            {
+                Some(format!("{:?}", loading_indication))
+            },
+            _ => None,
+        })
+        .expect("plugin was not stopped");
+    assert!(plugin_error.contains("exceeded its memory limit of 64MB"));
+}
+
+fn call_plugin_from_fixture(
+    plugin_thread_sender: &SenderWithContext<PluginInstruction>,
+    args: BTreeMap<String, String>,
+    cli_client_id: ClientId,
+) {
+    let _ = plugin_thread_sender.send(PluginInstruction::CliPipe {
+        pipe_id: "input_pipe_id".to_owned(),
+        name: "call_plugin".to_owned(),
+        payload: None,
+        plugin: None, // broadcast
+        args: Some(args),
+        configuration: None,
+        floating: None,
+        pane_id_to_replace: None,
+        pane_title: None,
+        cwd: None,
+        skip_cache: false,
+        cli_client_id,
+    });
+}
+
+// the last render of each plugin instance (by client id) that shows a call result
+fn rendered_plugin_call_results(
+    received_screen_instructions: &Arc<Mutex<Vec<ScreenInstruction>>>,
+) -> BTreeMap<ClientId, String> {
+    let mut plugin_call_results = BTreeMap::new();
+    for instruction in received_screen_instructions.lock().unwrap().iter() {
+        if let ScreenInstruction::PluginBytes(plugin_render_assets) = instruction {
+            for plugin_render_asset in plugin_render_assets {
+                let plugin_bytes = String::from_utf8_lossy(&plugin_render_asset.bytes).to_string();
+                if plugin_bytes.contains("PluginCallResult") {
+                    plugin_call_results.insert(plugin_render_asset.client_id, plugin_bytes);
+                }
+            }
+        }
+    }
+    plugin_call_results
+}
+
+#[test]
+#[ignore]
+pub fn plugin_calls_are_routed_to_the_callers_client_instance() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        // plugin urls are normalized when parsed, so the location must not contain ".."
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE).canonicalize().unwrap()),
+        configuration: Default::default(),
+        ..Default::default()
+    });
+    let tab_index = 1;
+    let client_id = 1;
+    let second_client_id = 2;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::MessageAndLaunchOtherPlugins,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(second_client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        plugin_title,
+        run_plugin,
+        Some(tab_index),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    // permissions are granted separately to the plugin instance of each client
+    let _ = plugin_thread_sender.send(PluginInstruction::PermissionRequestResult(
+        0,
+        Some(second_client_id),
+        vec![PermissionType::MessageAndLaunchOtherPlugins],
+        PermissionStatus::Granted,
+        Some(cache_path),
+    ));
+    call_plugin_from_fixture(
+        &plugin_thread_sender,
+        BTreeMap::from([("method".to_owned(), "get_client_id".to_owned())]),
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let plugin_call_results = rendered_plugin_call_results(&received_screen_instructions);
+    assert!(plugin_call_results
+        .get(&client_id)
+        .unwrap()
+        .contains("Ok(\"handled by client 1\")"));
+    assert!(plugin_call_results
+        .get(&second_client_id)
+        .unwrap()
+        .contains("Ok(\"handled by client 2\")"));
+}
+
+#[test]
+#[ignore]
+pub fn plugin_call_times_out_when_the_callee_does_not_respond() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        // plugin urls are normalized when parsed, so the location must not contain ".."
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE).canonicalize().unwrap()),
+        configuration: Default::default(),
+        ..Default::default()
+    });
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::MessageAndLaunchOtherPlugins,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        plugin_title,
+        run_plugin,
+        Some(tab_index),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    call_plugin_from_fixture(
+        &plugin_thread_sender,
+        BTreeMap::from([
+            ("method".to_owned(), "never_respond".to_owned()),
+            ("timeout_ms".to_owned(), "100".to_owned()),
+        ]),
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let plugin_call_results = rendered_plugin_call_results(&received_screen_instructions);
+    assert!(plugin_call_results
+        .get(&client_id)
+        .unwrap()
+        .contains("Err(\"Timed out after 100ms\")"));
+}
+
+#[test]
+#[ignore]
+pub fn plugin_call_to_a_plugin_that_is_not_loaded_fails() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        // plugin urls are normalized when parsed, so the location must not contain ".."
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE).canonicalize().unwrap()),
+        configuration: Default::default(),
+        ..Default::default()
+    });
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::MessageAndLaunchOtherPlugins,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        plugin_title,
+        run_plugin,
+        Some(tab_index),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    call_plugin_from_fixture(
+        &plugin_thread_sender,
+        BTreeMap::from([
+            (
+                "destination".to_owned(),
+                "file:/path/to/nonexistent.wasm".to_owned(),
+            ),
+            ("method".to_owned(), "get_client_id".to_owned()),
+        ]),
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let plugin_call_results = rendered_plugin_call_results(&received_screen_instructions);
+    assert!(plugin_call_results
+        .get(&client_id)
+        .unwrap()
+        .contains("Err(\"Plugin file:/path/to/nonexistent.wasm is not loaded\")"));
+}
+
+fn pipe_to_fixture(
+    plugin_thread_sender: &SenderWithContext<PluginInstruction>,
+    name: &str,
+    payload: Option<String>,
+    args: BTreeMap<String, String>,
+) {
+    let _ = plugin_thread_sender.send(PluginInstruction::CliPipe {
+        pipe_id: "input_pipe_id".to_owned(),
+        name: name.to_owned(),
+        payload,
+        plugin: None, // broadcast
+        args: Some(args),
+        configuration: None,
+        floating: None,
+        pane_id_to_replace: None,
+        pane_title: None,
+        cwd: None,
+        skip_cache: false,
+        cli_client_id: 1,
+    });
+}
+
+fn all_rendered_plugin_bytes(
+    received_screen_instructions: &Arc<Mutex<Vec<ScreenInstruction>>>,
+) -> String {
+    let mut all_rendered_bytes = String::new();
+    for instruction in received_screen_instructions.lock().unwrap().iter() {
+        if let ScreenInstruction::PluginBytes(plugin_render_assets) = instruction {
+            for plugin_render_asset in plugin_render_assets {
+                all_rendered_bytes.push_str(&String::from_utf8_lossy(&plugin_render_asset.bytes));
+            }
+        }
+    }
+    all_rendered_bytes
+}
+
+fn load_fixture_plugin(
+    plugin_thread_sender: &SenderWithContext<PluginInstruction>,
+    client_id: ClientId,
+) {
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+        ..Default::default()
+    });
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        Some(false),
+        false,
+        Some("test_plugin".to_owned()),
+        run_plugin,
+        Some(1),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+    ));
+}
+
+#[test]
+#[ignore]
+pub fn watched_paths_are_not_reported_after_unwatching() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let watched_folder = tempdir().unwrap();
+    let watched_folder_path = watched_folder.path().canonicalize().unwrap();
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let client_id = 1;
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::FullHdAccess,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+    load_fixture_plugin(&plugin_thread_sender, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    pipe_to_fixture(
+        &plugin_thread_sender,
+        "watch_paths",
+        Some(watched_folder_path.display().to_string()),
+        BTreeMap::new(),
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    std::fs::write(watched_folder_path.join("watched_file"), "watched").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    pipe_to_fixture(
+        &plugin_thread_sender,
+        "unwatch_paths",
+        Some("0".to_owned()),
+        BTreeMap::new(),
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    std::fs::write(watched_folder_path.join("unwatched_file"), "unwatched").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let rendered_bytes = all_rendered_plugin_bytes(&received_screen_instructions);
+    assert!(rendered_bytes.contains("WatchedPathsChanged(0"));
+    assert!(rendered_bytes.contains("watched_file"));
+    assert!(!rendered_bytes.contains("unwatched_file"));
+}
+
+#[test]
+#[ignore]
+pub fn path_watches_are_stopped_when_the_plugin_is_reloaded() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let watched_folder = tempdir().unwrap();
+    let watched_folder_path = watched_folder.path().canonicalize().unwrap();
+    let other_watched_folder = tempdir().unwrap();
+    let other_watched_folder_path = other_watched_folder.path().canonicalize().unwrap();
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let client_id = 1;
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::FullHdAccess,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+    load_fixture_plugin(&plugin_thread_sender, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    // watch ids 0 and 1
+    pipe_to_fixture(
+        &plugin_thread_sender,
+        "watch_paths",
+        Some(watched_folder_path.display().to_string()),
+        BTreeMap::from([("count".to_owned(), "2".to_owned())]),
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    // reloading and unloading a plugin share the same cleanup of its path watches
+    let _ = plugin_thread_sender.send(PluginInstruction::ReloadPluginWithId(0));
+    std::thread::sleep(std::time::Duration::from_millis(5000)); // reloading recompiles the plugin
+                                                                // the reloaded plugin starts counting its watch ids from 0 again, replacing only the first
+                                                                // watch of its previous instance
+    pipe_to_fixture(
+        &plugin_thread_sender,
+        "watch_paths",
+        Some(other_watched_folder_path.display().to_string()),
+        BTreeMap::new(),
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    std::fs::write(watched_folder_path.join("file_after_reload"), "reloaded").unwrap();
+    std::fs::write(
+        other_watched_folder_path.join("other_file_after_reload"),
+        "reloaded",
+    )
+    .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let rendered_bytes = all_rendered_plugin_bytes(&received_screen_instructions);
+    assert!(rendered_bytes.contains("other_file_after_reload"));
+    assert!(!rendered_bytes.contains("WatchedPathsChanged(1"));
+}
+
+#[test]
+#[ignore]
+pub fn reloaded_plugin_receives_the_state_saved_by_its_previous_instance() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let client_id = 1;
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::ChangeApplicationState,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+    load_fixture_plugin(&plugin_thread_sender, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    pipe_to_fixture(
+        &plugin_thread_sender,
+        "hand_over_state",
+        Some("state_from_previous_instance".to_owned()),
+        BTreeMap::new(),
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let _ = plugin_thread_sender.send(PluginInstruction::ReloadPluginWithId(0));
+    std::thread::sleep(std::time::Duration::from_millis(5000)); // reloading recompiles the plugin
+    pipe_to_fixture(
+        &plugin_thread_sender,
+        "show_handed_over_state",
+        None,
+        BTreeMap::new(),
+    );
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let rendered_bytes = all_rendered_plugin_bytes(&received_screen_instructions);
+    assert!(
+        rendered_bytes.contains("handed over state: Some(\\\"state_from_previous_instance\\\")")
+    );
+}
+
+#[test]
+#[ignore]
+pub fn watching_a_nonexistent_path_reports_a_failure_to_the_plugin() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let client_id = 1;
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::FullHdAccess,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+    load_fixture_plugin(&plugin_thread_sender, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    pipe_to_fixture(
+        &plugin_thread_sender,
+        "watch_paths",
+        Some("/path/to/nonexistent/folder".to_owned()),
+        BTreeMap::new(),
+    );
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let rendered_bytes = all_rendered_plugin_bytes(&received_screen_instructions);
+    assert!(rendered_bytes
+        .contains("WatchPathsFailed(0, \"failed to watch /path/to/nonexistent/folder"));
+}
+
+#[test]
+#[ignore]
+pub fn plugins_cannot_watch_more_than_the_maximum_number_of_path_sets() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let watched_folder = tempdir().unwrap();
+    let watched_folder_path = watched_folder.path().canonicalize().unwrap();
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let client_id = 1;
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::FullHdAccess,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+    load_fixture_plugin(&plugin_thread_sender, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    pipe_to_fixture(
+        &plugin_thread_sender,
+        "watch_paths",
+        Some(watched_folder_path.display().to_string()),
+        BTreeMap::from([("count".to_owned(), "17".to_owned())]),
+    );
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    // once a watch is removed, there is room for another one
+    pipe_to_fixture(
+        &plugin_thread_sender,
+        "unwatch_paths",
+        Some("0".to_owned()),
+        BTreeMap::new(),
+    );
+    pipe_to_fixture(
+        &plugin_thread_sender,
+        "watch_paths",
+        Some(watched_folder_path.display().to_string()),
+        BTreeMap::new(),
+    );
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let rendered_bytes = all_rendered_plugin_bytes(&received_screen_instructions);
+    assert!(!rendered_bytes.contains("WatchPathsFailed(15,"));
+    assert!(rendered_bytes
+        .contains("WatchPathsFailed(16, \"plugin is already watching the maximum of 16 path sets"));
+    assert!(!rendered_bytes.contains("WatchPathsFailed(17,"));
+}
+
+// the renders of each plugin instance (by plugin and client id) that show an invoked command
+fn rendered_invoked_commands(
+    received_screen_instructions: &Arc<Mutex<Vec<ScreenInstruction>>>,
+) -> BTreeMap<(u32, ClientId), String> {
+    let mut invoked_commands = BTreeMap::new();
+    for instruction in received_screen_instructions.lock().unwrap().iter() {
+        if let ScreenInstruction::PluginBytes(plugin_render_assets) = instruction {
+            for plugin_render_asset in plugin_render_assets {
+                let plugin_bytes = String::from_utf8_lossy(&plugin_render_asset.bytes).to_string();
+                if plugin_bytes.contains("fixture_command from") {
+                    invoked_commands.insert(
+                        (plugin_render_asset.plugin_id, plugin_render_asset.client_id),
+                        plugin_bytes,
+                    );
+                }
+            }
+        }
+    }
+    invoked_commands
+}
+
+#[test]
+#[ignore]
+pub fn registered_command_is_routed_to_the_invoking_clients_instance() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let client_id = 1;
+    let second_client_id = 2;
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::ReadCliPipes,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(second_client_id));
+    load_fixture_plugin(&plugin_thread_sender, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    pipe_to_fixture(
+        &plugin_thread_sender,
+        "register_commands",
+        None,
+        BTreeMap::new(),
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let _ = plugin_thread_sender.send(PluginInstruction::InvokeRegisteredCommand {
+        name: "fixture_command".to_owned(),
+        args: BTreeMap::from([("required_arg".to_owned(), "value".to_owned())]),
+        client_id: second_client_id,
+        source: PipeSource::Cli("cli_pipe_id".to_owned()),
+    });
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let invoked_commands = rendered_invoked_commands(&received_screen_instructions);
+    assert_eq!(invoked_commands.len(), 1);
+    assert!(invoked_commands
+        .get(&(0, second_client_id))
+        .unwrap()
+        .contains(
+            "fixture_command from Cli(\\\"cli_pipe_id\\\") with {\\\"required_arg\\\": \\\"value\\\"} on client 2"
+        ));
+}
+
+#[test]
+#[ignore]
+pub fn registered_command_missing_a_required_argument_is_not_invoked() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, server_receiver, screen_receiver, teardown) =
+        create_plugin_thread_with_server_receiver(Some(plugin_host_folder));
+    let client_id = 1;
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::LogError,
+        server_receiver,
+        1
+    );
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::ReadCliPipes,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+    load_fixture_plugin(&plugin_thread_sender, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    pipe_to_fixture(
+        &plugin_thread_sender,
+        "register_commands",
+        None,
+        BTreeMap::new(),
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let _ = plugin_thread_sender.send(PluginInstruction::InvokeRegisteredCommand {
+        name: "fixture_command".to_owned(),
+        args: BTreeMap::new(),
+        client_id,
+        source: PipeSource::Keybind,
+    });
+    server_thread.join().unwrap(); // this might take a while if the cache is cold
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    teardown();
+    screen_thread.join().unwrap();
+    let logged_error = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| match i {
+            ServerInstruction::LogError(errors, error_client_id) => {
+                Some((errors.clone(), *error_client_id))
+            },
+            _ => None,
+        });
+    assert_eq!(
+        logged_error,
+        Some((
+            vec![
+                "Command fixture_command is missing the required argument(s): required_arg"
+                    .to_owned()
+            ],
+            client_id
+        ))
+    );
+    assert!(rendered_invoked_commands(&received_screen_instructions).is_empty());
+}
+
+#[test]
+#[ignore]
+pub fn commands_cannot_be_registered_under_a_name_another_plugin_registered() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let client_id = 1;
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread_naked_variant!(
+        received_screen_instructions,
+        ScreenInstruction::Exit,
+        screen_receiver,
+        1,
+        &PermissionType::ReadCliPipes,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+    load_fixture_plugin(&plugin_thread_sender, client_id);
+    // a differently configured instance of the fixture is a separate plugin
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        Some(false),
+        false,
+        Some("test_plugin".to_owned()),
+        RunPluginOrAlias::RunPlugin(RunPlugin {
+            location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+            configuration: PluginUserConfiguration::new(BTreeMap::from([(
+                "fake_config".to_owned(),
+                "fake_value".to_owned(),
+            )])),
+            ..Default::default()
+        }),
+        Some(1),
+        None,
+        client_id,
+        Size {
+            cols: 121,
+            rows: 20,
+        },
+        None,
+        None,
+        false,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    // the permissions of the second plugin are not granted by the fake screen thread
+    let _ = plugin_thread_sender.send(PluginInstruction::PermissionRequestResult(
+        1,
+        Some(client_id),
+        vec![PermissionType::ReadApplicationState],
+        PermissionStatus::Granted,
+        Some(cache_path),
+    ));
+    // both plugins try to register the same command
+    pipe_to_fixture(
+        &plugin_thread_sender,
+        "register_commands",
+        None,
+        BTreeMap::new(),
+    );
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    teardown();
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    let rendered_bytes = all_rendered_plugin_bytes(&received_screen_instructions);
+    let last_registered_commands_update = rendered_bytes
+        .rsplit("RegisteredCommandsUpdate(")
+        .next()
+        .unwrap();
+    assert_eq!(
+        last_registered_commands_update
+            .matches("name: \"fixture_command\"")
+            .count(),
+        1
+    );
+}
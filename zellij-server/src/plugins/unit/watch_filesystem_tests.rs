@@ -0,0 +1,79 @@
+use super::*;
+
+fn globs(globs: &[&str]) -> GlobSet {
+    glob_set(&globs.iter().map(|g| g.to_string()).collect::<Vec<_>>()).unwrap()
+}
+
+#[test]
+fn all_paths_are_watched_without_include_or_exclude_globs() {
+    let include_globs = globs(&[]);
+    let exclude_globs = globs(&[]);
+    assert!(is_watched_path(
+        Path::new("/project/src/main.rs"),
+        &include_globs,
+        &exclude_globs
+    ));
+    assert!(is_watched_path(
+        Path::new("/project/target/debug/app"),
+        &include_globs,
+        &exclude_globs
+    ));
+}
+
+#[test]
+fn only_included_paths_are_watched() {
+    let include_globs = globs(&["**/*.rs", "**/Cargo.toml"]);
+    let exclude_globs = globs(&[]);
+    assert!(is_watched_path(
+        Path::new("/project/src/main.rs"),
+        &include_globs,
+        &exclude_globs
+    ));
+    assert!(is_watched_path(
+        Path::new("/project/Cargo.toml"),
+        &include_globs,
+        &exclude_globs
+    ));
+    assert!(!is_watched_path(
+        Path::new("/project/README.md"),
+        &include_globs,
+        &exclude_globs
+    ));
+}
+
+#[test]
+fn excluded_paths_are_not_watched_even_if_included() {
+    let include_globs = globs(&["**/*.rs"]);
+    let exclude_globs = globs(&["**/target/**"]);
+    assert!(is_watched_path(
+        Path::new("/project/src/main.rs"),
+        &include_globs,
+        &exclude_globs
+    ));
+    assert!(!is_watched_path(
+        Path::new("/project/target/debug/build/out.rs"),
+        &include_globs,
+        &exclude_globs
+    ));
+}
+
+#[test]
+fn excluded_paths_are_not_watched_without_include_globs() {
+    let include_globs = globs(&[]);
+    let exclude_globs = globs(&["**/.git/**"]);
+    assert!(is_watched_path(
+        Path::new("/project/src/main.rs"),
+        &include_globs,
+        &exclude_globs
+    ));
+    assert!(!is_watched_path(
+        Path::new("/project/.git/index"),
+        &include_globs,
+        &exclude_globs
+    ));
+}
+
+#[test]
+fn invalid_globs_are_an_error() {
+    assert!(glob_set(&["[unclosed".to_owned()]).is_err());
+}
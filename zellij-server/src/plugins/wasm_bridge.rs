@@ -3,10 +3,12 @@ use crate::plugins::pipes::{
     apply_pipe_message_to_plugin, pipes_to_block_or_unblock, PendingPipes, PipeStateChange,
 };
 use crate::plugins::plugin_loader::PluginLoader;
-use crate::plugins::plugin_map::{AtomicEvent, PluginEnv, PluginMap, RunningPlugin, Subscriptions};
+use crate::plugins::plugin_map::{
+    call_plugin_with_limits, AtomicEvent, PluginEnv, PluginMap, RunningPlugin, Subscriptions,
+};
 
 use crate::plugins::plugin_worker::MessageToWorker;
-use crate::plugins::watch_filesystem::watch_filesystem;
+use crate::plugins::watch_filesystem::{watch_filesystem, watch_paths, watch_plugin_file};
 use crate::plugins::zellij_exports::{wasi_read_string, wasi_write_object};
 use async_channel::Sender;
 use async_std::task::{self, JoinHandle};
@@ -15,19 +17,22 @@ use log::info;
 use notify_debouncer_full::{notify::RecommendedWatcher, Debouncer, FileIdMap};
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
-    path::PathBuf,
+    path::{Path, PathBuf},
     str::FromStr,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 use wasmtime::{Engine, Module};
 use zellij_utils::consts::{ZELLIJ_CACHE_DIR, ZELLIJ_TMP_DIR};
 use zellij_utils::data::{
-    FloatingPaneCoordinates, InputMode, PermissionStatus, PermissionType, PipeMessage, PipeSource,
+    CommandSpec, FloatingPaneCoordinates, InputMode, PermissionStatus, PermissionType, PipeMessage,
+    PipeSource, PluginCall, RegisteredCommand, WatchOptions,
 };
 use zellij_utils::downloader::Downloader;
 use zellij_utils::input::keybinds::Keybinds;
 use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::plugin_api::event::ProtobufEvent;
+use zellij_utils::plugin_api::plugin_call::ProtobufPluginCall;
 
 use prost::Message;
 
@@ -42,12 +47,25 @@ use zellij_utils::{
     input::{
         command::TerminalAction,
         layout::{Layout, PluginUserConfiguration, RunPlugin, RunPluginLocation, RunPluginOrAlias},
-        plugins::PluginConfig,
+        plugins::{PluginAliases, PluginConfig, PluginLimits, PluginLock},
     },
     ipc::ClientAttributes,
     pane_size::Size,
 };
 
+const DEFAULT_PLUGIN_CALL_TIMEOUT: Duration = Duration::from_secs(30);
+// plugin states larger than this are not stored in the serialized session layout
+const MAX_SERIALIZED_PLUGIN_STATE_SIZE: usize = 64 * 1024; // bytes
+const MAX_PATH_WATCHES_PER_PLUGIN: usize = 16;
+
+#[derive(Debug, Clone, Copy)]
+struct PendingPluginCall {
+    caller_plugin_id: PluginId,
+    caller_client_id: ClientId,
+    caller_call_id: u64,        // the id the caller knows this call by
+    callee_plugin_id: PluginId, // only this plugin may respond to the call
+}
+
 #[derive(Debug, Clone)]
 pub enum EventOrPipeMessage {
     Event(Event),
@@ -97,6 +115,11 @@ pub struct WasmBridge {
     pending_plugin_reloads: HashSet<RunPlugin>,
     path_to_default_shell: PathBuf,
     watcher: Option<Debouncer<RecommendedWatcher, FileIdMap>>,
+    path_watchers: HashMap<(PluginId, ClientId, u32), Debouncer<RecommendedWatcher, FileIdMap>>, // u32 -> watch id
+    plugin_file_watchers: HashMap<
+        (RunPluginLocation, PluginUserConfiguration),
+        Debouncer<RecommendedWatcher, FileIdMap>,
+    >,
     zellij_cwd: PathBuf,
     capabilities: PluginCapabilities,
     client_attributes: ClientAttributes,
@@ -111,6 +134,12 @@ pub struct WasmBridge {
     keybinds: HashMap<ClientId, Keybinds>,
     base_modes: HashMap<ClientId, InputMode>,
     downloader: Downloader,
+    plugin_limits: PluginLimits,
+    plugin_lock: PluginLock,
+    pending_plugin_calls: HashMap<u64, PendingPluginCall>, // u64 -> the call id the callee knows
+    next_plugin_call_id: u64,
+    registered_commands: BTreeMap<PluginId, Vec<CommandSpec>>,
+    pane_content_changed_has_subscribers: bool,
 }
 
 impl WasmBridge {
@@ -127,6 +156,8 @@ impl WasmBridge {
         layout_dir: Option<PathBuf>,
         default_mode: InputMode,
         default_keybinds: Keybinds,
+        plugin_limits: PluginLimits,
+        plugin_lock: PluginLock,
     ) -> Self {
         let plugin_map = Arc::new(Mutex::new(PluginMap::default()));
         let connected_clients: Arc<Mutex<Vec<ClientId>>> = Arc::new(Mutex::new(vec![]));
@@ -143,6 +174,8 @@ impl WasmBridge {
             plugin_map,
             path_to_default_shell,
             watcher,
+            path_watchers: HashMap::new(),
+            plugin_file_watchers: HashMap::new(),
             next_plugin_id: 0,
             cached_events_for_pending_plugins: HashMap::new(),
             plugin_ids_waiting_for_permission_request: HashSet::new(),
@@ -163,6 +196,12 @@ impl WasmBridge {
             keybinds: HashMap::new(),
             base_modes: HashMap::new(),
             downloader,
+            plugin_limits,
+            plugin_lock,
+            pending_plugin_calls: HashMap::new(),
+            registered_commands: BTreeMap::new(),
+            next_plugin_call_id: 0,
+            pane_content_changed_has_subscribers: false,
         }
     }
     pub fn load_plugin(
@@ -198,6 +237,12 @@ impl WasmBridge {
                 let mut plugin = PluginConfig::from_run_plugin(run)
                     .with_context(|| format!("failed to resolve plugin {run:?}"))
                     .with_context(err_context)?;
+                plugin.limits = self.plugin_limits.with_overrides(&run.limit_overrides);
+                let pinned_sha256 = self.plugin_lock.pinned_sha256(run);
+                let saved_state = run.saved_state.clone();
+                if run.watches_for_changes() {
+                    self.start_plugin_file_watcher_if_not_started(run, &plugin.path);
+                }
                 let plugin_name = run.location.to_string();
 
                 self.cached_events_for_pending_plugins
@@ -245,15 +290,34 @@ impl WasmBridge {
 
                             // if the url is already in cache, we'll use that version, otherwise
                             // we'll download it, place it in cache and then use it
-                            match downloader.download(url, Some(&file_name)).await {
+                            let downloaded = match &pinned_sha256 {
+                                Some(sha256) => {
+                                    downloader
+                                        .download_and_verify(url, Some(&file_name), sha256)
+                                        .await
+                                },
+                                None => downloader.download(url, Some(&file_name)).await,
+                            };
+                            match downloaded {
                                 Ok(_) => plugin.path = ZELLIJ_CACHE_DIR.join(&file_name),
-                                Err(e) => handle_plugin_loading_failure(
-                                    &senders,
-                                    plugin_id,
-                                    &mut loading_indication,
-                                    e,
-                                    cli_client_id,
-                                ),
+                                Err(e) => {
+                                    handle_plugin_loading_failure(
+                                        &senders,
+                                        plugin_id,
+                                        &mut loading_indication,
+                                        e,
+                                        cli_client_id,
+                                    );
+                                    // there is nothing to start, and we do not want the error
+                                    // of trying to start it to hide why the download failed
+                                    let _ = senders.send_to_plugin(
+                                        PluginInstruction::ApplyCachedEvents {
+                                            plugin_ids: vec![plugin_id],
+                                            done_receiving_permissions: false,
+                                        },
+                                    );
+                                    return;
+                                },
                             }
                         }
 
@@ -282,6 +346,13 @@ impl WasmBridge {
                             keybinds,
                         ) {
                             Ok(_) => {
+                                if let Some(saved_state) = &saved_state {
+                                    restore_resurrected_plugin_state(
+                                        &plugin_map,
+                                        plugin_id,
+                                        saved_state,
+                                    );
+                                }
                                 let plugin_list = plugin_map.lock().unwrap().list_plugins();
                                 handle_plugin_successful_loading(&senders, plugin_id, plugin_list);
                             },
@@ -317,8 +388,35 @@ impl WasmBridge {
         }
         Ok((plugin_id, client_id))
     }
+    // PaneContentChanged is reported on every change to a terminal pane, so we let screen know
+    // whether anyone is listening for it before it starts sending those our way
+    pub fn update_pane_content_changed_subscribers(&mut self) {
+        let has_subscribers = self
+            .plugin_map
+            .lock()
+            .unwrap()
+            .running_plugins_and_subscriptions()
+            .iter()
+            .any(|(_plugin_id, _client_id, _running_plugin, subscriptions)| {
+                subscriptions
+                    .lock()
+                    .unwrap()
+                    .contains(&EventType::PaneContentChanged)
+            });
+        if has_subscribers != self.pane_content_changed_has_subscribers {
+            self.pane_content_changed_has_subscribers = has_subscribers;
+            let _ = self
+                .senders
+                .send_to_screen(ScreenInstruction::SetReportPaneContentChanges(
+                    has_subscribers,
+                ));
+        }
+    }
     pub fn unload_plugin(&mut self, pid: PluginId) -> Result<()> {
         info!("Bye from plugin {}", &pid);
+        self.stop_path_watchers_of_plugin(pid);
+        self.stop_plugin_file_watcher_if_unused(pid);
+        self.unregister_commands_of_plugin(pid);
         let mut plugin_map = self.plugin_map.lock().unwrap();
         for ((plugin_id, client_id), (running_plugin, subscriptions, workers)) in
             plugin_map.remove_plugins(pid)
@@ -389,6 +487,8 @@ impl WasmBridge {
         };
 
         let (rows, columns) = self.size_of_plugin_id(plugin_id).unwrap_or((0, 0));
+        let saved_states = self.save_plugin_states(&[plugin_id]);
+        self.stop_path_watchers_of_plugin(plugin_id);
         self.cached_events_for_pending_plugins
             .insert(plugin_id, vec![]);
         self.cached_resizes_for_pending_plugins
@@ -427,6 +527,7 @@ impl WasmBridge {
                     layout_dir.clone(),
                 ) {
                     Ok(_) => {
+                        restore_plugin_states(&plugin_map, plugin_id, &saved_states);
                         let plugin_list = plugin_map.lock().unwrap().list_plugins();
                         handle_plugin_successful_loading(&senders, plugin_id, plugin_list);
                     },
@@ -458,8 +559,10 @@ impl WasmBridge {
 
         let plugin_ids = self
             .all_plugin_ids_for_plugin_location(&run_plugin.location, &run_plugin.configuration)?;
+        let saved_states = self.save_plugin_states(&plugin_ids);
         for plugin_id in &plugin_ids {
             let (rows, columns) = self.size_of_plugin_id(*plugin_id).unwrap_or((0, 0));
+            self.stop_path_watchers_of_plugin(*plugin_id);
             self.cached_events_for_pending_plugins
                 .insert(*plugin_id, vec![]);
             self.cached_resizes_for_pending_plugins
@@ -502,6 +605,7 @@ impl WasmBridge {
                     layout_dir.clone(),
                 ) {
                     Ok(_) => {
+                        restore_plugin_states(&plugin_map, first_plugin_id, &saved_states);
                         let plugin_list = plugin_map.lock().unwrap().list_plugins();
                         handle_plugin_successful_loading(&senders, first_plugin_id, plugin_list);
                         for plugin_id in &plugin_ids {
@@ -528,6 +632,7 @@ impl WasmBridge {
                                 layout_dir.clone(),
                             ) {
                                 Ok(_) => {
+                                    restore_plugin_states(&plugin_map, *plugin_id, &saved_states);
                                     let plugin_list = plugin_map.lock().unwrap().list_plugins();
                                     handle_plugin_successful_loading(
                                         &senders,
@@ -635,7 +740,9 @@ impl WasmBridge {
                     async move {
                         let mut running_plugin = running_plugin.lock().unwrap();
                         let _s = _s; // guard to allow the task to complete before cleanup/shutdown
-                        if running_plugin.apply_event_id(AtomicEvent::Resize, event_id) {
+                        if running_plugin.apply_event_id(AtomicEvent::Resize, event_id)
+                            && !running_plugin.exceeded_limits()
+                        {
                             let old_rows = running_plugin.rows;
                             let old_columns = running_plugin.columns;
                             running_plugin.rows = new_rows;
@@ -652,8 +759,9 @@ impl WasmBridge {
                                         "render",
                                     )
                                     .and_then(|render| {
-                                        render.call(
+                                        call_plugin_with_limits(
                                             &mut running_plugin.store,
+                                            &render,
                                             (new_rows as i32, new_columns as i32),
                                         )
                                     })
@@ -1045,6 +1153,12 @@ impl WasmBridge {
         if let Some(watcher) = self.watcher.take() {
             watcher.stop_nonblocking();
         }
+        for (_, path_watcher) in self.path_watchers.drain() {
+            path_watcher.stop_nonblocking();
+        }
+        for (_, plugin_file_watcher) in self.plugin_file_watchers.drain() {
+            plugin_file_watcher.stop_nonblocking();
+        }
     }
     pub fn run_plugin_of_loading_plugin_id(&self, plugin_id: PluginId) -> Option<&RunPlugin> {
         self.loading_plugins
@@ -1356,6 +1470,307 @@ impl WasmBridge {
         }
         Ok(())
     }
+    pub fn watch_paths(
+        &mut self,
+        plugin_id: PluginId,
+        client_id: ClientId,
+        watch_id: u32,
+        paths: Vec<PathBuf>,
+        watch_options: WatchOptions,
+    ) -> Result<()> {
+        let err_context = || format!("failed to watch paths for plugin {}", plugin_id);
+        // re-using a watch id replaces that watch, so it does not count towards the limit
+        let other_watch_count = self
+            .path_watchers
+            .keys()
+            .filter(|(p_id, c_id, w_id)| {
+                *p_id == plugin_id && *c_id == client_id && *w_id != watch_id
+            })
+            .count();
+        let path_watcher = if other_watch_count >= MAX_PATH_WATCHES_PER_PLUGIN {
+            Err(anyhow!(
+                "plugin is already watching the maximum of {} path sets",
+                MAX_PATH_WATCHES_PER_PLUGIN
+            ))
+        } else {
+            watch_paths(
+                self.senders.clone(),
+                plugin_id,
+                client_id,
+                watch_id,
+                &paths,
+                &watch_options,
+            )
+        };
+        let path_watcher = match path_watcher {
+            Ok(path_watcher) => path_watcher,
+            Err(e) => {
+                let _ = self.senders.send_to_plugin(PluginInstruction::Update(vec![(
+                    Some(plugin_id),
+                    Some(client_id),
+                    Event::WatchPathsFailed(watch_id, format!("{:#}", e)),
+                )]));
+                return Err(e).with_context(err_context);
+            },
+        };
+        if let Some(previous_path_watcher) = self
+            .path_watchers
+            .insert((plugin_id, client_id, watch_id), path_watcher)
+        {
+            previous_path_watcher.stop_nonblocking();
+        }
+        Ok(())
+    }
+    pub fn unwatch_paths(&mut self, plugin_id: PluginId, client_id: ClientId, watch_id: u32) {
+        if let Some(path_watcher) = self.path_watchers.remove(&(plugin_id, client_id, watch_id)) {
+            path_watcher.stop_nonblocking();
+        }
+    }
+    pub fn register_commands(&mut self, plugin_id: PluginId, mut commands: Vec<CommandSpec>) {
+        // command names must be unique, so that invoking one by name is never ambiguous
+        let mut seen_names = HashSet::new();
+        commands.retain(|command| {
+            let registered_by = self
+                .registered_commands
+                .iter()
+                .find(|(p_id, command_specs)| {
+                    **p_id != plugin_id && command_specs.iter().any(|c| c.name == command.name)
+                })
+                .map(|(p_id, _)| *p_id);
+            if let Some(registered_by) = registered_by {
+                log::error!(
+                    "Plugin {} cannot register the command {}, it is already registered by plugin {}",
+                    plugin_id,
+                    command.name,
+                    registered_by
+                );
+                false
+            } else if !seen_names.insert(command.name.clone()) {
+                log::error!(
+                    "Plugin {} registered the command {} more than once",
+                    plugin_id,
+                    command.name
+                );
+                false
+            } else {
+                true
+            }
+        });
+        if commands.is_empty() {
+            self.registered_commands.remove(&plugin_id);
+        } else {
+            self.registered_commands.insert(plugin_id, commands);
+        }
+        self.broadcast_registered_commands();
+    }
+    pub fn list_registered_commands(
+        &mut self,
+        plugin_id: PluginId,
+        client_id: ClientId,
+        shutdown_sender: Sender<()>,
+    ) -> Result<()> {
+        let registered_commands = self.registered_commands();
+        self.update_plugins(
+            vec![(
+                Some(plugin_id),
+                Some(client_id),
+                Event::RegisteredCommandsUpdate(registered_commands),
+            )],
+            shutdown_sender,
+        )
+    }
+    pub fn invoke_registered_command(
+        &mut self,
+        name: String,
+        args: BTreeMap<String, String>,
+        client_id: ClientId,
+        source: PipeSource,
+        shutdown_sender: Sender<()>,
+    ) -> Result<()> {
+        let Some((plugin_id, command_spec)) =
+            self.registered_commands
+                .iter()
+                .find_map(|(plugin_id, command_specs)| {
+                    command_specs
+                        .iter()
+                        .find(|command_spec| command_spec.name == name)
+                        .map(|command_spec| (*plugin_id, command_spec))
+                })
+        else {
+            return self.log_command_error(
+                format!("No plugin registered the command {}", name),
+                client_id,
+            );
+        };
+        let missing_args: Vec<&str> = command_spec
+            .args
+            .iter()
+            .filter(|arg| arg.required && !args.contains_key(&arg.name))
+            .map(|arg| arg.name.as_str())
+            .collect();
+        if !missing_args.is_empty() {
+            return self.log_command_error(
+                format!(
+                    "Command {} is missing the required argument(s): {}",
+                    name,
+                    missing_args.join(", ")
+                ),
+                client_id,
+            );
+        }
+        // prefer the instance of the plugin belonging to the invoking client, since each client
+        // has its own instance of most plugins
+        let plugin_and_client_ids = self.plugin_map.lock().unwrap().all_plugin_ids();
+        let client_id = plugin_and_client_ids
+            .iter()
+            .find(|(p_id, c_id)| *p_id == plugin_id && *c_id == client_id)
+            .or_else(|| {
+                plugin_and_client_ids
+                    .iter()
+                    .find(|(p_id, _c_id)| *p_id == plugin_id)
+            })
+            .map(|(_p_id, c_id)| *c_id)
+            .unwrap_or(client_id);
+        let is_private = true;
+        self.pipe_messages(
+            vec![(
+                Some(plugin_id),
+                Some(client_id),
+                PipeMessage::new(source, name, &None, &Some(args), is_private),
+            )],
+            shutdown_sender,
+        )
+    }
+    fn log_command_error(&self, error: String, client_id: ClientId) -> Result<()> {
+        log::error!("{}", error);
+        self.senders
+            .send_to_server(ServerInstruction::LogError(vec![error], client_id))
+            .context("failed to send command error to client")
+    }
+    fn registered_commands(&self) -> Vec<RegisteredCommand> {
+        let plugin_map = self.plugin_map.lock().unwrap();
+        let mut registered_commands = vec![];
+        for (plugin_id, command_specs) in &self.registered_commands {
+            let plugin_url = plugin_map
+                .run_plugin_of_plugin_id(*plugin_id)
+                .map(|run_plugin| run_plugin.location.display())
+                .unwrap_or_default();
+            for command_spec in command_specs {
+                registered_commands.push(RegisteredCommand {
+                    plugin_id: *plugin_id,
+                    plugin_url: plugin_url.clone(),
+                    command: command_spec.clone(),
+                });
+            }
+        }
+        registered_commands
+    }
+    fn broadcast_registered_commands(&self) {
+        let _ = self.senders.send_to_plugin(PluginInstruction::Update(vec![(
+            None,
+            None,
+            Event::RegisteredCommandsUpdate(self.registered_commands()),
+        )]));
+    }
+    fn unregister_commands_of_plugin(&mut self, plugin_id: PluginId) {
+        if self.registered_commands.remove(&plugin_id).is_some() {
+            self.broadcast_registered_commands();
+        }
+    }
+    // collects the states the running instances of these plugins want to hand over to the
+    // instances that will replace them when they are reloaded
+    fn save_plugin_states(&self, plugin_ids: &[PluginId]) -> HashMap<(PluginId, ClientId), String> {
+        let mut saved_states = HashMap::new();
+        let running_plugins = self.plugin_map.lock().unwrap().running_plugins();
+        for (plugin_id, client_id, running_plugin) in running_plugins {
+            if !plugin_ids.contains(&plugin_id) {
+                continue;
+            }
+            let mut running_plugin = running_plugin.lock().unwrap();
+            match save_plugin_state(&mut running_plugin) {
+                Ok(Some(state)) => {
+                    saved_states.insert((plugin_id, client_id), state);
+                },
+                Ok(None) => {},
+                Err(e) => log::error!("Failed to save state of plugin {}: {:?}", plugin_id, e),
+            }
+        }
+        saved_states
+    }
+    // the states to store in the serialized session layout next to these plugins' panes, one per
+    // plugin (plugins running for multiple clients are resurrected from the state of one of them)
+    pub fn serializable_plugin_states(&self, plugin_ids: &[PluginId]) -> HashMap<PluginId, String> {
+        let mut serializable_states = HashMap::new();
+        for ((plugin_id, _client_id), state) in self.save_plugin_states(plugin_ids) {
+            if state.len() > MAX_SERIALIZED_PLUGIN_STATE_SIZE {
+                log::warn!(
+                    "Not serializing state of plugin {}: it is {} bytes, the limit is {}",
+                    plugin_id,
+                    state.len(),
+                    MAX_SERIALIZED_PLUGIN_STATE_SIZE
+                );
+                continue;
+            }
+            serializable_states.entry(plugin_id).or_insert(state);
+        }
+        serializable_states
+    }
+    fn stop_path_watchers_of_plugin(&mut self, plugin_id: PluginId) {
+        let watch_keys: Vec<(PluginId, ClientId, u32)> = self
+            .path_watchers
+            .keys()
+            .filter(|(p_id, _client_id, _watch_id)| *p_id == plugin_id)
+            .copied()
+            .collect();
+        for watch_key in watch_keys {
+            if let Some(path_watcher) = self.path_watchers.remove(&watch_key) {
+                path_watcher.stop_nonblocking();
+            }
+        }
+    }
+    fn start_plugin_file_watcher_if_not_started(
+        &mut self,
+        run_plugin: &RunPlugin,
+        plugin_path: &Path,
+    ) {
+        let watch_key = (
+            run_plugin.location.clone(),
+            run_plugin.configuration.clone(),
+        );
+        if self.plugin_file_watchers.contains_key(&watch_key) {
+            return;
+        }
+        match watch_plugin_file(self.senders.clone(), run_plugin.clone(), plugin_path) {
+            Ok(plugin_file_watcher) => {
+                self.plugin_file_watchers
+                    .insert(watch_key, plugin_file_watcher);
+            },
+            Err(e) => {
+                log::error!(
+                    "Failed to watch {} for changes: {:?}",
+                    plugin_path.display(),
+                    e
+                );
+            },
+        }
+    }
+    fn stop_plugin_file_watcher_if_unused(&mut self, plugin_id: PluginId) {
+        let Some(run_plugin) = self.run_plugin_of_plugin_id(plugin_id) else {
+            return;
+        };
+        let has_other_instances = self
+            .all_plugin_ids_for_plugin_location(&run_plugin.location, &run_plugin.configuration)
+            .map(|plugin_ids| plugin_ids.iter().any(|p_id| *p_id != plugin_id))
+            .unwrap_or(false);
+        if !has_other_instances {
+            if let Some(plugin_file_watcher) = self
+                .plugin_file_watchers
+                .remove(&(run_plugin.location, run_plugin.configuration))
+            {
+                plugin_file_watcher.stop_nonblocking();
+            }
+        }
+    }
     pub fn start_fs_watcher_if_not_started(&mut self) {
         if self.watcher.is_none() {
             self.watcher = match watch_filesystem(self.senders.clone(), &self.zellij_cwd) {
@@ -1494,6 +1909,195 @@ impl WasmBridge {
     pub fn clear_plugin_map_cache(&mut self) {
         self.cached_plugin_map.clear();
     }
+    #[allow(clippy::too_many_arguments)]
+    pub fn call_plugin(
+        &mut self,
+        caller_plugin_id: PluginId,
+        caller_client_id: ClientId,
+        caller_call_id: u64,
+        destination: String,
+        method: String,
+        payload: Option<String>,
+        timeout: Option<Duration>,
+        plugin_aliases: &PluginAliases,
+        shutdown_sender: Sender<()>,
+    ) -> Result<()> {
+        let Some((callee_plugin_id, running_plugin)) =
+            self.running_plugin_for_call(&destination, caller_client_id, plugin_aliases)
+        else {
+            return self.send_plugin_call_result(
+                caller_plugin_id,
+                caller_client_id,
+                caller_call_id,
+                Err(format!("Plugin {} is not loaded", destination)),
+                shutdown_sender,
+            );
+        };
+        let pending_plugin_call = PendingPluginCall {
+            caller_plugin_id,
+            caller_client_id,
+            caller_call_id,
+            callee_plugin_id,
+        };
+        let plugin_call_id = self.next_plugin_call_id;
+        self.next_plugin_call_id += 1;
+        self.pending_plugin_calls
+            .insert(plugin_call_id, pending_plugin_call);
+        task::spawn({
+            let senders = self.senders.clone();
+            let timeout = timeout.unwrap_or(DEFAULT_PLUGIN_CALL_TIMEOUT);
+            async move {
+                task::sleep(timeout).await;
+                let _ = senders.send_to_plugin(PluginInstruction::PluginCallTimedOut(
+                    plugin_call_id,
+                    timeout,
+                ));
+            }
+        });
+        let plugin_call = PluginCall {
+            id: plugin_call_id,
+            caller_plugin_id,
+            method,
+            payload,
+        };
+        task::spawn({
+            let senders = self.senders.clone();
+            let _s = shutdown_sender.clone();
+            async move {
+                let _s = _s; // guard to allow the task to complete before cleanup/shutdown
+                let mut running_plugin = running_plugin.lock().unwrap();
+                match apply_plugin_call_to_plugin(&mut running_plugin, plugin_call) {
+                    Ok(true) => {},
+                    Ok(false) => {
+                        let _ = senders.send_to_plugin(PluginInstruction::PluginCallResponse(
+                            callee_plugin_id,
+                            plugin_call_id,
+                            Err(format!("Plugin {} does not handle calls", destination)),
+                        ));
+                    },
+                    Err(e) => {
+                        log::error!("{:?}", e);
+                        let _ = senders.send_to_plugin(PluginInstruction::PluginCallResponse(
+                            callee_plugin_id,
+                            plugin_call_id,
+                            Err(format!("Plugin {} failed to handle the call", destination)),
+                        ));
+                        // https://stackoverflow.com/questions/66450942/in-rust-is-there-a-way-to-make-literal-newlines-in-r-using-windows-c
+                        let stringified_error = format!("{:?}", e).replace("\n", "\n\r");
+                        handle_plugin_crash(callee_plugin_id, stringified_error, senders);
+                    },
+                }
+            }
+        });
+        Ok(())
+    }
+    pub fn respond_to_plugin_call(
+        &mut self,
+        responding_plugin_id: PluginId,
+        plugin_call_id: u64,
+        result: std::result::Result<String, String>,
+        shutdown_sender: Sender<()>,
+    ) -> Result<()> {
+        match self.pending_plugin_calls.get(&plugin_call_id) {
+            Some(pending_plugin_call)
+                if pending_plugin_call.callee_plugin_id == responding_plugin_id =>
+            {
+                let pending_plugin_call = *pending_plugin_call;
+                self.pending_plugin_calls.remove(&plugin_call_id);
+                self.send_pending_plugin_call_result(pending_plugin_call, result, shutdown_sender)
+            },
+            Some(_) => {
+                log::error!(
+                    "Plugin {} tried to respond to call {} which was not made to it",
+                    responding_plugin_id,
+                    plugin_call_id
+                );
+                Ok(())
+            },
+            None => {
+                // the call already timed out
+                Ok(())
+            },
+        }
+    }
+    pub fn plugin_call_timed_out(
+        &mut self,
+        plugin_call_id: u64,
+        timeout: Duration,
+        shutdown_sender: Sender<()>,
+    ) -> Result<()> {
+        match self.pending_plugin_calls.remove(&plugin_call_id) {
+            Some(pending_plugin_call) => self.send_pending_plugin_call_result(
+                pending_plugin_call,
+                Err(format!("Timed out after {}ms", timeout.as_millis())),
+                shutdown_sender,
+            ),
+            None => Ok(()),
+        }
+    }
+    fn running_plugin_for_call(
+        &self,
+        destination: &str,
+        caller_client_id: ClientId,
+        plugin_aliases: &PluginAliases,
+    ) -> Option<(PluginId, Arc<Mutex<RunningPlugin>>)> {
+        let run_plugin = RunPluginOrAlias::from_url(destination, &None, Some(plugin_aliases), None)
+            .ok()
+            .and_then(|run_plugin_or_alias| run_plugin_or_alias.get_run_plugin())?;
+        let plugin_map = self.plugin_map.lock().unwrap();
+        let mut plugin_and_client_ids: Vec<(PluginId, ClientId)> = plugin_map
+            .clone_plugin_assets()
+            .remove(&run_plugin.location)?
+            .into_values()
+            .flatten()
+            .filter(|(plugin_id, _client_id)| {
+                !self
+                    .cached_events_for_pending_plugins
+                    .contains_key(plugin_id)
+            })
+            .collect();
+        // prefer the instance of the plugin belonging to the same client as the caller
+        plugin_and_client_ids
+            .sort_by_key(|(plugin_id, client_id)| (*client_id != caller_client_id, *plugin_id));
+        plugin_and_client_ids
+            .into_iter()
+            .find_map(|(plugin_id, client_id)| {
+                plugin_map
+                    .get_running_plugin(plugin_id, Some(client_id))
+                    .map(|running_plugin| (plugin_id, running_plugin))
+            })
+    }
+    fn send_pending_plugin_call_result(
+        &mut self,
+        pending_plugin_call: PendingPluginCall,
+        result: std::result::Result<String, String>,
+        shutdown_sender: Sender<()>,
+    ) -> Result<()> {
+        self.send_plugin_call_result(
+            pending_plugin_call.caller_plugin_id,
+            pending_plugin_call.caller_client_id,
+            pending_plugin_call.caller_call_id,
+            result,
+            shutdown_sender,
+        )
+    }
+    fn send_plugin_call_result(
+        &mut self,
+        caller_plugin_id: PluginId,
+        caller_client_id: ClientId,
+        caller_call_id: u64,
+        result: std::result::Result<String, String>,
+        shutdown_sender: Sender<()>,
+    ) -> Result<()> {
+        self.update_plugins(
+            vec![(
+                Some(caller_plugin_id),
+                Some(caller_client_id),
+                Event::PluginCallResult(caller_call_id, result),
+            )],
+            shutdown_sender,
+        )
+    }
     // returns the pipe names to unblock
     pub fn update_cli_pipe_state(
         &mut self,
@@ -1601,7 +2205,13 @@ fn check_event_permission(
         | Event::EditPaneExited(..)
         | Event::FailedToWriteConfigToDisk(..)
         | Event::CommandPaneReRun(..)
+        | Event::PaneNotification { .. }
+        | Event::RegisteredCommandsUpdate(..)
+        | Event::PaneScrollbackSearchResult { .. }
         | Event::InputReceived => PermissionType::ReadApplicationState,
+        Event::PaneScrollback { .. } | Event::PaneContentChanged(..) => {
+            PermissionType::ReadPaneContents
+        },
         Event::WebServerStatus(..) => PermissionType::StartWebServer,
         _ => return (PermissionStatus::Granted, None),
     };
@@ -1623,6 +2233,9 @@ pub fn apply_event_to_plugin(
     plugin_render_assets: &mut Vec<PluginRenderAsset>,
     senders: ThreadSenders,
 ) -> Result<()> {
+    if running_plugin.exceeded_limits() {
+        return Ok(());
+    }
     let instance = &running_plugin.instance;
     let rows = running_plugin.rows;
     let columns = running_plugin.columns;
@@ -1651,8 +2264,7 @@ pub fn apply_event_to_plugin(
                 .with_context(err_context)?;
             wasi_write_object(running_plugin.store.data(), &protobuf_event.encode_to_vec())
                 .with_context(err_context)?;
-            let should_render = update
-                .call(&mut running_plugin.store, ())
+            let should_render = call_plugin_with_limits(&mut running_plugin.store, &update, ())
                 .with_context(err_context)?;
             let mut should_render = should_render == 1;
             if let Event::PermissionRequestResult(..) = event {
@@ -1664,7 +2276,11 @@ pub fn apply_event_to_plugin(
                 let rendered_bytes = instance
                     .get_typed_func::<(i32, i32), ()>(&mut running_plugin.store, "render")
                     .and_then(|render| {
-                        render.call(&mut running_plugin.store, (rows as i32, columns as i32))
+                        call_plugin_with_limits(
+                            &mut running_plugin.store,
+                            &render,
+                            (rows as i32, columns as i32),
+                        )
                     })
                     .and_then(|_| wasi_read_string(running_plugin.store.data()))
                     .with_context(err_context)?;
@@ -1712,12 +2328,126 @@ pub fn handle_plugin_crash(plugin_id: PluginId, message: String, senders: Thread
     ));
 }
 
+fn save_plugin_state(running_plugin: &mut RunningPlugin) -> Result<Option<String>> {
+    if running_plugin.exceeded_limits() {
+        return Ok(None);
+    }
+    let err_context = || format!("Failed to save plugin state");
+    let instance = &running_plugin.instance;
+    match instance.get_typed_func::<(), ()>(&mut running_plugin.store, "save_state") {
+        Ok(save_state) => {
+            call_plugin_with_limits(&mut running_plugin.store, &save_state, ())
+                .with_context(err_context)?;
+            let state = wasi_read_string(running_plugin.store.data()).with_context(err_context)?;
+            if state.trim().is_empty() {
+                // the plugin has no state to save
+                return Ok(None);
+            }
+            serde_json::from_str(&state)
+                .map(Some)
+                .with_context(err_context)
+        },
+        Err(_e) => {
+            // this is probably an old plugin that does not have this interface
+            Ok(None)
+        },
+    }
+}
+
+fn restore_plugin_state(running_plugin: &mut RunningPlugin, state: &str) -> Result<()> {
+    let err_context = || format!("Failed to restore plugin state");
+    let instance = &running_plugin.instance;
+    match instance.get_typed_func::<(), ()>(&mut running_plugin.store, "restore_state") {
+        Ok(restore_state) => {
+            wasi_write_object(running_plugin.store.data(), state).with_context(err_context)?;
+            call_plugin_with_limits(&mut running_plugin.store, &restore_state, ())
+                .with_context(err_context)?;
+            Ok(())
+        },
+        Err(_e) => {
+            // the new version of the plugin no longer implements this interface
+            Ok(())
+        },
+    }
+}
+
+// hands the states saved before reloading this plugin over to its new instances
+fn restore_plugin_states(
+    plugin_map: &Arc<Mutex<PluginMap>>,
+    plugin_id: PluginId,
+    saved_states: &HashMap<(PluginId, ClientId), String>,
+) {
+    let running_plugins = plugin_map.lock().unwrap().running_plugins();
+    for (p_id, client_id, running_plugin) in running_plugins {
+        if p_id != plugin_id {
+            continue;
+        }
+        if let Some(state) = saved_states.get(&(plugin_id, client_id)) {
+            let mut running_plugin = running_plugin.lock().unwrap();
+            if let Err(e) = restore_plugin_state(&mut running_plugin, state) {
+                log::error!("Failed to restore state of plugin {}: {:?}", plugin_id, e);
+            }
+        }
+    }
+}
+
+// hands the state a plugin saved when its session was serialized over to all its new instances
+fn restore_resurrected_plugin_state(
+    plugin_map: &Arc<Mutex<PluginMap>>,
+    plugin_id: PluginId,
+    saved_state: &str,
+) {
+    let running_plugins = plugin_map.lock().unwrap().running_plugins();
+    for (p_id, _client_id, running_plugin) in running_plugins {
+        if p_id != plugin_id {
+            continue;
+        }
+        let mut running_plugin = running_plugin.lock().unwrap();
+        if let Err(e) = restore_plugin_state(&mut running_plugin, saved_state) {
+            log::error!("Failed to restore state of plugin {}: {:?}", plugin_id, e);
+        }
+    }
+}
+
+// returns false if the plugin does not handle calls
+fn apply_plugin_call_to_plugin(
+    running_plugin: &mut RunningPlugin,
+    plugin_call: PluginCall,
+) -> Result<bool> {
+    if running_plugin.exceeded_limits() {
+        return Ok(false);
+    }
+    let plugin_call_id = plugin_call.id;
+    let err_context = || format!("Failed to apply call {} to plugin", plugin_call_id);
+    let instance = &running_plugin.instance;
+    match instance.get_typed_func::<(), ()>(&mut running_plugin.store, "handle_call") {
+        Ok(handle_call) => {
+            let protobuf_plugin_call: ProtobufPluginCall = plugin_call.into();
+            wasi_write_object(
+                running_plugin.store.data(),
+                &protobuf_plugin_call.encode_to_vec(),
+            )
+            .with_context(err_context)?;
+            call_plugin_with_limits(&mut running_plugin.store, &handle_call, ())
+                .with_context(err_context)?;
+            Ok(true)
+        },
+        Err(_e) => {
+            // this is probably an old plugin that does not have this interface
+            Ok(false)
+        },
+    }
+}
+
 pub fn apply_before_close_event_to_plugin(
     plugin_id: PluginId,
     client_id: ClientId,
     running_plugin: &mut RunningPlugin,
     senders: ThreadSenders,
 ) -> Result<()> {
+    if running_plugin.exceeded_limits() {
+        return Ok(());
+    }
     let instance = &running_plugin.instance;
 
     let err_context = || format!("Failed to apply event to plugin {plugin_id}");
@@ -1731,8 +2461,7 @@ pub fn apply_before_close_event_to_plugin(
         .with_context(err_context)?;
     wasi_write_object(running_plugin.store.data(), &protobuf_event.encode_to_vec())
         .with_context(err_context)?;
-    let _should_render = update
-        .call(&mut running_plugin.store, ())
+    let _should_render = call_plugin_with_limits(&mut running_plugin.store, &update, ())
         .with_context(err_context)?;
     let pipes_to_block_or_unblock = pipes_to_block_or_unblock(running_plugin, None);
     let plugin_render_asset =
@@ -2,17 +2,26 @@ use super::PluginInstruction;
 use std::path::PathBuf;
 
 use crate::thread_bus::ThreadSenders;
+use crate::ClientId;
 use std::path::Path;
 use std::time::Duration;
 
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use notify_debouncer_full::{
     new_debouncer,
     notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher},
     DebounceEventResult, Debouncer, FileIdMap,
 };
-use zellij_utils::{data::Event, errors::prelude::Result};
+use zellij_utils::{
+    data::{Event, FileSystemChange, WatchOptions},
+    errors::prelude::*,
+    input::layout::RunPlugin,
+};
 
 const DEBOUNCE_DURATION_MS: u64 = 400;
+// plugins can ask for a shorter debounce than the default, but not so short that a busy folder
+// floods them (and us) with events
+const MIN_WATCH_DEBOUNCE_MS: u64 = 50;
 
 pub fn watch_filesystem(
     senders: ThreadSenders,
@@ -133,3 +142,144 @@ pub fn watch_filesystem(
         .watch(zellij_cwd, RecursiveMode::Recursive)?;
     Ok(debouncer)
 }
+
+// watches paths on the host on behalf of a single plugin (see PluginCommand::WatchPaths),
+// reporting the changes only to it, without rewriting them to the plugin's /host folder
+pub fn watch_paths(
+    senders: ThreadSenders,
+    plugin_id: u32,
+    client_id: ClientId,
+    watch_id: u32,
+    paths: &[PathBuf],
+    watch_options: &WatchOptions,
+) -> Result<Debouncer<RecommendedWatcher, FileIdMap>> {
+    let include_globs = glob_set(&watch_options.include_globs)?;
+    let exclude_globs = glob_set(&watch_options.exclude_globs)?;
+    let debounce_duration = Duration::from_millis(
+        watch_options
+            .debounce_ms
+            .unwrap_or(DEBOUNCE_DURATION_MS)
+            .max(MIN_WATCH_DEBOUNCE_MS),
+    );
+    let mut debouncer = new_debouncer(
+        debounce_duration,
+        None,
+        move |result: DebounceEventResult| match result {
+            Ok(events) => {
+                let mut changes = vec![];
+                for event in events {
+                    let change = match event.kind {
+                        EventKind::Access(_) => FileSystemChange::Read,
+                        EventKind::Create(_) => FileSystemChange::Create,
+                        EventKind::Modify(_) => FileSystemChange::Update,
+                        EventKind::Remove(_) => FileSystemChange::Delete,
+                        _ => continue,
+                    };
+                    for path in &event.paths {
+                        if is_watched_path(path, &include_globs, &exclude_globs) {
+                            changes.push((path.clone(), change));
+                        }
+                    }
+                }
+                if !changes.is_empty() {
+                    let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                        Some(plugin_id),
+                        Some(client_id),
+                        Event::WatchedPathsChanged(watch_id, changes),
+                    )]));
+                }
+            },
+            Err(errors) => {
+                let error_messages: Vec<String> = errors
+                    .iter()
+                    .map(|error| {
+                        log::error!("watch error: {error:?}");
+                        error.to_string()
+                    })
+                    .collect();
+                let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                    Some(plugin_id),
+                    Some(client_id),
+                    Event::WatchPathsFailed(watch_id, error_messages.join(", ")),
+                )]));
+            },
+        },
+    )?;
+
+    let recursive_mode = if watch_options.recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    for path in paths {
+        debouncer
+            .watcher()
+            .watch(path, recursive_mode)
+            .with_context(|| format!("failed to watch {}", path.display()))?;
+    }
+    Ok(debouncer)
+}
+
+// watches the .wasm file of a plugin declared with `watch_for_changes true`, reloading all its
+// instances whenever it is rebuilt
+pub fn watch_plugin_file(
+    senders: ThreadSenders,
+    run_plugin: RunPlugin,
+    plugin_path: &Path,
+) -> Result<Debouncer<RecommendedWatcher, FileIdMap>> {
+    // the plugin path might go through symlinks, while the paths of the events we get do not
+    let plugin_path = plugin_path
+        .canonicalize()
+        .with_context(|| format!("failed to canonicalize {}", plugin_path.display()))?;
+    // we watch the containing folder rather than the file itself, because build tools often
+    // replace the file rather than modifying it in place, which would orphan the watch
+    let plugin_folder = plugin_path
+        .parent()
+        .map(|p| p.to_path_buf())
+        .with_context(|| format!("plugin path {} has no parent", plugin_path.display()))?;
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(DEBOUNCE_DURATION_MS),
+        None,
+        move |result: DebounceEventResult| match result {
+            Ok(events) => {
+                let plugin_file_changed = events.iter().any(|event| {
+                    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                        && event
+                            .paths
+                            .iter()
+                            .any(|p| p.canonicalize().map(|p| p == plugin_path).unwrap_or(false))
+                });
+                if plugin_file_changed {
+                    let _ = senders
+                        .send_to_plugin(PluginInstruction::PluginFileChanged(run_plugin.clone()));
+                }
+            },
+            Err(errors) => errors
+                .iter()
+                .for_each(|error| log::error!("watch error: {error:?}")),
+        },
+    )?;
+    debouncer
+        .watcher()
+        .watch(&plugin_folder, RecursiveMode::NonRecursive)
+        .with_context(|| format!("failed to watch {}", plugin_folder.display()))?;
+    Ok(debouncer)
+}
+
+// an empty include list means all paths are included
+fn is_watched_path(path: &Path, include_globs: &GlobSet, exclude_globs: &GlobSet) -> bool {
+    let is_included = include_globs.is_empty() || include_globs.is_match(path);
+    is_included && !exclude_globs.is_match(path)
+}
+
+fn glob_set(globs: &[String]) -> Result<GlobSet> {
+    let mut glob_set_builder = GlobSetBuilder::new();
+    for glob in globs {
+        glob_set_builder.add(Glob::new(glob).with_context(|| format!("invalid glob: {}", glob))?);
+    }
+    glob_set_builder.build().context("failed to build glob set")
+}
+
+#[path = "./unit/watch_filesystem_tests.rs"]
+#[cfg(test)]
+mod watch_filesystem_tests;
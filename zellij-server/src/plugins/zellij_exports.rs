@@ -19,14 +19,15 @@ use std::{
 };
 use wasmtime::{Caller, Linker};
 use zellij_utils::data::{
-    CommandType, ConnectToSession, FloatingPaneCoordinates, HttpVerb, KeyWithModifier, LayoutInfo,
-    MessageToPlugin, OriginatingPlugin, PermissionStatus, PermissionType, PluginPermission,
+    CommandSpec, CommandType, ConnectToSession, FloatingPaneCoordinates, HttpVerb, KeyWithModifier,
+    LayoutInfo, MessageToPlugin, OriginatingPlugin, PermissionStatus, PermissionType, PipeSource,
+    PluginPermission, ScrollbackRange, StoreScope, WatchOptions, WebTokenScope,
 };
 use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::ipc::{ClientToServerMsg, IpcSenderWithContext, IpcSocketStream};
 #[cfg(feature = "web_server_capability")]
 use zellij_utils::web_authentication_tokens::{
-    create_token, list_tokens, rename_token, revoke_all_tokens, revoke_token,
+    create_scoped_token, list_tokens, rename_token, revoke_all_tokens, revoke_token,
 };
 #[cfg(feature = "web_server_capability")]
 use zellij_utils::web_server_commands::shutdown_all_webserver_instances;
@@ -47,7 +48,7 @@ use zellij_utils::{
         layout::{Layout, RunPluginOrAlias},
     },
     plugin_api::{
-        plugin_command::ProtobufPluginCommand,
+        plugin_command::{ContextItem, ProtobufPluginCommand, StoreGetResponse, StoreListResponse},
         plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion},
     },
 };
@@ -478,8 +479,8 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     PluginCommand::SetSelfMouseSelectionSupport(selection_support) => {
                         set_self_mouse_selection_support(env, selection_support);
                     },
-                    PluginCommand::GenerateWebLoginToken(token_label) => {
-                        generate_web_login_token(env, token_label);
+                    PluginCommand::GenerateWebLoginToken(token_label, scope) => {
+                        generate_web_login_token(env, token_label, scope);
                     },
                     PluginCommand::RevokeWebLoginToken(label) => {
                         revoke_web_login_token(env, label);
@@ -505,6 +506,44 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                         pane_id_to_replace.into(),
                         existing_pane_id.into(),
                     ),
+                    PluginCommand::SearchPaneScrollback(pane_id, pattern) => {
+                        search_pane_scrollback(env, pane_id.into(), pattern)
+                    },
+                    PluginCommand::GetPaneScrollback(pane_id, range, with_styles) => {
+                        get_pane_scrollback(env, pane_id.into(), range, with_styles)
+                    },
+                    PluginCommand::StoreGet(key, scope) => store_get(env, key, scope),
+                    PluginCommand::StoreSet(key, value, scope) => store_set(env, key, value, scope),
+                    PluginCommand::StoreDelete(key, scope) => store_delete(env, key, scope),
+                    PluginCommand::StoreList(scope) => {
+                        store_list(env, env.plugin.location.display(), scope)
+                    },
+                    PluginCommand::ListPluginStore(plugin_location, scope) => {
+                        store_list(env, plugin_location, scope)
+                    },
+                    PluginCommand::ClearPluginStore(plugin_location) => {
+                        clear_plugin_store(env, plugin_location)
+                    },
+                    PluginCommand::CallPlugin {
+                        call_id,
+                        destination,
+                        method,
+                        payload,
+                        timeout,
+                    } => call_plugin(env, call_id, destination, method, payload, timeout)?,
+                    PluginCommand::RespondToPluginCall(call_id, result) => {
+                        respond_to_plugin_call(env, call_id, result)?
+                    },
+                    PluginCommand::WatchPaths(watch_id, paths, watch_options) => {
+                        watch_paths(env, watch_id, paths, watch_options)?
+                    },
+                    PluginCommand::UnwatchPaths(watch_id) => unwatch_paths(env, watch_id)?,
+                    PluginCommand::RegisterCommands(commands) => register_commands(env, commands)?,
+                    PluginCommand::ListRegisteredCommands => list_registered_commands(env)?,
+                    PluginCommand::InvokeRegisteredCommand(name, args) => {
+                        invoke_registered_command(env, name, args)?
+                    },
+                    PluginCommand::RunActions(actions) => run_actions(env, actions),
                 },
                 (PermissionStatus::Denied, permission) => {
                     log::error!(
@@ -562,12 +601,55 @@ fn message_to_plugin(env: &PluginEnv, mut message_to_plugin: MessageToPlugin) ->
         .context("failed to send message to plugin")
 }
 
+fn call_plugin(
+    env: &PluginEnv,
+    call_id: u64,
+    mut destination: String,
+    method: String,
+    payload: Option<String>,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    if destination == "zellij:OWN_URL" {
+        destination = env.plugin.location.display();
+    }
+    env.senders
+        .send_to_plugin(PluginInstruction::CallPlugin {
+            caller_plugin_id: env.plugin_id,
+            caller_client_id: env.client_id,
+            call_id,
+            destination,
+            method,
+            payload,
+            timeout,
+        })
+        .context("failed to call plugin")
+}
+
+fn respond_to_plugin_call(
+    env: &PluginEnv,
+    call_id: u64,
+    result: std::result::Result<String, String>,
+) -> Result<()> {
+    env.senders
+        .send_to_plugin(PluginInstruction::PluginCallResponse(
+            env.plugin_id,
+            call_id,
+            result,
+        ))
+        .context("failed to respond to plugin call")
+}
+
 fn unsubscribe(env: &PluginEnv, event_list: HashSet<EventType>) -> Result<()> {
     env.subscriptions
         .lock()
         .to_anyhow()?
         .retain(|k| !event_list.contains(k));
-    Ok(())
+    env.senders
+        .send_to_plugin(PluginInstruction::PluginUnsubscribedFromEvents(
+            env.plugin_id,
+            env.client_id,
+            event_list,
+        ))
 }
 
 fn set_selectable(env: &PluginEnv, selectable: bool) {
@@ -969,6 +1051,7 @@ fn open_command_pane_in_place_of_plugin(
             context,
         )),
         use_terminal_title,
+        env: BTreeMap::new(),
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
     let _ = env
@@ -1008,6 +1091,7 @@ fn open_command_pane(
             context,
         )),
         use_terminal_title,
+        env: BTreeMap::new(),
     };
     let action = Action::NewTiledPane(direction, Some(run_command_action), name);
     apply_action!(action, error_msg, env);
@@ -1039,6 +1123,7 @@ fn open_command_pane_near_plugin(
             context,
         )),
         use_terminal_title,
+        env: BTreeMap::new(),
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
     let _ = env.senders.send_to_pty(PtyInstruction::SpawnTerminal(
@@ -1078,6 +1163,7 @@ fn open_command_pane_floating(
             context,
         )),
         use_terminal_title,
+        env: BTreeMap::new(),
     };
     let action = Action::NewFloatingPane(Some(run_command_action), name, floating_pane_coordinates);
     apply_action!(action, error_msg, env);
@@ -1110,6 +1196,7 @@ fn open_command_pane_floating_near_plugin(
             context,
         )),
         use_terminal_title,
+        env: BTreeMap::new(),
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
     let _ = env.senders.send_to_pty(PtyInstruction::SpawnTerminal(
@@ -1148,6 +1235,7 @@ fn open_command_pane_in_place(
             context,
         )),
         use_terminal_title,
+        env: BTreeMap::new(),
     };
     let action = Action::NewInPlacePane(Some(run_command_action), name);
     apply_action!(action, error_msg, env);
@@ -1183,6 +1271,7 @@ fn open_command_pane_background(
             context,
         )),
         use_terminal_title,
+        env: BTreeMap::new(),
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
     let _ = env.senders.send_to_pty(PtyInstruction::SpawnTerminal(
@@ -1910,6 +1999,74 @@ fn watch_filesystem(env: &PluginEnv) {
         .map(|sender| sender.send(PluginInstruction::WatchFilesystem));
 }
 
+fn watch_paths(
+    env: &PluginEnv,
+    watch_id: u32,
+    paths: Vec<PathBuf>,
+    watch_options: WatchOptions,
+) -> Result<()> {
+    let paths = paths
+        .into_iter()
+        .map(|path| env.plugin_cwd.join(path))
+        .collect();
+    env.senders
+        .send_to_plugin(PluginInstruction::WatchPaths {
+            plugin_id: env.plugin_id,
+            client_id: env.client_id,
+            watch_id,
+            paths,
+            watch_options,
+        })
+        .context("failed to watch paths")
+}
+
+fn unwatch_paths(env: &PluginEnv, watch_id: u32) -> Result<()> {
+    env.senders
+        .send_to_plugin(PluginInstruction::UnwatchPaths {
+            plugin_id: env.plugin_id,
+            client_id: env.client_id,
+            watch_id,
+        })
+        .context("failed to unwatch paths")
+}
+
+fn register_commands(env: &PluginEnv, commands: Vec<CommandSpec>) -> Result<()> {
+    env.senders
+        .send_to_plugin(PluginInstruction::RegisterCommands(env.plugin_id, commands))
+        .context("failed to register commands")
+}
+
+fn list_registered_commands(env: &PluginEnv) -> Result<()> {
+    env.senders
+        .send_to_plugin(PluginInstruction::ListRegisteredCommands(
+            env.plugin_id,
+            env.client_id,
+        ))
+        .context("failed to list registered commands")
+}
+
+fn invoke_registered_command(
+    env: &PluginEnv,
+    name: String,
+    args: BTreeMap<String, String>,
+) -> Result<()> {
+    env.senders
+        .send_to_plugin(PluginInstruction::InvokeRegisteredCommand {
+            name,
+            args,
+            client_id: env.client_id,
+            source: PipeSource::Plugin(env.plugin_id),
+        })
+        .context("failed to invoke registered command")
+}
+
+fn run_actions(env: &PluginEnv, actions: Vec<Action>) {
+    let error_msg = || format!("Failed to run actions");
+    for action in actions {
+        apply_action!(action, error_msg, env);
+    }
+}
+
 fn dump_session_layout(env: &PluginEnv) {
     let _ = env
         .senders
@@ -2328,9 +2485,65 @@ fn embed_multiple_panes(env: &PluginEnv, pane_ids: Vec<PaneId>) {
         ));
 }
 
+fn store_get(env: &PluginEnv, key: String, scope: StoreScope) {
+    let response = match env
+        .plugin_store
+        .get(&env.plugin.location.display(), &key, scope)
+    {
+        Ok(value) => StoreGetResponse { value, error: None },
+        Err(e) => StoreGetResponse {
+            value: None,
+            error: Some(e.to_string()),
+        },
+    };
+    wasi_write_object(env, &response.encode_to_vec())
+        .with_context(|| format!("failed to get stored value for plugin {}", env.name()))
+        .non_fatal();
+}
+
+fn store_set(env: &PluginEnv, key: String, value: String, scope: StoreScope) {
+    env.plugin_store
+        .set(&env.plugin.location.display(), key, value, scope)
+        .with_context(|| format!("failed to store value for plugin {}", env.name()))
+        .non_fatal();
+}
+
+fn store_delete(env: &PluginEnv, key: String, scope: StoreScope) {
+    env.plugin_store
+        .delete(&env.plugin.location.display(), &key, scope)
+        .with_context(|| format!("failed to delete stored value for plugin {}", env.name()))
+        .non_fatal();
+}
+
+fn store_list(env: &PluginEnv, plugin_location: String, scope: StoreScope) {
+    let response = match env.plugin_store.list(&plugin_location, scope) {
+        Ok(entries) => StoreListResponse {
+            entries: entries
+                .into_iter()
+                .map(|(name, value)| ContextItem { name, value })
+                .collect(),
+            error: None,
+        },
+        Err(e) => StoreListResponse {
+            entries: vec![],
+            error: Some(e.to_string()),
+        },
+    };
+    wasi_write_object(env, &response.encode_to_vec())
+        .with_context(|| format!("failed to list plugin store of {plugin_location}"))
+        .non_fatal();
+}
+
+fn clear_plugin_store(env: &PluginEnv, plugin_location: String) {
+    env.plugin_store
+        .clear(&plugin_location)
+        .with_context(|| format!("failed to clear plugin store of {plugin_location}"))
+        .non_fatal();
+}
+
 #[cfg(feature = "web_server_capability")]
-fn generate_web_login_token(env: &PluginEnv, token_label: Option<String>) {
-    let serialized = match create_token(token_label) {
+fn generate_web_login_token(env: &PluginEnv, token_label: Option<String>, scope: WebTokenScope) {
+    let serialized = match create_scoped_token(token_label, scope) {
         Ok((token, token_label)) => CreateTokenResponse {
             token: Some(token),
             token_label: Some(token_label),
@@ -2346,7 +2559,7 @@ fn generate_web_login_token(env: &PluginEnv, token_label: Option<String>) {
 }
 
 #[cfg(not(feature = "web_server_capability"))]
-fn generate_web_login_token(env: &PluginEnv, _token_label: Option<String>) {
+fn generate_web_login_token(env: &PluginEnv, _token_label: Option<String>, _scope: WebTokenScope) {
     log::error!("This version of Zellij was compiled without the web server capabilities!");
     let empty_vec: Vec<&str> = vec![];
     let _ = wasi_write_object(env, &empty_vec);
@@ -2429,11 +2642,13 @@ fn list_web_login_tokens(env: &PluginEnv) {
             tokens: token_list.iter().map(|t| t.name.clone()).collect(),
             creation_times: token_list.iter().map(|t| t.created_at.clone()).collect(),
             error: None,
+            scopes: token_list.iter().map(|t| t.scope.clone().into()).collect(),
         },
         Err(e) => ListTokensResponse {
             tokens: vec![],
             creation_times: vec![],
             error: Some(e.to_string()),
+            scopes: vec![],
         },
     };
     let _ = wasi_write_object(env, &serialized.encode_to_vec());
@@ -2492,6 +2707,34 @@ fn replace_pane_with_existing_pane(
         ));
 }
 
+fn search_pane_scrollback(env: &PluginEnv, pane_id: PaneId, pattern: String) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::SearchPaneScrollback(
+            pane_id,
+            pattern,
+            env.plugin_id,
+            env.client_id,
+        ));
+}
+
+fn get_pane_scrollback(
+    env: &PluginEnv,
+    pane_id: PaneId,
+    range: ScrollbackRange,
+    with_styles: bool,
+) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::GetPaneScrollback(
+            pane_id,
+            range,
+            with_styles,
+            env.plugin_id,
+            env.client_id,
+        ));
+}
+
 // Custom panic handler for plugins.
 //
 // This is called when a panic occurs in a plugin. Since most panics will likely originate in the
@@ -2551,6 +2794,10 @@ fn check_command_permission(
         // there's no use to deny them anything
         return (PermissionStatus::Granted, None);
     }
+    if let PluginCommand::RunActions(..) = command {
+        // actions can do anything a keybinding can, which would sidestep all other permissions
+        return (PermissionStatus::Denied, None);
+    }
     let permission = match command {
         PluginCommand::OpenFile(..)
         | PluginCommand::OpenFileFloating(..)
@@ -2663,14 +2910,25 @@ fn check_command_permission(
         PluginCommand::UnblockCliPipeInput(..)
         | PluginCommand::BlockCliPipeInput(..)
         | PluginCommand::CliPipeOutput(..) => PermissionType::ReadCliPipes,
-        PluginCommand::MessageToPlugin(..) => PermissionType::MessageAndLaunchOtherPlugins,
-        PluginCommand::ListClients | PluginCommand::DumpSessionLayout => {
-            PermissionType::ReadApplicationState
+        PluginCommand::MessageToPlugin(..)
+        | PluginCommand::CallPlugin { .. }
+        | PluginCommand::InvokeRegisteredCommand(..) => {
+            PermissionType::MessageAndLaunchOtherPlugins
         },
+        PluginCommand::ListClients
+        | PluginCommand::DumpSessionLayout
+        | PluginCommand::ListRegisteredCommands
+        | PluginCommand::SearchPaneScrollback(..) => PermissionType::ReadApplicationState,
+        PluginCommand::GetPaneScrollback(..) => PermissionType::ReadPaneContents,
         PluginCommand::RebindKeys { .. } | PluginCommand::Reconfigure(..) => {
             PermissionType::Reconfigure
         },
-        PluginCommand::ChangeHostFolder(..) => PermissionType::FullHdAccess,
+        PluginCommand::ChangeHostFolder(..)
+        // other plugins' stores are their private files on the hard-drive
+        | PluginCommand::ListPluginStore(..)
+        | PluginCommand::ClearPluginStore(..)
+        | PluginCommand::WatchPaths(..)
+        | PluginCommand::UnwatchPaths(..) => PermissionType::FullHdAccess,
         PluginCommand::ShareCurrentSession
         | PluginCommand::StopSharingCurrentSession
         | PluginCommand::StopWebServer
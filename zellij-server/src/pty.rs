@@ -184,6 +184,7 @@ pub enum PtyInstruction {
     },
     ListClientsToPlugin(SessionLayoutMetadata, PluginId, ClientId),
     ReportPluginCwd(PluginId, PathBuf),
+    ReportTerminalCwd(u32, PathBuf), // u32 is the terminal id
     Exit,
 }
 
@@ -208,6 +209,7 @@ impl From<&PtyInstruction> for PtyContext {
             PtyInstruction::Reconfigure { .. } => PtyContext::Reconfigure,
             PtyInstruction::ListClientsToPlugin(..) => PtyContext::ListClientsToPlugin,
             PtyInstruction::ReportPluginCwd(..) => PtyContext::ReportPluginCwd,
+            PtyInstruction::ReportTerminalCwd(..) => PtyContext::ReportTerminalCwd,
             PtyInstruction::Exit => PtyContext::Exit,
         }
     }
@@ -226,6 +228,7 @@ pub(crate) struct Pty {
     default_editor: Option<PathBuf>,
     post_command_discovery_hook: Option<String>,
     plugin_cwds: HashMap<u32, PathBuf>, // plugin_id -> cwd
+    terminal_cwds: HashMap<u32, PathBuf>, // terminal_id -> cwd reported by the shell (OSC 7)
 }
 
 pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
@@ -268,6 +271,7 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                         payload.path.clone(),
                         payload.line_number.clone(),
                         payload.cwd.clone(),
+                        payload.env.clone(),
                     )),
                     _ => None,
                 };
@@ -394,6 +398,7 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                         payload.path.clone(),
                         payload.line_number.clone(),
                         payload.cwd.clone(),
+                        payload.env.clone(),
                     )),
                     _ => None,
                 };
@@ -729,6 +734,9 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
             PtyInstruction::ReportPluginCwd(plugin_id, cwd) => {
                 pty.plugin_cwds.insert(plugin_id, cwd);
             },
+            PtyInstruction::ReportTerminalCwd(terminal_id, cwd) => {
+                pty.terminal_cwds.insert(terminal_id, cwd);
+            },
             PtyInstruction::LogLayoutToHd(mut session_layout_metadata) => {
                 let err_context = || format!("Failed to dump layout");
                 pty.populate_session_layout_metadata(&mut session_layout_metadata);
@@ -810,6 +818,7 @@ impl Pty {
             originating_plugins: HashMap::new(),
             post_command_discovery_hook,
             plugin_cwds: HashMap::new(),
+            terminal_cwds: HashMap::new(),
         }
     }
     pub fn get_default_terminal(
@@ -851,6 +860,37 @@ impl Pty {
             },
         }
     }
+    #[cfg(unix)]
+    fn terminal_cwd(&self, terminal_id: u32) -> Option<PathBuf> {
+        // the cwd reported by the shell itself is preferred, because the cwd of the child process
+        // is not the one the user sees when eg. running ssh, a container or a nested shell
+        self.terminal_cwds
+            .get(&terminal_id)
+            .filter(|cwd| cwd.is_dir())
+            .cloned()
+            .or_else(|| {
+                self.id_to_child_pid.get(&terminal_id).and_then(|&id| {
+                    self.bus
+                        .os_input
+                        .as_ref()
+                        .and_then(|input| input.get_cwd(Pid::from_raw(id)))
+                })
+            })
+    }
+    #[cfg(windows)]
+    fn terminal_cwd(&self, terminal_id: u32) -> Option<PathBuf> {
+        self.terminal_cwds
+            .get(&terminal_id)
+            .filter(|cwd| cwd.is_dir())
+            .cloned()
+            .or_else(|| {
+                self.id_to_child_pid.get(&terminal_id).and_then(|winpty| {
+                    self.bus.os_input.as_ref().and_then(|input| {
+                        input.get_cwd(Pid::from(winpty.pty.read().unwrap().get_pid() as usize))
+                    })
+                })
+            })
+    }
     fn fill_cwd(&self, terminal_action: &mut TerminalAction, client_id: ClientId) {
         if let TerminalAction::RunCommand(run_command) = terminal_action {
             if run_command.cwd.is_none() {
@@ -859,24 +899,7 @@ impl Pty {
                     .get(&client_id)
                     .and_then(|pane| match pane {
                         PaneId::Plugin(..) => None,
-                        PaneId::Terminal(id) => self.id_to_child_pid.get(id),
-                    })
-                    .and_then(|winptyreference| {
-                        #[cfg(windows)]
-                        {
-                            let pty = winptyreference.pty.read().unwrap();
-                            self.bus.os_input.as_ref().and_then(|input| {
-                                input.get_cwd((pty.get_pid() as usize).into())
-                            })
-                        }
-                        #[cfg(unix)]
-                        {
-                            let pid = Pid::from_raw(id);
-                            self.bus
-                                .os_input
-                                .as_ref()
-                                .and_then(|input| input.get_cwd(pid))
-                        }
+                        PaneId::Terminal(id) => self.terminal_cwd(*id),
                     });
             };
         };
@@ -886,14 +909,7 @@ impl Pty {
         if let TerminalAction::RunCommand(run_command) = terminal_action {
             if run_command.cwd.is_none() {
                 run_command.cwd = match pane_id {
-                    PaneId::Terminal(terminal_pane_id) => {
-                        self.id_to_child_pid.get(terminal_pane_id).and_then(|&id| {
-                            self.bus
-                                .os_input
-                                .as_ref()
-                                .and_then(|input| input.get_cwd(Pid::from_raw(id)))
-                        })
-                    },
+                    PaneId::Terminal(terminal_pane_id) => self.terminal_cwd(*terminal_pane_id),
                     PaneId::Plugin(plugin_id) => self.plugin_cwds.get(plugin_id).cloned(),
                 };
             };
@@ -904,11 +920,7 @@ impl Pty {
 
         if let TerminalAction::RunCommand(run_command) = terminal_action {
             if run_command.cwd.is_none() {
-                run_command.cwd = self.id_to_child_pid.get(pane_id).and_then(|winpty| {
-                    self.bus.os_input.as_ref().and_then(|input| {
-                        input.get_cwd(Pid::from(winpty.pty.read().unwrap().get_pid() as usize))
-                    })
-                });
+                run_command.cwd = self.terminal_cwd(*pane_id);
             };
         };
     }
@@ -1560,7 +1572,35 @@ impl Pty {
                 #[cfg(windows)]
                 todo!()
             },
-            Some(Run::EditFile(path_to_file, line_number, cwd)) => {
+            Some(Run::Shell(cwd, env)) => {
+                let starts_held = false; // we do not hold shell panes
+                let mut shell = self.get_default_terminal(cwd, Some(default_shell.clone()));
+                shell.add_env(&env);
+                #[cfg(unix)]
+                match self
+                    .bus
+                    .os_input
+                    .as_mut()
+                    .context("no OS I/O interface found")
+                    .with_context(err_context)?
+                    .spawn_terminal(shell, quit_cb, self.default_editor.clone())
+                    .with_context(err_context)
+                {
+                    Ok((terminal_id, pid_primary, child_fd)) => {
+                        self.id_to_child_pid.insert(terminal_id, child_fd);
+                        Ok(Some((terminal_id, starts_held, None, Ok(pid_primary))))
+                    },
+                    Err(err) => match err.downcast_ref::<ZellijError>() {
+                        Some(ZellijError::CommandNotFound { terminal_id, .. }) => {
+                            Ok(Some((*terminal_id, starts_held, None, Err(err))))
+                        },
+                        _ => Err(err),
+                    },
+                }
+                #[cfg(windows)]
+                todo!()
+            },
+            Some(Run::EditFile(path_to_file, line_number, cwd, env)) => {
                 let starts_held = false; // we do not hold edit panes (for now?)
                 #[cfg(unix)]
                 match self
@@ -1570,11 +1610,9 @@ impl Pty {
                     .context("no OS I/O interface found")
                     .with_context(err_context)?
                     .spawn_terminal(
-                        TerminalAction::OpenFile(OpenFilePayload::new(
-                            path_to_file,
-                            line_number,
-                            cwd,
-                        )),
+                        TerminalAction::OpenFile(
+                            OpenFilePayload::new(path_to_file, line_number, cwd).with_env(env),
+                        ),
                         quit_cb,
                         self.default_editor.clone(),
                     )
@@ -1770,7 +1808,35 @@ impl Pty {
                 #[cfg(windows)]
                 todo!()
             },
-            Some(Run::EditFile(path_to_file, line_number, cwd)) => {
+            Some(Run::Shell(cwd, env)) => {
+                let starts_held = false; // we do not hold shell panes
+                let mut shell = self.get_default_terminal(cwd, Some(default_shell.clone()));
+                shell.add_env(&env);
+                #[cfg(unix)]
+                match self
+                    .bus
+                    .os_input
+                    .as_mut()
+                    .context("no OS I/O interface found")
+                    .with_context(err_context)?
+                    .spawn_terminal(shell, quit_cb, self.default_editor.clone())
+                    .with_context(err_context)
+                {
+                    Ok((terminal_id, pid_primary, child_fd)) => {
+                        self.id_to_child_pid.insert(terminal_id, child_fd);
+                        Ok(Some((terminal_id, starts_held, None, Ok(pid_primary))))
+                    },
+                    Err(err) => match err.downcast_ref::<ZellijError>() {
+                        Some(ZellijError::CommandNotFound { terminal_id, .. }) => {
+                            Ok(Some((*terminal_id, starts_held, None, Err(err))))
+                        },
+                        _ => Err(err),
+                    },
+                }
+                #[cfg(windows)]
+                todo!()
+            },
+            Some(Run::EditFile(path_to_file, line_number, cwd, env)) => {
                 let starts_held = false; // we do not hold edit panes (for now?)
                 #[cfg(unix)]
                 match self
@@ -1861,6 +1927,7 @@ impl Pty {
         match id {
             PaneId::Terminal(id) => {
                 self.task_handles.remove(&id);
+                self.terminal_cwds.remove(&id);
                 if let Some(child_fd) = self.id_to_child_pid.remove(&id) {
                     task::block_on(async {
                         let err_context = || format!("failed to run async task for pane {id}");
@@ -1896,6 +1963,7 @@ impl Pty {
         match id {
             PaneId::Terminal(id) => {
                 self.task_handles.remove(&id);
+                self.terminal_cwds.remove(&id);
                 if let Some(child_fd) = self.id_to_child_pid.remove(&id) {
                     task::block_on(async {
                         let err_context = || format!("failed to run async task for pane {id}");
@@ -2068,9 +2136,15 @@ impl Pty {
 
         for terminal_id in terminal_ids {
             let process_id = self.id_to_child_pid.get(&terminal_id);
-            let cwd = process_id
-                .as_ref()
-                .and_then(|pid| pids_to_cwds.get(&Self::child_to_pid(*pid)));
+            let cwd = self
+                .terminal_cwds
+                .get(&terminal_id)
+                .filter(|cwd| cwd.is_dir())
+                .or_else(|| {
+                    process_id
+                        .as_ref()
+                        .and_then(|pid| pids_to_cwds.get(&Self::child_to_pid(*pid)))
+                });
             let cmd_sysinfo = process_id
                 .as_ref()
                 .and_then(|pid| pids_to_cmds.get(&Self::child_to_pid(*pid)));
@@ -2113,12 +2187,7 @@ impl Pty {
                 .get(&client_id)
                 .and_then(|pane| match pane {
                     PaneId::Plugin(plugin_id) => self.plugin_cwds.get(plugin_id).cloned(),
-                    PaneId::Terminal(id) => self.id_to_child_pid.get(id).and_then(|&id| {
-                        self.bus
-                            .os_input
-                            .as_ref()
-                            .and_then(|input| input.get_cwd(Pid::from_raw(id)))
-                    }),
+                    PaneId::Terminal(id) => self.terminal_cwd(*id),
                 })
         };
 
@@ -6,7 +6,7 @@ use crate::{
     os_input_output::ServerOsApi,
     panes::PaneId,
     plugins::PluginInstruction,
-    pty::{ClientTabIndexOrPaneId, NewPanePlacement, PtyInstruction},
+    pty::{get_default_shell, ClientTabIndexOrPaneId, NewPanePlacement, PtyInstruction},
     screen::ScreenInstruction,
     ServerInstruction, SessionMetaData, SessionState,
 };
@@ -15,11 +15,11 @@ use std::time::Duration;
 use uuid::Uuid;
 use zellij_utils::{
     channels::SenderWithContext,
-    data::{Direction, Event, InputMode, PluginCapabilities, ResizeStrategy},
+    data::{Direction, Event, InputMode, PipeSource, PluginCapabilities, ResizeStrategy},
     errors::prelude::*,
     input::{
         actions::{Action, SearchDirection, SearchOption},
-        command::TerminalAction,
+        command::{RunCommand, RunCommandAction, TerminalAction},
         get_mode_info,
         keybinds::Keybinds,
         layout::Layout,
@@ -181,6 +181,23 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::DumpScreen(val, client_id, full))
                 .with_context(err_context)?;
         },
+        Action::StartRecording(path, pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::StartRecording(
+                    path,
+                    pane_id.map(|p| p.into()),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::StopRecording(pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::StopRecording(
+                    pane_id.map(|p| p.into()),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::DumpLayout => {
             let default_shell = match default_shell {
                 Some(TerminalAction::RunCommand(run_command)) => Some(run_command.command),
@@ -225,6 +242,26 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::ScrollToTop(client_id))
                 .with_context(err_context)?;
         },
+        Action::ScrollToPreviousPrompt => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollToPreviousPrompt(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ScrollToNextPrompt => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollToNextPrompt(client_id))
+                .with_context(err_context)?;
+        },
+        Action::CopyLastCommandOutput => {
+            senders
+                .send_to_screen(ScreenInstruction::CopyLastCommandOutput(client_id))
+                .with_context(err_context)?;
+        },
+        Action::EditLastCommandOutput => {
+            senders
+                .send_to_screen(ScreenInstruction::EditLastCommandOutput(client_id))
+                .with_context(err_context)?;
+        },
         Action::PageScrollUp => {
             senders
                 .send_to_screen(ScreenInstruction::PageScrollUp(client_id))
@@ -340,9 +377,7 @@ pub(crate) fn route_action(
                 .with_context(err_context)?;
         },
         Action::NewFloatingPane(run_command, name, floating_pane_coordinates) => {
-            let run_cmd = run_command
-                .map(|cmd| TerminalAction::RunCommand(cmd.into()))
-                .or_else(|| default_shell.clone());
+            let run_cmd = command_or_default_shell(run_command, &default_shell);
             senders
                 .send_to_pty(PtyInstruction::SpawnTerminal(
                     run_cmd,
@@ -354,9 +389,7 @@ pub(crate) fn route_action(
                 .with_context(err_context)?;
         },
         Action::NewInPlacePane(run_command, name) => {
-            let run_cmd = run_command
-                .map(|cmd| TerminalAction::RunCommand(cmd.into()))
-                .or_else(|| default_shell.clone());
+            let run_cmd = command_or_default_shell(run_command, &default_shell);
             match pane_id {
                 Some(pane_id) => {
                     senders
@@ -381,9 +414,7 @@ pub(crate) fn route_action(
             }
         },
         Action::NewStackedPane(run_command, name) => {
-            let run_cmd = run_command
-                .map(|cmd| TerminalAction::RunCommand(cmd.into()))
-                .or_else(|| default_shell.clone());
+            let run_cmd = command_or_default_shell(run_command, &default_shell);
             match pane_id {
                 Some(pane_id) => {
                     senders
@@ -410,9 +441,7 @@ pub(crate) fn route_action(
             }
         },
         Action::NewTiledPane(direction, run_command, name) => {
-            let run_cmd = run_command
-                .map(|cmd| TerminalAction::RunCommand(cmd.into()))
-                .or_else(|| default_shell.clone());
+            let run_cmd = command_or_default_shell(run_command, &default_shell);
             let _ = senders.send_to_pty(PtyInstruction::SpawnTerminal(
                 run_cmd,
                 name,
@@ -610,11 +639,22 @@ pub(crate) fn route_action(
                 },
                 SearchOption::WholeWord => ScreenInstruction::SearchToggleWholeWord(client_id),
                 SearchOption::Wrap => ScreenInstruction::SearchToggleWrap(client_id),
+                SearchOption::Regex => ScreenInstruction::SearchToggleRegex(client_id),
             };
             senders
                 .send_to_screen(instruction)
                 .with_context(err_context)?;
         },
+        Action::ListSearchMatches => {
+            senders
+                .send_to_screen(ScreenInstruction::ListSearchMatches(client_id))
+                .with_context(err_context)?;
+        },
+        Action::SearchJumpToMatch(index) => {
+            senders
+                .send_to_screen(ScreenInstruction::SearchJumpToMatch(index, client_id))
+                .with_context(err_context)?;
+        },
         Action::ToggleMouseMode => {}, // Handled client side
         Action::PreviousSwapLayout => {
             senders
@@ -883,6 +923,24 @@ pub(crate) fn route_action(
                 log::error!("Message must have a name");
             }
         },
+        Action::PluginCommand {
+            name,
+            args,
+            pipe_id,
+        } => {
+            let source = match pipe_id {
+                Some(pipe_id) => PipeSource::Cli(pipe_id),
+                None => PipeSource::Keybind,
+            };
+            senders
+                .send_to_plugin(PluginInstruction::InvokeRegisteredCommand {
+                    name,
+                    args,
+                    client_id,
+                    source,
+                })
+                .with_context(err_context)?;
+        },
         Action::ListClients => {
             let default_shell = match default_shell {
                 Some(TerminalAction::RunCommand(run_command)) => Some(run_command.command),
@@ -946,6 +1004,29 @@ macro_rules! send_to_screen_or_retry_queue {
     }};
 }
 
+fn command_or_default_shell(
+    run_command: Option<RunCommandAction>,
+    default_shell: &Option<TerminalAction>,
+) -> Option<TerminalAction> {
+    match run_command {
+        // a run command without a command (eg. `new-pane --env KEY=VALUE`) only carries the env
+        // variables the default shell should be spawned with
+        Some(run_command) if run_command.command.as_os_str().is_empty() => {
+            let mut shell = default_shell.clone().unwrap_or_else(|| {
+                TerminalAction::RunCommand(RunCommand {
+                    command: get_default_shell(),
+                    use_terminal_title: true,
+                    ..Default::default()
+                })
+            });
+            shell.add_env(&run_command.env);
+            Some(shell)
+        },
+        Some(run_command) => Some(TerminalAction::RunCommand(run_command.into())),
+        None => default_shell.clone(),
+    }
+}
+
 pub(crate) fn route_thread_main(
     session_data: Arc<RwLock<Option<SessionMetaData>>>,
     session_state: Arc<RwLock<SessionState>>,
@@ -972,6 +1053,11 @@ pub(crate) fn route_thread_main(
                     match instruction {
                         ClientToServerMsg::Key(key, raw_bytes, is_kitty_keyboard_protocol) => {
                             if let Some(rlocked_sessions) = rlocked_sessions.as_ref() {
+                                let is_read_only = session_state
+                                    .read()
+                                    .to_anyhow()
+                                    .with_context(err_context)?
+                                    .is_read_only_client(&client_id);
                                 match rlocked_sessions.get_client_keybinds_and_mode(&client_id) {
                                     Some((keybinds, input_mode, default_input_mode)) => {
                                         for action in keybinds
@@ -982,6 +1068,10 @@ pub(crate) fn route_thread_main(
                                                 default_input_mode,
                                                 is_kitty_keyboard_protocol,
                                             )
+                                            .into_iter()
+                                            .filter(|action| {
+                                                !is_read_only || action.is_viewer_local()
+                                            })
                                         {
                                             if route_action(
                                                 action,
@@ -1013,6 +1103,19 @@ pub(crate) fn route_thread_main(
                             }
                         },
                         ClientToServerMsg::Action(action, maybe_pane_id, maybe_client_id) => {
+                            let is_read_only = session_state
+                                .read()
+                                .to_anyhow()
+                                .with_context(err_context)?
+                                .is_read_only_client(&client_id);
+                            if is_read_only && !action.is_viewer_local() {
+                                log::warn!(
+                                    "Dropping action {:?} from read-only client {}",
+                                    action,
+                                    client_id
+                                );
+                                return Ok(should_break);
+                            }
                             let client_id = maybe_client_id.unwrap_or(client_id);
                             if let Some(rlocked_sessions) = rlocked_sessions.as_ref() {
                                 if route_action(
@@ -1047,6 +1150,14 @@ pub(crate) fn route_thread_main(
                                 .to_anyhow()
                                 .with_context(err_context)?
                                 .set_client_size(client_id, new_size);
+                            rlocked_sessions
+                                .as_ref()
+                                .context("couldn't get reference to read-locked session")?
+                                .senders
+                                .send_to_screen(ScreenInstruction::ClientTerminalResize(
+                                    client_id, new_size,
+                                ))
+                                .with_context(err_context)?;
                             session_state
                                 .read()
                                 .to_anyhow()
@@ -1104,6 +1215,24 @@ pub(crate) fn route_thread_main(
                             )
                             .with_context(err_context)?;
                         },
+                        ClientToServerMsg::TerminalSupportsKittyGraphics => {
+                            send_to_screen_or_retry_queue!(
+                                rlocked_sessions,
+                                ScreenInstruction::TerminalSupportsKittyGraphics(client_id),
+                                instruction,
+                                retry_queue
+                            )
+                            .with_context(err_context)?;
+                        },
+                        ClientToServerMsg::EnableDiffRendering => {
+                            send_to_screen_or_retry_queue!(
+                                rlocked_sessions,
+                                ScreenInstruction::EnableDiffRendering(client_id),
+                                instruction,
+                                retry_queue
+                            )
+                            .with_context(err_context)?;
+                        },
                         ClientToServerMsg::NewClient(
                             client_attributes,
                             cli_args,
@@ -1138,6 +1267,7 @@ pub(crate) fn route_thread_main(
                             tab_position_to_focus,
                             pane_id_to_focus,
                             is_web_client,
+                            is_read_only,
                         ) => {
                             let allow_web_connections = rlocked_sessions
                                 .as_ref()
@@ -1154,6 +1284,7 @@ pub(crate) fn route_thread_main(
                                     tab_position_to_focus,
                                     pane_id_to_focus,
                                     is_web_client,
+                                    is_read_only,
                                     client_id,
                                 );
                                 to_server
@@ -1240,3 +1371,7 @@ pub(crate) fn route_thread_main(
     }
     Ok(())
 }
+
+#[path = "./unit/route_tests.rs"]
+#[cfg(test)]
+mod route_tests;
@@ -11,7 +11,7 @@ use std::time::Duration;
 use log::{debug, warn};
 use zellij_utils::data::{
     Direction, FloatingPaneCoordinates, KeyWithModifier, PaneManifest, PluginPermission, Resize,
-    ResizeStrategy, SessionInfo, Styling, WebSharing,
+    ResizeStrategy, ScrollbackRange, SessionInfo, Styling, WebSharing,
 };
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
@@ -22,6 +22,7 @@ use zellij_utils::input::options::Clipboard;
 use zellij_utils::pane_size::{Size, SizeInPixels};
 use zellij_utils::shared::clean_string_from_control_and_linebreak;
 use zellij_utils::{
+    asciicast::AsciicastWriter,
     consts::{session_info_folder_for_session, ZELLIJ_SOCK_DIR},
     envs::set_session_name,
     input::command::TerminalAction,
@@ -41,12 +42,13 @@ use crate::panes::terminal_pane::{BRACKETED_PASTE_BEGIN, BRACKETED_PASTE_END};
 use crate::session_layout_metadata::{PaneLayoutMetadata, SessionLayoutMetadata};
 
 use crate::{
-    output::Output,
+    output::{Output, RenderDiffState},
+    panes::kitty_graphics::KittyGraphicsOutputState,
     panes::sixel::SixelImageStore,
     panes::PaneId,
     plugins::{PluginId, PluginInstruction, PluginRenderAsset},
     pty::{get_default_shell, ClientTabIndexOrPaneId, NewPanePlacement, PtyInstruction, VteBytes},
-    tab::{SuppressedPanes, Tab},
+    tab::{Pane, SuppressedPanes, Tab, ViewerScroll},
     thread_bus::Bus,
     ui::{
         loading_indication::LoadingIndication,
@@ -58,7 +60,7 @@ use zellij_utils::{
     data::{Event, InputMode, ModeInfo, Palette, PaletteColor, PluginCapabilities, Style, TabInfo},
     errors::{ContextType, ScreenContext},
     input::get_mode_info,
-    ipc::{ClientAttributes, PixelDimensions, ServerToClientMsg},
+    ipc::{ClientAttributes, PixelDimensions, RenderDiff, ServerToClientMsg},
 };
 
 /// Get the active tab and call a closure on it
@@ -184,6 +186,8 @@ pub enum ScreenInstruction {
     Exit,
     ClearScreen(ClientId),
     DumpScreen(String, ClientId, bool),
+    StartRecording(PathBuf, Option<PaneId>, ClientId),
+    StopRecording(Option<PaneId>, ClientId),
     DumpLayout(Option<PathBuf>, ClientId), // PathBuf is the default configured
     // shell
     DumpLayoutToPlugin(PluginId),
@@ -194,6 +198,10 @@ pub enum ScreenInstruction {
     ScrollDownAt(Position, ClientId),
     ScrollToBottom(ClientId),
     ScrollToTop(ClientId),
+    ScrollToPreviousPrompt(ClientId),
+    ScrollToNextPrompt(ClientId),
+    CopyLastCommandOutput(ClientId),
+    EditLastCommandOutput(ClientId),
     PageScrollUp(ClientId),
     PageScrollDown(ClientId),
     HalfPageScrollUp(ClientId),
@@ -245,10 +253,13 @@ pub enum ScreenInstruction {
     MoveTabLeft(ClientId),
     MoveTabRight(ClientId),
     TerminalResize(Size),
+    ClientTerminalResize(ClientId, Size),
     TerminalPixelDimensions(PixelDimensions),
     TerminalBackgroundColor(String),
     TerminalForegroundColor(String),
     TerminalColorRegisters(Vec<(usize, String)>),
+    TerminalSupportsKittyGraphics(ClientId),
+    EnableDiffRendering(ClientId),
     ChangeMode(ModeInfo, ClientId),
     ChangeModeForAllClients(ModeInfo),
     MouseEvent(MouseEvent, ClientId),
@@ -256,6 +267,7 @@ pub enum ScreenInstruction {
     AddClient(
         ClientId,
         bool,                // is_web_client
+        bool,                // is_read_only
         Option<usize>,       // tab position to focus
         Option<(u32, bool)>, // (pane_id, is_plugin) => pane_id to focus
     ),
@@ -270,6 +282,11 @@ pub enum ScreenInstruction {
     SearchToggleCaseSensitivity(ClientId),
     SearchToggleWholeWord(ClientId),
     SearchToggleWrap(ClientId),
+    SearchToggleRegex(ClientId),
+    ListSearchMatches(ClientId),
+    SearchJumpToMatch(usize, ClientId),
+    SearchPaneScrollback(PaneId, String, PluginId, ClientId), // String -> regex pattern
+    GetPaneScrollback(PaneId, ScrollbackRange, bool, PluginId, ClientId), // bool -> with styles
     AddRedPaneFrameColorOverride(Vec<PaneId>, Option<String>), // Option<String> => optional error text
     ClearPaneFrameColorOverride(Vec<PaneId>),
     PreviousSwapLayout(ClientId),
@@ -375,6 +392,7 @@ pub enum ScreenInstruction {
         stacked_resize: bool,
         default_editor: Option<PathBuf>,
         advanced_mouse_actions: bool,
+        forward_pane_notifications: bool,
     },
     RerunCommandPane(u32), // u32 - terminal pane id
     ResizePaneWithId(ResizeStrategy, PaneId),
@@ -423,6 +441,7 @@ pub enum ScreenInstruction {
     InterceptKeyPresses(PluginId, ClientId),
     ClearKeyPressesIntercepts(ClientId),
     ReplacePaneWithExistingPane(PaneId, PaneId),
+    SetReportPaneContentChanges(bool), // whether any plugin is subscribed to PaneContentChanged
 }
 
 impl From<&ScreenInstruction> for ScreenContext {
@@ -487,6 +506,8 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::Exit => ScreenContext::Exit,
             ScreenInstruction::ClearScreen(..) => ScreenContext::ClearScreen,
             ScreenInstruction::DumpScreen(..) => ScreenContext::DumpScreen,
+            ScreenInstruction::StartRecording(..) => ScreenContext::StartRecording,
+            ScreenInstruction::StopRecording(..) => ScreenContext::StopRecording,
             ScreenInstruction::DumpLayout(..) => ScreenContext::DumpLayout,
             ScreenInstruction::DumpLayoutToPlugin(..) => ScreenContext::DumpLayoutToPlugin,
             ScreenInstruction::EditScrollback(..) => ScreenContext::EditScrollback,
@@ -494,6 +515,10 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ScrollDown(..) => ScreenContext::ScrollDown,
             ScreenInstruction::ScrollToBottom(..) => ScreenContext::ScrollToBottom,
             ScreenInstruction::ScrollToTop(..) => ScreenContext::ScrollToTop,
+            ScreenInstruction::ScrollToPreviousPrompt(..) => ScreenContext::ScrollToPreviousPrompt,
+            ScreenInstruction::ScrollToNextPrompt(..) => ScreenContext::ScrollToNextPrompt,
+            ScreenInstruction::CopyLastCommandOutput(..) => ScreenContext::CopyLastCommandOutput,
+            ScreenInstruction::EditLastCommandOutput(..) => ScreenContext::EditLastCommandOutput,
             ScreenInstruction::PageScrollUp(..) => ScreenContext::PageScrollUp,
             ScreenInstruction::PageScrollDown(..) => ScreenContext::PageScrollDown,
             ScreenInstruction::HalfPageScrollUp(..) => ScreenContext::HalfPageScrollUp,
@@ -521,6 +546,7 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::MoveTabLeft(..) => ScreenContext::MoveTabLeft,
             ScreenInstruction::MoveTabRight(..) => ScreenContext::MoveTabRight,
             ScreenInstruction::TerminalResize(..) => ScreenContext::TerminalResize,
+            ScreenInstruction::ClientTerminalResize(..) => ScreenContext::ClientTerminalResize,
             ScreenInstruction::TerminalPixelDimensions(..) => {
                 ScreenContext::TerminalPixelDimensions
             },
@@ -531,6 +557,10 @@ impl From<&ScreenInstruction> for ScreenContext {
                 ScreenContext::TerminalForegroundColor
             },
             ScreenInstruction::TerminalColorRegisters(..) => ScreenContext::TerminalColorRegisters,
+            ScreenInstruction::TerminalSupportsKittyGraphics(..) => {
+                ScreenContext::TerminalSupportsKittyGraphics
+            },
+            ScreenInstruction::EnableDiffRendering(..) => ScreenContext::EnableDiffRendering,
             ScreenInstruction::ChangeMode(..) => ScreenContext::ChangeMode,
             ScreenInstruction::ChangeModeForAllClients(..) => {
                 ScreenContext::ChangeModeForAllClients
@@ -555,6 +585,11 @@ impl From<&ScreenInstruction> for ScreenContext {
             },
             ScreenInstruction::SearchToggleWholeWord(..) => ScreenContext::SearchToggleWholeWord,
             ScreenInstruction::SearchToggleWrap(..) => ScreenContext::SearchToggleWrap,
+            ScreenInstruction::SearchToggleRegex(..) => ScreenContext::SearchToggleRegex,
+            ScreenInstruction::ListSearchMatches(..) => ScreenContext::ListSearchMatches,
+            ScreenInstruction::SearchJumpToMatch(..) => ScreenContext::SearchJumpToMatch,
+            ScreenInstruction::SearchPaneScrollback(..) => ScreenContext::SearchPaneScrollback,
+            ScreenInstruction::GetPaneScrollback(..) => ScreenContext::GetPaneScrollback,
             ScreenInstruction::AddRedPaneFrameColorOverride(..) => {
                 ScreenContext::AddRedPaneFrameColorOverride
             },
@@ -660,6 +695,9 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ReplacePaneWithExistingPane(..) => {
                 ScreenContext::ReplacePaneWithExistingPane
             },
+            ScreenInstruction::SetReportPaneContentChanges(..) => {
+                ScreenContext::SetReportPaneContentChanges
+            },
         }
     }
 }
@@ -709,11 +747,19 @@ pub(crate) struct Screen {
     character_cell_size: Rc<RefCell<Option<SizeInPixels>>>,
     stacked_resize: Rc<RefCell<bool>>,
     sixel_image_store: Rc<RefCell<SixelImageStore>>,
+    kitty_graphics_output_state: Rc<RefCell<KittyGraphicsOutputState>>,
+    render_diff_state: RenderDiffState,
+    session_recordings: HashMap<ClientId, AsciicastWriter>,
+    client_sizes: HashMap<ClientId, Size>, // the terminal size of each client, which can be larger than the screen
+    pane_recordings: HashMap<u32, AsciicastWriter>, // u32 -> terminal id
+    report_pane_content_changes: bool,
     /// The overlay that is drawn on top of [`Pane`]'s', [`Tab`]'s and the [`Screen`]
     overlay: OverlayWindow,
     terminal_emulator_colors: Rc<RefCell<Palette>>,
     terminal_emulator_color_codes: Rc<RefCell<HashMap<usize, String>>>,
     connected_clients: Rc<RefCell<HashMap<ClientId, bool>>>, // bool -> is_web_client
+    /// Clients attached as spectators, their input is not sent to the session
+    read_only_clients: HashSet<ClientId>,
     /// The indices of this [`Screen`]'s active [`Tab`]s.
     active_tab_indices: BTreeMap<ClientId, usize>,
     tab_history: BTreeMap<ClientId, Vec<usize>>,
@@ -745,6 +791,7 @@ pub(crate) struct Screen {
     web_sharing: WebSharing,
     current_pane_group: Rc<RefCell<PaneGroups>>,
     advanced_mouse_actions: bool,
+    forward_pane_notifications: bool,
     currently_marking_pane_group: Rc<RefCell<HashMap<ClientId, bool>>>,
     // the below are the configured values - the ones that will be set if and when the web server
     // is brought online
@@ -779,6 +826,7 @@ impl Screen {
         web_clients_allowed: bool,
         web_sharing: WebSharing,
         advanced_mouse_actions: bool,
+        forward_pane_notifications: bool,
         web_server_ip: IpAddr,
         web_server_port: u16,
     ) -> Self {
@@ -796,8 +844,15 @@ impl Screen {
             character_cell_size: Rc::new(RefCell::new(None)),
             stacked_resize: Rc::new(RefCell::new(stacked_resize)),
             sixel_image_store: Rc::new(RefCell::new(SixelImageStore::default())),
+            kitty_graphics_output_state: Rc::new(RefCell::new(KittyGraphicsOutputState::default())),
+            render_diff_state: RenderDiffState::default(),
+            session_recordings: HashMap::new(),
+            client_sizes: HashMap::new(),
+            pane_recordings: HashMap::new(),
+            report_pane_content_changes: false,
             style: client_attributes.style,
             connected_clients: Rc::new(RefCell::new(HashMap::new())),
+            read_only_clients: HashSet::new(),
             active_tab_indices: BTreeMap::new(),
             tabs: BTreeMap::new(),
             overlay: OverlayWindow::default(),
@@ -830,6 +885,7 @@ impl Screen {
             current_pane_group: Rc::new(RefCell::new(current_pane_group)),
             currently_marking_pane_group: Rc::new(RefCell::new(HashMap::new())),
             advanced_mouse_actions,
+            forward_pane_notifications,
             web_server_ip,
             web_server_port,
         }
@@ -1155,6 +1211,7 @@ impl Screen {
         for suppressed_pane_id in suppressed_panes.keys() {
             pane_ids.retain(|p| p != suppressed_pane_id);
         }
+        tab_to_close.remove_persisted_scrollbacks();
 
         let _ = self.bus.senders.send_to_plugin(PluginInstruction::Update(
             pane_ids
@@ -1173,6 +1230,7 @@ impl Screen {
             .with_context(err_context)?;
         if self.tabs.is_empty() {
             self.active_tab_indices.clear();
+            self.retain_only_existing_pane_recordings();
             self.bus
                 .senders
                 .send_to_server(ServerInstruction::Render(None))
@@ -1183,6 +1241,7 @@ impl Screen {
                 .with_context(err_context)?;
             self.move_suppressed_panes_from_closed_tab(suppressed_panes)
                 .with_context(err_context)?;
+            self.retain_only_existing_pane_recordings();
             let visible_tab_indices: HashSet<usize> =
                 self.active_tab_indices.values().copied().collect();
             for t in self.tabs.values_mut() {
@@ -1309,6 +1368,7 @@ impl Screen {
 
         let mut output = Output::new(
             self.sixel_image_store.clone(),
+            self.kitty_graphics_output_state.clone(),
             self.character_cell_size.clone(),
             self.styled_underlines,
         );
@@ -1327,12 +1387,25 @@ impl Screen {
                 .non_fatal();
         }
         if output.is_dirty() {
+            // clients that opted into diff rendering are taken out of the output here, so that
+            // they are not also sent the full render below
+            let render_diffs = output
+                .serialize_render_diffs(&mut self.render_diff_state)
+                .context(err_context)?;
             let serialized_output = output.serialize().context(err_context)?;
+            self.record_render(&serialized_output, &render_diffs);
             let _ = self
                 .bus
                 .senders
                 .send_to_server(ServerInstruction::Render(Some(serialized_output)))
                 .context(err_context);
+            if !render_diffs.is_empty() {
+                let _ = self
+                    .bus
+                    .senders
+                    .send_to_server(ServerInstruction::RenderDiff(render_diffs))
+                    .context(err_context);
+            }
         }
         Ok(())
     }
@@ -1367,6 +1440,116 @@ impl Screen {
         self.active_tab_indices.keys().next().copied()
     }
 
+    pub fn start_recording(
+        &mut self,
+        path: PathBuf,
+        pane_id: Option<PaneId>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        match pane_id {
+            Some(PaneId::Terminal(terminal_id)) => {
+                let (columns, rows, title) = self
+                    .tabs
+                    .values()
+                    .find_map(|tab| tab.get_pane_with_id(PaneId::Terminal(terminal_id)))
+                    .map(|pane| {
+                        (
+                            pane.get_content_columns(),
+                            pane.get_content_rows(),
+                            pane.current_title(),
+                        )
+                    })
+                    .with_context(|| format!("Terminal pane {} not found", terminal_id))?;
+                let recording = AsciicastWriter::create(&path, columns, rows, Some(title))?;
+                self.pane_recordings.insert(terminal_id, recording);
+            },
+            Some(PaneId::Plugin(plugin_id)) => {
+                bail!("Cannot record plugin pane {}", plugin_id);
+            },
+            None => {
+                let client_id = self
+                    .recorded_client_id(client_id)
+                    .context("No connected clients to record")?;
+                // the recording is played back in a terminal the size of the recorded client
+                let size = self
+                    .client_sizes
+                    .get(&client_id)
+                    .copied()
+                    .unwrap_or(self.size);
+                let recording = AsciicastWriter::create(
+                    &path,
+                    size.cols,
+                    size.rows,
+                    Some(self.session_name.clone()),
+                )?;
+                self.session_recordings.insert(client_id, recording);
+                // render everything again so that the recording starts with the whole screen,
+                // clients that opted into diff rendering would otherwise only be sent the cells
+                // that changed
+                if self.render_diff_state.has_client(client_id) {
+                    self.render_diff_state.add_client(client_id);
+                }
+                if let Ok(active_tab) = self.get_active_tab_mut(client_id) {
+                    active_tab.set_force_render();
+                }
+                self.render(None)?;
+            },
+        }
+        Ok(())
+    }
+
+    pub fn stop_recording(&mut self, pane_id: Option<PaneId>, client_id: ClientId) -> Result<()> {
+        let recording = match pane_id {
+            Some(PaneId::Terminal(terminal_id)) => self.pane_recordings.remove(&terminal_id),
+            Some(PaneId::Plugin(_)) => None,
+            None => self
+                .recorded_client_id(client_id)
+                .and_then(|client_id| self.session_recordings.remove(&client_id)),
+        };
+        match recording {
+            Some(_) => Ok(()),
+            None => Err(anyhow!("No recording in progress")),
+        }
+    }
+
+    fn retain_only_existing_pane_recordings(&mut self) {
+        // dropping the recordings of panes that were closed closes their files
+        let tabs = &self.tabs;
+        self.pane_recordings.retain(|terminal_id, _| {
+            tabs.values()
+                .any(|tab| tab.has_pane_with_pid(&PaneId::Terminal(*terminal_id)))
+        });
+    }
+
+    fn recorded_client_id(&self, client_id: ClientId) -> Option<ClientId> {
+        // actions from the CLI come from a client that is not rendered to, in which case we
+        // record the first connected client
+        if self.active_tab_indices.contains_key(&client_id) {
+            Some(client_id)
+        } else {
+            self.get_first_client_id()
+        }
+    }
+
+    fn record_render(
+        &mut self,
+        serialized_output: &HashMap<ClientId, String>,
+        render_diffs: &HashMap<ClientId, RenderDiff>,
+    ) {
+        for (client_id, recording) in self.session_recordings.iter_mut() {
+            let rendered = serialized_output
+                .get(client_id)
+                .cloned()
+                .or_else(|| render_diffs.get(client_id).map(|r| r.to_vte()));
+            if let Some(rendered) = rendered {
+                recording
+                    .write_output(&rendered)
+                    .context("failed to record render")
+                    .non_fatal();
+            }
+        }
+    }
+
     /// Returns an immutable reference to this [`Screen`]'s previous active [`Tab`].
     /// Consumes the last entry in tab history.
     pub fn get_previous_tab(&mut self, client_id: ClientId) -> Result<Option<&Tab>> {
@@ -1643,13 +1826,43 @@ impl Screen {
             self.tab_history.remove(&client_id);
         }
         self.connected_clients.borrow_mut().remove(&client_id);
+        self.read_only_clients.remove(&client_id);
+        self.kitty_graphics_output_state
+            .borrow_mut()
+            .remove_client(client_id);
+        self.render_diff_state.remove_client(client_id);
+        self.session_recordings.remove(&client_id);
+        self.client_sizes.remove(&client_id);
         self.log_and_report_session_state()
             .with_context(err_context)
     }
 
+    /// Scrolls only the view of a read-only client, so that spectators do not move the scroll
+    /// position of the pane for everyone else. Returns `false` for other clients.
+    pub fn scroll_read_only_client_view(
+        &mut self,
+        scroll: ViewerScroll,
+        point: Option<&Position>,
+        client_id: ClientId,
+    ) -> Result<bool> {
+        if !self.read_only_clients.contains(&client_id) {
+            return Ok(false);
+        }
+        self.get_active_tab_mut(client_id)
+            .and_then(|tab| tab.scroll_viewer(scroll, point, client_id))
+            .with_context(|| format!("failed to scroll the view of client {client_id}"))?;
+        Ok(true)
+    }
+
     pub fn generate_and_report_tab_state(&mut self) -> Result<Vec<TabInfo>> {
         let mut plugin_updates = vec![];
         let mut tab_infos_for_screen_state = BTreeMap::new();
+        for tab in self.tabs.values_mut() {
+            // notifications are considered read once a client focuses their tab
+            if self.active_tab_indices.values().any(|i| i == &tab.index) {
+                tab.set_has_unread_notifications(false);
+            }
+        }
         for tab in self.tabs.values() {
             let all_focused_clients: Vec<ClientId> = self
                 .active_tab_indices
@@ -1680,6 +1893,7 @@ impl Screen {
                 display_area_columns: tab_display_area.cols,
                 selectable_tiled_panes_count,
                 selectable_floating_panes_count,
+                has_unread_notifications: tab.has_unread_notifications(),
             };
             tab_infos_for_screen_state.insert(tab.position, tab_info_for_screen);
         }
@@ -1720,6 +1934,7 @@ impl Screen {
                     display_area_columns: tab_display_area.cols,
                     selectable_tiled_panes_count,
                     selectable_floating_panes_count,
+                    has_unread_notifications: tab.has_unread_notifications(),
                 };
                 plugin_tab_updates.push(tab_info_for_plugins);
             }
@@ -1731,6 +1946,46 @@ impl Screen {
             .context("failed to update tabs")?;
         Ok(tab_infos_for_screen_state.values().cloned().collect())
     }
+    fn handle_pane_notifications(
+        &mut self,
+        tab_index: usize,
+        notifications: Vec<(PaneId, Option<String>, String)>, // (pane_id, title, body)
+    ) -> Result<()> {
+        let err_context = || format!("failed to handle pane notifications in tab {tab_index}");
+        let tab_is_active = self.active_tab_indices.values().any(|i| i == &tab_index);
+        let mut should_report_tab_state = false;
+        let mut plugin_updates = vec![];
+        if let Some(tab) = self.tabs.get_mut(&tab_index) {
+            for (pane_id, title, body) in notifications {
+                if self.forward_pane_notifications {
+                    tab.forward_notification_to_clients(&title, &body)
+                        .with_context(err_context)?;
+                }
+                plugin_updates.push((
+                    None,
+                    None,
+                    Event::PaneNotification {
+                        pane_id: pane_id.into(),
+                        title,
+                        body,
+                    },
+                ));
+            }
+            if !tab_is_active && !tab.has_unread_notifications() {
+                tab.set_has_unread_notifications(true);
+                should_report_tab_state = true;
+            }
+        }
+        self.bus
+            .senders
+            .send_to_plugin(PluginInstruction::Update(plugin_updates))
+            .with_context(err_context)?;
+        if should_report_tab_state {
+            self.log_and_report_session_state()
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
     fn generate_and_report_pane_state(&mut self) -> Result<PaneManifest> {
         let mut pane_manifest = PaneManifest::default();
         for tab in self.tabs.values() {
@@ -1799,6 +2054,14 @@ impl Screen {
     }
     fn dump_layout_to_hd(&mut self) -> Result<()> {
         let err_context = || format!("Failed to log and report session state");
+        if self.serialize_pane_viewport {
+            if let Some(scrollback_lines_to_serialize) = self.scrollback_lines_to_serialize {
+                let session_folder = session_info_folder_for_session(&self.session_name);
+                for tab in self.tabs.values_mut() {
+                    tab.persist_scrollbacks(&session_folder, scrollback_lines_to_serialize);
+                }
+            }
+        }
         let session_layout_metadata = self.get_layout_metadata(Some(self.default_shell.clone()));
         self.bus
             .senders
@@ -2067,6 +2330,7 @@ impl Screen {
             }
         }
 
+        mode_info.is_read_only = Some(self.read_only_clients.contains(&client_id));
         self.style = mode_info.style;
         self.mode_info.insert(client_id, mode_info.clone());
         for tab in self.tabs.values_mut() {
@@ -2680,6 +2944,7 @@ impl Screen {
         stacked_resize: bool,
         default_editor: Option<PathBuf>,
         advanced_mouse_actions: bool,
+        forward_pane_notifications: bool,
         client_id: ClientId,
     ) -> Result<()> {
         let should_support_arrow_fonts = !simplified_ui;
@@ -2695,6 +2960,7 @@ impl Screen {
         self.copy_options.copy_on_select = copy_on_select;
         self.draw_pane_frames = pane_frames;
         self.advanced_mouse_actions = advanced_mouse_actions;
+        self.forward_pane_notifications = forward_pane_notifications;
         self.default_mode_info
             .update_arrow_fonts(should_support_arrow_fonts);
         self.default_mode_info
@@ -2932,6 +3198,14 @@ impl Screen {
         }
         Ok(())
     }
+    fn log_error_to_client(&mut self, client_id: ClientId, error: anyError) {
+        let error_text = format!("{:#}", error);
+        log::error!("{}", error_text);
+        if let Some(os_input) = &mut self.bus.os_input {
+            let _ =
+                os_input.send_to_client(client_id, ServerToClientMsg::LogError(vec![error_text]));
+        }
+    }
     fn unblock_input(&self) -> Result<()> {
         self.bus
             .senders
@@ -2943,6 +3217,7 @@ impl Screen {
         if let Some(default_shell) = default_shell {
             session_layout_metadata.update_default_shell(default_shell);
         }
+        session_layout_metadata.set_read_only_clients(self.read_only_clients.clone());
         let first_client_id = self.get_first_client_id();
         let active_tab_index =
             first_client_id.and_then(|client_id| self.active_tab_indices.get(&client_id));
@@ -2993,6 +3268,7 @@ impl Screen {
                             p_id.and_then(|p_id| if p_id == pane_id { Some(*c_id) } else { None })
                         })
                         .collect();
+                    let (pane_contents, scrollback_file) = self.serialize_pane_contents(p.as_ref());
                     PaneLayoutMetadata::new(
                         pane_id,
                         p.position_and_size(),
@@ -3000,11 +3276,8 @@ impl Screen {
                         p.invoked_with().clone(),
                         p.custom_title(),
                         !focused_clients.is_empty(),
-                        if self.serialize_pane_viewport {
-                            p.serialize(self.scrollback_lines_to_serialize)
-                        } else {
-                            None
-                        },
+                        pane_contents,
+                        scrollback_file,
                         focused_clients,
                     )
                 })
@@ -3031,6 +3304,7 @@ impl Screen {
                             p_id.and_then(|p_id| if p_id == pane_id { Some(*c_id) } else { None })
                         })
                         .collect();
+                    let (pane_contents, scrollback_file) = self.serialize_pane_contents(p.as_ref());
                     PaneLayoutMetadata::new(
                         pane_id,
                         p.position_and_size(),
@@ -3038,11 +3312,8 @@ impl Screen {
                         p.invoked_with().clone(),
                         p.custom_title(),
                         !focused_clients.is_empty(),
-                        if self.serialize_pane_viewport {
-                            p.serialize(self.scrollback_lines_to_serialize)
-                        } else {
-                            None
-                        },
+                        pane_contents,
+                        scrollback_file,
                         focused_clients,
                     )
                 })
@@ -3057,6 +3328,16 @@ impl Screen {
         }
         session_layout_metadata
     }
+    fn serialize_pane_contents(&self, pane: &dyn Pane) -> (Option<String>, Option<String>) {
+        if !self.serialize_pane_viewport {
+            return (None, None);
+        }
+        // panes whose scrollback is in a scrollback store only need their viewport serialized
+        match pane.persisted_scrollback_file() {
+            Some(scrollback_file) => (pane.serialize(None), Some(scrollback_file)),
+            None => (pane.serialize(self.scrollback_lines_to_serialize), None),
+        }
+    }
     fn update_plugin_loading_stage(
         &mut self,
         pid: u32,
@@ -3268,6 +3549,7 @@ pub(crate) fn screen_thread_main(
         .unwrap_or(false);
     let web_sharing = config_options.web_sharing.unwrap_or_else(Default::default);
     let advanced_mouse_actions = config_options.advanced_mouse_actions.unwrap_or(true);
+    let forward_pane_notifications = config_options.forward_pane_notifications.unwrap_or(false);
 
     let thread_senders = bus.senders.clone();
     let mut screen = Screen::new(
@@ -3304,6 +3586,7 @@ pub(crate) fn screen_thread_main(
         web_clients_allowed,
         web_sharing,
         advanced_mouse_actions,
+        forward_pane_notifications,
         web_server_ip,
         web_server_port,
     );
@@ -3327,14 +3610,35 @@ pub(crate) fn screen_thread_main(
 
         match event {
             ScreenInstruction::PtyBytes(pid, vte_bytes) => {
+                if let Some(recording) = screen.pane_recordings.get_mut(&pid) {
+                    recording
+                        .write_output_bytes(&vte_bytes)
+                        .context("failed to record pane output")
+                        .non_fatal();
+                }
                 let all_tabs = screen.get_tabs_mut();
+                let mut pane_notifications = None;
                 for tab in all_tabs.values_mut() {
                     if tab.has_terminal_pid(pid) {
                         tab.handle_pty_bytes(pid, vte_bytes)
                             .context("failed to process pty bytes")?;
+                        let notifications = tab.drain_pane_notifications();
+                        if !notifications.is_empty() {
+                            pane_notifications = Some((tab.index, notifications));
+                        }
                         break;
                     }
                 }
+                if let Some((tab_index, notifications)) = pane_notifications {
+                    screen
+                        .handle_pane_notifications(tab_index, notifications)
+                        .non_fatal();
+                }
+                if screen.report_pane_content_changes {
+                    let _ = screen.bus.senders.send_to_background_jobs(
+                        BackgroundJob::ReportPaneContentChanged(PaneId::Terminal(pid)),
+                    );
+                }
                 let _ = screen
                     .bus
                     .senders
@@ -3774,6 +4078,18 @@ pub(crate) fn screen_thread_main(
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::StartRecording(path, pane_id, client_id) => {
+                match screen.start_recording(path, pane_id, client_id) {
+                    Ok(()) => screen.unblock_input()?,
+                    Err(e) => screen.log_error_to_client(client_id, e),
+                }
+            },
+            ScreenInstruction::StopRecording(pane_id, client_id) => {
+                match screen.stop_recording(pane_id, client_id) {
+                    Ok(()) => screen.unblock_input()?,
+                    Err(e) => screen.log_error_to_client(client_id, e),
+                }
+            },
             ScreenInstruction::DumpLayout(default_shell, client_id) => {
                 let err_context = || format!("Failed to dump layout");
                 let session_layout_metadata = screen.get_layout_metadata(default_shell);
@@ -3838,11 +4154,14 @@ pub(crate) fn screen_thread_main(
                 screen.log_and_report_session_state()?;
             },
             ScreenInstruction::ScrollUp(client_id) => {
-                active_tab_and_connected_client_id!(
-                    screen,
-                    client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.scroll_active_terminal_up(client_id)
-                );
+                if !screen.scroll_read_only_client_view(ViewerScroll::Up(1), None, client_id)? {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| tab
+                            .scroll_active_terminal_up(client_id)
+                    );
+                }
                 screen.unblock_input()?;
                 screen.render(None)?;
             },
@@ -3907,91 +4226,165 @@ pub(crate) fn screen_thread_main(
                 screen.log_and_report_session_state()?;
             },
             ScreenInstruction::ScrollUpAt(point, client_id) => {
-                active_tab_and_connected_client_id!(
-                    screen,
+                if !screen.scroll_read_only_client_view(
+                    ViewerScroll::Up(3),
+                    Some(&point),
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab
-                        .handle_scrollwheel_up(&point, 3, client_id), ?
-                );
+                )? {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| tab
+                            .handle_scrollwheel_up(&point, 3, client_id), ?
+                    );
+                }
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
             ScreenInstruction::ScrollDown(client_id) => {
-                active_tab_and_connected_client_id!(
-                    screen,
-                    client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.scroll_active_terminal_down(client_id), ?
-                );
+                if !screen.scroll_read_only_client_view(ViewerScroll::Down(1), None, client_id)? {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| tab.scroll_active_terminal_down(client_id), ?
+                    );
+                }
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
             ScreenInstruction::ScrollDownAt(point, client_id) => {
-                active_tab_and_connected_client_id!(
-                    screen,
+                if !screen.scroll_read_only_client_view(
+                    ViewerScroll::Down(3),
+                    Some(&point),
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab
-                        .handle_scrollwheel_down(&point, 3, client_id), ?
-                );
+                )? {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| tab
+                            .handle_scrollwheel_down(&point, 3, client_id), ?
+                    );
+                }
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
             ScreenInstruction::ScrollToBottom(client_id) => {
+                if !screen.scroll_read_only_client_view(ViewerScroll::ToBottom, None, client_id)? {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| tab
+                            .scroll_active_terminal_to_bottom(client_id), ?
+                    );
+                }
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ScrollToTop(client_id) => {
+                if !screen.scroll_read_only_client_view(ViewerScroll::ToTop, None, client_id)? {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| tab
+                            .scroll_active_terminal_to_top(client_id), ?
+                    );
+                }
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ScrollToPreviousPrompt(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
                     |tab: &mut Tab, client_id: ClientId| tab
-                        .scroll_active_terminal_to_bottom(client_id), ?
+                        .scroll_active_terminal_to_previous_prompt(client_id), ?
                 );
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::ScrollToTop(client_id) => {
+            ScreenInstruction::ScrollToNextPrompt(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
                     |tab: &mut Tab, client_id: ClientId| tab
-                        .scroll_active_terminal_to_top(client_id), ?
+                        .scroll_active_terminal_to_next_prompt(client_id), ?
                 );
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::PageScrollUp(client_id) => {
+            ScreenInstruction::CopyLastCommandOutput(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
                     |tab: &mut Tab, client_id: ClientId| tab
-                        .scroll_active_terminal_up_page(client_id)
+                        .copy_last_command_output(client_id), ?
                 );
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::PageScrollDown(client_id) => {
+            ScreenInstruction::EditLastCommandOutput(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
                     |tab: &mut Tab, client_id: ClientId| tab
-                        .scroll_active_terminal_down_page(client_id), ?
+                        .edit_last_command_output(client_id), ?
                 );
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::PageScrollUp(client_id) => {
+                if !screen.scroll_read_only_client_view(ViewerScroll::PageUp, None, client_id)? {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| tab
+                            .scroll_active_terminal_up_page(client_id)
+                    );
+                }
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::PageScrollDown(client_id) => {
+                if !screen.scroll_read_only_client_view(ViewerScroll::PageDown, None, client_id)? {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| tab
+                            .scroll_active_terminal_down_page(client_id), ?
+                    );
+                }
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::HalfPageScrollUp(client_id) => {
-                active_tab_and_connected_client_id!(
-                    screen,
+                if !screen.scroll_read_only_client_view(
+                    ViewerScroll::HalfPageUp,
+                    None,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab
-                        .scroll_active_terminal_up_half_page(client_id)
-                );
+                )? {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| tab
+                            .scroll_active_terminal_up_half_page(client_id)
+                    );
+                }
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
             ScreenInstruction::HalfPageScrollDown(client_id) => {
-                active_tab_and_connected_client_id!(
-                    screen,
+                if !screen.scroll_read_only_client_view(
+                    ViewerScroll::HalfPageDown,
+                    None,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab
-                        .scroll_active_terminal_down_half_page(client_id), ?
-                );
+                )? {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| tab
+                            .scroll_active_terminal_down_half_page(client_id), ?
+                    );
+                }
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
@@ -4011,6 +4404,7 @@ pub(crate) fn screen_thread_main(
                     client_id,
                     |tab: &mut Tab, client_id: ClientId| tab.close_focused_pane(client_id), ?
                 );
+                screen.retain_only_existing_pane_recordings();
                 screen.render(None)?;
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
@@ -4069,6 +4463,7 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
                 screen.retain_only_existing_panes_in_pane_groups();
+                screen.retain_only_existing_pane_recordings();
             },
             ScreenInstruction::HoldPane(id, exit_status, run_command) => {
                 let is_first_run = false;
@@ -4359,6 +4754,9 @@ pub(crate) fn screen_thread_main(
                 screen.log_and_report_session_state()?; // update tabs so that the ui indication will be send to the plugins
                 screen.render(None)?;
             },
+            ScreenInstruction::ClientTerminalResize(client_id, size) => {
+                screen.client_sizes.insert(client_id, size);
+            },
             ScreenInstruction::TerminalPixelDimensions(pixel_dimensions) => {
                 screen.update_pixel_dimensions(pixel_dimensions);
             },
@@ -4371,6 +4769,17 @@ pub(crate) fn screen_thread_main(
             ScreenInstruction::TerminalColorRegisters(color_registers) => {
                 screen.update_terminal_color_registers(color_registers);
             },
+            ScreenInstruction::TerminalSupportsKittyGraphics(client_id) => {
+                screen
+                    .kitty_graphics_output_state
+                    .borrow_mut()
+                    .add_supported_client(client_id);
+                screen.render(None)?;
+            },
+            ScreenInstruction::EnableDiffRendering(client_id) => {
+                screen.render_diff_state.add_client(client_id);
+                screen.render(None)?;
+            },
             ScreenInstruction::ChangeMode(mode_info, client_id) => {
                 screen.change_mode(mode_info, client_id)?;
                 screen.render(None)?;
@@ -4410,9 +4819,13 @@ pub(crate) fn screen_thread_main(
             ScreenInstruction::AddClient(
                 client_id,
                 is_web_client,
+                is_read_only,
                 tab_position_to_focus,
                 pane_id_to_focus,
             ) => {
+                if is_read_only {
+                    screen.read_only_clients.insert(client_id);
+                }
                 screen.add_client(client_id, is_web_client)?;
                 let pane_id = pane_id_to_focus.map(|(pane_id, is_plugin)| {
                     if is_plugin {
@@ -4532,6 +4945,67 @@ pub(crate) fn screen_thread_main(
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::SearchToggleRegex(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.toggle_search_regex(client_id)
+                );
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ListSearchMatches(client_id) => {
+                let mut search_matches = vec![];
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| search_matches =
+                        tab.list_search_matches(client_id)
+                );
+                if search_matches.is_empty() {
+                    let error_text = "No search matches found in the focused pane".to_owned();
+                    screen
+                        .bus
+                        .senders
+                        .send_to_server(ServerInstruction::LogError(vec![error_text], client_id))?;
+                } else {
+                    let lines = search_matches
+                        .iter()
+                        .enumerate()
+                        .map(|(i, search_match)| {
+                            format!(
+                                "{}. line {}: {}",
+                                i + 1,
+                                search_match.line_number,
+                                search_match.line
+                            )
+                        })
+                        .collect();
+                    screen
+                        .bus
+                        .senders
+                        .send_to_server(ServerInstruction::Log(lines, client_id))?;
+                }
+            },
+            ScreenInstruction::SearchJumpToMatch(index, client_id) => {
+                let mut jumped = false;
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| jumped =
+                        tab.search_jump_to_match(index, client_id)
+                );
+                if jumped {
+                    screen.render(None)?;
+                    screen.unblock_input()?;
+                } else {
+                    let error_text = format!("No search match with index {}", index);
+                    screen
+                        .bus
+                        .senders
+                        .send_to_server(ServerInstruction::LogError(vec![error_text], client_id))?;
+                }
+            },
             ScreenInstruction::AddRedPaneFrameColorOverride(pane_ids, error_text) => {
                 let all_tabs = screen.get_tabs_mut();
                 for pane_id in pane_ids {
@@ -5224,6 +5698,7 @@ pub(crate) fn screen_thread_main(
                 stacked_resize,
                 default_editor,
                 advanced_mouse_actions,
+                forward_pane_notifications,
             } => {
                 screen
                     .reconfigure(
@@ -5242,6 +5717,7 @@ pub(crate) fn screen_thread_main(
                         stacked_resize,
                         default_editor,
                         advanced_mouse_actions,
+                        forward_pane_notifications,
                         client_id,
                     )
                     .non_fatal();
@@ -5262,6 +5738,63 @@ pub(crate) fn screen_thread_main(
                 }
                 screen.render(None)?;
             },
+            ScreenInstruction::SearchPaneScrollback(pane_id, pattern, plugin_id, client_id) => {
+                let search_matches = screen
+                    .get_tabs()
+                    .values()
+                    .find_map(|tab| tab.get_pane_with_id(pane_id))
+                    .ok_or_else(|| anyhow!("failed to find pane with id {:?}", pane_id))
+                    .and_then(|pane| pane.search_scrollback(&pattern))
+                    .with_context(|| format!("failed to search the scrollback of {:?}", pane_id));
+                match search_matches {
+                    Ok(matches) => {
+                        let _ = screen
+                            .bus
+                            .senders
+                            .send_to_plugin(PluginInstruction::Update(vec![(
+                                Some(plugin_id),
+                                Some(client_id),
+                                Event::PaneScrollbackSearchResult {
+                                    pane_id: pane_id.into(),
+                                    pattern,
+                                    matches,
+                                },
+                            )]));
+                    },
+                    Err(e) => Err::<(), _>(e).non_fatal(),
+                }
+            },
+            ScreenInstruction::GetPaneScrollback(
+                pane_id,
+                range,
+                with_styles,
+                plugin_id,
+                client_id,
+            ) => {
+                let scrollback = screen
+                    .get_tabs()
+                    .values()
+                    .find_map(|tab| tab.get_pane_with_id(pane_id))
+                    .ok_or_else(|| anyhow!("failed to find pane with id {:?}", pane_id))
+                    .and_then(|pane| pane.pane_scrollback(range, with_styles))
+                    .with_context(|| format!("failed to get the scrollback of {:?}", pane_id));
+                match scrollback {
+                    Ok(scrollback) => {
+                        let _ = screen
+                            .bus
+                            .senders
+                            .send_to_plugin(PluginInstruction::Update(vec![(
+                                Some(plugin_id),
+                                Some(client_id),
+                                Event::PaneScrollback {
+                                    pane_id: pane_id.into(),
+                                    scrollback,
+                                },
+                            )]));
+                    },
+                    Err(e) => Err::<(), _>(e).non_fatal(),
+                }
+            },
             ScreenInstruction::WriteToPaneId(bytes, pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
                 for tab in all_tabs.values_mut() {
@@ -5648,6 +6181,9 @@ pub(crate) fn screen_thread_main(
             ScreenInstruction::ReplacePaneWithExistingPane(old_pane_id, new_pane_id) => {
                 screen.replace_pane_with_existing_pane(old_pane_id, new_pane_id)
             },
+            ScreenInstruction::SetReportPaneContentChanges(report_pane_content_changes) => {
+                screen.report_pane_content_changes = report_pane_content_changes;
+            },
         }
     }
     Ok(())
@@ -1,6 +1,6 @@
 use crate::panes::PaneId;
 use crate::ClientId;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use zellij_utils::common_path::common_path_all;
 use zellij_utils::pane_size::PaneGeom;
@@ -21,6 +21,7 @@ pub struct SessionLayoutMetadata {
     pub default_shell: Option<PathBuf>,
     pub default_editor: Option<PathBuf>,
     tabs: Vec<TabLayoutMetadata>,
+    read_only_clients: HashSet<ClientId>,
 }
 
 impl SessionLayoutMetadata {
@@ -59,6 +60,9 @@ impl SessionLayoutMetadata {
             }
         }
     }
+    pub fn set_read_only_clients(&mut self, read_only_clients: HashSet<ClientId>) {
+        self.read_only_clients = read_only_clients;
+    }
     pub fn list_clients_metadata(&self) -> String {
         let mut clients_metadata: BTreeMap<ClientId, ClientMetadata> = BTreeMap::new();
         for tab in &self.tabs {
@@ -74,6 +78,7 @@ impl SessionLayoutMetadata {
                         ClientMetadata {
                             pane_id: pane.id.clone(),
                             command: pane.run.clone(),
+                            is_read_only: self.read_only_clients.contains(focused_client),
                         },
                     );
                 }
@@ -97,6 +102,7 @@ impl SessionLayoutMetadata {
                         ClientMetadata {
                             pane_id: pane.id.clone(),
                             command: pane.run.clone(),
+                            is_read_only: self.read_only_clients.contains(focused_client),
                         },
                     );
                 }
@@ -175,6 +181,9 @@ impl SessionLayoutMetadata {
             if location_string == "zellij:share" {
                 return true;
             }
+            if location_string == "zellij:command-palette" {
+                return true;
+            }
         }
         false
     }
@@ -317,6 +326,29 @@ impl SessionLayoutMetadata {
             }
         }
     }
+    pub fn update_plugin_saved_states(
+        &mut self,
+        mut plugin_ids_to_saved_states: HashMap<u32, String>,
+    ) {
+        let mut update_saved_state_in_pane_metadata =
+            |pane_layout_metadata: &mut PaneLayoutMetadata| {
+                if let PaneId::Plugin(id) = pane_layout_metadata.id {
+                    if let Some(Run::Plugin(RunPluginOrAlias::RunPlugin(run_plugin))) =
+                        pane_layout_metadata.run.as_mut()
+                    {
+                        run_plugin.saved_state = plugin_ids_to_saved_states.remove(&id);
+                    }
+                }
+            };
+        for tab in self.tabs.iter_mut() {
+            for pane_layout_metadata in tab.tiled_panes.iter_mut() {
+                update_saved_state_in_pane_metadata(pane_layout_metadata);
+            }
+            for pane_layout_metadata in tab.floating_panes.iter_mut() {
+                update_saved_state_in_pane_metadata(pane_layout_metadata);
+            }
+        }
+    }
     pub fn update_default_editor(&mut self, default_editor: &Option<PathBuf>) {
         let default_editor = default_editor.clone().unwrap_or_else(|| {
             PathBuf::from(
@@ -368,6 +400,7 @@ impl Into<PaneLayoutManifest> for PaneLayoutMetadata {
             title: self.title,
             is_focused: self.is_focused,
             pane_contents: self.pane_contents,
+            scrollback_file: self.scrollback_file,
         }
     }
 }
@@ -391,6 +424,7 @@ pub struct PaneLayoutMetadata {
     title: Option<String>,
     is_focused: bool,
     pane_contents: Option<String>,
+    scrollback_file: Option<String>,
     focused_clients: Vec<ClientId>,
 }
 
@@ -403,6 +437,7 @@ impl PaneLayoutMetadata {
         title: Option<String>,
         is_focused: bool,
         pane_contents: Option<String>,
+        scrollback_file: Option<String>,
         focused_clients: Vec<ClientId>,
     ) -> Self {
         PaneLayoutMetadata {
@@ -414,6 +449,7 @@ impl PaneLayoutMetadata {
             title,
             is_focused,
             pane_contents,
+            scrollback_file,
             focused_clients,
         }
     }
@@ -422,6 +458,7 @@ impl PaneLayoutMetadata {
 pub struct ClientMetadata {
     pane_id: PaneId,
     command: Option<Run>,
+    is_read_only: bool,
 }
 impl ClientMetadata {
     pub fn stringify_pane_id(&self) -> String {
@@ -454,19 +491,32 @@ impl ClientMetadata {
     pub fn get_pane_id(&self) -> PaneId {
         self.pane_id
     }
+    pub fn is_read_only(&self) -> bool {
+        self.is_read_only
+    }
     pub fn render_many(
         clients_metadata: BTreeMap<ClientId, ClientMetadata>,
         default_editor: &Option<PathBuf>,
     ) -> String {
         let mut lines = vec![];
-        lines.push(String::from("CLIENT_ID ZELLIJ_PANE_ID RUNNING_COMMAND"));
+        lines.push(String::from(
+            "CLIENT_ID ZELLIJ_PANE_ID READ_ONLY RUNNING_COMMAND",
+        ));
 
         for (client_id, client_metadata) in clients_metadata.iter() {
-            // 9 - CLIENT_ID, 14 - ZELLIJ_PANE_ID, 15 - RUNNING_COMMAND
+            // 9 - CLIENT_ID, 14 - ZELLIJ_PANE_ID, 9 - READ_ONLY, 15 - RUNNING_COMMAND
             lines.push(format!(
-                "{} {} {}",
+                "{} {} {} {}",
                 format!("{0: <9}", client_id),
                 format!("{0: <14}", client_metadata.stringify_pane_id()),
+                format!(
+                    "{0: <9}",
+                    if client_metadata.is_read_only() {
+                        "yes"
+                    } else {
+                        "no"
+                    }
+                ),
                 format!(
                     "{0: <15}",
                     client_metadata.stringify_command(default_editor)
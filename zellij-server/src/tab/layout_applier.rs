@@ -459,6 +459,9 @@ impl<'a> LayoutApplier<'a> {
             self.styled_underlines,
             self.explicitly_disable_kitty_keyboard_protocol,
         );
+        if let Some(pane_scrollback_file) = &floating_pane_layout.pane_scrollback_file {
+            new_pane.restore_scrollback(pane_scrollback_file);
+        }
         if let Some(pane_initial_contents) = &floating_pane_layout.pane_initial_contents {
             new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
             new_pane.handle_pty_bytes("\n\r".as_bytes().into());
@@ -512,6 +515,9 @@ impl<'a> LayoutApplier<'a> {
             self.styled_underlines,
             self.explicitly_disable_kitty_keyboard_protocol,
         );
+        if let Some(pane_scrollback_file) = &layout.pane_scrollback_file {
+            new_pane.restore_scrollback(pane_scrollback_file);
+        }
         if let Some(pane_initial_contents) = &layout.pane_initial_contents {
             new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
             new_pane.handle_pty_bytes("\n\r".as_bytes().into());
@@ -10,7 +10,7 @@ use copy_command::CopyCommand;
 use serde;
 use std::env::temp_dir;
 use std::net::IpAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 use zellij_utils::data::{
     Direction, KeyWithModifier, PaneInfo, PermissionStatus, PermissionType, PluginPermission,
@@ -34,7 +34,7 @@ use swap_layouts::SwapLayouts;
 use self::clipboard::ClipboardProvider;
 use crate::{
     os_input_output::ServerOsApi,
-    output::{CharacterChunk, Output, SixelImageChunk},
+    output::{CharacterChunk, KittyImageChunk, Output, SixelImageChunk},
     panes::floating_panes::floating_pane_grid::half_size_middle_geom,
     panes::sixel::SixelImageStore,
     panes::{FloatingPanes, TiledPanes},
@@ -54,7 +54,8 @@ use std::{
 };
 use zellij_utils::{
     data::{
-        Event, FloatingPaneCoordinates, InputMode, ModeInfo, Palette, PaletteColor, Style, Styling,
+        Event, FloatingPaneCoordinates, InputMode, ModeInfo, Palette, PaletteColor, PaneScrollback,
+        ScrollbackRange, ScrollbackSearchMatch, Style, Styling,
     },
     input::{
         command::TerminalAction,
@@ -271,6 +272,8 @@ pub(crate) struct Tab {
     advanced_mouse_actions: bool,
     currently_marking_pane_group: Rc<RefCell<HashMap<ClientId, bool>>>,
     connected_clients_in_app: Rc<RefCell<HashMap<ClientId, bool>>>, // bool -> is_web_client
+    pending_pane_notifications: Vec<(PaneId, Option<String>, String)>, // (pane_id, title, body)
+    has_unread_notifications: bool,
     // the below are the configured values - the ones that will be set if and when the web server
     // is brought online
     web_server_ip: IpAddr,
@@ -358,6 +361,24 @@ pub trait Pane {
     fn scroll_down(&mut self, count: usize, client_id: ClientId);
     fn clear_scroll(&mut self);
     fn is_scrolled(&self) -> bool;
+    // these scroll only what the given client sees of the pane (eg. a read-only client), leaving
+    // the scroll position shared by the other clients as is
+    fn viewer_scroll_up(&mut self, _count: usize, _client_id: ClientId) {}
+    fn viewer_scroll_down(&mut self, _count: usize, _client_id: ClientId) {}
+    fn has_viewer_scroll(&self, _client_id: ClientId) -> bool {
+        false
+    }
+    fn scroll_to_previous_prompt(&mut self, _client_id: ClientId) -> bool {
+        // returns true if the pane scrolled
+        false
+    }
+    fn scroll_to_next_prompt(&mut self, _client_id: ClientId) -> bool {
+        // returns true if the pane scrolled
+        false
+    }
+    fn last_command_output(&self) -> Option<String> {
+        None
+    }
     fn active_at(&self) -> Instant;
     fn set_active_at(&mut self, instant: Instant);
     fn set_frame(&mut self, frame: bool);
@@ -463,6 +484,19 @@ pub trait Pane {
     fn drain_clipboard_update(&mut self) -> Option<String> {
         None
     }
+    fn drain_cwd_update(&mut self) -> Option<PathBuf> {
+        None
+    }
+    fn drain_notifications(&mut self) -> Vec<(Option<String>, String)> {
+        // (title, body)
+        vec![]
+    }
+    fn cwd(&self) -> Option<PathBuf> {
+        None
+    }
+    fn kitty_image_chunks(&self) -> Vec<KittyImageChunk> {
+        vec![]
+    }
     fn render_full_viewport(&mut self) {}
     fn relative_position(&self, position_on_screen: &Position) -> Position {
         position_on_screen.relative_to(self.get_content_y(), self.get_content_x())
@@ -570,6 +604,27 @@ pub trait Pane {
     fn toggle_search_wrap(&mut self) {
         // No-op by default (only terminal-panes currently have search capability)
     }
+    fn toggle_search_regex(&mut self) {
+        // No-op by default (only terminal-panes currently have search capability)
+    }
+    fn list_search_matches(&mut self) -> Vec<ScrollbackSearchMatch> {
+        // Empty by default (only terminal-panes currently have search capability)
+        vec![]
+    }
+    fn search_jump_to_match(&mut self, _index: usize) -> bool {
+        // No-op by default (only terminal-panes currently have search capability)
+        false
+    }
+    fn search_scrollback(&self, _pattern: &str) -> Result<Vec<ScrollbackSearchMatch>> {
+        Err(anyhow!("only terminal panes can be searched"))
+    }
+    fn pane_scrollback(
+        &self,
+        _range: ScrollbackRange,
+        _with_styles: bool,
+    ) -> Result<PaneScrollback> {
+        Err(anyhow!("only the contents of terminal panes can be read"))
+    }
     fn clear_search(&mut self) {
         // No-op by default (only terminal-panes currently have search capability)
     }
@@ -609,6 +664,17 @@ pub trait Pane {
     fn serialize(&self, _scrollback_lines_to_serialize: Option<usize>) -> Option<String> {
         None
     }
+    fn persist_scrollback(
+        &mut self,
+        _folder: &Path,
+        _scrollback_lines_to_persist: usize,
+    ) -> Result<()> {
+        Ok(())
+    }
+    fn persisted_scrollback_file(&self) -> Option<String> {
+        None
+    } // only relevant to terminal panes
+    fn remove_persisted_scrollback(&mut self) {} // only relevant to terminal panes
     fn rerun(&mut self) -> Option<RunCommand> {
         None
     } // only relevant to terminal panes
@@ -628,6 +694,19 @@ pub trait Pane {
     fn set_mouse_selection_support(&mut self, _selection_support: bool) {}
 }
 
+// how a read-only client scrolls its own view of a pane
+#[derive(Clone, Copy, Debug)]
+pub enum ViewerScroll {
+    Up(usize),
+    Down(usize),
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    ToTop,
+    ToBottom,
+}
+
 #[derive(Clone, Debug)]
 pub enum AdjustedInput {
     WriteBytesToTerminal(Vec<u8>),
@@ -797,6 +876,8 @@ impl Tab {
             currently_marking_pane_group,
             advanced_mouse_actions,
             connected_clients_in_app,
+            pending_pane_notifications: vec![],
+            has_unread_notifications: false,
             web_server_ip,
             web_server_port,
         }
@@ -2002,8 +2083,9 @@ impl Tab {
             self.tiled_panes
                 .replace_pane(pane_id_to_replace, pane_to_replace_with)
         };
-        if let Some(replaced_pane) = replaced_pane.take() {
+        if let Some(mut replaced_pane) = replaced_pane.take() {
             let pane_id = replaced_pane.pid();
+            replaced_pane.remove_persisted_scrollback();
             let _ = self.senders.send_to_pty(PtyInstruction::ClosePane(pane_id));
             let _ = self.senders.send_to_plugin(PluginInstruction::Update(vec![(
                 None,
@@ -2313,6 +2395,8 @@ impl Tab {
             terminal_output.handle_pty_bytes(bytes);
             let messages_to_pty = terminal_output.drain_messages_to_pty();
             let clipboard_update = terminal_output.drain_clipboard_update();
+            let cwd_update = terminal_output.drain_cwd_update();
+            let notifications = terminal_output.drain_notifications();
             for message in messages_to_pty {
                 self.write_to_pane_id_without_preprocessing(message, PaneId::Terminal(pid))
                     .with_context(err_context)?;
@@ -2321,6 +2405,15 @@ impl Tab {
                 self.write_selection_to_clipboard(&string)
                     .with_context(err_context)?;
             }
+            if let Some(cwd) = cwd_update {
+                self.senders
+                    .send_to_pty(PtyInstruction::ReportTerminalCwd(pid, cwd))
+                    .with_context(err_context)?;
+            }
+            for (title, body) in notifications {
+                self.pending_pane_notifications
+                    .push((PaneId::Terminal(pid), title, body));
+            }
         }
         Ok(())
     }
@@ -2762,10 +2855,8 @@ impl Tab {
             hide_cursor,
         );
         if self.should_clear_display_before_rendering {
-            let clear_display = "\u{1b}[2J";
-            output.add_pre_vte_instruction_to_multiple_clients(
+            output.add_clear_display_instruction_to_multiple_clients(
                 connected_clients.iter().copied(),
-                clear_display,
             );
             self.should_clear_display_before_rendering = false;
         }
@@ -2898,6 +2989,7 @@ impl Tab {
     }
     pub fn resize_whole_tab(&mut self, new_screen_size: Size) -> Result<()> {
         let err_context = || format!("failed to resize whole tab (index {})", self.index);
+        let previous_display_area = *self.display_area.borrow();
         self.floating_panes.resize(new_screen_size);
         // we need to do this explicitly because floating_panes.resize does not do this
         self.floating_panes
@@ -2907,11 +2999,21 @@ impl Tab {
         if self.auto_layout && !self.swap_layouts.is_floating_damaged() {
             // we do this only for floating panes, because the constraint system takes care of the
             // tiled panes
+            self.swap_layouts
+                .move_to_floating_layout_fitting_new_display_area(
+                    &self.floating_panes,
+                    previous_display_area,
+                );
             self.swap_layouts.set_is_floating_damaged();
             let _ = self.relayout_floating_panes(false);
         }
         if self.auto_layout && !self.swap_layouts.is_tiled_damaged() && !self.is_fullscreen_active()
         {
+            self.swap_layouts
+                .move_to_tiled_layout_fitting_new_display_area(
+                    &self.tiled_panes,
+                    previous_display_area,
+                );
             self.swap_layouts.set_is_tiled_damaged();
             let _ = self.relayout_tiled_panes(false);
         }
@@ -3273,6 +3375,25 @@ impl Tab {
         static_and_floating_pane_ids.append(&mut suppressed_pane_ids);
         static_and_floating_pane_ids
     }
+    pub fn persist_scrollbacks(&mut self, folder: &Path, scrollback_lines_to_persist: usize) {
+        let mut pane_ids = self.get_static_and_floating_pane_ids();
+        pane_ids.extend(self.suppressed_panes.keys().copied());
+        for pane_id in pane_ids {
+            if let Some(pane) = self.get_pane_with_id_mut(pane_id) {
+                pane.persist_scrollback(folder, scrollback_lines_to_persist)
+                    .non_fatal();
+            }
+        }
+    }
+    pub fn remove_persisted_scrollbacks(&mut self) {
+        let mut pane_ids = self.get_static_and_floating_pane_ids();
+        pane_ids.extend(self.suppressed_panes.keys().copied());
+        for pane_id in pane_ids {
+            if let Some(pane) = self.get_pane_with_id_mut(pane_id) {
+                pane.remove_persisted_scrollback();
+            }
+        }
+    }
     pub fn get_static_and_floating_pane_ids(&self) -> Vec<PaneId> {
         self.tiled_panes
             .pane_ids()
@@ -3324,7 +3445,11 @@ impl Tab {
         // pane, we're moving it
         if !ignore_suppressed_panes && self.suppressed_panes.contains_key(&id) {
             return match self.replace_pane_with_suppressed_pane(id) {
-                Ok(_pane) => {},
+                Ok(closed_pane) => {
+                    if let Some(mut closed_pane) = closed_pane {
+                        closed_pane.remove_persisted_scrollback();
+                    }
+                },
                 Err(e) => {
                     Err::<(), _>(e)
                         .with_context(|| format!("failed to close pane {:?}", id))
@@ -3333,7 +3458,9 @@ impl Tab {
             };
         }
         if self.floating_panes.panes_contain(&id) {
-            let _closed_pane = self.floating_panes.remove_pane(id);
+            if let Some(mut closed_pane) = self.floating_panes.remove_pane(id) {
+                closed_pane.remove_persisted_scrollback();
+            }
             self.floating_panes.move_clients_out_of_pane(id);
             if !self.floating_panes.has_selectable_panes() {
                 self.swap_layouts.reset_floating_damage();
@@ -3354,7 +3481,9 @@ impl Tab {
             if self.tiled_panes.fullscreen_is_active() {
                 self.tiled_panes.unset_fullscreen();
             }
-            let _closed_pane = self.tiled_panes.remove_pane(id);
+            if let Some(mut closed_pane) = self.tiled_panes.remove_pane(id) {
+                closed_pane.remove_persisted_scrollback();
+            }
             self.set_force_render();
             self.tiled_panes.set_force_render();
             if self.auto_layout && !self.swap_layouts.is_tiled_damaged() {
@@ -3599,6 +3728,32 @@ impl Tab {
             ))
             .with_context(err_context)
     }
+    pub fn edit_last_command_output(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to edit last command output for client {client_id}");
+
+        let Some(last_command_output) = self
+            .get_active_pane(client_id)
+            .and_then(|p| p.last_command_output())
+        else {
+            log::warn!("No command output found, is shell integration (OSC 133) enabled?");
+            return Ok(());
+        };
+        let mut file = temp_dir();
+        file.push(format!("{}.dump", Uuid::new_v4()));
+        self.os_api
+            .write_to_file(
+                last_command_output,
+                Some(String::from(file.to_string_lossy())),
+            )
+            .with_context(err_context)?;
+        self.senders
+            .send_to_pty(PtyInstruction::OpenInPlaceEditor(
+                file,
+                None,
+                ClientTabIndexOrPaneId::ClientId(client_id),
+            ))
+            .with_context(err_context)
+    }
     pub fn edit_scrollback_for_pane_with_id(&mut self, pane_id: PaneId) -> Result<()> {
         if let PaneId::Terminal(_terminal_pane_id) = pane_id {
             let mut file = temp_dir();
@@ -3760,6 +3915,33 @@ impl Tab {
         }
     }
 
+    pub fn scroll_active_terminal_to_previous_prompt(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context =
+            || format!("failed to scroll to previous prompt in active pane for client {client_id}");
+
+        let active_pane = self
+            .get_active_pane_or_floating_pane_mut(client_id)
+            .with_context(err_context)?;
+        active_pane.scroll_to_previous_prompt(client_id);
+        Ok(())
+    }
+
+    pub fn scroll_active_terminal_to_next_prompt(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context =
+            || format!("failed to scroll to next prompt in active pane for client {client_id}");
+
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.scroll_to_next_prompt(client_id);
+            if !active_pane.is_scrolled() {
+                if let PaneId::Terminal(raw_fd) = active_pane.pid() {
+                    self.process_pending_vte_events(raw_fd)
+                        .with_context(err_context)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn scroll_active_terminal_to_top(&mut self, client_id: ClientId) -> Result<()> {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             active_pane.clear_scroll();
@@ -3799,6 +3981,34 @@ impl Tab {
         Ok(())
     }
 
+    pub fn scroll_viewer(
+        &mut self,
+        scroll: ViewerScroll,
+        point: Option<&Position>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to scroll the view of client {client_id}");
+
+        let pane = match point {
+            Some(point) => self.get_pane_at(point, false).with_context(err_context)?,
+            None => self.get_active_pane_or_floating_pane_mut(client_id),
+        };
+        if let Some(pane) = pane {
+            let page = pane.get_content_rows().max(1).saturating_sub(1);
+            match scroll {
+                ViewerScroll::Up(lines) => pane.viewer_scroll_up(lines, client_id),
+                ViewerScroll::Down(lines) => pane.viewer_scroll_down(lines, client_id),
+                ViewerScroll::PageUp => pane.viewer_scroll_up(page, client_id),
+                ViewerScroll::PageDown => pane.viewer_scroll_down(page, client_id),
+                ViewerScroll::HalfPageUp => pane.viewer_scroll_up(page / 2, client_id),
+                ViewerScroll::HalfPageDown => pane.viewer_scroll_down(page / 2, client_id),
+                ViewerScroll::ToTop => pane.viewer_scroll_up(usize::MAX, client_id),
+                ViewerScroll::ToBottom => pane.viewer_scroll_down(usize::MAX, client_id),
+            }
+        }
+        Ok(())
+    }
+
     pub fn handle_scrollwheel_up(
         &mut self,
         point: &Position,
@@ -4553,6 +4763,23 @@ impl Tab {
         Ok(())
     }
 
+    pub fn copy_last_command_output(&self, client_id: ClientId) -> Result<()> {
+        let last_command_output = self
+            .get_active_pane(client_id)
+            .and_then(|p| p.last_command_output());
+        match last_command_output {
+            Some(last_command_output) => self
+                .write_selection_to_clipboard(&last_command_output)
+                .with_context(|| {
+                    format!("failed to copy last command output for client {client_id}")
+                })?,
+            None => {
+                log::warn!("No command output found, is shell integration (OSC 133) enabled?")
+            },
+        }
+        Ok(())
+    }
+
     fn write_selection_to_clipboard(&self, selection: &str) -> Result<()> {
         let err_context = || format!("failed to write selection to clipboard: '{}'", selection);
 
@@ -4594,6 +4821,42 @@ impl Tab {
 
         Ok(())
     }
+    pub fn forward_notification_to_clients(
+        &self,
+        title: &Option<String>,
+        body: &str,
+    ) -> Result<()> {
+        let err_context = || format!("failed to forward notification to clients");
+
+        let notification = match title {
+            Some(title) => format!("\u{1b}]777;notify;{};{}\u{1b}\\", title, body),
+            None => format!("\u{1b}]9;{}\u{1b}\\", body),
+        };
+        let mut output = Output::default();
+        let connected_clients: HashSet<ClientId> =
+            { self.connected_clients.borrow().iter().copied().collect() };
+        output.add_clients(&connected_clients, self.link_handler.clone(), None);
+        output.add_pre_vte_instruction_to_multiple_clients(
+            connected_clients.iter().copied(),
+            &notification,
+        );
+        output
+            .serialize()
+            .and_then(|serialized_output| {
+                self.senders
+                    .send_to_server(ServerInstruction::Render(Some(serialized_output)))
+            })
+            .with_context(err_context)
+    }
+    pub fn drain_pane_notifications(&mut self) -> Vec<(PaneId, Option<String>, String)> {
+        self.pending_pane_notifications.drain(..).collect()
+    }
+    pub fn has_unread_notifications(&self) -> bool {
+        self.has_unread_notifications
+    }
+    pub fn set_has_unread_notifications(&mut self, has_unread_notifications: bool) {
+        self.has_unread_notifications = has_unread_notifications;
+    }
     pub fn visible(&mut self, visible: bool) -> Result<()> {
         let pids_in_this_tab = self.tiled_panes.pane_ids().filter_map(|p| match p {
             PaneId::Plugin(pid) => Some(pid),
@@ -4738,6 +5001,24 @@ impl Tab {
         }
     }
 
+    pub fn toggle_search_regex(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.toggle_search_regex();
+        }
+    }
+
+    pub fn list_search_matches(&mut self, client_id: ClientId) -> Vec<ScrollbackSearchMatch> {
+        self.get_active_pane_or_floating_pane_mut(client_id)
+            .map(|active_pane| active_pane.list_search_matches())
+            .unwrap_or_default()
+    }
+
+    pub fn search_jump_to_match(&mut self, index: usize, client_id: ClientId) -> bool {
+        self.get_active_pane_or_floating_pane_mut(client_id)
+            .map(|active_pane| active_pane.search_jump_to_match(index))
+            .unwrap_or(false)
+    }
+
     pub fn clear_search(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             active_pane.clear_search();
@@ -5402,6 +5683,7 @@ pub fn pane_info_for_pane(
     pane_info.exited = pane.exited();
     pane_info.exit_status = pane.exit_status();
     pane_info.is_held = pane.is_held();
+    pane_info.cwd = pane.cwd();
     let index_in_pane_group: BTreeMap<ClientId, usize> = current_pane_group
         .iter()
         .filter_map(|(client_id, pane_ids)| {
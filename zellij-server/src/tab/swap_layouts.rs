@@ -70,6 +70,41 @@ impl SwapLayouts {
     pub fn is_tiled_damaged(&self) -> bool {
         self.is_tiled_damaged
     }
+    pub fn move_to_tiled_layout_fitting_new_display_area(
+        &mut self,
+        tiled_panes: &TiledPanes,
+        previous_display_area: Size,
+    ) {
+        // when moving between screens of different sizes, a swap layout constrained to the new
+        // display area (eg. min_columns) takes precedence over the current one
+        let position = self.swap_tiled_layouts.iter().position(|(layouts, _name)| {
+            layouts.keys().any(|constraint| {
+                !constraint.fits_display_area(&previous_display_area)
+                    && self.state_fits_tiled_panes_constraint(constraint, tiled_panes)
+            })
+        });
+        if let Some(position) = position {
+            self.current_tiled_layout_position = position;
+        }
+    }
+    pub fn move_to_floating_layout_fitting_new_display_area(
+        &mut self,
+        floating_panes: &FloatingPanes,
+        previous_display_area: Size,
+    ) {
+        let position = self
+            .swap_floating_layouts
+            .iter()
+            .position(|(layouts, _name)| {
+                layouts.keys().any(|constraint| {
+                    !constraint.fits_display_area(&previous_display_area)
+                        && self.state_fits_floating_panes_constraint(constraint, floating_panes)
+                })
+            });
+        if let Some(position) = position {
+            self.current_floating_layout_position = position;
+        }
+    }
     pub fn tiled_layout_info(&self) -> (Option<String>, bool) {
         // (swap_layout_name, is_swap_layout_dirty)
         match self
@@ -172,36 +207,16 @@ impl SwapLayouts {
         constraint: &LayoutConstraint,
         tiled_panes: &TiledPanes,
     ) -> bool {
-        match constraint {
-            LayoutConstraint::MaxPanes(max_panes) => {
-                tiled_panes.visible_panes_count() <= *max_panes
-            },
-            LayoutConstraint::MinPanes(min_panes) => {
-                tiled_panes.visible_panes_count() >= *min_panes
-            },
-            LayoutConstraint::ExactPanes(pane_count) => {
-                tiled_panes.visible_panes_count() == *pane_count
-            },
-            LayoutConstraint::NoConstraint => true,
-        }
+        constraint.fits_pane_count(tiled_panes.visible_panes_count())
+            && constraint.fits_display_area(&self.display_area.borrow())
     }
     fn state_fits_floating_panes_constraint(
         &self,
         constraint: &LayoutConstraint,
         floating_panes: &FloatingPanes,
     ) -> bool {
-        match constraint {
-            LayoutConstraint::MaxPanes(max_panes) => {
-                floating_panes.visible_panes_count() <= *max_panes
-            },
-            LayoutConstraint::MinPanes(min_panes) => {
-                floating_panes.visible_panes_count() >= *min_panes
-            },
-            LayoutConstraint::ExactPanes(pane_count) => {
-                floating_panes.visible_panes_count() == *pane_count
-            },
-            LayoutConstraint::NoConstraint => true,
-        }
+        constraint.fits_pane_count(floating_panes.visible_panes_count())
+            && constraint.fits_display_area(&self.display_area.borrow())
     }
     pub fn swap_tiled_panes(
         &mut self,
@@ -1,4 +1,5 @@
-use super::{Output, Tab};
+use super::{Output, Tab, ViewerScroll};
+use crate::panes::kitty_graphics::KittyGraphicsOutputState;
 use crate::panes::sixel::SixelImageStore;
 use crate::screen::CopyOptions;
 use crate::Arc;
@@ -1268,6 +1269,53 @@ fn dump_screen() {
     );
 }
 
+#[test]
+fn viewer_scroll_only_changes_the_view_of_the_scrolling_client() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let viewer_client_id = 2;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    tab.add_client(viewer_client_id, None).unwrap();
+    let mut output = Output::default();
+    let pane_content: String = (0..40).map(|i| format!("line {i}\n\r")).collect();
+    tab.handle_pty_bytes(1, Vec::from(pane_content.as_bytes()))
+        .unwrap();
+    tab.render(&mut output).unwrap();
+    output.serialize().unwrap(); // drain the initial render
+    tab.scroll_viewer(ViewerScroll::Up(10), None, viewer_client_id)
+        .unwrap();
+    tab.render(&mut output).unwrap();
+    let serialized_output = output.serialize().unwrap();
+    let viewer_snapshot = take_snapshot(
+        serialized_output.get(&viewer_client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert!(
+        viewer_snapshot.contains("line 13"),
+        "viewer sees its scrolled back view: {viewer_snapshot}"
+    );
+    assert!(
+        !viewer_snapshot.contains("line 38"),
+        "viewer no longer sees the bottom of the pane: {viewer_snapshot}"
+    );
+    assert!(
+        !serialized_output
+            .get(&client_id)
+            .map(|o| o.contains("line 13"))
+            .unwrap_or(false),
+        "other clients are not scrolled"
+    );
+    assert!(
+        !tab.get_active_pane(client_id).unwrap().is_scrolled(),
+        "shared scroll position did not move"
+    );
+}
+
 #[test]
 fn clear_screen() {
     let size = Size {
@@ -3233,7 +3281,12 @@ fn move_floating_pane_with_sixel_image() {
         width: 8,
         height: 21,
     })));
-    let mut output = Output::new(sixel_image_store.clone(), character_cell_size, true);
+    let mut output = Output::new(
+        sixel_image_store.clone(),
+        Rc::new(RefCell::new(KittyGraphicsOutputState::default())),
+        character_cell_size,
+        true,
+    );
 
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
     tab.new_pane(
@@ -3285,7 +3338,12 @@ fn floating_pane_above_sixel_image() {
         width: 8,
         height: 21,
     })));
-    let mut output = Output::new(sixel_image_store.clone(), character_cell_size, true);
+    let mut output = Output::new(
+        sixel_image_store.clone(),
+        Rc::new(RefCell::new(KittyGraphicsOutputState::default())),
+        character_cell_size,
+        true,
+    );
 
     tab.toggle_floating_panes(Some(client_id), None).unwrap();
     tab.new_pane(
@@ -4710,6 +4768,99 @@ fn can_swap_tiled_layout_at_runtime() {
     assert_snapshot!(snapshot);
 }
 
+#[test]
+fn swap_tiled_layout_follows_display_area_constraints_on_resize() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let swap_layouts = r#"
+        layout {
+            swap_tiled_layout name="default" {
+                tab max_panes=2 {
+                    pane
+                    pane
+                }
+            }
+            swap_tiled_layout name="wide" {
+                tab max_panes=2 min_columns=150 split_direction="vertical" {
+                    pane
+                    pane
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(swap_layouts, Some("file_name.kdl".into()), None, None).unwrap();
+    let swap_tiled_layouts = layout.swap_tiled_layouts.clone();
+    let swap_floating_layouts = layout.swap_floating_layouts.clone();
+    let stacked_resize = true;
+    let mut tab = create_new_tab_with_swap_layouts(
+        size,
+        ModeInfo::default(),
+        (swap_tiled_layouts, swap_floating_layouts),
+        None,
+        true,
+        stacked_resize,
+    );
+    tab.new_pane(
+        PaneId::Terminal(2),
+        None,
+        None,
+        false,
+        true,
+        NewPanePlacement::default(),
+        Some(client_id),
+    )
+    .unwrap();
+    let panes_are_side_by_side = |tab: &Tab| {
+        let first = tab
+            .tiled_panes
+            .get_pane(PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size();
+        let second = tab
+            .tiled_panes
+            .get_pane(PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size();
+        first.y == second.y && first.x != second.x
+    };
+    assert_eq!(
+        tab.swap_layouts.tiled_layout_info().0,
+        Some("default".to_owned()),
+        "wide layout does not fit the initial display area"
+    );
+    assert!(!panes_are_side_by_side(&tab), "default layout applied");
+
+    tab.resize_whole_tab(Size {
+        cols: 200,
+        rows: 20,
+    })
+    .unwrap();
+    assert_eq!(
+        tab.swap_layouts.tiled_layout_info().0,
+        Some("wide".to_owned()),
+        "wide layout chosen once it fits the display area"
+    );
+    assert!(panes_are_side_by_side(&tab), "wide layout applied");
+
+    tab.resize_whole_tab(Size {
+        cols: 121,
+        rows: 20,
+    })
+    .unwrap();
+    assert_eq!(
+        tab.swap_layouts.tiled_layout_info().0,
+        Some("default".to_owned()),
+        "default layout chosen again once the wide layout no longer fits"
+    );
+    assert!(
+        !panes_are_side_by_side(&tab),
+        "default layout applied again"
+    );
+}
+
 #[test]
 fn can_swap_floating_layout_at_runtime() {
     let size = Size {
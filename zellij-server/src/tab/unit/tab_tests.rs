@@ -886,6 +886,181 @@ pub fn cannot_split_panes_horizontally_when_active_pane_has_fixed_rows() {
     assert_eq!(tab.tiled_panes.panes.len(), 2, "Tab still has two panes");
 }
 
+#[test]
+pub fn cannot_resize_pane_below_its_min_size() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    let mut bounded_child = TiledPaneLayout::default();
+    bounded_child.min_size = Some(SplitSize::Fixed(55));
+    initial_layout.children = vec![bounded_child, TiledPaneLayout::default()];
+    let mut tab = create_new_tab_with_layout(size, initial_layout);
+    tab.focus_pane_with_id(PaneId::Terminal(1), false, 1)
+        .unwrap();
+    for _ in 0..5 {
+        tab_resize_left(&mut tab, 1);
+    }
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(0))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        55,
+        "pane with min size was not resized below it"
+    );
+}
+
+#[test]
+pub fn cannot_resize_pane_past_its_max_size() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    let mut bounded_child = TiledPaneLayout::default();
+    bounded_child.max_size = Some(SplitSize::Percent(55));
+    initial_layout.children = vec![bounded_child, TiledPaneLayout::default()];
+    let mut tab = create_new_tab_with_layout(size, initial_layout);
+    for _ in 0..5 {
+        tab_resize_right(&mut tab, 1);
+    }
+    let bounded_pane_cols = tab
+        .tiled_panes
+        .panes
+        .get(&PaneId::Terminal(0))
+        .unwrap()
+        .position_and_size()
+        .cols
+        .as_usize();
+    assert!(
+        bounded_pane_cols <= 66,
+        "pane with max size was not resized past it (cols: {})",
+        bounded_pane_cols
+    );
+}
+
+#[test]
+pub fn pane_keeps_its_min_size_when_screen_shrinks() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    let mut bounded_child = TiledPaneLayout::default();
+    bounded_child.min_size = Some(SplitSize::Fixed(50));
+    initial_layout.children = vec![bounded_child, TiledPaneLayout::default()];
+    let mut tab = create_new_tab_with_layout(size, initial_layout);
+    tab.resize_whole_tab(Size { cols: 80, rows: 20 }).unwrap();
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(0))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        50,
+        "pane kept its min size"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        30,
+        "other pane took the rest of the space"
+    );
+}
+
+#[test]
+pub fn new_pane_does_not_split_pane_below_its_min_size() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    let mut bounded_child = TiledPaneLayout::default();
+    bounded_child.min_size = Some(SplitSize::Fixed(50));
+    initial_layout.children = vec![TiledPaneLayout::default(), bounded_child];
+    let mut tab = create_new_tab_with_layout(size, initial_layout);
+    let client_id = None; // not splitting the focused pane
+    tab.new_pane(
+        PaneId::Terminal(2),
+        None,
+        None,
+        false,
+        true,
+        NewPanePlacement::default(),
+        client_id,
+    )
+    .unwrap();
+    assert_eq!(tab.tiled_panes.panes.len(), 3, "The tab has three panes");
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        61,
+        "pane with min size was not split"
+    );
+}
+
+#[test]
+pub fn closing_pane_prefers_growing_neighbors_within_their_max_size() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    let mut bounded_child = TiledPaneLayout::default();
+    bounded_child.max_size = Some(SplitSize::Fixed(50));
+    initial_layout.children = vec![
+        bounded_child,
+        TiledPaneLayout::default(),
+        TiledPaneLayout::default(),
+    ];
+    let mut tab = create_new_tab_with_layout(size, initial_layout);
+    tab.close_pane(PaneId::Terminal(1), false);
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(0))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        40,
+        "pane with max size did not grow"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        81,
+        "its neighbor took the freed space"
+    );
+}
+
 #[test]
 pub fn toggle_focused_pane_fullscreen() {
     let size = Size {
@@ -1197,6 +1372,47 @@ fn switch_to_prev_pane_fullscreen() {
     );
 }
 
+#[test]
+pub fn closing_a_pane_removes_its_persisted_scrollback() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let stacked_resize = true;
+    let mut tab = create_new_tab(size, stacked_resize);
+    tab.horizontal_split(PaneId::Terminal(2), None, 1).unwrap();
+    tab.handle_pty_bytes(
+        2,
+        Vec::from("1\r\n2\r\n3\r\n4\r\n5\r\n6".repeat(5).as_bytes()),
+    )
+    .unwrap();
+    let session_folder = tempfile::tempdir().unwrap();
+    tab.persist_scrollbacks(session_folder.path(), 0);
+    let scrollback_file = |tab: &Tab, pane_id| {
+        tab.get_pane_with_id(pane_id)
+            .and_then(|pane| pane.persisted_scrollback_file())
+            .map(|file_name| session_folder.path().join(file_name))
+            .unwrap()
+    };
+    let closed_pane_file = scrollback_file(&tab, PaneId::Terminal(2));
+    let remaining_pane_file = scrollback_file(&tab, PaneId::Terminal(1));
+    assert_ne!(
+        closed_pane_file, remaining_pane_file,
+        "each pane has its own scrollback store"
+    );
+    assert!(closed_pane_file.exists());
+
+    tab.close_pane(PaneId::Terminal(2), false);
+    assert!(
+        !closed_pane_file.exists(),
+        "scrollback of closed pane was removed"
+    );
+    assert!(
+        remaining_pane_file.exists(),
+        "scrollback of other pane was kept"
+    );
+}
+
 #[test]
 pub fn close_pane_with_another_pane_above_it() {
     // ┌───────────┐            ┌───────────┐
@@ -1,4 +1,4 @@
-use crate::output::Output;
+use crate::output::{InvalidatedRegion, Output};
 use crate::panes::PaneId;
 use crate::tab::Pane;
 use crate::ui::boundaries::Boundaries;
@@ -75,10 +75,27 @@ impl<'a> PaneContentsAndUi<'a> {
         // and we can clear them from the UI below
         drop(self.pane.drain_fake_cursors());
 
+        // clients who scrolled their own view of the pane get it rendered separately, and before
+        // the shared render so that it does not mark the pane as rendered for them
+        let (viewer_scrolled_clients, clients): (Vec<ClientId>, Vec<ClientId>) =
+            clients.partition(|client_id| self.pane.has_viewer_scroll(*client_id));
+        for client_id in viewer_scrolled_clients {
+            self.render_pane_contents_for_client(client_id)
+                .context(err_context)?;
+        }
+        let kitty_image_chunks = self.pane.kitty_image_chunks();
+        if !kitty_image_chunks.is_empty() {
+            // kitty images are diffed against what each client already displays, so we add
+            // them whether or not the pane itself changed
+            self.output.add_kitty_image_chunks_to_multiple_clients(
+                kitty_image_chunks.clone(),
+                clients.iter().copied(),
+                self.z_index,
+            );
+        }
         if let Some((character_chunks, raw_vte_output, sixel_image_chunks)) =
             self.pane.render(None).context(err_context)?
         {
-            let clients: Vec<ClientId> = clients.collect();
             self.output
                 .add_character_chunks_to_multiple_clients(
                     character_chunks,
@@ -91,6 +108,15 @@ impl<'a> PaneContentsAndUi<'a> {
                 clients.iter().copied(),
                 self.z_index,
             );
+            if !kitty_image_chunks.is_empty() {
+                self.output
+                    .add_kitty_image_placeholders_to_multiple_clients(
+                        kitty_image_chunks,
+                        clients.iter().copied(),
+                        self.z_index,
+                    )
+                    .context(err_context)?;
+            }
             if let Some(raw_vte_output) = raw_vte_output {
                 if !raw_vte_output.is_empty() {
                     self.output.add_post_vte_instruction_to_multiple_clients(
@@ -102,6 +128,10 @@ impl<'a> PaneContentsAndUi<'a> {
                             raw_vte_output
                         ),
                     );
+                    self.output.invalidate_region_for_multiple_clients(
+                        clients.iter().copied(),
+                        self.pane_region(),
+                    );
                 }
             }
         }
@@ -133,6 +163,8 @@ impl<'a> PaneContentsAndUi<'a> {
                         raw_vte_output
                     ),
                 );
+                self.output
+                    .invalidate_region_for_client(client_id, self.pane_region());
             }
         }
         Ok(())
@@ -157,15 +189,12 @@ impl<'a> PaneContentsAndUi<'a> {
                 *fake_cursor_client_id,
                 self.style.colors.multiplayer_user_colors,
             ) {
-                let cursor_is_visible = self
+                let cursor_coordinates = self
                     .pane
                     .cursor_coordinates()
-                    .map(|(x, y)| {
-                        self.output
-                            .cursor_is_visible(self.pane.x() + x, self.pane.y() + y)
-                    })
-                    .unwrap_or(false);
-                if cursor_is_visible {
+                    .map(|(x, y)| (self.pane.x() + x, self.pane.y() + y))
+                    .filter(|(x, y)| self.output.cursor_is_visible(*x, *y));
+                if let Some((cursor_x, cursor_y)) = cursor_coordinates {
                     if let Some(vte_output) = self.pane.render_fake_cursor(colors.0, colors.1) {
                         self.output.add_post_vte_instruction_to_client(
                             client_id,
@@ -176,6 +205,15 @@ impl<'a> PaneContentsAndUi<'a> {
                                 vte_output
                             ),
                         );
+                        self.output.invalidate_region_for_client(
+                            client_id,
+                            InvalidatedRegion {
+                                x: cursor_x,
+                                y: cursor_y,
+                                columns: 1,
+                                rows: 1,
+                            },
+                        );
                     }
                 }
             }
@@ -299,6 +337,14 @@ impl<'a> PaneContentsAndUi<'a> {
             self.pane_is_stacked_under,
         );
     }
+    fn pane_region(&self) -> InvalidatedRegion {
+        InvalidatedRegion {
+            x: self.pane.x(),
+            y: self.pane.y(),
+            columns: self.pane.cols(),
+            rows: self.pane.rows(),
+        }
+    }
     fn frame_color(
         &self,
         client_id: ClientId,
@@ -0,0 +1,405 @@
+use super::route_thread_main;
+use crate::panes::PaneId;
+use crate::{
+    background_jobs::BackgroundJob,
+    channels::SenderWithContext,
+    os_input_output::{AsyncReader, Pid, ServerOsApi},
+    plugins::PluginInstruction,
+    pty::PtyInstruction,
+    pty_writer::PtyWriteInstruction,
+    screen::ScreenInstruction,
+    ClientId, ServerInstruction, SessionMetaData, SessionState, ThreadSenders,
+};
+use interprocess::local_socket::{LocalSocketListener, LocalSocketStream};
+use std::collections::HashMap;
+use std::os::unix::io::RawFd;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use zellij_utils::channels::{self, ChannelWithContext, Receiver};
+use zellij_utils::data::{Palette, PluginCapabilities, WebSharing};
+use zellij_utils::errors::prelude::*;
+use zellij_utils::input::actions::Action;
+use zellij_utils::input::command::{RunCommand, TerminalAction};
+use zellij_utils::input::layout::Layout;
+use zellij_utils::ipc::{
+    ClientAttributes, ClientToServerMsg, IpcReceiverWithContext, IpcSenderWithContext,
+    ServerToClientMsg,
+};
+use zellij_utils::pane_size::Size;
+
+#[derive(Clone, Default)]
+struct FakeInputOutput {
+    server_to_client_messages: Arc<Mutex<HashMap<ClientId, Vec<ServerToClientMsg>>>>,
+}
+
+impl ServerOsApi for FakeInputOutput {
+    fn set_terminal_size_using_terminal_id(
+        &self,
+        _terminal_id: u32,
+        _cols: u16,
+        _rows: u16,
+        _width_in_pixels: Option<u16>,
+        _height_in_pixels: Option<u16>,
+    ) -> Result<()> {
+        unimplemented!()
+    }
+    fn spawn_terminal(
+        &self,
+        _file_to_open: TerminalAction,
+        _quit_db: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>,
+        _default_editor: Option<PathBuf>,
+    ) -> Result<(u32, RawFd, RawFd)> {
+        unimplemented!()
+    }
+    fn read_from_tty_stdout(&self, _fd: RawFd, _buf: &mut [u8]) -> Result<usize> {
+        unimplemented!()
+    }
+    fn async_file_reader(&self, _fd: RawFd) -> Box<dyn AsyncReader> {
+        unimplemented!()
+    }
+    fn write_to_tty_stdin(&self, _id: u32, _buf: &[u8]) -> Result<usize> {
+        unimplemented!()
+    }
+    fn tcdrain(&self, _id: u32) -> Result<()> {
+        unimplemented!()
+    }
+    fn kill(&self, _pid: Pid) -> Result<()> {
+        unimplemented!()
+    }
+    fn force_kill(&self, _pid: Pid) -> Result<()> {
+        unimplemented!()
+    }
+    fn box_clone(&self) -> Box<dyn ServerOsApi> {
+        Box::new((*self).clone())
+    }
+    fn send_to_client(&self, client_id: ClientId, msg: ServerToClientMsg) -> Result<()> {
+        self.server_to_client_messages
+            .lock()
+            .unwrap()
+            .entry(client_id)
+            .or_insert_with(Vec::new)
+            .push(msg);
+        Ok(())
+    }
+    fn new_client(
+        &mut self,
+        _client_id: ClientId,
+        _stream: LocalSocketStream,
+    ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
+        unimplemented!()
+    }
+    fn remove_client(&mut self, _client_id: ClientId) -> Result<()> {
+        unimplemented!()
+    }
+    fn load_palette(&self) -> Palette {
+        unimplemented!()
+    }
+    fn get_cwd(&self, _pid: Pid) -> Option<PathBuf> {
+        unimplemented!()
+    }
+    fn write_to_file(&mut self, _contents: String, _filename: Option<String>) -> Result<()> {
+        unimplemented!()
+    }
+    fn re_run_command_in_terminal(
+        &self,
+        _terminal_id: u32,
+        _run_command: RunCommand,
+        _quit_cb: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>, // u32 is the exit status
+    ) -> Result<(RawFd, RawFd)> {
+        unimplemented!()
+    }
+    fn clear_terminal_id(&self, _terminal_id: u32) -> Result<()> {
+        unimplemented!()
+    }
+}
+
+struct RoutedInstructions {
+    to_screen: Vec<ScreenInstruction>,
+    to_pty: Vec<PtyInstruction>,
+    to_pty_writer: Vec<PtyWriteInstruction>,
+}
+
+fn drain<T>(receiver: &Receiver<(T, zellij_utils::errors::ErrorContext)>) -> Vec<T> {
+    receiver
+        .try_iter()
+        .map(|(instruction, _)| instruction)
+        .collect()
+}
+
+// sends the messages to the route thread of `client_id` and returns the instructions it sent to
+// the other threads once the client disconnected
+fn route_messages_from_client(
+    session_state: SessionState,
+    client_id: ClientId,
+    messages: Vec<ClientToServerMsg>,
+) -> RoutedInstructions {
+    let (to_screen, screen_receiver): ChannelWithContext<ScreenInstruction> = channels::unbounded();
+    let (to_pty, pty_receiver): ChannelWithContext<PtyInstruction> = channels::unbounded();
+    let (to_plugin, _plugin_receiver): ChannelWithContext<PluginInstruction> =
+        channels::unbounded();
+    let (to_server, _server_receiver): ChannelWithContext<ServerInstruction> =
+        channels::unbounded();
+    let (to_pty_writer, pty_writer_receiver): ChannelWithContext<PtyWriteInstruction> =
+        channels::unbounded();
+    let (to_background_jobs, _background_jobs_receiver): ChannelWithContext<BackgroundJob> =
+        channels::unbounded();
+    let to_server = SenderWithContext::new(to_server);
+    let mut current_input_modes = HashMap::new();
+    current_input_modes.insert(client_id, Default::default());
+    let session_metadata = SessionMetaData {
+        senders: ThreadSenders {
+            to_screen: Some(SenderWithContext::new(to_screen)),
+            to_pty: Some(SenderWithContext::new(to_pty)),
+            to_plugin: Some(SenderWithContext::new(to_plugin)),
+            to_pty_writer: Some(SenderWithContext::new(to_pty_writer)),
+            to_background_jobs: Some(SenderWithContext::new(to_background_jobs)),
+            to_server: Some(to_server.clone()),
+            should_silently_fail: true,
+        },
+        capabilities: PluginCapabilities::default(),
+        default_shell: None,
+        client_attributes: ClientAttributes::default(),
+        screen_thread: None,
+        pty_thread: None,
+        plugin_thread: None,
+        pty_writer_thread: None,
+        background_jobs_thread: None,
+        layout: Box::new(Layout::default()),
+        session_configuration: Default::default(),
+        current_input_modes,
+        web_sharing: WebSharing::Off,
+    };
+
+    let socket_path = std::env::temp_dir().join(format!(
+        "zellij-route-test-{}-{}",
+        std::process::id(),
+        uuid::Uuid::new_v4()
+    ));
+    let listener = LocalSocketListener::bind(&*socket_path).unwrap();
+    let mut client_sender: IpcSenderWithContext<ClientToServerMsg> =
+        IpcSenderWithContext::new(LocalSocketStream::connect(&*socket_path).unwrap());
+    let server_side = listener.accept().unwrap();
+    for message in messages {
+        client_sender.send(message).unwrap();
+    }
+    // disconnecting the client ends the route thread once it handled all the messages
+    drop(client_sender);
+    // we keep the session data around until we drained the instructions, since dropping it sends
+    // an Exit instruction to all threads
+    let session_data = Arc::new(RwLock::new(Some(session_metadata)));
+    route_thread_main(
+        session_data.clone(),
+        Arc::new(RwLock::new(session_state)),
+        Box::new(FakeInputOutput::default()),
+        to_server,
+        IpcReceiverWithContext::new(server_side),
+        client_id,
+    )
+    .unwrap();
+    let _ = std::fs::remove_file(&socket_path);
+    RoutedInstructions {
+        to_screen: drain(&screen_receiver),
+        to_pty: drain(&pty_receiver),
+        to_pty_writer: drain(&pty_writer_receiver),
+    }
+}
+
+fn session_state_with_client(client_id: ClientId, size: Size, is_read_only: bool) -> SessionState {
+    let mut session_state = SessionState::new();
+    session_state.set_client_data(client_id, size, false);
+    session_state.set_client_read_only(client_id, is_read_only);
+    session_state
+}
+
+fn mutating_actions() -> Vec<ClientToServerMsg> {
+    vec![
+        ClientToServerMsg::Action(Action::Write(None, vec![b'a'], false), None, None),
+        ClientToServerMsg::Action(Action::NewPane(None, None, false), None, None),
+        ClientToServerMsg::Action(Action::CloseFocus, None, None),
+        ClientToServerMsg::Action(Action::ScrollToPreviousPrompt, None, None),
+    ]
+}
+
+#[test]
+fn mutating_actions_from_read_only_clients_are_dropped() {
+    let client_id = 1;
+    let size = Size { rows: 20, cols: 80 };
+    let routed = route_messages_from_client(
+        session_state_with_client(client_id, size, true),
+        client_id,
+        mutating_actions(),
+    );
+    assert!(
+        routed.to_screen.is_empty(),
+        "no screen instructions routed: {:?}",
+        routed.to_screen
+    );
+    assert!(routed.to_pty.is_empty(), "no pty instructions routed");
+    assert!(
+        routed.to_pty_writer.is_empty(),
+        "no pty writer instructions routed"
+    );
+}
+
+#[test]
+fn mutating_actions_from_read_write_clients_are_routed() {
+    let client_id = 1;
+    let size = Size { rows: 20, cols: 80 };
+    let routed = route_messages_from_client(
+        session_state_with_client(client_id, size, false),
+        client_id,
+        mutating_actions(),
+    );
+    assert!(
+        routed
+            .to_screen
+            .iter()
+            .any(|i| matches!(i, ScreenInstruction::WriteCharacter(..))),
+        "write routed to screen"
+    );
+    assert!(
+        routed
+            .to_screen
+            .iter()
+            .any(|i| matches!(i, ScreenInstruction::ScrollToPreviousPrompt(..))),
+        "scroll to prompt routed to screen"
+    );
+    assert!(
+        routed
+            .to_pty
+            .iter()
+            .any(|i| matches!(i, PtyInstruction::SpawnTerminal(..))),
+        "new pane routed to pty"
+    );
+}
+
+#[test]
+fn viewer_local_actions_from_read_only_clients_are_routed() {
+    let client_id = 1;
+    let size = Size { rows: 20, cols: 80 };
+    let routed = route_messages_from_client(
+        session_state_with_client(client_id, size, true),
+        client_id,
+        vec![
+            ClientToServerMsg::Action(Action::GoToNextTab, None, None),
+            ClientToServerMsg::Action(Action::GoToTab(1), None, None),
+        ],
+    );
+    assert!(
+        routed
+            .to_screen
+            .iter()
+            .any(|i| matches!(i, ScreenInstruction::SwitchTabNext(1))),
+        "tab switch routed to screen"
+    );
+    assert!(
+        routed
+            .to_screen
+            .iter()
+            .any(|i| matches!(i, ScreenInstruction::GoToTab(1, Some(1)))),
+        "go to tab routed to screen"
+    );
+}
+
+#[test]
+fn scrolling_from_read_only_clients_is_routed_while_writes_are_dropped() {
+    let client_id = 1;
+    let size = Size { rows: 20, cols: 80 };
+    let routed = route_messages_from_client(
+        session_state_with_client(client_id, size, true),
+        client_id,
+        vec![
+            ClientToServerMsg::Action(Action::ScrollUp, None, None),
+            ClientToServerMsg::Action(Action::Write(None, vec![b'a'], false), None, None),
+            ClientToServerMsg::Action(Action::PageScrollDown, None, None),
+        ],
+    );
+    assert!(
+        routed
+            .to_screen
+            .iter()
+            .any(|i| matches!(i, ScreenInstruction::ScrollUp(1))),
+        "scroll routed to screen"
+    );
+    assert!(
+        routed
+            .to_screen
+            .iter()
+            .any(|i| matches!(i, ScreenInstruction::PageScrollDown(1))),
+        "page scroll routed to screen"
+    );
+    assert!(
+        !routed
+            .to_screen
+            .iter()
+            .any(|i| matches!(i, ScreenInstruction::WriteCharacter(..))),
+        "write dropped"
+    );
+    assert!(
+        routed.to_pty_writer.is_empty(),
+        "no pty writer instructions routed"
+    );
+}
+
+#[test]
+fn terminal_resize_from_read_only_client_does_not_resize_the_session() {
+    let read_write_client_id = 1;
+    let read_only_client_id = 2;
+    let read_write_client_size = Size {
+        rows: 50,
+        cols: 200,
+    };
+    let mut session_state =
+        session_state_with_client(read_write_client_id, read_write_client_size, false);
+    session_state.set_client_data(read_only_client_id, read_write_client_size, false);
+    session_state.set_client_read_only(read_only_client_id, true);
+    let routed = route_messages_from_client(
+        session_state,
+        read_only_client_id,
+        vec![ClientToServerMsg::TerminalResize(Size {
+            rows: 10,
+            cols: 20,
+        })],
+    );
+    let resizes: Vec<Size> = routed
+        .to_screen
+        .iter()
+        .filter_map(|i| match i {
+            ScreenInstruction::TerminalResize(size) => Some(*size),
+            _ => None,
+        })
+        .collect();
+    assert!(
+        resizes.iter().all(|size| *size == read_write_client_size),
+        "session keeps the size of the read-write client: {:?}",
+        resizes
+    );
+}
+
+#[test]
+fn read_only_clients_only_size_the_session_when_alone() {
+    let mut session_state = SessionState::new();
+    session_state.set_client_data(1, Size { rows: 10, cols: 20 }, false);
+    session_state.set_client_read_only(1, true);
+    assert_eq!(
+        session_state.min_client_terminal_size(),
+        Some(Size { rows: 10, cols: 20 }),
+        "read-only client sizes the session when it is the only one"
+    );
+    session_state.set_client_data(
+        2,
+        Size {
+            rows: 50,
+            cols: 200,
+        },
+        false,
+    );
+    assert_eq!(
+        session_state.min_client_terminal_size(),
+        Some(Size {
+            rows: 50,
+            cols: 200
+        }),
+        "read-only client size ignored once a read-write client is attached"
+    );
+}
@@ -302,6 +302,7 @@ fn create_new_screen(size: Size, advanced_mouse_actions: bool) -> Screen {
         false,
         web_sharing,
         advanced_mouse_actions,
+        false,
         web_server_ip,
         web_server_port,
     );
@@ -2019,6 +2020,150 @@ pub fn send_cli_dump_screen_action() {
     ));
 }
 
+#[test]
+pub fn send_cli_start_and_stop_recording_pane_action() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let recording_dir = tempfile::tempdir().unwrap();
+    let recording_path = recording_dir.path().join("recording.cast");
+    let start_recording = CliAction::StartRecording {
+        path: recording_path.clone(),
+        pane_id: Some("terminal_1".to_owned()),
+    };
+    send_cli_action_to_server(&session_metadata, start_recording, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "not recorded".as_bytes().to_vec(),
+    ));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        1,
+        "recorded".as_bytes().to_vec(),
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let stop_recording = CliAction::StopRecording {
+        pane_id: Some("terminal_1".to_owned()),
+    };
+    send_cli_action_to_server(&session_metadata, stop_recording, client_id);
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        1,
+        "after recording stopped".as_bytes().to_vec(),
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let recording = zellij_utils::asciicast::AsciicastRecording::parse(
+        &std::fs::read_to_string(&recording_path).unwrap(),
+    )
+    .unwrap();
+    let recorded_output: Vec<&str> = recording.events.iter().map(|e| e.data()).collect();
+    assert_eq!(recorded_output, vec!["recorded"]);
+    assert_eq!(recording.header.width, 38);
+    assert_eq!(recording.header.height, 18);
+}
+
+#[test]
+pub fn closing_a_tab_stops_the_recordings_of_its_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size, true);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    let recording_dir = tempfile::tempdir().unwrap();
+    for terminal_id in [1, 2] {
+        screen
+            .start_recording(
+                recording_dir
+                    .path()
+                    .join(format!("recording_{}.cast", terminal_id)),
+                Some(PaneId::Terminal(terminal_id)),
+                1,
+            )
+            .expect("TEST");
+    }
+    screen.close_tab(1).expect("TEST");
+
+    assert!(
+        !screen.pane_recordings.contains_key(&2),
+        "recording of pane in closed tab stopped"
+    );
+    assert!(
+        screen.pane_recordings.contains_key(&1),
+        "recording of pane in other tab still running"
+    );
+}
+
+#[test]
+pub fn closing_a_pane_stops_its_recording() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size, true);
+
+    new_tab(&mut screen, 1, 1);
+    let recording_dir = tempfile::tempdir().unwrap();
+    screen
+        .start_recording(
+            recording_dir.path().join("recording.cast"),
+            Some(PaneId::Terminal(1)),
+            1,
+        )
+        .expect("TEST");
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .close_pane(PaneId::Terminal(1), false);
+    screen.retain_only_existing_pane_recordings();
+
+    assert!(screen.pane_recordings.is_empty(), "recording stopped");
+}
+
+#[test]
+pub fn session_recording_has_the_size_of_the_recorded_client() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size, true);
+
+    new_tab(&mut screen, 1, 1);
+    screen.client_sizes.insert(
+        1,
+        Size {
+            cols: 150,
+            rows: 40,
+        },
+    );
+    let recording_dir = tempfile::tempdir().unwrap();
+    let recording_path = recording_dir.path().join("recording.cast");
+    screen
+        .start_recording(recording_path.clone(), None, 1)
+        .expect("TEST");
+    screen.stop_recording(None, 1).expect("TEST");
+    let recording = zellij_utils::asciicast::AsciicastRecording::parse(
+        &std::fs::read_to_string(&recording_path).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(recording.header.width, 150);
+    assert_eq!(recording.header.height, 40);
+}
+
 #[test]
 pub fn send_cli_edit_scrollback_action() {
     let size = Size { cols: 80, rows: 20 };
@@ -2574,6 +2719,7 @@ pub fn send_cli_new_pane_action_with_default_parameters() {
         command: vec![],
         plugin: None,
         cwd: None,
+        env: vec![],
         floating: false,
         in_place: false,
         name: None,
@@ -2619,6 +2765,7 @@ pub fn send_cli_new_pane_action_with_split_direction() {
         command: vec![],
         plugin: None,
         cwd: None,
+        env: vec![],
         floating: false,
         in_place: false,
         name: None,
@@ -2664,6 +2811,7 @@ pub fn send_cli_new_pane_action_with_command_and_cwd() {
         command: vec!["htop".into()],
         plugin: None,
         cwd: Some("/some/folder".into()),
+        env: vec![],
         floating: false,
         in_place: false,
         name: None,
@@ -2720,6 +2868,7 @@ pub fn send_cli_new_pane_action_with_floating_pane_and_coordinates() {
         command: vec!["htop".into()],
         plugin: None,
         cwd: Some("/some/folder".into()),
+        env: vec![],
         floating: true,
         in_place: false,
         name: None,
@@ -3033,6 +3182,7 @@ pub fn send_cli_new_tab_action_default_params() {
         name: None,
         layout: None,
         layout_dir: None,
+        layout_arg: vec![],
         cwd: None,
     };
     send_cli_action_to_server(&session_metadata, new_tab_action, client_id);
@@ -3073,6 +3223,7 @@ pub fn send_cli_new_tab_action_with_name_and_layout() {
             env!("CARGO_MANIFEST_DIR")
         ))),
         layout_dir: None,
+        layout_arg: vec![],
         cwd: None,
     };
     send_cli_action_to_server(&session_metadata, new_tab_action, client_id);
@@ -21,7 +21,7 @@ pub mod ui_components;
 
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-use zellij_utils::data::{Event, PipeMessage};
+use zellij_utils::data::{Event, PipeMessage, PluginCall};
 
 // use zellij_tile::shim::plugin_api::event::ProtobufEvent;
 
@@ -43,9 +43,33 @@ pub trait ZellijPlugin: Default {
     fn pipe(&mut self, pipe_message: PipeMessage) -> bool {
         false
     } // return true if it should render
+    /// Will be called when another plugin calls this one with [`call_plugin`](shim::call_plugin).
+    /// The returned result is sent back to the caller as an
+    /// [`Event::PluginCallResult`](prelude::Event::PluginCallResult). Return `None` to respond
+    /// later (eg. after running a command) with
+    /// [`respond_to_plugin_call`](shim::respond_to_plugin_call) and the id of the call.
+    fn handle_call(&mut self, call: PluginCall) -> Option<Result<String, String>> {
+        Some(Err(format!(
+            "Method {} is not handled by this plugin",
+            call.method
+        )))
+    }
     /// Will be called either after an `update` that requested it, or when the plugin otherwise needs to be re-rendered (eg. on startup, or when the plugin is resized).
     /// The `rows` and `cols` values represent the "content size" of the plugin (this will not include its surrounding frame if the user has pane frames enabled).
     fn render(&mut self, rows: usize, cols: usize) {}
+    /// Will be called right before the plugin is reloaded (eg. when its `.wasm` file changes and
+    /// it was declared with `watch_for_changes true`), and whenever the session is serialized so
+    /// that the plugin can be resurrected along with it (states larger than 64KiB are not
+    /// serialized).
+    /// The returned state is handed to [`restore_state`](ZellijPlugin::restore_state) of the new
+    /// instance of the plugin once it is loaded. Return `None` to start the new instance afresh.
+    fn save_state(&self) -> Option<String> {
+        None
+    }
+    /// Will be called after `load` with the state returned from
+    /// [`save_state`](ZellijPlugin::save_state) by the instance of the plugin this one replaced,
+    /// or by the instance that ran in the session this one was resurrected from.
+    fn restore_state(&mut self, state: String) {}
 }
 
 /// This trait is used to create workers. Workers can be used by plugins to run longer running
@@ -157,6 +181,23 @@ macro_rules! register_plugin {
             })
         }
 
+        #[no_mangle]
+        pub fn handle_call() {
+            use zellij_tile::shim::plugin_api::plugin_call::ProtobufPluginCall;
+            use zellij_tile::shim::prost::Message;
+            STATE.with(|state| {
+                let protobuf_bytes: Vec<u8> = $crate::shim::object_from_stdin().unwrap();
+                let protobuf_plugin_call: ProtobufPluginCall =
+                    ProtobufPluginCall::decode(protobuf_bytes.as_slice()).unwrap();
+                let plugin_call: $crate::prelude::PluginCall = protobuf_plugin_call.into();
+                let call_id = plugin_call.id;
+                let result = state.borrow_mut().handle_call(plugin_call);
+                if let Some(result) = result {
+                    $crate::shim::respond_to_plugin_call(call_id, result);
+                }
+            })
+        }
+
         #[no_mangle]
         pub fn render(rows: i32, cols: i32) {
             STATE.with(|state| {
@@ -164,6 +205,23 @@ macro_rules! register_plugin {
             });
         }
 
+        #[no_mangle]
+        pub fn save_state() {
+            STATE.with(|state| {
+                if let Some(saved_state) = state.borrow().save_state() {
+                    $crate::shim::object_to_stdout(&saved_state);
+                }
+            });
+        }
+
+        #[no_mangle]
+        pub fn restore_state() {
+            STATE.with(|state| {
+                let saved_state: String = $crate::shim::object_from_stdin().unwrap();
+                state.borrow_mut().restore_state(saved_state);
+            });
+        }
+
         #[no_mangle]
         pub fn plugin_version() {
             println!("{}", $crate::prelude::VERSION);
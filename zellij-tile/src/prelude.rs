@@ -1,5 +1,6 @@
 pub use crate::shim::*;
 pub use crate::*;
+pub use zellij_utils::cli::CliAction;
 pub use zellij_utils::consts::VERSION;
 pub use zellij_utils::data::*;
 pub use zellij_utils::errors::prelude::*;
@@ -1,5 +1,7 @@
 use serde::{de::DeserializeOwned, Serialize};
 use std::collections::{BTreeMap, HashSet};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
 use std::{
     io,
     path::{Path, PathBuf},
@@ -10,7 +12,7 @@ use zellij_utils::input::actions::Action;
 pub use zellij_utils::plugin_api;
 use zellij_utils::plugin_api::plugin_command::{
     CreateTokenResponse, ListTokensResponse, ProtobufPluginCommand, RenameWebTokenResponse,
-    RevokeAllWebTokensResponse, RevokeTokenResponse,
+    RevokeAllWebTokensResponse, RevokeTokenResponse, StoreGetResponse, StoreListResponse,
 };
 use zellij_utils::plugin_api::plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion};
 
@@ -1371,7 +1373,15 @@ pub fn set_self_mouse_selection_support(selection_support: bool) {
 }
 
 pub fn generate_web_login_token(token_label: Option<String>) -> Result<String, String> {
-    let plugin_command = PluginCommand::GenerateWebLoginToken(token_label);
+    generate_scoped_web_login_token(token_label, WebTokenScope::default())
+}
+
+/// Generate a login token that can be limited to specific sessions, be read-only and/or expire
+pub fn generate_scoped_web_login_token(
+    token_label: Option<String>,
+    scope: WebTokenScope,
+) -> Result<String, String> {
+    let plugin_command = PluginCommand::GenerateWebLoginToken(token_label, scope);
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
     object_to_stdout(&protobuf_plugin_command.encode_to_vec());
     unsafe { host_run_plugin_command() };
@@ -1420,6 +1430,28 @@ pub fn list_web_login_tokens() -> Result<Vec<(String, String)>, String> {
     }
 }
 
+pub fn list_web_login_tokens_with_scope() -> Result<Vec<(String, String, WebTokenScope)>, String> {
+    // (name, created_at, scope)
+    let plugin_command = PluginCommand::ListWebLoginTokens;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let list_tokens_response =
+        ListTokensResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    if let Some(error) = list_tokens_response.error {
+        Err(error)
+    } else {
+        let tokens: Vec<(String, String, WebTokenScope)> = std::iter::zip(
+            list_tokens_response.tokens,
+            list_tokens_response.creation_times,
+        )
+        .zip(list_tokens_response.scopes)
+        .map(|((token, created_at), scope)| (token, created_at, scope.into()))
+        .collect();
+        Ok(tokens)
+    }
+}
+
 pub fn revoke_all_web_tokens() -> Result<(), String> {
     let plugin_command = PluginCommand::RevokeAllWebLoginTokens;
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
@@ -1471,6 +1503,200 @@ pub fn replace_pane_with_existing_pane(pane_id_to_replace: PaneId, existing_pane
     unsafe { host_run_plugin_command() };
 }
 
+/// Search the scrollback of the pane with the given id for `pattern` (a regular expression),
+/// the matches are sent back as an `Event::PaneScrollbackSearchResult` (note: this event must be
+/// subscribed to)
+pub fn search_pane_scrollback(pane_id: PaneId, pattern: &str) {
+    let plugin_command = PluginCommand::SearchPaneScrollback(pane_id, pattern.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Get the (unwrapped) lines of the pane with the given id in the given range, optionally with
+/// their styles as ANSI escape sequences, the lines are sent back as an `Event::PaneScrollback`
+/// (note: this event must be subscribed to)
+pub fn get_pane_scrollback(pane_id: PaneId, range: ScrollbackRange, with_styles: bool) {
+    let plugin_command = PluginCommand::GetPaneScrollback(pane_id, range, with_styles);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Get the value stored under `key` in this plugin's persistent store, the store is shared by all
+/// instances of the plugin (by its location) and survives restarting zellij
+pub fn store_get(key: &str, scope: StoreScope) -> Result<Option<String>, String> {
+    let plugin_command = PluginCommand::StoreGet(key.to_owned(), scope);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    let store_get_response =
+        StoreGetResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    if let Some(error) = store_get_response.error {
+        Err(error)
+    } else {
+        Ok(store_get_response.value)
+    }
+}
+
+/// Store `value` under `key` in this plugin's persistent store
+pub fn store_set(key: &str, value: &str, scope: StoreScope) {
+    let plugin_command = PluginCommand::StoreSet(key.to_owned(), value.to_owned(), scope);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Delete `key` from this plugin's persistent store
+pub fn store_delete(key: &str, scope: StoreScope) {
+    let plugin_command = PluginCommand::StoreDelete(key.to_owned(), scope);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// List all the keys and values in this plugin's persistent store
+pub fn store_list(scope: StoreScope) -> Result<BTreeMap<String, String>, String> {
+    let plugin_command = PluginCommand::StoreList(scope);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    store_entries_from_stdin()
+}
+
+/// List all the keys and values in the persistent store of the plugin with the given location
+/// (as it appears in `PluginInfo`), requires the `FullHdAccess` permission
+pub fn list_plugin_store(
+    plugin_location: &str,
+    scope: StoreScope,
+) -> Result<BTreeMap<String, String>, String> {
+    let plugin_command = PluginCommand::ListPluginStore(plugin_location.to_owned(), scope);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    store_entries_from_stdin()
+}
+
+/// Remove all the stored data (in all scopes and sessions) of the plugin with the given location
+/// (as it appears in `PluginInfo`), requires the `FullHdAccess` permission
+pub fn clear_plugin_store(plugin_location: &str) {
+    let plugin_command = PluginCommand::ClearPluginStore(plugin_location.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+fn store_entries_from_stdin() -> Result<BTreeMap<String, String>, String> {
+    let store_list_response =
+        StoreListResponse::decode(bytes_from_stdin().unwrap().as_slice()).unwrap();
+    if let Some(error) = store_list_response.error {
+        Err(error)
+    } else {
+        Ok(store_list_response
+            .entries
+            .into_iter()
+            .map(|e| (e.name, e.value))
+            .collect())
+    }
+}
+
+static NEXT_PLUGIN_CALL_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Call `method` on the plugin with the given url or alias (it must already be loaded), which
+/// handles it in its `handle_call` method. Returns the id of the call, the response is sent back
+/// as an `Event::PluginCallResult` with this id (note: this event must be subscribed to). If no
+/// response arrives within `timeout` (30 seconds by default), the call fails.
+pub fn call_plugin(
+    destination: &str,
+    method: &str,
+    payload: Option<String>,
+    timeout: Option<Duration>,
+) -> u64 {
+    let call_id = NEXT_PLUGIN_CALL_ID.fetch_add(1, Ordering::SeqCst);
+    let plugin_command = PluginCommand::CallPlugin {
+        call_id,
+        destination: destination.to_owned(),
+        method: method.to_owned(),
+        payload,
+        timeout,
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    call_id
+}
+
+/// Respond to a call made to this plugin by another plugin, for calls that were not answered
+/// directly from `handle_call`
+pub fn respond_to_plugin_call(call_id: u64, result: Result<String, String>) {
+    let plugin_command = PluginCommand::RespondToPluginCall(call_id, result);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+static NEXT_WATCH_ID: AtomicU32 = AtomicU32::new(0);
+
+/// Start watching the given paths on the host machine (requires the `FullHdAccess` permission),
+/// relative paths are resolved against the plugin's host folder. Returns the id of the watch,
+/// changes are reported as an `Event::WatchedPathsChanged` with this id (note: this event must be
+/// subscribed to). If the paths cannot be watched (eg. they do not exist, or the plugin already
+/// has too many watches) an `Event::WatchPathsFailed` is sent with this id instead.
+pub fn watch_paths(paths: Vec<PathBuf>, watch_options: WatchOptions) -> u32 {
+    let watch_id = NEXT_WATCH_ID.fetch_add(1, Ordering::SeqCst);
+    let plugin_command = PluginCommand::WatchPaths(watch_id, paths, watch_options);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    watch_id
+}
+
+/// Stop a watch started with `watch_paths`
+pub fn unwatch_paths(watch_id: u32) {
+    let plugin_command = PluginCommand::UnwatchPaths(watch_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Register the commands this plugin offers, replacing any previously registered ones. They can
+/// be invoked with the `PluginCommand` keybinding action, `zellij action plugin-command` or by
+/// other plugins, and arrive at this plugin's `pipe` method as a message named after the command.
+/// Command names are unique across plugins, commands already registered by another plugin are
+/// ignored.
+pub fn register_commands(commands: Vec<CommandSpec>) {
+    let plugin_command = PluginCommand::RegisterCommands(commands);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Get the commands registered by all loaded plugins as an `Event::RegisteredCommandsUpdate`
+/// (note: this event must be subscribed to)
+pub fn list_registered_commands() {
+    let plugin_command = PluginCommand::ListRegisteredCommands;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Invoke a command registered by one of the loaded plugins
+pub fn invoke_registered_command(name: &str, args: BTreeMap<String, String>) {
+    let plugin_command = PluginCommand::InvokeRegisteredCommand(name.to_owned(), args);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Run the given actions as if they were triggered by a keybinding of the plugin's client (only
+/// available to built-in plugins)
+pub fn run_actions(actions: Vec<Action>) {
+    let plugin_command = PluginCommand::RunActions(actions);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 // Utility Functions
 
 #[allow(unused)]
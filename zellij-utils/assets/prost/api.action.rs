@@ -3,7 +3,7 @@
 pub struct Action {
     #[prost(enumeration="ActionName", tag="1")]
     pub name: i32,
-    #[prost(oneof="action::OptionalPayload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49")]
+    #[prost(oneof="action::OptionalPayload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52")]
     pub optional_payload: ::core::option::Option<action::OptionalPayload>,
 }
 /// Nested message and enum types in `Action`.
@@ -101,10 +101,38 @@ pub mod action {
         MoveTabPayload(i32),
         #[prost(message, tag="49")]
         MouseEventPayload(super::MouseEventPayload),
+        #[prost(message, tag="50")]
+        PluginCommandPayload(super::PluginCommandPayload),
+        #[prost(message, tag="51")]
+        StartRecordingPayload(super::RecordingPayload),
+        #[prost(message, tag="52")]
+        StopRecordingPayload(super::RecordingPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PluginCommandPayload {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="2")]
+    pub args: ::prost::alloc::vec::Vec<NameAndValue>,
+    #[prost(string, optional, tag="3")]
+    pub pipe_id: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecordingPayload {
+    /// empty when stopping a recording
+    #[prost(string, tag="1")]
+    pub path: ::prost::alloc::string::String,
+    /// the whole session is recorded if neither of these is set
+    #[prost(uint32, optional, tag="2")]
+    pub terminal_pane_id: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="3")]
+    pub plugin_pane_id: ::core::option::Option<u32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CliPipePayload {
     #[prost(string, optional, tag="1")]
     pub name: ::core::option::Option<::prost::alloc::string::String>,
@@ -290,6 +318,8 @@ pub struct RunCommandAction {
     pub hold_on_close: bool,
     #[prost(bool, tag="7")]
     pub hold_on_start: bool,
+    #[prost(message, repeated, tag="8")]
+    pub env: ::prost::alloc::vec::Vec<NameAndValue>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -337,6 +367,7 @@ pub enum SearchOption {
     CaseSensitivity = 0,
     WholeWord = 1,
     Wrap = 2,
+    Regex = 3,
 }
 impl SearchOption {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -348,6 +379,7 @@ impl SearchOption {
             SearchOption::CaseSensitivity => "CaseSensitivity",
             SearchOption::WholeWord => "WholeWord",
             SearchOption::Wrap => "Wrap",
+            SearchOption::Regex => "Regex",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -356,6 +388,7 @@ impl SearchOption {
             "CaseSensitivity" => Some(Self::CaseSensitivity),
             "WholeWord" => Some(Self::WholeWord),
             "Wrap" => Some(Self::Wrap),
+            "Regex" => Some(Self::Regex),
             _ => None,
         }
     }
@@ -476,6 +509,13 @@ pub enum ActionName {
     TogglePaneInGroup = 87,
     ToggleGroupMarking = 88,
     NewStackedPane = 89,
+    ScrollToPreviousPrompt = 90,
+    ScrollToNextPrompt = 91,
+    CopyLastCommandOutput = 92,
+    EditLastCommandOutput = 93,
+    PluginCommand = 94,
+    StartRecording = 95,
+    StopRecording = 96,
 }
 impl ActionName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -571,6 +611,13 @@ impl ActionName {
             ActionName::TogglePaneInGroup => "TogglePaneInGroup",
             ActionName::ToggleGroupMarking => "ToggleGroupMarking",
             ActionName::NewStackedPane => "NewStackedPane",
+            ActionName::ScrollToPreviousPrompt => "ScrollToPreviousPrompt",
+            ActionName::ScrollToNextPrompt => "ScrollToNextPrompt",
+            ActionName::CopyLastCommandOutput => "CopyLastCommandOutput",
+            ActionName::EditLastCommandOutput => "EditLastCommandOutput",
+            ActionName::PluginCommand => "PluginCommand",
+            ActionName::StartRecording => "StartRecording",
+            ActionName::StopRecording => "StopRecording",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -663,6 +710,13 @@ impl ActionName {
             "TogglePaneInGroup" => Some(Self::TogglePaneInGroup),
             "ToggleGroupMarking" => Some(Self::ToggleGroupMarking),
             "NewStackedPane" => Some(Self::NewStackedPane),
+            "ScrollToPreviousPrompt" => Some(Self::ScrollToPreviousPrompt),
+            "ScrollToNextPrompt" => Some(Self::ScrollToNextPrompt),
+            "CopyLastCommandOutput" => Some(Self::CopyLastCommandOutput),
+            "EditLastCommandOutput" => Some(Self::EditLastCommandOutput),
+            "PluginCommand" => Some(Self::PluginCommand),
+            "StartRecording" => Some(Self::StartRecording),
+            "StopRecording" => Some(Self::StopRecording),
             _ => None,
         }
     }
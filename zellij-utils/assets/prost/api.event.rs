@@ -9,7 +9,7 @@ pub struct EventNameList {
 pub struct Event {
     #[prost(enumeration="EventType", tag="1")]
     pub name: i32,
-    #[prost(oneof="event::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29")]
+    #[prost(oneof="event::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37")]
     pub payload: ::core::option::Option<event::Payload>,
 }
 /// Nested message and enum types in `Event`.
@@ -73,10 +73,146 @@ pub mod event {
         FailedToStartWebServerPayload(super::FailedToStartWebServerPayload),
         #[prost(message, tag="29")]
         InterceptedKeyPayload(super::super::key::Key),
+        #[prost(message, tag="30")]
+        PaneNotificationPayload(super::PaneNotificationPayload),
+        #[prost(message, tag="31")]
+        PaneScrollbackSearchResultPayload(super::PaneScrollbackSearchResultPayload),
+        #[prost(message, tag="32")]
+        PaneScrollbackPayload(super::PaneScrollbackPayload),
+        #[prost(message, tag="33")]
+        PaneContentChangedPayload(super::PaneContentChangedPayload),
+        #[prost(message, tag="34")]
+        PluginCallResultPayload(super::PluginCallResultPayload),
+        #[prost(message, tag="35")]
+        WatchedPathsChangedPayload(super::WatchedPathsChangedPayload),
+        #[prost(message, tag="36")]
+        RegisteredCommandsUpdatePayload(super::RegisteredCommandsUpdatePayload),
+        #[prost(message, tag="37")]
+        WatchPathsFailedPayload(super::WatchPathsFailedPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegisteredCommandsUpdatePayload {
+    #[prost(message, repeated, tag="1")]
+    pub registered_commands: ::prost::alloc::vec::Vec<RegisteredCommand>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegisteredCommand {
+    #[prost(uint32, tag="1")]
+    pub plugin_id: u32,
+    #[prost(string, tag="2")]
+    pub plugin_url: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub command: ::core::option::Option<CommandSpec>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CommandSpec {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub description: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="3")]
+    pub args: ::prost::alloc::vec::Vec<CommandArgSpec>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CommandArgSpec {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub description: ::prost::alloc::string::String,
+    #[prost(bool, tag="3")]
+    pub required: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WatchedPathsChangedPayload {
+    #[prost(uint32, tag="1")]
+    pub watch_id: u32,
+    #[prost(message, repeated, tag="2")]
+    pub changes: ::prost::alloc::vec::Vec<WatchedPathChange>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WatchPathsFailedPayload {
+    #[prost(uint32, tag="1")]
+    pub watch_id: u32,
+    #[prost(string, tag="2")]
+    pub error: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WatchedPathChange {
+    #[prost(string, tag="1")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(enumeration="FileSystemChange", tag="2")]
+    pub change: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PluginCallResultPayload {
+    #[prost(uint64, tag="1")]
+    pub call_id: u64,
+    #[prost(string, optional, tag="2")]
+    pub response: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="3")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneScrollbackPayload {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(string, repeated, tag="2")]
+    pub lines: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint32, tag="3")]
+    pub first_line_index: u32,
+    #[prost(uint32, tag="4")]
+    pub total_lines: u32,
+    #[prost(bool, tag="5")]
+    pub with_styles: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneContentChangedPayload {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneScrollbackSearchResultPayload {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(string, tag="2")]
+    pub pattern: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="3")]
+    pub matches: ::prost::alloc::vec::Vec<ScrollbackSearchMatch>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScrollbackSearchMatch {
+    #[prost(uint32, tag="1")]
+    pub line_number: u32,
+    #[prost(uint32, tag="2")]
+    pub column: u32,
+    #[prost(string, tag="3")]
+    pub line: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneNotificationPayload {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(string, optional, tag="2")]
+    pub title: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag="3")]
+    pub body: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FailedToStartWebServerPayload {
     #[prost(string, tag="1")]
     pub error: ::prost::alloc::string::String,
@@ -124,6 +260,8 @@ pub struct ClientInfo {
     pub running_command: ::prost::alloc::string::String,
     #[prost(bool, tag="4")]
     pub is_current_client: bool,
+    #[prost(bool, tag="5")]
+    pub is_read_only: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -421,6 +559,8 @@ pub struct PaneInfo {
     pub is_selectable: bool,
     #[prost(message, repeated, tag="23")]
     pub index_in_pane_group: ::prost::alloc::vec::Vec<IndexInPaneGroup>,
+    #[prost(string, optional, tag="24")]
+    pub cwd: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -465,6 +605,8 @@ pub struct TabInfo {
     pub selectable_tiled_panes_count: u32,
     #[prost(uint32, tag="16")]
     pub selectable_floating_panes_count: u32,
+    #[prost(bool, tag="17")]
+    pub has_unread_notifications: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -499,6 +641,8 @@ pub struct ModeUpdatePayload {
     pub web_server_port: ::core::option::Option<u32>,
     #[prost(bool, optional, tag="15")]
     pub web_server_capability: ::core::option::Option<bool>,
+    #[prost(bool, optional, tag="16")]
+    pub is_read_only: ::core::option::Option<bool>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -569,6 +713,15 @@ pub enum EventType {
     BeforeClose = 32,
     FailedToStartWebServer = 34,
     InterceptedKeyPress = 35,
+    /// / A terminal pane sent a desktop notification (OSC 9 or OSC 777)
+    PaneNotification = 36,
+    PaneScrollbackSearchResult = 37,
+    PaneScrollback = 38,
+    PaneContentChanged = 39,
+    PluginCallResult = 40,
+    WatchedPathsChanged = 41,
+    RegisteredCommandsUpdate = 42,
+    WatchPathsFailed = 43,
 }
 impl EventType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -612,6 +765,14 @@ impl EventType {
             EventType::BeforeClose => "BeforeClose",
             EventType::FailedToStartWebServer => "FailedToStartWebServer",
             EventType::InterceptedKeyPress => "InterceptedKeyPress",
+            EventType::PaneNotification => "PaneNotification",
+            EventType::PaneScrollbackSearchResult => "PaneScrollbackSearchResult",
+            EventType::PaneScrollback => "PaneScrollback",
+            EventType::PaneContentChanged => "PaneContentChanged",
+            EventType::PluginCallResult => "PluginCallResult",
+            EventType::WatchedPathsChanged => "WatchedPathsChanged",
+            EventType::RegisteredCommandsUpdate => "RegisteredCommandsUpdate",
+            EventType::WatchPathsFailed => "WatchPathsFailed",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -652,6 +813,46 @@ impl EventType {
             "BeforeClose" => Some(Self::BeforeClose),
             "FailedToStartWebServer" => Some(Self::FailedToStartWebServer),
             "InterceptedKeyPress" => Some(Self::InterceptedKeyPress),
+            "PaneNotification" => Some(Self::PaneNotification),
+            "PaneScrollbackSearchResult" => Some(Self::PaneScrollbackSearchResult),
+            "PaneScrollback" => Some(Self::PaneScrollback),
+            "PaneContentChanged" => Some(Self::PaneContentChanged),
+            "PluginCallResult" => Some(Self::PluginCallResult),
+            "WatchedPathsChanged" => Some(Self::WatchedPathsChanged),
+            "RegisteredCommandsUpdate" => Some(Self::RegisteredCommandsUpdate),
+            "WatchPathsFailed" => Some(Self::WatchPathsFailed),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum FileSystemChange {
+    Create = 0,
+    Read = 1,
+    Update = 2,
+    Delete = 3,
+}
+impl FileSystemChange {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            FileSystemChange::Create => "Create",
+            FileSystemChange::Read => "Read",
+            FileSystemChange::Update => "Update",
+            FileSystemChange::Delete => "Delete",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Create" => Some(Self::Create),
+            "Read" => Some(Self::Read),
+            "Update" => Some(Self::Update),
+            "Delete" => Some(Self::Delete),
             _ => None,
         }
     }
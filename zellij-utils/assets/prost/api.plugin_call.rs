@@ -0,0 +1,12 @@
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PluginCall {
+    #[prost(uint64, tag="1")]
+    pub id: u64,
+    #[prost(uint32, tag="2")]
+    pub caller_plugin_id: u32,
+    #[prost(string, tag="3")]
+    pub method: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="4")]
+    pub payload: ::core::option::Option<::prost::alloc::string::String>,
+}
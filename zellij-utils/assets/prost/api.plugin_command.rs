@@ -3,7 +3,7 @@
 pub struct PluginCommand {
     #[prost(enumeration="CommandName", tag="1")]
     pub name: i32,
-    #[prost(oneof="plugin_command::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112")]
+    #[prost(oneof="plugin_command::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122, 123, 124, 125, 126, 127")]
     pub payload: ::core::option::Option<plugin_command::Payload>,
 }
 /// Nested message and enum types in `PluginCommand`.
@@ -215,10 +215,182 @@ pub mod plugin_command {
         ReplacePaneWithExistingPanePayload(super::ReplacePaneWithExistingPanePayload),
         #[prost(message, tag="112")]
         NewTabPayload(super::NewTabPayload),
+        #[prost(message, tag="113")]
+        SearchPaneScrollbackPayload(super::SearchPaneScrollbackPayload),
+        #[prost(message, tag="114")]
+        GetPaneScrollbackPayload(super::GetPaneScrollbackPayload),
+        #[prost(message, tag="115")]
+        StoreGetPayload(super::StoreGetPayload),
+        #[prost(message, tag="116")]
+        StoreSetPayload(super::StoreSetPayload),
+        #[prost(message, tag="117")]
+        StoreDeletePayload(super::StoreDeletePayload),
+        #[prost(message, tag="118")]
+        StoreListPayload(super::StoreListPayload),
+        #[prost(message, tag="119")]
+        ListPluginStorePayload(super::ListPluginStorePayload),
+        #[prost(message, tag="120")]
+        ClearPluginStorePayload(super::ClearPluginStorePayload),
+        #[prost(message, tag="121")]
+        CallPluginPayload(super::CallPluginPayload),
+        #[prost(message, tag="122")]
+        RespondToPluginCallPayload(super::RespondToPluginCallPayload),
+        #[prost(message, tag="123")]
+        WatchPathsPayload(super::WatchPathsPayload),
+        #[prost(message, tag="124")]
+        UnwatchPathsPayload(super::UnwatchPathsPayload),
+        #[prost(message, tag="125")]
+        RegisterCommandsPayload(super::RegisterCommandsPayload),
+        #[prost(message, tag="126")]
+        InvokeRegisteredCommandPayload(super::InvokeRegisteredCommandPayload),
+        #[prost(message, tag="127")]
+        RunActionsPayload(super::RunActionsPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegisterCommandsPayload {
+    #[prost(message, repeated, tag="1")]
+    pub commands: ::prost::alloc::vec::Vec<super::event::CommandSpec>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InvokeRegisteredCommandPayload {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="2")]
+    pub args: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RunActionsPayload {
+    #[prost(message, repeated, tag="1")]
+    pub actions: ::prost::alloc::vec::Vec<super::action::Action>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WatchPathsPayload {
+    #[prost(uint32, tag="1")]
+    pub watch_id: u32,
+    #[prost(string, repeated, tag="2")]
+    pub paths: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bool, tag="3")]
+    pub recursive: bool,
+    #[prost(uint64, optional, tag="4")]
+    pub debounce_ms: ::core::option::Option<u64>,
+    #[prost(string, repeated, tag="5")]
+    pub include_globs: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag="6")]
+    pub exclude_globs: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnwatchPathsPayload {
+    #[prost(uint32, tag="1")]
+    pub watch_id: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CallPluginPayload {
+    #[prost(uint64, tag="1")]
+    pub call_id: u64,
+    #[prost(string, tag="2")]
+    pub destination: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub method: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="4")]
+    pub payload: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint64, optional, tag="5")]
+    pub timeout_ms: ::core::option::Option<u64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RespondToPluginCallPayload {
+    #[prost(uint64, tag="1")]
+    pub call_id: u64,
+    #[prost(string, optional, tag="2")]
+    pub response: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="3")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StoreGetPayload {
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(enumeration="StoreScope", tag="2")]
+    pub scope: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StoreSetPayload {
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub value: ::prost::alloc::string::String,
+    #[prost(enumeration="StoreScope", tag="3")]
+    pub scope: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StoreDeletePayload {
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(enumeration="StoreScope", tag="2")]
+    pub scope: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StoreListPayload {
+    #[prost(enumeration="StoreScope", tag="1")]
+    pub scope: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPluginStorePayload {
+    #[prost(string, tag="1")]
+    pub plugin_location: ::prost::alloc::string::String,
+    #[prost(enumeration="StoreScope", tag="2")]
+    pub scope: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClearPluginStorePayload {
+    #[prost(string, tag="1")]
+    pub plugin_location: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPaneScrollbackPayload {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(message, optional, tag="2")]
+    pub range: ::core::option::Option<ScrollbackRange>,
+    #[prost(bool, tag="3")]
+    pub with_styles: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScrollbackRange {
+    #[prost(enumeration="ScrollbackRangeType", tag="1")]
+    pub r#type: i32,
+    #[prost(uint32, tag="2")]
+    pub start: u32,
+    #[prost(uint32, tag="3")]
+    pub end: u32,
+    #[prost(uint32, tag="4")]
+    pub line_count: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchPaneScrollbackPayload {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(string, tag="2")]
+    pub pattern: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NewTabPayload {
     #[prost(string, optional, tag="1")]
     pub name: ::core::option::Option<::prost::alloc::string::String>,
@@ -252,6 +424,19 @@ pub struct RevokeWebLoginTokenPayload {
 pub struct GenerateWebLoginTokenPayload {
     #[prost(string, optional, tag="1")]
     pub token_label: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, optional, tag="2")]
+    pub scope: ::core::option::Option<WebTokenScope>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebTokenScope {
+    /// empty means all sessions are allowed
+    #[prost(string, repeated, tag="1")]
+    pub allowed_sessions: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bool, tag="2")]
+    pub read_only: bool,
+    #[prost(uint64, optional, tag="3")]
+    pub expires_in_secs: ::core::option::Option<u64>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -865,13 +1050,15 @@ pub struct RevokeTokenResponse {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListTokensResponse {
-    /// tokens/creation_times should be synchronized
+    /// tokens/creation_times/scopes should be synchronized
     #[prost(string, repeated, tag="1")]
     pub tokens: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(string, repeated, tag="2")]
     pub creation_times: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(string, optional, tag="3")]
     pub error: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag="4")]
+    pub scopes: ::prost::alloc::vec::Vec<WebTokenScope>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -889,6 +1076,22 @@ pub struct RenameWebTokenResponse {
     #[prost(string, optional, tag="2")]
     pub error: ::core::option::Option<::prost::alloc::string::String>,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StoreGetResponse {
+    #[prost(string, optional, tag="1")]
+    pub value: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="2")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StoreListResponse {
+    #[prost(message, repeated, tag="1")]
+    pub entries: ::prost::alloc::vec::Vec<ContextItem>,
+    #[prost(string, optional, tag="2")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum CommandName {
@@ -1038,6 +1241,22 @@ pub enum CommandName {
     InterceptKeyPresses = 143,
     ClearKeyPressesIntercepts = 144,
     ReplacePaneWithExistingPane = 155,
+    SearchPaneScrollback = 156,
+    GetPaneScrollback = 157,
+    StoreGet = 158,
+    StoreSet = 159,
+    StoreDelete = 160,
+    StoreList = 161,
+    ListPluginStore = 162,
+    ClearPluginStore = 163,
+    CallPlugin = 164,
+    RespondToPluginCall = 165,
+    WatchPaths = 166,
+    UnwatchPaths = 167,
+    RegisterCommands = 168,
+    ListRegisteredCommands = 169,
+    InvokeRegisteredCommand = 170,
+    RunActions = 171,
 }
 impl CommandName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1192,6 +1411,22 @@ impl CommandName {
             CommandName::InterceptKeyPresses => "InterceptKeyPresses",
             CommandName::ClearKeyPressesIntercepts => "ClearKeyPressesIntercepts",
             CommandName::ReplacePaneWithExistingPane => "ReplacePaneWithExistingPane",
+            CommandName::SearchPaneScrollback => "SearchPaneScrollback",
+            CommandName::GetPaneScrollback => "GetPaneScrollback",
+            CommandName::StoreGet => "StoreGet",
+            CommandName::StoreSet => "StoreSet",
+            CommandName::StoreDelete => "StoreDelete",
+            CommandName::StoreList => "StoreList",
+            CommandName::ListPluginStore => "ListPluginStore",
+            CommandName::ClearPluginStore => "ClearPluginStore",
+            CommandName::CallPlugin => "CallPlugin",
+            CommandName::RespondToPluginCall => "RespondToPluginCall",
+            CommandName::WatchPaths => "WatchPaths",
+            CommandName::UnwatchPaths => "UnwatchPaths",
+            CommandName::RegisterCommands => "RegisterCommands",
+            CommandName::ListRegisteredCommands => "ListRegisteredCommands",
+            CommandName::InvokeRegisteredCommand => "InvokeRegisteredCommand",
+            CommandName::RunActions => "RunActions",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1343,6 +1578,80 @@ impl CommandName {
             "InterceptKeyPresses" => Some(Self::InterceptKeyPresses),
             "ClearKeyPressesIntercepts" => Some(Self::ClearKeyPressesIntercepts),
             "ReplacePaneWithExistingPane" => Some(Self::ReplacePaneWithExistingPane),
+            "SearchPaneScrollback" => Some(Self::SearchPaneScrollback),
+            "GetPaneScrollback" => Some(Self::GetPaneScrollback),
+            "StoreGet" => Some(Self::StoreGet),
+            "StoreSet" => Some(Self::StoreSet),
+            "StoreDelete" => Some(Self::StoreDelete),
+            "StoreList" => Some(Self::StoreList),
+            "ListPluginStore" => Some(Self::ListPluginStore),
+            "ClearPluginStore" => Some(Self::ClearPluginStore),
+            "CallPlugin" => Some(Self::CallPlugin),
+            "RespondToPluginCall" => Some(Self::RespondToPluginCall),
+            "WatchPaths" => Some(Self::WatchPaths),
+            "UnwatchPaths" => Some(Self::UnwatchPaths),
+            "RegisterCommands" => Some(Self::RegisterCommands),
+            "ListRegisteredCommands" => Some(Self::ListRegisteredCommands),
+            "InvokeRegisteredCommand" => Some(Self::InvokeRegisteredCommand),
+            "RunActions" => Some(Self::RunActions),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum StoreScope {
+    Global = 0,
+    Session = 1,
+}
+impl StoreScope {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            StoreScope::Global => "Global",
+            StoreScope::Session => "Session",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Global" => Some(Self::Global),
+            "Session" => Some(Self::Session),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ScrollbackRangeType {
+    Viewport = 0,
+    Full = 1,
+    Lines = 2,
+    Last = 3,
+}
+impl ScrollbackRangeType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ScrollbackRangeType::Viewport => "Viewport",
+            ScrollbackRangeType::Full => "Full",
+            ScrollbackRangeType::Lines => "Lines",
+            ScrollbackRangeType::Last => "Last",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Viewport" => Some(Self::Viewport),
+            "Full" => Some(Self::Full),
+            "Lines" => Some(Self::Lines),
+            "Last" => Some(Self::Last),
             _ => None,
         }
     }
@@ -14,6 +14,7 @@ pub enum PermissionType {
     FullHdAccess = 10,
     StartWebServer = 11,
     InterceptInput = 12,
+    ReadPaneContents = 13,
 }
 impl PermissionType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -35,6 +36,7 @@ impl PermissionType {
             PermissionType::FullHdAccess => "FullHdAccess",
             PermissionType::StartWebServer => "StartWebServer",
             PermissionType::InterceptInput => "InterceptInput",
+            PermissionType::ReadPaneContents => "ReadPaneContents",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -53,6 +55,7 @@ impl PermissionType {
             "FullHdAccess" => Some(Self::FullHdAccess),
             "StartWebServer" => Some(Self::StartWebServer),
             "InterceptInput" => Some(Self::InterceptInput),
+            "ReadPaneContents" => Some(Self::ReadPaneContents),
             _ => None,
         }
     }
@@ -23,6 +23,9 @@ pub mod api {
     pub mod pipe_message {
         include!("api.pipe_message.rs");
     }
+    pub mod plugin_call {
+        include!("api.plugin_call.rs");
+    }
     pub mod plugin_command {
         include!("api.plugin_command.rs");
     }
@@ -0,0 +1,181 @@
+//! Session recordings in the asciicast v2 format
+//! (<https://docs.asciinema.org/manual/asciicast/v2/>), the first line of the file is a JSON
+//! header and every following line is an event of the form `[time, type, data]`.
+use crate::errors::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+const ASCIICAST_VERSION: u8 = 2;
+pub const OUTPUT_EVENT: &str = "o";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AsciicastHeader {
+    pub version: u8,
+    pub width: usize,
+    pub height: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AsciicastEvent(pub f64, pub String, pub String); // time in seconds, event type, data
+
+impl AsciicastEvent {
+    pub fn time(&self) -> f64 {
+        self.0
+    }
+    pub fn is_output(&self) -> bool {
+        self.1 == OUTPUT_EVENT
+    }
+    pub fn data(&self) -> &str {
+        &self.2
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsciicastRecording {
+    pub header: AsciicastHeader,
+    pub events: Vec<AsciicastEvent>,
+}
+
+impl AsciicastRecording {
+    pub fn parse(recording: &str) -> Result<Self> {
+        let mut lines = recording.lines().filter(|line| !line.trim().is_empty());
+        let header: AsciicastHeader =
+            lines
+                .next()
+                .context("recording is empty")
+                .and_then(|header| {
+                    serde_json::from_str(header).context("failed to parse recording header")
+                })?;
+        if header.version != ASCIICAST_VERSION {
+            return Err(anyhow!(
+                "unsupported asciicast version {}, only version {} is supported",
+                header.version,
+                ASCIICAST_VERSION
+            ));
+        }
+        let mut events = vec![];
+        for (index, line) in lines.enumerate() {
+            let event: AsciicastEvent = serde_json::from_str(line)
+                .with_context(|| format!("failed to parse event {} of recording", index + 1))?;
+            events.push(event);
+        }
+        Ok(AsciicastRecording { header, events })
+    }
+    pub fn duration(&self) -> f64 {
+        self.events.last().map(|e| e.time()).unwrap_or(0.0)
+    }
+}
+
+/// Writes a recording to disk as it happens, so that it is usable even if it's never stopped
+/// (eg. if the session crashes)
+#[derive(Debug)]
+pub struct AsciicastWriter {
+    file: File,
+    started_at: Instant,
+    incomplete_utf8_sequence: Vec<u8>,
+}
+
+impl AsciicastWriter {
+    pub fn create(path: &Path, width: usize, height: usize, title: Option<String>) -> Result<Self> {
+        let err_context = || format!("failed to create recording at {}", path.display());
+
+        let mut file = File::create(path).with_context(err_context)?;
+        let header = AsciicastHeader {
+            version: ASCIICAST_VERSION,
+            width,
+            height,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs()),
+            title,
+        };
+        let header = serde_json::to_string(&header).with_context(err_context)?;
+        writeln!(file, "{}", header).with_context(err_context)?;
+        Ok(AsciicastWriter {
+            file,
+            started_at: Instant::now(),
+            incomplete_utf8_sequence: vec![],
+        })
+    }
+    pub fn write_output(&mut self, output: &str) -> Result<()> {
+        if output.is_empty() {
+            return Ok(());
+        }
+        // asciinema itself records with microsecond precision
+        let time = (self.started_at.elapsed().as_secs_f64() * 1_000_000.0).round() / 1_000_000.0;
+        let event = AsciicastEvent(time, OUTPUT_EVENT.to_owned(), output.to_owned());
+        let event = serde_json::to_string(&event).context("failed to serialize recording event")?;
+        writeln!(self.file, "{}", event).context("failed to write recording event")
+    }
+    /// Like `write_output`, but for raw bytes which might end in the middle of a UTF-8 sequence
+    /// (eg. PTY output), in which case the rest of it is expected in the next write
+    pub fn write_output_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut bytes_to_write = std::mem::take(&mut self.incomplete_utf8_sequence);
+        bytes_to_write.extend_from_slice(bytes);
+        let output = match std::str::from_utf8(&bytes_to_write) {
+            Ok(output) => output.to_owned(),
+            Err(e) if e.error_len().is_none() => {
+                let (complete, incomplete) = bytes_to_write.split_at(e.valid_up_to());
+                self.incomplete_utf8_sequence = incomplete.to_vec();
+                String::from_utf8_lossy(complete).into_owned()
+            },
+            Err(_) => String::from_utf8_lossy(&bytes_to_write).into_owned(),
+        };
+        self.write_output(&output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recording_can_be_read_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording.cast");
+        let mut writer =
+            AsciicastWriter::create(&path, 80, 24, Some("my session".to_owned())).unwrap();
+        writer.write_output("hello \u{1b}[31mthere\n").unwrap();
+        // "é" is split across the two writes
+        writer.write_output_bytes(&[b'a', 0xc3]).unwrap();
+        writer.write_output_bytes(&[0xa9, b'b']).unwrap();
+        drop(writer);
+
+        let recording =
+            AsciicastRecording::parse(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(recording.header.width, 80);
+        assert_eq!(recording.header.height, 24);
+        assert_eq!(recording.header.title, Some("my session".to_owned()));
+        let output: Vec<&str> = recording.events.iter().map(|e| e.data()).collect();
+        assert_eq!(output, vec!["hello \u{1b}[31mthere\n", "a", "\u{e9}b"]);
+        assert!(recording.events.iter().all(|e| e.is_output()));
+    }
+
+    #[test]
+    fn asciinema_recordings_are_parsed() {
+        let recording = "{\"version\": 2, \"width\": 100, \"height\": 30, \"timestamp\": 1700000000, \"env\": {\"SHELL\": \"/bin/bash\"}}\n\
+                         [0.248848, \"o\", \"$ \"]\n\
+                         [1.001376, \"i\", \"l\"]\n\
+                         [1.5, \"o\", \"ls\\r\\n\"]\n";
+        let recording = AsciicastRecording::parse(recording).unwrap();
+        assert_eq!(recording.header.width, 100);
+        assert_eq!(recording.events.len(), 3);
+        assert!(!recording.events[1].is_output());
+        assert_eq!(recording.events[2].data(), "ls\r\n");
+        assert_eq!(recording.duration(), 1.5);
+    }
+
+    #[test]
+    fn unsupported_versions_are_rejected() {
+        let recording = "{\"version\": 1, \"width\": 100, \"height\": 30}\n";
+        assert!(AsciicastRecording::parse(recording).is_err());
+    }
+}
@@ -10,6 +10,16 @@ use std::net::IpAddr;
 use std::path::PathBuf;
 use url::Url;
 
+fn parse_key_value_pair(key_value_pair: &str) -> Result<(String, String), String> {
+    match key_value_pair.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_owned(), value.to_owned())),
+        _ => Err(format!(
+            "invalid argument \"{}\", expected KEY=VALUE",
+            key_value_pair
+        )),
+    }
+}
+
 fn validate_session(name: &str) -> Result<String, String> {
     #[cfg(unix)]
     {
@@ -63,6 +73,11 @@ pub struct CliArgs {
     #[clap(short, long, value_parser, overrides_with = "layout")]
     pub layout: Option<PathBuf>,
 
+    /// Set a parameter declared in the params block of the layout, can be specified multiple
+    /// times (eg. --layout-arg service=billing)
+    #[clap(long, value_parser = parse_key_value_pair)]
+    pub layout_arg: Vec<(String, String)>,
+
     /// Name of a predefined layout inside the layout directory or the path to a layout file
     /// Will always start a new session, even if inside an existing session
     #[clap(short, long, value_parser, overrides_with = "new_session_with_layout")]
@@ -191,6 +206,14 @@ impl WebCli {
     }
 }
 
+#[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
+pub enum PluginSubcommand {
+    /// Download the remote plugins referenced in the configuration and record their sha256 hashes
+    /// in a lockfile next to it (plugins.lock), these plugins will then fail to load if their
+    /// contents change
+    Lock,
+}
+
 #[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
 pub enum SessionCommand {
     /// Change the behaviour of zellij
@@ -244,6 +267,10 @@ pub enum Sessions {
         /// If resurrecting a dead session, immediately run all its commands on startup
         #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
         force_run_commands: bool,
+
+        /// Attach as a spectator: the session is displayed but no input is sent to it
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        read_only: bool,
     },
 
     /// Kill a specific session
@@ -303,6 +330,11 @@ pub enum Sessions {
         #[clap(long, value_parser)]
         cwd: Option<PathBuf>,
 
+        /// Set an environment variable for the command, can be specified multiple times (eg.
+        /// --env RUST_LOG=debug)
+        #[clap(long, value_parser = parse_key_value_pair)]
+        env: Vec<(String, String)>,
+
         /// Open the new pane in floating mode
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         floating: bool,
@@ -357,11 +389,18 @@ pub enum Sessions {
         stacked: bool,
     },
     /// Load a plugin
-    #[clap(visible_alias = "p")]
+    #[clap(
+        visible_alias = "p",
+        subcommand_negates_reqs(true),
+        args_conflicts_with_subcommands(true)
+    )]
     Plugin {
+        #[clap(subcommand)]
+        command: Option<PluginSubcommand>,
+
         /// Plugin URL, can either start with http(s), file: or zellij:
         #[clap(last(true), required(true))]
-        url: String,
+        url: Option<String>,
 
         /// Plugin configuration
         #[clap(short, long, value_parser)]
@@ -458,6 +497,13 @@ pub enum Sessions {
     ConvertTheme {
         old_theme_file: PathBuf,
     },
+    /// Play back a recording made with `zellij action start-recording` (or any asciicast v2
+    /// file) in this terminal. Space pauses, left/right (or h/l) seek by 5 seconds and q quits,
+    /// the replay stays paused at its end until then.
+    Replay {
+        /// The recording to play back
+        recording: PathBuf,
+    },
     /// Send data to one or more plugins, launch them if they are not running.
     #[clap(override_usage(
 r#"
@@ -542,6 +588,23 @@ pub enum CliAction {
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         full: bool,
     },
+    /// Record what this client sees (or only the output of a single terminal pane) to an
+    /// asciicast v2 file, which can be played back with `zellij replay`
+    StartRecording {
+        /// The file to write the recording to
+        #[clap(short, long, value_parser)]
+        path: PathBuf,
+        /// Record only this terminal pane, eg. terminal_1 or 1 (equivalent to terminal_1)
+        #[clap(long, value_parser)]
+        pane_id: Option<String>,
+    },
+    /// Stop a recording started with start-recording
+    StopRecording {
+        /// Stop recording this terminal pane rather than the whole session, eg. terminal_1 or 1
+        /// (equivalent to terminal_1)
+        #[clap(long, value_parser)]
+        pane_id: Option<String>,
+    },
     /// Dump current layout to stdout
     DumpLayout,
     /// Open the pane scrollback in your default editor
@@ -554,6 +617,16 @@ pub enum CliAction {
     ScrollToBottom,
     /// Scroll up to top in focus pane.
     ScrollToTop,
+    /// Scroll up to the previous shell prompt in focus pane (requires OSC 133 shell integration).
+    ScrollToPreviousPrompt,
+    /// Scroll down to the next shell prompt in focus pane (requires OSC 133 shell integration).
+    ScrollToNextPrompt,
+    /// Copy the output of the last command in focus pane to the clipboard (requires OSC 133 shell
+    /// integration).
+    CopyLastCommandOutput,
+    /// Open the output of the last command in focus pane in your default editor (requires OSC 133
+    /// shell integration).
+    EditLastCommandOutput,
     /// Scroll up one page in focus pane.
     PageScrollUp,
     /// Scroll down one page in focus pane.
@@ -585,6 +658,11 @@ pub enum CliAction {
         #[clap(long, value_parser)]
         cwd: Option<PathBuf>,
 
+        /// Set an environment variable for the command or shell, can be specified multiple times
+        /// (eg. --env RUST_LOG=debug)
+        #[clap(long, value_parser = parse_key_value_pair, conflicts_with("plugin"))]
+        env: Vec<(String, String)>,
+
         /// Open the new pane in floating mode
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         floating: bool,
@@ -750,6 +828,11 @@ pub enum CliAction {
         #[clap(long, value_parser, requires("layout"))]
         layout_dir: Option<PathBuf>,
 
+        /// Set a parameter declared in the params block of the layout, can be specified multiple
+        /// times (eg. --layout-arg service=billing)
+        #[clap(long, value_parser = parse_key_value_pair, requires("layout"))]
+        layout_arg: Vec<(String, String)>,
+
         /// Name of the new tab
         #[clap(short, long, value_parser)]
         name: Option<String>,
@@ -766,6 +849,14 @@ pub enum CliAction {
     NextSwapLayout,
     /// Query all tab names
     QueryTabNames,
+    /// List all the matches of the current search in the focused pane, along with their line
+    /// in the scrollback
+    ListSearchMatches,
+    /// Scroll the focused pane to the match of the current search with index [index], as
+    /// listed by list-search-matches
+    SearchJumpToMatch {
+        index: usize,
+    },
     StartOrReloadPlugin {
         url: String,
         #[clap(short, long, value_parser)]
@@ -873,6 +964,14 @@ tail -f /tmp/my-live-logfile | zellij action pipe --name logs --plugin https://e
         #[clap(short('t'), long, value_parser, display_order(10))]
         plugin_title: Option<String>,
     },
+    /// Invoke a command registered by one of the loaded plugins, with optional arguments in the
+    /// form of key=value
+    ///
+    /// Example: zellij action plugin-command open-note title=todo
+    PluginCommand {
+        name: String,
+        args: Vec<String>,
+    },
     ListClients,
     TogglePanePinned,
     /// Stack pane ids
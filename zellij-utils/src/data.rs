@@ -863,6 +863,28 @@ pub struct FileMetadata {
     pub len: u64,
 }
 
+/// The kind of change that happened to a path watched with `watch_paths`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FileSystemChange {
+    Create,
+    Read,
+    Update,
+    Delete,
+}
+
+/// Options for watching paths with `watch_paths`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchOptions {
+    /// Also watch the contents of subdirectories
+    pub recursive: bool,
+    /// How long to wait for changes to settle before reporting them, defaults to 400ms (at least 50ms)
+    pub debounce_ms: Option<u64>,
+    /// Only report paths matching one of these globs (eg. `*.md`), all paths if empty
+    pub include_globs: Vec<String>,
+    /// Never report paths matching one of these globs (eg. `**/.git/**`)
+    pub exclude_globs: Vec<String>,
+}
+
 impl From<Metadata> for FileMetadata {
     fn from(metadata: Metadata) -> Self {
         FileMetadata {
@@ -945,6 +967,35 @@ pub enum Event {
     FailedToStartWebServer(String),
     BeforeClose,
     InterceptedKeyPress(KeyWithModifier),
+    /// A terminal pane sent a desktop notification (OSC 9 or OSC 777)
+    PaneNotification {
+        pane_id: PaneId,
+        title: Option<String>,
+        body: String,
+    },
+    /// The result of searching the scrollback of a pane with `SearchPaneScrollback`
+    PaneScrollbackSearchResult {
+        pane_id: PaneId,
+        pattern: String,
+        matches: Vec<ScrollbackSearchMatch>,
+    },
+    /// The lines requested from a pane with `GetPaneScrollback`
+    PaneScrollback {
+        pane_id: PaneId,
+        scrollback: PaneScrollback,
+    },
+    /// The contents of a terminal pane changed (sent at most once in a short interval per pane)
+    PaneContentChanged(PaneId),
+    /// The response to a call made with `call_plugin`, or the reason it failed (eg. the
+    /// destination plugin is not loaded or did not respond in time)
+    PluginCallResult(u64, Result<String, String>), // u64 -> call id
+    /// Changes to paths watched with `watch_paths`
+    WatchedPathsChanged(u32, Vec<(PathBuf, FileSystemChange)>), // u32 -> watch id
+    /// The commands registered by all loaded plugins (see `register_commands`), sent whenever
+    /// they change or when requested with `list_registered_commands`
+    RegisteredCommandsUpdate(Vec<RegisteredCommand>),
+    /// Watching paths with `watch_paths` failed, or the watch with this id ran into an error
+    WatchPathsFailed(u32, String), // u32 -> watch id, String -> the error
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, EnumDiscriminants, ToString, Serialize, Deserialize)]
@@ -985,6 +1036,7 @@ pub enum Permission {
     FullHdAccess,
     StartWebServer,
     InterceptInput,
+    ReadPaneContents,
 }
 
 impl PermissionType {
@@ -1011,6 +1063,9 @@ impl PermissionType {
                 "Start a local web server to serve Zellij sessions".to_owned()
             },
             PermissionType::InterceptInput => "Intercept Input (keyboard & mouse)".to_owned(),
+            PermissionType::ReadPaneContents => {
+                "Read the contents and scrollback of panes".to_owned()
+            },
         }
     }
 }
@@ -1579,6 +1634,7 @@ pub struct ModeInfo {
     pub web_sharing: Option<WebSharing>,
     pub currently_marking_pane_group: Option<bool>,
     pub is_web_client: Option<bool>,
+    pub is_read_only: Option<bool>,
     // note: these are only the configured ip/port that will be bound if and when the server is up
     pub web_server_ip: Option<IpAddr>,
     pub web_server_port: Option<u16>,
@@ -1773,6 +1829,8 @@ pub struct TabInfo {
     pub selectable_tiled_panes_count: usize,
     /// The number of selectable (eg. not the UI bars) floating panes currently in this tab
     pub selectable_floating_panes_count: usize,
+    /// Whether a pane in this tab sent a notification (OSC 9 or OSC 777) since it was last focused
+    pub has_unread_notifications: bool,
 }
 
 /// The `PaneManifest` contains a dictionary of panes, indexed by the tab position (0 indexed).
@@ -1841,6 +1899,8 @@ pub struct PaneInfo {
     /// Grouped panes (usually through an explicit user action) that are staged for a bulk action
     /// the index is kept track of in order to preserve the pane group order
     pub index_in_pane_group: BTreeMap<ClientId, usize>,
+    /// The current working directory of a terminal pane, as reported by its shell (OSC 7)
+    pub cwd: Option<PathBuf>,
 }
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct ClientInfo {
@@ -1848,6 +1908,57 @@ pub struct ClientInfo {
     pub pane_id: PaneId,
     pub running_command: String,
     pub is_current_client: bool,
+    /// Read-only (spectator) clients can watch the session but not send input to it
+    pub is_read_only: bool,
+}
+
+/// A match found when searching the scrollback of a pane
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ScrollbackSearchMatch {
+    /// The line the match starts on, counting from the top of the scrollback (starting at 1)
+    pub line_number: usize,
+    /// The character index in the line where the match starts (starting at 0)
+    pub column: usize,
+    /// The full line the match was found on
+    pub line: String,
+}
+
+/// The range of lines to get with `GetPaneScrollback`. Lines are unwrapped and counted from the
+/// top of the scrollback starting at 0 (ie. line `n` is reported as `line_number` `n + 1` when
+/// searching the scrollback)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ScrollbackRange {
+    /// The lines currently visible in the pane
+    #[default]
+    Viewport,
+    /// All the lines of the pane, including the scrollback
+    Full,
+    /// The lines from `start` up to (but not including) `end`
+    Lines { start: usize, end: usize },
+    /// The last `n` lines of the pane
+    Last(usize),
+}
+
+/// The lines of a pane, as returned by `GetPaneScrollback`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PaneScrollback {
+    pub lines: Vec<String>,
+    /// The index of the first line in `lines`, counting from the top of the scrollback
+    pub first_line_index: usize,
+    /// The total number of lines in the pane, including the scrollback
+    pub total_lines: usize,
+    /// Whether the lines include their styles as ANSI escape sequences
+    pub with_styles: bool,
+}
+
+/// The scope of a plugin's persistent key/value store (see `StoreGet`, `StoreSet` and friends)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum StoreScope {
+    /// Shared between all sessions
+    #[default]
+    Global,
+    /// Only visible in the current session
+    Session,
 }
 
 impl ClientInfo {
@@ -1856,12 +1967,14 @@ impl ClientInfo {
         pane_id: PaneId,
         running_command: String,
         is_current_client: bool,
+        is_read_only: bool,
     ) -> Self {
         ClientInfo {
             client_id,
             pane_id,
             running_command,
             is_current_client,
+            is_read_only,
         }
     }
 }
@@ -1968,6 +2081,33 @@ impl CommandToRun {
     }
 }
 
+/// A named command offered by a plugin with `register_commands`. It can be invoked with the
+/// `PluginCommand` keybinding action, `zellij action plugin-command` or by other plugins, and is
+/// delivered to the plugin's `pipe` method as a message with the command's name and arguments,
+/// whose source is the cli, the invoking plugin or a keybinding.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandSpec {
+    pub name: String,
+    pub description: String,
+    pub args: Vec<CommandArgSpec>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandArgSpec {
+    pub name: String,
+    pub description: String,
+    /// Invoking the command without this argument fails
+    pub required: bool,
+}
+
+/// A command registered by one of the loaded plugins
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisteredCommand {
+    pub plugin_id: u32,
+    pub plugin_url: String,
+    pub command: CommandSpec,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct MessageToPlugin {
     pub plugin_url: Option<String>,
@@ -2171,6 +2311,17 @@ impl PipeMessage {
     }
 }
 
+/// A call made to this plugin by another plugin with `call_plugin`, see
+/// `ZellijPlugin::handle_call`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginCall {
+    /// Identifies the call when responding to it later with `respond_to_plugin_call`
+    pub id: u64,
+    pub caller_plugin_id: u32,
+    pub method: String,
+    pub payload: Option<String>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, Default)]
 pub struct FloatingPaneCoordinates {
     pub x: Option<SplitSize>,
@@ -2342,6 +2493,60 @@ impl WebSharing {
     }
 }
 
+/// Limits placed on what a web login token grants access to
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct WebTokenScope {
+    /// names of the sessions this token can attach to, all sessions if empty
+    pub allowed_sessions: Vec<String>,
+    /// clients logged in with this token attach as spectators
+    pub read_only: bool,
+    /// when creating a token: how long it is valid for, when listing: how long it has left
+    pub expires_in: Option<Duration>,
+}
+
+impl WebTokenScope {
+    pub fn allows_session(&self, session_name: &str) -> bool {
+        self.allowed_sessions.is_empty() || self.allowed_sessions.iter().any(|s| s == session_name)
+    }
+    pub fn restricts_sessions(&self) -> bool {
+        !self.allowed_sessions.is_empty()
+    }
+    pub fn is_expired(&self) -> bool {
+        self.expires_in.map(|e| e.is_zero()).unwrap_or(false)
+    }
+    /// a short description of the restrictions of this scope (eg. "sessions: a, b, read-only"),
+    /// None if the token is unrestricted
+    pub fn description(&self) -> Option<String> {
+        let mut description = vec![];
+        if self.restricts_sessions() {
+            description.push(format!("sessions: {}", self.allowed_sessions.join(", ")));
+        }
+        if self.read_only {
+            description.push("read-only".to_owned());
+        }
+        match self.expires_in {
+            Some(_) if self.is_expired() => description.push("expired".to_owned()),
+            Some(expires_in) => {
+                let secs = expires_in.as_secs();
+                let expires_in = if secs >= 24 * 60 * 60 {
+                    format!("{}d", secs / (24 * 60 * 60))
+                } else if secs >= 60 * 60 {
+                    format!("{}h", secs / (60 * 60))
+                } else {
+                    format!("{}m", (secs / 60).max(1))
+                };
+                description.push(format!("expires in {}", expires_in));
+            },
+            None => {},
+        }
+        if description.is_empty() {
+            None
+        } else {
+            Some(description.join(", "))
+        }
+    }
+}
+
 impl FromStr for WebSharing {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -2529,12 +2734,34 @@ pub enum PluginCommand {
     EmbedMultiplePanes(Vec<PaneId>),
     QueryWebServerStatus,
     SetSelfMouseSelectionSupport(bool),
-    GenerateWebLoginToken(Option<String>), // String -> optional token label
-    RevokeWebLoginToken(String),           // String -> token id (provided name or generated id)
+    GenerateWebLoginToken(Option<String>, WebTokenScope), // String -> optional token label
+    RevokeWebLoginToken(String), // String -> token id (provided name or generated id)
     ListWebLoginTokens,
     RevokeAllWebLoginTokens,
     RenameWebLoginToken(String, String), // (original_name, new_name)
     InterceptKeyPresses,
     ClearKeyPressesIntercepts,
     ReplacePaneWithExistingPane(PaneId, PaneId), // (pane id to replace, pane id of existing)
+    SearchPaneScrollback(PaneId, String),        // String -> regex pattern
+    GetPaneScrollback(PaneId, ScrollbackRange, bool), // bool -> include styles (ANSI)
+    StoreGet(String, StoreScope),                // key
+    StoreSet(String, String, StoreScope),        // key, value
+    StoreDelete(String, StoreScope),             // key
+    StoreList(StoreScope),
+    ListPluginStore(String, StoreScope), // plugin location
+    ClearPluginStore(String),            // plugin location
+    CallPlugin {
+        call_id: u64,
+        destination: String, // plugin url or alias
+        method: String,
+        payload: Option<String>,
+        timeout: Option<Duration>,
+    },
+    RespondToPluginCall(u64, Result<String, String>), // call id, response or error
+    WatchPaths(u32, Vec<PathBuf>, WatchOptions),      // watch id, paths to watch
+    UnwatchPaths(u32),                                // watch id
+    RegisterCommands(Vec<CommandSpec>),
+    ListRegisteredCommands,
+    InvokeRegisteredCommand(String, BTreeMap<String, String>), // command name, args
+    RunActions(Vec<Action>),
 }
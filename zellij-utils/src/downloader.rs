@@ -6,12 +6,15 @@ use async_std::{
 };
 use isahc::prelude::*;
 use isahc::{config::RedirectPolicy, HttpClient, Request};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
 use std::sync::Arc;
 use thiserror::Error;
 use url::Url;
 
+use crate::input::plugins::PluginLock;
+
 #[derive(Error, Debug)]
 pub enum DownloaderError {
     #[error("RequestError: {0}")]
@@ -26,6 +29,14 @@ pub enum DownloaderError {
     NotFoundFileName(String),
     #[error("Failed to parse URL body: {0}")]
     InvalidUrlBody(String),
+    #[error("Invalid sha256 hash for {0}: {1} (expected 64 hexadecimal characters)")]
+    InvalidSha256(String, String),
+    #[error("Integrity check failed for {url}: expected sha256 {expected}, got {actual}")]
+    IntegrityMismatch {
+        url: String,
+        expected: String,
+        actual: String,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -141,7 +152,56 @@ impl Downloader {
 
         Ok(())
     }
+    /// Like `download`, but makes sure the downloaded file (or the one already in the cache)
+    /// matches the given sha256 hash. A cached file that does not match is downloaded again, a
+    /// downloaded file that does not match is removed.
+    pub async fn download_and_verify(
+        &self,
+        url: &str,
+        file_name: Option<&str>,
+        sha256: &str,
+    ) -> Result<(), DownloaderError> {
+        let file_name = match file_name {
+            Some(name) => name.to_string(),
+            None => self.parse_name(url)?,
+        };
+        let file_path = self.location.join(file_name.as_str());
+        if file_path.exists() {
+            let cached_bytes = fs::read(&file_path)
+                .await
+                .map_err(|e| DownloaderError::Io(e))?;
+            match verify_sha256(url, &cached_bytes, sha256) {
+                Ok(()) => return Ok(()),
+                Err(DownloaderError::IntegrityMismatch { .. }) => {
+                    log::warn!(
+                        "Cached file {:?} does not match its pinned hash, downloading it again",
+                        file_path
+                    );
+                    fs::remove_file(&file_path)
+                        .await
+                        .map_err(|e| DownloaderError::Io(e))?;
+                },
+                Err(e) => return Err(e),
+            }
+        }
+        self.download(url, Some(&file_name)).await?;
+        let downloaded_bytes = fs::read(&file_path)
+            .await
+            .map_err(|e| DownloaderError::Io(e))?;
+        if let Err(e) = verify_sha256(url, &downloaded_bytes, sha256) {
+            let _ = fs::remove_file(&file_path).await;
+            return Err(e);
+        }
+        Ok(())
+    }
     pub async fn download_without_cache(url: &str) -> Result<String, DownloaderError> {
+        let downloaded_bytes = Downloader::download_bytes_without_cache(url).await?;
+        let stringified = String::from_utf8(downloaded_bytes)
+            .map_err(|e| DownloaderError::InvalidUrlBody(format!("{}", e)))?;
+
+        Ok(stringified)
+    }
+    pub async fn download_bytes_without_cache(url: &str) -> Result<Vec<u8>, DownloaderError> {
         let request = Request::get(url)
             .header("Content-Type", "application/octet-stream")
             .body(())?;
@@ -161,10 +221,7 @@ impl Downloader {
         }
 
         log::debug!("Download complete");
-        let stringified = String::from_utf8(downloaded_bytes)
-            .map_err(|e| DownloaderError::InvalidUrlBody(format!("{}", e)))?;
-
-        Ok(stringified)
+        Ok(downloaded_bytes)
     }
 
     fn parse_name(&self, url: &str) -> Result<String, DownloaderError> {
@@ -185,6 +242,45 @@ impl Downloader {
     }
 }
 
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+pub fn verify_sha256(url: &str, bytes: &[u8], sha256: &str) -> Result<(), DownloaderError> {
+    let expected = sha256.trim().to_lowercase();
+    if expected.len() != 64 || !expected.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(DownloaderError::InvalidSha256(
+            url.to_owned(),
+            sha256.to_owned(),
+        ));
+    }
+    let actual = sha256_hex(bytes);
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(DownloaderError::IntegrityMismatch {
+            url: url.to_owned(),
+            expected,
+            actual,
+        })
+    }
+}
+
+/// Downloads the given plugins and records their hashes, this is used to create the plugin
+/// lockfile
+pub fn lock_remote_plugins(urls: BTreeSet<String>) -> Result<PluginLock, DownloaderError> {
+    async_std::task::block_on(async move {
+        let mut plugin_lock = PluginLock::default();
+        for url in urls {
+            let downloaded_bytes = Downloader::download_bytes_without_cache(&url).await?;
+            plugin_lock.insert(url, sha256_hex(&downloaded_bytes));
+        }
+        Ok(plugin_lock)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -232,4 +328,46 @@ mod tests {
 
         location.close().expect("Failed to close temp directory");
     }
+
+    #[test]
+    fn verify_sha256_of_bytes() {
+        let url = "https://example.com/plugin.wasm";
+        let bytes = b"not really a wasm file";
+        let sha256 = sha256_hex(bytes);
+        assert!(verify_sha256(url, bytes, &sha256).is_ok());
+        assert!(
+            verify_sha256(url, bytes, &sha256.to_uppercase()).is_ok(),
+            "hashes are case insensitive"
+        );
+        assert!(matches!(
+            verify_sha256(url, b"a tampered wasm file", &sha256),
+            Err(DownloaderError::IntegrityMismatch { .. })
+        ));
+        assert!(matches!(
+            verify_sha256(url, bytes, "not-a-hash"),
+            Err(DownloaderError::InvalidSha256(..))
+        ));
+    }
+
+    #[async_std::test]
+    async fn download_and_verify_replaces_tampered_cached_file() {
+        let location = tempdir().expect("Failed to create temp directory");
+        let location_path = location.path();
+        std::fs::write(location_path.join("plugin.wasm"), b"tampered").unwrap();
+
+        // the url cannot be downloaded, so the cached file is removed and downloading it fails
+        let downloader = Downloader::new(location_path.to_path_buf());
+        let result = downloader
+            .download_and_verify(
+                "http://127.0.0.1:0/plugin.wasm",
+                Some("plugin.wasm"),
+                &sha256_hex(b"original"),
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert!(!location_path.join("plugin.wasm").exists());
+
+        location.close().expect("Failed to close temp directory");
+    }
 }
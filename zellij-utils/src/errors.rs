@@ -258,6 +258,8 @@ pub enum ScreenContext {
     Exit,
     ClearScreen,
     DumpScreen,
+    StartRecording,
+    StopRecording,
     DumpLayout,
     EditScrollback,
     ScrollUp,
@@ -266,6 +268,10 @@ pub enum ScreenContext {
     ScrollDownAt,
     ScrollToBottom,
     ScrollToTop,
+    ScrollToPreviousPrompt,
+    ScrollToNextPrompt,
+    CopyLastCommandOutput,
+    EditLastCommandOutput,
     PageScrollUp,
     PageScrollDown,
     HalfPageScrollUp,
@@ -295,10 +301,13 @@ pub enum ScreenContext {
     MoveTabLeft,
     MoveTabRight,
     TerminalResize,
+    ClientTerminalResize,
     TerminalPixelDimensions,
     TerminalBackgroundColor,
     TerminalForegroundColor,
     TerminalColorRegisters,
+    TerminalSupportsKittyGraphics,
+    EnableDiffRendering,
     ChangeMode,
     ChangeModeForAllClients,
     LeftClick,
@@ -322,6 +331,11 @@ pub enum ScreenContext {
     SearchToggleCaseSensitivity,
     SearchToggleWholeWord,
     SearchToggleWrap,
+    SearchToggleRegex,
+    ListSearchMatches,
+    SearchJumpToMatch,
+    SearchPaneScrollback,
+    GetPaneScrollback,
     AddRedPaneFrameColorOverride,
     ClearPaneFrameColorOverride,
     PreviousSwapLayout,
@@ -388,6 +402,7 @@ pub enum ScreenContext {
     InterceptKeyPresses,
     ClearKeyPressesIntercepts,
     ReplacePaneWithExistingPane,
+    SetReportPaneContentChanges,
 }
 
 /// Stack call representations corresponding to the different types of [`PtyInstruction`]s.
@@ -413,6 +428,7 @@ pub enum PtyContext {
     Reconfigure,
     ListClientsToPlugin,
     ReportPluginCwd,
+    ReportTerminalCwd,
     Exit,
 }
 
@@ -426,6 +442,7 @@ pub enum PluginContext {
     Unload,
     Reload,
     ReloadPluginWithId,
+    PluginFileChanged,
     Resize,
     Exit,
     AddClient,
@@ -436,6 +453,7 @@ pub enum PluginContext {
     PostMessageToPluginWorker,
     PostMessageToPlugin,
     PluginSubscribedToEvents,
+    PluginUnsubscribedFromEvents,
     PermissionRequestResult,
     DumpLayout,
     LogLayoutToHd,
@@ -454,6 +472,14 @@ pub enum PluginContext {
     ChangePluginHostDir,
     WebServerStarted,
     FailedToStartWebServer,
+    CallPlugin,
+    PluginCallResponse,
+    PluginCallTimedOut,
+    WatchPaths,
+    UnwatchPaths,
+    RegisterCommands,
+    ListRegisteredCommands,
+    InvokeRegisteredCommand,
 }
 
 /// Stack call representations corresponding to the different types of [`ClientInstruction`]s.
@@ -486,6 +512,7 @@ pub enum ClientContext {
 pub enum ServerContext {
     NewClient,
     Render,
+    RenderDiff,
     UnblockInputThread,
     ClientExit,
     RemoveClient,
@@ -539,6 +566,7 @@ pub enum BackgroundJobContext {
     RenderToClients,
     HighlightPanesWithMessage,
     QueryZellijWebServerStatus,
+    ReportPaneContentChanged,
     Exit,
 }
 
@@ -623,6 +651,12 @@ open an issue on GitHub:
 
     #[error("The plugin does not exist")]
     PluginDoesNotExist,
+
+    #[error("The plugin exceeded its memory limit of {max_memory_mb}MB")]
+    PluginMemoryLimitExceeded { max_memory_mb: usize },
+
+    #[error("The plugin did not finish handling an event within {execution_timeout_ms}ms and was stopped")]
+    PluginExecutionTimeout { execution_timeout_ms: u64 },
 }
 
 #[cfg(not(target_family = "wasm"))]
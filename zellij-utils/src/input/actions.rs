@@ -74,6 +74,7 @@ pub enum SearchOption {
     CaseSensitivity,
     WholeWord,
     Wrap,
+    Regex,
 }
 
 impl FromStr for SearchOption {
@@ -85,6 +86,7 @@ impl FromStr for SearchOption {
             },
             "WholeWord" | "wholeword" | "Wholeword" => Ok(SearchOption::WholeWord),
             "Wrap" | "wrap" => Ok(SearchOption::Wrap),
+            "Regex" | "regex" => Ok(SearchOption::Regex),
             _ => Err(format!(
                 "Failed to parse SearchOption. Unknown SearchOption: {}",
                 s
@@ -127,6 +129,11 @@ pub enum Action {
     ClearScreen,
     /// Dumps the screen to a file
     DumpScreen(String, bool),
+    /// Start recording the session as seen by this client, or only a single terminal pane, to an
+    /// asciicast file
+    StartRecording(PathBuf, Option<PaneId>),
+    /// Stop recording the session as seen by this client, or a single terminal pane
+    StopRecording(Option<PaneId>),
     /// Dumps
     DumpLayout,
     /// Scroll up in focus pane.
@@ -142,6 +149,14 @@ pub enum Action {
     ScrollToBottom,
     /// Scroll up to top in focus pane.
     ScrollToTop,
+    /// Scroll up to the previous shell prompt (OSC 133) in focus pane.
+    ScrollToPreviousPrompt,
+    /// Scroll down to the next shell prompt (OSC 133) in focus pane.
+    ScrollToNextPrompt,
+    /// Copy the output of the last command (OSC 133) in focus pane to the clipboard.
+    CopyLastCommandOutput,
+    /// Open the output of the last command (OSC 133) in focus pane in the default editor.
+    EditLastCommandOutput,
     /// Scroll up one page in focus pane.
     PageScrollUp,
     /// Scroll down one page in focus pane.
@@ -237,6 +252,10 @@ pub enum Action {
     Search(SearchDirection),
     /// Toggle case sensitivity of search
     SearchToggleOption(SearchOption),
+    /// List all the matches of the current search in the focused pane
+    ListSearchMatches,
+    /// Scroll to the Nth match (starting at 1) of the current search in the focused pane
+    SearchJumpToMatch(usize),
     ToggleMouseMode,
     PreviousSwapLayout,
     NextSwapLayout,
@@ -295,6 +314,14 @@ pub enum Action {
         cwd: Option<PathBuf>,
         pane_title: Option<String>,
     },
+    /// Invoke a command registered by one of the loaded plugins
+    PluginCommand {
+        name: String,
+        args: BTreeMap<String, String>,
+        /// only set when invoked from the cli, so that the plugin receives the command as
+        /// a cli pipe message rather than one triggered by a keybinding
+        pipe_id: Option<String>,
+    },
     ListClients,
     TogglePanePinned,
     StackPanes(Vec<PaneId>),
@@ -303,6 +330,20 @@ pub enum Action {
     ToggleGroupMarking,
 }
 
+fn parse_recorded_pane_id(pane_id: &str) -> Result<PaneId, String> {
+    match PaneId::from_str(pane_id) {
+        Ok(PaneId::Terminal(terminal_id)) => Ok(PaneId::Terminal(terminal_id)),
+        Ok(PaneId::Plugin(_)) => Err(format!(
+            "Cannot record plugin pane {}, only terminal panes can be recorded",
+            pane_id
+        )),
+        Err(_) => Err(format!(
+            "Malformed pane id: {}, expecting either a bare integer (eg. 1) or a terminal pane id (eg. terminal_1)",
+            pane_id
+        )),
+    }
+}
+
 impl Action {
     /// Checks that two Action are match except their mutable attributes.
     pub fn shallow_eq(&self, other_action: &Action) -> bool {
@@ -335,12 +376,25 @@ impl Action {
                 path.as_os_str().to_string_lossy().into(),
                 full,
             )]),
+            CliAction::StartRecording { path, pane_id } => {
+                let path = get_current_dir().join(path);
+                let pane_id = pane_id.map(|p| parse_recorded_pane_id(&p)).transpose()?;
+                Ok(vec![Action::StartRecording(path, pane_id)])
+            },
+            CliAction::StopRecording { pane_id } => {
+                let pane_id = pane_id.map(|p| parse_recorded_pane_id(&p)).transpose()?;
+                Ok(vec![Action::StopRecording(pane_id)])
+            },
             CliAction::DumpLayout => Ok(vec![Action::DumpLayout]),
             CliAction::EditScrollback => Ok(vec![Action::EditScrollback]),
             CliAction::ScrollUp => Ok(vec![Action::ScrollUp]),
             CliAction::ScrollDown => Ok(vec![Action::ScrollDown]),
             CliAction::ScrollToBottom => Ok(vec![Action::ScrollToBottom]),
             CliAction::ScrollToTop => Ok(vec![Action::ScrollToTop]),
+            CliAction::ScrollToPreviousPrompt => Ok(vec![Action::ScrollToPreviousPrompt]),
+            CliAction::ScrollToNextPrompt => Ok(vec![Action::ScrollToNextPrompt]),
+            CliAction::CopyLastCommandOutput => Ok(vec![Action::CopyLastCommandOutput]),
+            CliAction::EditLastCommandOutput => Ok(vec![Action::EditLastCommandOutput]),
             CliAction::PageScrollUp => Ok(vec![Action::PageScrollUp]),
             CliAction::PageScrollDown => Ok(vec![Action::PageScrollDown]),
             CliAction::HalfPageScrollUp => Ok(vec![Action::HalfPageScrollUp]),
@@ -353,6 +407,7 @@ impl Action {
                 command,
                 plugin,
                 cwd,
+                env,
                 floating,
                 in_place,
                 name,
@@ -383,6 +438,10 @@ impl Action {
                                 location,
                                 configuration: user_configuration,
                                 initial_cwd: cwd.clone(),
+                                saved_state: None,
+                                limit_overrides: Default::default(),
+                                sha256: None,
+                                watch_for_changes: false,
                             })
                         },
                         Err(_) => {
@@ -437,6 +496,7 @@ impl Action {
                         direction,
                         hold_on_close,
                         hold_on_start,
+                        env: env.into_iter().collect(),
                         ..Default::default()
                     };
                     if floating {
@@ -457,18 +517,32 @@ impl Action {
                         )])
                     }
                 } else {
+                    // a run command without a command spawns the default shell with these env
+                    // variables
+                    let run_command_action = if env.is_empty() {
+                        None
+                    } else {
+                        Some(RunCommandAction {
+                            env: env.into_iter().collect(),
+                            ..Default::default()
+                        })
+                    };
                     if floating {
                         Ok(vec![Action::NewFloatingPane(
-                            None,
+                            run_command_action,
                             name,
                             FloatingPaneCoordinates::new(x, y, width, height, pinned),
                         )])
                     } else if in_place {
-                        Ok(vec![Action::NewInPlacePane(None, name)])
+                        Ok(vec![Action::NewInPlacePane(run_command_action, name)])
                     } else if stacked {
-                        Ok(vec![Action::NewStackedPane(None, name)])
+                        Ok(vec![Action::NewStackedPane(run_command_action, name)])
                     } else {
-                        Ok(vec![Action::NewTiledPane(direction, None, name)])
+                        Ok(vec![Action::NewTiledPane(
+                            direction,
+                            run_command_action,
+                            name,
+                        )])
                     }
                 }
             },
@@ -530,6 +604,7 @@ impl Action {
                 name,
                 layout,
                 layout_dir,
+                layout_arg,
                 cwd,
             } => {
                 let current_dir = get_current_dir();
@@ -558,10 +633,14 @@ impl Action {
                             None,
                         )
                     } else {
-                        Layout::stringified_from_path_or_default(Some(&layout_path), layout_dir)
-                            .map_err(|e| format!("Failed to load layout: {}", e))?
+                        Layout::stringified_from_path_or_default(
+                            Some(&layout_path),
+                            layout_dir.clone(),
+                        )
+                        .map_err(|e| format!("Failed to load layout: {}", e))?
                     };
-                    let mut layout = Layout::from_str(&raw_layout, path_to_raw_layout, swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())), cwd).map_err(|e| {
+                    let layout_args = layout_arg.into_iter().collect();
+                    let mut layout = Layout::from_kdl_with_args(&raw_layout, Some(path_to_raw_layout), swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())), cwd, layout_args, layout_dir).map_err(|e| {
                         let stringified_error = match e {
                             ConfigError::KdlError(kdl_error) => {
                                 let error = kdl_error.add_src(layout_path.as_path().as_os_str().to_string_lossy().to_string(), String::from(raw_layout));
@@ -657,6 +736,29 @@ impl Action {
             CliAction::PreviousSwapLayout => Ok(vec![Action::PreviousSwapLayout]),
             CliAction::NextSwapLayout => Ok(vec![Action::NextSwapLayout]),
             CliAction::QueryTabNames => Ok(vec![Action::QueryTabNames]),
+            CliAction::ListSearchMatches => Ok(vec![Action::ListSearchMatches]),
+            CliAction::SearchJumpToMatch { index } => Ok(vec![Action::SearchJumpToMatch(index)]),
+            CliAction::PluginCommand { name, args } => {
+                let mut parsed_args = BTreeMap::new();
+                for arg in args {
+                    match arg.split_once('=') {
+                        Some((key, value)) => {
+                            parsed_args.insert(key.to_owned(), value.to_owned());
+                        },
+                        None => {
+                            return Err(format!(
+                                "Malformed argument: {}, expecting the form key=value",
+                                arg
+                            ))
+                        },
+                    }
+                }
+                Ok(vec![Action::PluginCommand {
+                    name,
+                    args: parsed_args,
+                    pipe_id: Some(Uuid::new_v4().to_string()),
+                }])
+            },
             CliAction::StartOrReloadPlugin { url, configuration } => {
                 let current_dir = get_current_dir();
                 let run_plugin_or_alias = RunPluginOrAlias::from_url(
@@ -819,6 +921,36 @@ impl Action {
         }
         false
     }
+    /// Actions that only change what the issuing client sees (eg. moving to another tab or
+    /// scrolling) and are thus allowed for read-only (spectator) clients.
+    /// Scrolling by a read-only client only moves that client's own view of the pane. Mouse
+    /// events are excluded since they can be forwarded to the pane.
+    pub fn is_viewer_local(&self) -> bool {
+        match self {
+            Action::ScrollUp
+            | Action::ScrollUpAt(..)
+            | Action::ScrollDown
+            | Action::ScrollDownAt(..)
+            | Action::ScrollToTop
+            | Action::ScrollToBottom
+            | Action::PageScrollUp
+            | Action::PageScrollDown
+            | Action::HalfPageScrollUp
+            | Action::HalfPageScrollDown
+            | Action::SwitchToMode(..)
+            | Action::GoToNextTab
+            | Action::GoToPreviousTab
+            | Action::GoToTab(..)
+            | Action::GoToTabName(_, false)
+            | Action::ToggleTab
+            | Action::QueryTabNames
+            | Action::ListSearchMatches
+            | Action::ListClients
+            | Action::Detach
+            | Action::NoOp => true,
+            _ => false,
+        }
+    }
 }
 
 impl From<OnForceClose> for Action {
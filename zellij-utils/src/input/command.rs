@@ -1,6 +1,7 @@
 //! Trigger a command
 use crate::data::{Direction, OriginatingPlugin};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
@@ -20,6 +21,14 @@ impl TerminalAction {
             },
         }
     }
+    pub fn add_env(&mut self, env: &BTreeMap<String, String>) {
+        // overrides the matching env variables of the command or editor
+        let action_env = match self {
+            TerminalAction::OpenFile(open_file_payload) => &mut open_file_payload.env,
+            TerminalAction::RunCommand(run_command) => &mut run_command.env,
+        };
+        action_env.extend(env.iter().map(|(key, value)| (key.clone(), value.clone())));
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -28,6 +37,9 @@ pub struct OpenFilePayload {
     pub line_number: Option<usize>,
     pub cwd: Option<PathBuf>,
     pub originating_plugin: Option<OriginatingPlugin>,
+    /// Environment variables set for the editor in addition to those inherited from the server
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
 }
 
 impl OpenFilePayload {
@@ -37,8 +49,13 @@ impl OpenFilePayload {
             line_number,
             cwd,
             originating_plugin: None,
+            env: BTreeMap::new(),
         }
     }
+    pub fn with_env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.env = env;
+        self
+    }
     pub fn with_originating_plugin(mut self, originating_plugin: OriginatingPlugin) -> Self {
         self.originating_plugin = Some(originating_plugin);
         self
@@ -61,6 +78,9 @@ pub struct RunCommand {
     pub originating_plugin: Option<OriginatingPlugin>,
     #[serde(default)]
     pub use_terminal_title: bool,
+    /// Environment variables set for the command in addition to those inherited from the server
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
 }
 
 impl std::fmt::Display for RunCommand {
@@ -98,6 +118,8 @@ pub struct RunCommandAction {
     pub originating_plugin: Option<OriginatingPlugin>,
     #[serde(default)]
     pub use_terminal_title: bool,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
 }
 
 impl From<RunCommandAction> for RunCommand {
@@ -110,6 +132,7 @@ impl From<RunCommandAction> for RunCommand {
             hold_on_start: action.hold_on_start,
             originating_plugin: action.originating_plugin,
             use_terminal_title: action.use_terminal_title,
+            env: action.env,
         }
     }
 }
@@ -125,6 +148,7 @@ impl From<RunCommand> for RunCommandAction {
             hold_on_start: run_command.hold_on_start,
             originating_plugin: run_command.originating_plugin,
             use_terminal_title: run_command.use_terminal_title,
+            env: run_command.env,
         }
     }
 }
@@ -1,7 +1,7 @@
 use crate::data::Styling;
 use miette::{Diagnostic, LabeledSpan, NamedSource, SourceCode};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::fs::File;
 use std::io::{self, Read};
 use std::path::PathBuf;
@@ -10,9 +10,9 @@ use thiserror::Error;
 use std::convert::TryFrom;
 
 use super::keybinds::Keybinds;
-use super::layout::RunPluginOrAlias;
+use super::layout::{RunPlugin, RunPluginLocation, RunPluginOrAlias};
 use super::options::Options;
-use super::plugins::{PluginAliases, PluginsConfigError};
+use super::plugins::{PluginAliases, PluginLock, PluginsConfigError};
 use super::theme::{Themes, UiConfig};
 use super::web_client::WebClientConfig;
 use crate::cli::{CliArgs, Command};
@@ -34,6 +34,7 @@ pub struct Config {
     pub env: EnvironmentVariables,
     pub background_plugins: HashSet<RunPluginOrAlias>,
     pub web_client: WebClientConfig,
+    pub plugin_lock: PluginLock,
 }
 
 #[derive(Error, Debug)]
@@ -192,7 +193,11 @@ impl Config {
                 file.read_to_string(&mut kdl_config)
                     .map_err(|e| ConfigError::IoPath(e, path.to_path_buf()))?;
                 match Config::from_kdl(&kdl_config, default_config) {
-                    Ok(config) => Ok(config),
+                    Ok(mut config) => {
+                        config.plugin_lock =
+                            PluginLock::from_path(&PluginLock::path_for_config_file(path))?;
+                        Ok(config)
+                    },
                     Err(ConfigError::KdlDeserializationError(kdl_error)) => {
                         let error_message = match kdl_error.kind {
                             kdl::KdlErrorKind::Context("valid node terminator") => {
@@ -239,6 +244,23 @@ impl Config {
         self.env = self.env.merge(other.env);
         Ok(())
     }
+    /// The urls of all the remote plugins referenced in this configuration's plugin aliases and
+    /// background plugins
+    pub fn remote_plugin_urls(&self) -> BTreeSet<String> {
+        let background_plugins = self.background_plugins.iter().filter_map(|p| match p {
+            RunPluginOrAlias::RunPlugin(run_plugin) => Some(run_plugin),
+            RunPluginOrAlias::Alias(_) => None,
+        });
+        self.plugins
+            .aliases
+            .values()
+            .chain(background_plugins)
+            .filter_map(|run_plugin: &RunPlugin| match &run_plugin.location {
+                RunPluginLocation::Remote(url) => Some(url.clone()),
+                _ => None,
+            })
+            .collect()
+    }
     pub fn config_file_path(opts: &CliArgs) -> Option<PathBuf> {
         opts.config.clone().or_else(|| {
             opts.config_dir
@@ -488,8 +510,9 @@ where
 mod config_test {
     use super::*;
     use crate::data::{InputMode, Palette, PaletteColor, StyleDeclaration, Styling};
-    use crate::input::layout::RunPlugin;
+    use crate::input::layout::{RunPlugin, RunPluginOrAlias};
     use crate::input::options::{Clipboard, OnForceClose};
+    use crate::input::plugins::PluginLimits;
     use crate::input::theme::{FrameConfig, Theme, Themes, UiConfig};
     use std::collections::{BTreeMap, HashMap};
     use std::io::Write;
@@ -1199,6 +1222,210 @@ mod config_test {
         );
     }
 
+    #[test]
+    fn can_define_plugin_limits_in_configfile() {
+        let config_contents = r#"
+            plugin_max_memory_mb 256
+            plugin_execution_timeout_ms 5000
+            plugins {
+                strider location="zellij:strider"
+                greedy location="file:/path/to/greedy.wasm" {
+                    _max_memory_mb 1024
+                    _execution_timeout_ms 0
+                }
+            }
+        "#;
+        let config = Config::from_kdl(config_contents, None).unwrap();
+        let global_limits = PluginLimits::new(
+            config.options.plugin_max_memory_mb,
+            config.options.plugin_execution_timeout_ms,
+        );
+        assert_eq!(
+            global_limits,
+            PluginLimits {
+                max_memory_mb: Some(256),
+                execution_timeout_ms: Some(5000),
+            },
+            "Global plugin limits set in config"
+        );
+        let strider = config.plugins.aliases.get("strider").unwrap();
+        assert_eq!(
+            global_limits.with_overrides(&strider.limit_overrides),
+            global_limits,
+            "Plugin without overrides uses the global limits"
+        );
+        let greedy = config.plugins.aliases.get("greedy").unwrap();
+        assert_eq!(
+            global_limits.with_overrides(&greedy.limit_overrides),
+            PluginLimits {
+                max_memory_mb: Some(1024),
+                execution_timeout_ms: None,
+            },
+            "Plugin alias overrides the global limits"
+        );
+        assert!(
+            greedy.configuration.inner().get("_max_memory_mb").is_none(),
+            "Plugin limits are not passed to the plugin as configuration"
+        );
+    }
+
+    #[test]
+    fn plugin_limits_cannot_be_overridden_when_launching_a_plugin() {
+        let config_contents = r#"
+            plugins {
+                greedy location="file:/path/to/greedy.wasm" {
+                    _max_memory_mb 1024
+                }
+            }
+        "#;
+        let config = Config::from_kdl(config_contents, None).unwrap();
+        let global_limits = PluginLimits::new(Some(256), Some(5000));
+        let mut launch_configuration = BTreeMap::new();
+        launch_configuration.insert("_max_memory_mb".to_owned(), "0".to_owned());
+        launch_configuration.insert("_execution_timeout_ms".to_owned(), "0".to_owned());
+        let mut launched_alias = RunPluginOrAlias::from_url(
+            "greedy",
+            &Some(launch_configuration),
+            Some(&config.plugins),
+            None,
+        )
+        .unwrap();
+        launched_alias.populate_run_plugin_if_needed(&config.plugins);
+        let run_plugin = launched_alias.get_run_plugin().unwrap();
+        assert_eq!(
+            global_limits.with_overrides(&run_plugin.limit_overrides),
+            PluginLimits {
+                max_memory_mb: Some(1024),
+                execution_timeout_ms: Some(5000),
+            },
+            "Per-launch configuration does not override plugin limits"
+        );
+    }
+
+    #[test]
+    fn can_watch_plugins_for_changes_in_configfile() {
+        let config_contents = r#"
+            plugins {
+                strider location="zellij:strider" {
+                    watch_for_changes true
+                }
+                my-plugin location="file:/path/to/my-plugin.wasm" {
+                    watch_for_changes true
+                }
+                other-plugin location="file:/path/to/other-plugin.wasm"
+            }
+        "#;
+        let config = Config::from_kdl(config_contents, None).unwrap();
+        let watches_for_changes = |alias: &str| {
+            config
+                .plugins
+                .aliases
+                .get(alias)
+                .unwrap()
+                .watches_for_changes()
+        };
+        assert!(
+            watches_for_changes("my-plugin"),
+            "File plugin is watched for changes"
+        );
+        assert!(
+            !watches_for_changes("other-plugin"),
+            "File plugin is not watched for changes by default"
+        );
+        assert!(
+            !watches_for_changes("strider"),
+            "Builtin plugins are never watched for changes"
+        );
+        assert!(
+            config
+                .plugins
+                .aliases
+                .get("my-plugin")
+                .unwrap()
+                .configuration
+                .inner()
+                .get("watch_for_changes")
+                .is_none(),
+            "Watching for changes is not passed to the plugin as configuration"
+        );
+    }
+
+    #[test]
+    fn plugin_lock_is_loaded_from_next_to_the_config_file() {
+        let tmp = tempdir().unwrap();
+        let config_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        File::create(&config_path)
+            .unwrap()
+            .write_all(b"plugins { remote location=\"https://example.com/plugin.wasm\"; }\n")
+            .unwrap();
+        File::create(tmp.path().join("plugins.lock"))
+            .unwrap()
+            .write_all(b"plugin location=\"https://example.com/plugin.wasm\" sha256=\"abc\"\n")
+            .unwrap();
+        let config = Config::from_path(&config_path, None).unwrap();
+        assert_eq!(
+            config.plugin_lock.sha256("https://example.com/plugin.wasm"),
+            Some(&"abc".to_owned()),
+            "Plugin lock loaded"
+        );
+        assert_eq!(
+            config.remote_plugin_urls(),
+            BTreeSet::from(["https://example.com/plugin.wasm".to_owned()]),
+            "Remote plugins found in config"
+        );
+    }
+
+    #[test]
+    fn can_pin_remote_plugin_aliases_to_a_sha256() {
+        let config_contents = r#"
+            plugins {
+                pinned location="https://example.com/pinned.wasm" {
+                    sha256 "abc"
+                }
+                locked location="https://example.com/locked.wasm"
+            }
+        "#;
+        let config = Config::from_kdl(config_contents, None).unwrap();
+        let mut plugin_lock = PluginLock::default();
+        plugin_lock.insert(
+            "https://example.com/pinned.wasm".to_owned(),
+            "def".to_owned(),
+        );
+        plugin_lock.insert(
+            "https://example.com/locked.wasm".to_owned(),
+            "ghi".to_owned(),
+        );
+        let pinned = config.plugins.aliases.get("pinned").unwrap();
+        assert_eq!(
+            plugin_lock.pinned_sha256(pinned),
+            Some("abc".to_owned()),
+            "Explicit sha256 takes precedence over the lockfile"
+        );
+        assert!(
+            pinned.configuration.inner().get("sha256").is_none(),
+            "The sha256 is not passed to the plugin as configuration"
+        );
+        let locked = config.plugins.aliases.get("locked").unwrap();
+        assert_eq!(
+            plugin_lock.pinned_sha256(locked),
+            Some("ghi".to_owned()),
+            "Plugin without a sha256 is pinned by the lockfile"
+        );
+    }
+
+    #[test]
+    fn only_remote_plugin_aliases_can_be_pinned_to_a_sha256() {
+        let config_contents = r#"
+            plugins {
+                local location="file:/path/to/local.wasm" sha256="abc"
+            }
+        "#;
+        assert!(
+            Config::from_kdl(config_contents, None).is_err(),
+            "Local plugin cannot be pinned"
+        );
+    }
+
     #[test]
     fn can_define_ui_configuration_in_configfile() {
         let config_contents = r#"
@@ -17,7 +17,7 @@ use crate::{
         command::RunCommand,
         config::{Config, ConfigError},
     },
-    pane_size::{Constraint, Dimension, PaneGeom},
+    pane_size::{Constraint, Dimension, PaneGeom, Size, SizeBounds},
     setup::{self},
 };
 #[cfg(not(target_family = "wasm"))]
@@ -27,7 +27,7 @@ use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-use super::plugins::{PluginAliases, PluginTag, PluginsConfigError};
+use super::plugins::{PluginAliases, PluginLimitOverrides, PluginTag, PluginsConfigError};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::vec::Vec;
@@ -229,6 +229,33 @@ impl RunPluginOrAlias {
             },
         }
     }
+    pub fn with_saved_state(mut self, saved_state: Option<String>) -> Self {
+        // aliases are resolved to their plugin location before a session is serialized, so only
+        // plain plugins can have a saved state
+        if let RunPluginOrAlias::RunPlugin(ref mut run_plugin) = self {
+            run_plugin.saved_state = saved_state;
+        }
+        self
+    }
+    pub fn with_sha256(self, sha256: Option<String>) -> Result<Self, PluginsConfigError> {
+        match self {
+            RunPluginOrAlias::RunPlugin(run_plugin) => {
+                Ok(RunPluginOrAlias::RunPlugin(run_plugin.with_sha256(sha256)?))
+            },
+            // aliases are pinned in their definition
+            RunPluginOrAlias::Alias(alias) if sha256.is_some() => {
+                Err(PluginsConfigError::PinnedLocalPlugin(alias.name))
+            },
+            alias => Ok(alias),
+        }
+    }
+    pub fn with_watch_for_changes(mut self, watch_for_changes: bool) -> Self {
+        // aliases are watched as set in their definition
+        if let RunPluginOrAlias::RunPlugin(ref mut run_plugin) = self {
+            run_plugin.watch_for_changes = watch_for_changes;
+        }
+        self
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -237,8 +264,15 @@ pub enum Run {
     Plugin(RunPluginOrAlias),
     #[serde(rename = "command")]
     Command(RunCommand),
-    EditFile(PathBuf, Option<usize>, Option<PathBuf>), // TODO: merge this with TerminalAction::OpenFile
+    EditFile(
+        PathBuf,
+        Option<usize>,
+        Option<PathBuf>,
+        BTreeMap<String, String>,
+    ), // TODO: merge this with TerminalAction::OpenFile
     Cwd(PathBuf),
+    /// The default shell, spawned with these env variables (and in this cwd if there is one)
+    Shell(Option<PathBuf>, BTreeMap<String, String>),
 }
 
 impl Run {
@@ -256,6 +290,12 @@ impl Run {
                 if merged.args.is_empty() && !base_run_command.args.is_empty() {
                     merged.args = base_run_command.args.clone();
                 }
+                for (key, value) in &base_run_command.env {
+                    merged
+                        .env
+                        .entry(key.clone())
+                        .or_insert_with(|| value.clone());
+                }
                 Some(Run::Command(merged))
             },
             (Some(Run::Command(base_run_command)), Some(Run::Cwd(other_cwd))) => {
@@ -270,27 +310,90 @@ impl Run {
                 }
                 Some(Run::Command(merged))
             },
+            (Some(Run::Command(base_run_command)), Some(Run::Shell(other_cwd, other_env))) => {
+                let mut merged = base_run_command.clone();
+                if other_cwd.is_some() {
+                    merged.cwd = other_cwd.clone();
+                }
+                merged.env.extend(other_env.clone());
+                Some(Run::Command(merged))
+            },
+            (Some(Run::Shell(base_cwd, base_env)), Some(Run::Command(other_command))) => {
+                let mut merged = other_command.clone();
+                if merged.cwd.is_none() {
+                    merged.cwd = base_cwd.clone();
+                }
+                for (key, value) in base_env {
+                    merged
+                        .env
+                        .entry(key.clone())
+                        .or_insert_with(|| value.clone());
+                }
+                Some(Run::Command(merged))
+            },
             (
                 Some(Run::Command(base_run_command)),
-                Some(Run::EditFile(file_to_edit, line_number, edit_cwd)),
+                Some(Run::EditFile(file_to_edit, line_number, edit_cwd, edit_env)),
             ) => match &base_run_command.cwd {
                 Some(cwd) => Some(Run::EditFile(
                     cwd.join(&file_to_edit),
                     *line_number,
                     Some(cwd.join(edit_cwd.clone().unwrap_or_default())),
+                    edit_env.clone(),
                 )),
                 None => Some(Run::EditFile(
                     file_to_edit.clone(),
                     *line_number,
                     edit_cwd.clone(),
+                    edit_env.clone(),
                 )),
             },
-            (Some(Run::Cwd(cwd)), Some(Run::EditFile(file_to_edit, line_number, edit_cwd))) => {
+            (
+                Some(Run::Cwd(cwd)),
+                Some(Run::EditFile(file_to_edit, line_number, edit_cwd, edit_env)),
+            ) => {
                 let cwd = edit_cwd.clone().unwrap_or(cwd.clone());
                 Some(Run::EditFile(
                     cwd.join(&file_to_edit),
                     *line_number,
                     Some(cwd),
+                    edit_env.clone(),
+                ))
+            },
+            (
+                Some(Run::Shell(base_cwd, base_env)),
+                Some(Run::EditFile(file_to_edit, line_number, edit_cwd, edit_env)),
+            ) => {
+                let mut merged_env = base_env.clone();
+                merged_env.extend(edit_env.clone());
+                match edit_cwd.clone().or(base_cwd.clone()) {
+                    Some(cwd) => Some(Run::EditFile(
+                        cwd.join(file_to_edit),
+                        *line_number,
+                        Some(cwd),
+                        merged_env,
+                    )),
+                    None => Some(Run::EditFile(
+                        file_to_edit.clone(),
+                        *line_number,
+                        None,
+                        merged_env,
+                    )),
+                }
+            },
+            (Some(Run::Shell(_base_cwd, base_env)), Some(Run::Cwd(other_cwd))) => {
+                Some(Run::Shell(Some(other_cwd.clone()), base_env.clone()))
+            },
+            (Some(Run::Cwd(base_cwd)), Some(Run::Shell(other_cwd, other_env))) => Some(Run::Shell(
+                other_cwd.clone().or(Some(base_cwd.clone())),
+                other_env.clone(),
+            )),
+            (Some(Run::Shell(base_cwd, base_env)), Some(Run::Shell(other_cwd, other_env))) => {
+                let mut merged_env = base_env.clone();
+                merged_env.extend(other_env.clone());
+                Some(Run::Shell(
+                    other_cwd.clone().or(base_cwd.clone()),
+                    merged_env,
                 ))
             },
             (Some(_base), Some(other)) => Some(other.clone()),
@@ -309,7 +412,7 @@ impl Run {
                     run_command.cwd = Some(cwd.clone());
                 },
             },
-            Run::EditFile(path_to_file, _line_number, edit_cwd) => {
+            Run::EditFile(path_to_file, _line_number, edit_cwd, _env) => {
                 match edit_cwd.as_mut() {
                     Some(edit_cwd) => {
                         *edit_cwd = cwd.join(&edit_cwd);
@@ -323,6 +426,14 @@ impl Run {
             Run::Cwd(path) => {
                 *path = cwd.join(&path);
             },
+            Run::Shell(shell_cwd, _env) => match shell_cwd.as_mut() {
+                Some(shell_cwd) => {
+                    *shell_cwd = cwd.join(&shell_cwd);
+                },
+                None => {
+                    let _ = shell_cwd.insert(cwd.clone());
+                },
+            },
             Run::Plugin(run_plugin_or_alias) => {
                 run_plugin_or_alias.add_initial_cwd(&cwd);
             },
@@ -357,18 +468,66 @@ impl Run {
             }
         }
     }
+    pub fn add_env(&mut self, env: Option<BTreeMap<String, String>>) {
+        // overrides the matching env variables of a command, shell or editor if they are Some,
+        // plugins cannot receive env variables so they are left as is
+        if let Some(env) = env.filter(|env| !env.is_empty()) {
+            if let Run::Cwd(cwd) = self {
+                *self = Run::Shell(Some(cwd.clone()), BTreeMap::new());
+            }
+            match self {
+                Run::Command(RunCommand { env: run_env, .. })
+                | Run::EditFile(_, _, _, run_env)
+                | Run::Shell(_, run_env) => run_env.extend(env),
+                Run::Plugin(..) | Run::Cwd(..) => {},
+            }
+        }
+    }
+    pub fn inherit_env(&mut self, env: &BTreeMap<String, String>) {
+        // adds env variables to a command, shell or editor without overriding the ones it already
+        // has, plugins cannot receive env variables so they are left as is
+        if env.is_empty() {
+            return;
+        }
+        if let Run::Cwd(cwd) = self {
+            *self = Run::Shell(Some(cwd.clone()), BTreeMap::new());
+        }
+        match self {
+            Run::Command(RunCommand { env: run_env, .. })
+            | Run::EditFile(_, _, _, run_env)
+            | Run::Shell(_, run_env) => {
+                for (key, value) in env {
+                    run_env.entry(key.clone()).or_insert_with(|| value.clone());
+                }
+            },
+            Run::Plugin(..) | Run::Cwd(..) => {},
+        }
+    }
+    pub fn env_for_shell(env: &BTreeMap<String, String>) -> Option<Run> {
+        // a bare pane only needs a run instruction if it has env variables to spawn its shell with
+        if env.is_empty() {
+            None
+        } else {
+            Some(Run::Shell(None, env.clone()))
+        }
+    }
     pub fn is_same_category(first: &Option<Run>, second: &Option<Run>) -> bool {
         match (first, second) {
             (Some(Run::Plugin(..)), Some(Run::Plugin(..))) => true,
             (Some(Run::Command(..)), Some(Run::Command(..))) => true,
             (Some(Run::EditFile(..)), Some(Run::EditFile(..))) => true,
             (Some(Run::Cwd(..)), Some(Run::Cwd(..))) => true,
+            (Some(Run::Shell(..)), Some(Run::Shell(..))) => true,
             _ => false,
         }
     }
     pub fn is_terminal(run: &Option<Run>) -> bool {
         match run {
-            Some(Run::Command(..)) | Some(Run::EditFile(..)) | Some(Run::Cwd(..)) | None => true,
+            Some(Run::Command(..))
+            | Some(Run::EditFile(..))
+            | Some(Run::Cwd(..))
+            | Some(Run::Shell(..))
+            | None => true,
             _ => false,
         }
     }
@@ -376,8 +535,9 @@ impl Run {
         match self {
             Run::Plugin(_) => None, // TBD
             Run::Command(run_command) => run_command.cwd.clone(),
-            Run::EditFile(_file, _line_num, cwd) => cwd.clone(),
+            Run::EditFile(_file, _line_num, cwd, _env) => cwd.clone(),
             Run::Cwd(cwd) => Some(cwd.clone()),
+            Run::Shell(cwd, _env) => cwd.clone(),
         }
     }
     pub fn get_run_plugin(&self) -> Option<RunPlugin> {
@@ -399,14 +559,29 @@ impl Run {
     }
 }
 
-#[allow(clippy::derive_hash_xor_eq)]
-#[derive(Debug, Serialize, Deserialize, Clone, Hash, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct RunPlugin {
     #[serde(default)]
     pub _allow_exec_host_cmd: bool,
     pub location: RunPluginLocation,
     pub configuration: PluginUserConfiguration,
     pub initial_cwd: Option<PathBuf>,
+    /// State the plugin saved when its session was serialized, handed back to it once it loads
+    /// when the session is resurrected
+    #[serde(default)]
+    pub saved_state: Option<String>,
+    /// Resource limits set in the plugin's alias definition, these cannot be set when launching
+    /// the plugin
+    #[serde(default)]
+    pub limit_overrides: PluginLimitOverrides,
+    /// The sha256 hash the plugin is pinned to with its `sha256` property, only remote plugins
+    /// can be pinned
+    #[serde(default)]
+    pub sha256: Option<String>,
+    /// Reload the plugin whenever its `.wasm` file changes, set with its `watch_for_changes`
+    /// property
+    #[serde(default)]
+    pub watch_for_changes: bool,
 }
 
 impl RunPlugin {
@@ -425,6 +600,28 @@ impl RunPlugin {
         self.initial_cwd = initial_cwd;
         self
     }
+    pub fn with_limit_overrides(mut self, limit_overrides: PluginLimitOverrides) -> Self {
+        self.limit_overrides = limit_overrides;
+        self
+    }
+    pub fn with_sha256(mut self, sha256: Option<String>) -> Result<Self, PluginsConfigError> {
+        if sha256.is_some() && !matches!(self.location, RunPluginLocation::Remote(_)) {
+            return Err(PluginsConfigError::PinnedLocalPlugin(
+                self.location.display(),
+            ));
+        }
+        self.sha256 = sha256;
+        Ok(self)
+    }
+    pub fn with_watch_for_changes(mut self, watch_for_changes: bool) -> Self {
+        self.watch_for_changes = watch_for_changes;
+        self
+    }
+    /// Whether this plugin should be reloaded whenever its `.wasm` file changes (only meaningful
+    /// for `file:` plugins)
+    pub fn watches_for_changes(&self) -> bool {
+        self.watch_for_changes && matches!(self.location, RunPluginLocation::File(_))
+    }
     pub fn merge_configuration(mut self, configuration: &Option<BTreeMap<String, String>>) -> Self {
         if let Some(configuration) = configuration {
             self.configuration.merge(configuration);
@@ -494,9 +691,9 @@ impl PluginAlias {
     }
 }
 
-#[allow(clippy::derive_hash_xor_eq)]
 impl PartialEq for RunPlugin {
     fn eq(&self, other: &Self) -> bool {
+        // NOTE: Keep this in sync with what the `Hash` trait impl does.
         // TODO: normalize paths here if the location is a file so that relative/absolute paths
         // will work properly
         (&self.location, &self.configuration) == (&other.location, &other.configuration)
@@ -504,6 +701,14 @@ impl PartialEq for RunPlugin {
 }
 impl Eq for RunPlugin {}
 
+impl std::hash::Hash for RunPlugin {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // NOTE: Keep this in sync with what the `PartialEq` trait impl does.
+        self.location.hash(state);
+        self.configuration.hash(state);
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct PluginUserConfiguration(BTreeMap<String, String>);
 
@@ -521,6 +726,8 @@ impl PluginUserConfiguration {
         configuration.remove("payload");
         configuration.remove("skip_cache");
         configuration.remove("title");
+        configuration.remove("_max_memory_mb");
+        configuration.remove("_execution_timeout_ms");
         configuration.remove("in_place");
         configuration.remove("skip_plugin_cache");
 
@@ -648,18 +855,64 @@ impl fmt::Display for RunPluginLocation {
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum LayoutConstraint {
+    // this is first so that combined constraints (eg. max_panes + min_columns) are tried before
+    // the more general ones when choosing a layout
+    All(Vec<LayoutConstraint>),
     MaxPanes(usize),
     MinPanes(usize),
     ExactPanes(usize),
+    MinColumns(usize),
+    MaxColumns(usize),
+    MinRows(usize),
+    MaxRows(usize),
     NoConstraint,
 }
 
+impl LayoutConstraint {
+    pub fn fits_pane_count(&self, pane_count: usize) -> bool {
+        match self {
+            LayoutConstraint::All(constraints) => constraints
+                .iter()
+                .all(|constraint| constraint.fits_pane_count(pane_count)),
+            LayoutConstraint::MaxPanes(max_panes) => pane_count <= *max_panes,
+            LayoutConstraint::MinPanes(min_panes) => pane_count >= *min_panes,
+            LayoutConstraint::ExactPanes(exact_panes) => pane_count == *exact_panes,
+            _ => true,
+        }
+    }
+    pub fn fits_display_area(&self, display_area: &Size) -> bool {
+        match self {
+            LayoutConstraint::All(constraints) => constraints
+                .iter()
+                .all(|constraint| constraint.fits_display_area(display_area)),
+            LayoutConstraint::MinColumns(min_columns) => display_area.cols >= *min_columns,
+            LayoutConstraint::MaxColumns(max_columns) => display_area.cols <= *max_columns,
+            LayoutConstraint::MinRows(min_rows) => display_area.rows >= *min_rows,
+            LayoutConstraint::MaxRows(max_rows) => display_area.rows <= *max_rows,
+            _ => true,
+        }
+    }
+}
+
 impl Display for LayoutConstraint {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
             LayoutConstraint::MaxPanes(max_panes) => write!(f, "max_panes={}", max_panes),
             LayoutConstraint::MinPanes(min_panes) => write!(f, "min_panes={}", min_panes),
             LayoutConstraint::ExactPanes(exact_panes) => write!(f, "exact_panes={}", exact_panes),
+            LayoutConstraint::MinColumns(min_columns) => write!(f, "min_columns={}", min_columns),
+            LayoutConstraint::MaxColumns(max_columns) => write!(f, "max_columns={}", max_columns),
+            LayoutConstraint::MinRows(min_rows) => write!(f, "min_rows={}", min_rows),
+            LayoutConstraint::MaxRows(max_rows) => write!(f, "max_rows={}", max_rows),
+            LayoutConstraint::All(constraints) => write!(
+                f,
+                "{}",
+                constraints
+                    .iter()
+                    .map(|constraint| constraint.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
             LayoutConstraint::NoConstraint => write!(f, ""),
         }
     }
@@ -752,6 +1005,7 @@ pub struct FloatingPaneLayout {
     pub focus: Option<bool>,
     pub already_running: bool,
     pub pane_initial_contents: Option<String>,
+    pub pane_scrollback_file: Option<PathBuf>,
     pub logical_position: Option<usize>,
 }
 
@@ -768,6 +1022,7 @@ impl FloatingPaneLayout {
             focus: None,
             already_running: false,
             pane_initial_contents: None,
+            pane_scrollback_file: None,
             logical_position: None,
         }
     }
@@ -784,6 +1039,22 @@ impl FloatingPaneLayout {
             run.add_start_suspended(start_suspended);
         }
     }
+    pub fn add_env(&mut self, env: Option<BTreeMap<String, String>>) {
+        match self.run.as_mut() {
+            Some(run) => run.add_env(env),
+            None => {
+                self.run = env.and_then(|env| Run::env_for_shell(&env));
+            },
+        }
+    }
+    pub fn inherit_env(&mut self, env: &BTreeMap<String, String>) {
+        match self.run.as_mut() {
+            Some(run) => run.inherit_env(env),
+            None => {
+                self.run = Run::env_for_shell(env);
+            },
+        }
+    }
 }
 
 impl From<&TiledPaneLayout> for FloatingPaneLayout {
@@ -797,12 +1068,14 @@ impl From<&TiledPaneLayout> for FloatingPaneLayout {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
 pub struct TiledPaneLayout {
     pub children_split_direction: SplitDirection,
     pub name: Option<String>,
     pub children: Vec<TiledPaneLayout>,
     pub split_size: Option<SplitSize>,
+    pub min_size: Option<SplitSize>,
+    pub max_size: Option<SplitSize>,
     pub run: Option<Run>,
     pub borderless: bool,
     pub focus: Option<bool>,
@@ -813,6 +1086,41 @@ pub struct TiledPaneLayout {
     pub run_instructions_to_ignore: Vec<Option<Run>>,
     pub hide_floating_panes: bool, // only relevant if this is the base layout
     pub pane_initial_contents: Option<String>,
+    pub pane_scrollback_file: Option<PathBuf>,
+}
+
+impl fmt::Debug for TiledPaneLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // min_size and max_size are left out when not set, since most panes do not have them
+        let mut debug_struct = f.debug_struct("TiledPaneLayout");
+        debug_struct
+            .field("children_split_direction", &self.children_split_direction)
+            .field("name", &self.name)
+            .field("children", &self.children)
+            .field("split_size", &self.split_size);
+        if self.min_size.is_some() {
+            debug_struct.field("min_size", &self.min_size);
+        }
+        if self.max_size.is_some() {
+            debug_struct.field("max_size", &self.max_size);
+        }
+        debug_struct
+            .field("run", &self.run)
+            .field("borderless", &self.borderless)
+            .field("focus", &self.focus)
+            .field("external_children_index", &self.external_children_index)
+            .field("children_are_stacked", &self.children_are_stacked)
+            .field("is_expanded_in_stack", &self.is_expanded_in_stack)
+            .field("exclude_from_sync", &self.exclude_from_sync)
+            .field(
+                "run_instructions_to_ignore",
+                &self.run_instructions_to_ignore,
+            )
+            .field("hide_floating_panes", &self.hide_floating_panes)
+            .field("pane_initial_contents", &self.pane_initial_contents)
+            .field("pane_scrollback_file", &self.pane_scrollback_file)
+            .finish()
+    }
 }
 
 impl TiledPaneLayout {
@@ -1014,6 +1322,28 @@ impl TiledPaneLayout {
             child.add_cwd_to_layout(cwd);
         }
     }
+    pub fn add_env(&mut self, env: Option<BTreeMap<String, String>>) {
+        // only sets the env of this pane, its children have to inherit it themselves
+        match self.run.as_mut() {
+            Some(run) => run.add_env(env),
+            None if self.children.is_empty() && self.external_children_index.is_none() => {
+                self.run = env.and_then(|env| Run::env_for_shell(&env));
+            },
+            None => {},
+        }
+    }
+    pub fn inherit_env_in_layout(&mut self, env: &BTreeMap<String, String>) {
+        match self.run.as_mut() {
+            Some(run) => run.inherit_env(env),
+            None if self.children.is_empty() && self.external_children_index.is_none() => {
+                self.run = Run::env_for_shell(env);
+            },
+            None => {},
+        }
+        for child in self.children.iter_mut() {
+            child.inherit_env_in_layout(env);
+        }
+    }
     pub fn populate_plugin_aliases_in_layout(&mut self, plugin_aliases: &PluginAliases) {
         match self.run.as_mut() {
             Some(run) => run.populate_run_plugin_if_needed(plugin_aliases),
@@ -1232,13 +1562,15 @@ impl Layout {
             },
             LayoutInfo::Stringified(stringified_layout) => (None, stringified_layout, None),
         };
-        let mut layout = Layout::from_kdl(
+        let mut layout = Layout::from_kdl_with_args(
             &raw_layout,
             path_to_raw_layout,
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
             None,
+            BTreeMap::new(),
+            layout_dir.clone(),
         );
         if should_start_layout_commands_suspended {
             layout
@@ -1293,16 +1625,26 @@ impl Layout {
         layout_path: Option<&PathBuf>,
         layout_dir: Option<PathBuf>,
         config: Config,
+    ) -> Result<(Layout, Config), ConfigError> {
+        Layout::from_path_or_default_with_args(layout_path, layout_dir, BTreeMap::new(), config)
+    }
+    pub fn from_path_or_default_with_args(
+        layout_path: Option<&PathBuf>,
+        layout_dir: Option<PathBuf>,
+        layout_args: BTreeMap<String, String>,
+        config: Config,
     ) -> Result<(Layout, Config), ConfigError> {
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) =
-            Layout::stringified_from_path_or_default(layout_path, layout_dir)?;
-        let layout = Layout::from_kdl(
+            Layout::stringified_from_path_or_default(layout_path, layout_dir.clone())?;
+        let layout = Layout::from_kdl_with_args(
             &raw_layout,
             Some(path_to_raw_layout),
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
             None,
+            layout_args,
+            layout_dir,
         )?;
         let config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
         Ok((layout, config))
@@ -1340,14 +1682,16 @@ impl Layout {
         layout_dir: Option<PathBuf>,
     ) -> Result<Layout, ConfigError> {
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) =
-            Layout::stringified_from_path_or_default(layout_path, layout_dir)?;
-        let layout = Layout::from_kdl(
+            Layout::stringified_from_path_or_default(layout_path, layout_dir.clone())?;
+        let layout = Layout::from_kdl_with_args(
             &raw_layout,
             Some(path_to_raw_layout),
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
             None,
+            BTreeMap::new(),
+            layout_dir,
         )?;
         Ok(layout)
     }
@@ -1690,10 +2034,20 @@ fn split_space(
         ),
     };
 
-    let min_size_for_panes = sizes.iter().fold(0, |acc, size| match size {
-        Some(SplitSize::Percent(_)) | None => acc + 1, // TODO: minimum height/width as relevant here
-        Some(SplitSize::Fixed(fixed)) => acc + fixed,
-    });
+    let min_size_for_panes = sizes
+        .iter()
+        .zip(&*layout.children)
+        .fold(0, |acc, (size, part)| match size {
+            Some(SplitSize::Percent(_)) | None => {
+                let min_size = part
+                    .min_size
+                    .filter(|_| !layout.children_are_stacked)
+                    .map(|min_size| min_size.to_fixed(total_split_dimension_space.as_usize()))
+                    .unwrap_or(1);
+                acc + std::cmp::max(min_size, 1)
+            },
+            Some(SplitSize::Fixed(fixed)) => acc + fixed,
+        });
     if min_size_for_panes > split_dimension_space.as_usize() {
         return Err("Not enough room for panes"); // TODO: use error infra
     }
@@ -1715,7 +2069,7 @@ fn split_space(
     };
 
     let mut total_pane_size = 0;
-    for (&size, _part) in sizes.iter().zip(&*layout.children) {
+    for (&size, part) in sizes.iter().zip(&*layout.children) {
         let mut split_dimension = match size {
             Some(SplitSize::Percent(percent)) => Dimension::percent(percent as f64),
             Some(SplitSize::Fixed(size)) => Dimension::fixed(size),
@@ -1742,6 +2096,14 @@ fn split_space(
         );
         total_pane_size += split_dimension.as_usize();
 
+        // the bounds in the split direction come from the pane itself, the ones in the other
+        // direction are inherited from its parent
+        let (min_size, max_size) = if layout.children_are_stacked {
+            (None, None)
+        } else {
+            (part.min_size, part.max_size)
+        };
+        let inherited_bounds = space_to_split.size_bounds;
         let geom = match layout.children_split_direction {
             SplitDirection::Vertical => PaneGeom {
                 x: current_position,
@@ -1751,6 +2113,11 @@ fn split_space(
                 stacked,
                 is_pinned: false,
                 logical_position: None,
+                size_bounds: SizeBounds {
+                    min_cols: min_size,
+                    max_cols: max_size,
+                    ..inherited_bounds
+                },
             },
             SplitDirection::Horizontal => PaneGeom {
                 x: space_to_split.x,
@@ -1760,11 +2127,23 @@ fn split_space(
                 stacked,
                 is_pinned: false,
                 logical_position: None,
+                size_bounds: SizeBounds {
+                    min_rows: min_size,
+                    max_rows: max_size,
+                    ..inherited_bounds
+                },
             },
         };
         split_geom.push(geom);
         current_position += split_dimension.as_usize();
     }
+    if split_geom.iter().any(|g| !g.size_bounds.is_empty()) {
+        total_pane_size = apply_size_bounds_to_split_geoms(
+            &mut split_geom,
+            layout.children_split_direction,
+            total_split_dimension_space.as_usize(),
+        );
+    }
     adjust_geoms_for_rounding_errors(
         total_pane_size,
         &mut split_geom,
@@ -1802,6 +2181,118 @@ fn split_space(
     Ok(pane_positions)
 }
 
+fn clamp_in_split_direction(
+    size_bounds: &SizeBounds,
+    size: usize,
+    children_split_direction: SplitDirection,
+    full_size: usize,
+) -> usize {
+    match children_split_direction {
+        SplitDirection::Vertical => size_bounds.clamp_cols(size, full_size),
+        SplitDirection::Horizontal => size_bounds.clamp_rows(size, full_size),
+    }
+}
+
+fn split_dimension_mut(
+    geom: &mut PaneGeom,
+    children_split_direction: SplitDirection,
+) -> &mut Dimension {
+    match children_split_direction {
+        SplitDirection::Vertical => &mut geom.cols,
+        SplitDirection::Horizontal => &mut geom.rows,
+    }
+}
+
+fn apply_size_bounds_to_split_geoms(
+    split_geoms: &mut [PaneGeom],
+    children_split_direction: SplitDirection,
+    full_size: usize,
+) -> usize {
+    // returns the new total size of the geoms
+    //
+    // flexible geoms are clamped to their min/max size, and the space they gained or lost is
+    // taken from or given to their flexible siblings, as long as those stay within their own
+    // bounds
+    let original_sizes: Vec<usize> = split_geoms
+        .iter_mut()
+        .map(|g| split_dimension_mut(g, children_split_direction).as_usize())
+        .collect();
+    let mut difference: isize = 0;
+    let mut siblings_to_adjust = vec![];
+    for (i, geom) in split_geoms.iter_mut().enumerate() {
+        let size_bounds = geom.size_bounds;
+        let dimension = split_dimension_mut(geom, children_split_direction);
+        if !dimension.is_percent() {
+            continue;
+        }
+        let size = dimension.as_usize();
+        let clamped_size =
+            clamp_in_split_direction(&size_bounds, size, children_split_direction, full_size);
+        if clamped_size != size {
+            difference += clamped_size as isize - size as isize;
+            dimension.set_inner(clamped_size);
+        } else {
+            siblings_to_adjust.push(i);
+        }
+    }
+    let mut made_progress = true;
+    while difference != 0 && made_progress {
+        made_progress = false;
+        for i in &siblings_to_adjust {
+            if difference == 0 {
+                break;
+            }
+            let geom = &mut split_geoms[*i];
+            let size_bounds = geom.size_bounds;
+            let dimension = split_dimension_mut(geom, children_split_direction);
+            let size = dimension.as_usize();
+            let new_size = if difference > 0 {
+                size.saturating_sub(1)
+            } else {
+                size + 1
+            };
+            let new_size_is_in_bounds = new_size > 0
+                && clamp_in_split_direction(
+                    &size_bounds,
+                    new_size,
+                    children_split_direction,
+                    full_size,
+                ) == new_size;
+            if new_size_is_in_bounds {
+                dimension.set_inner(new_size);
+                difference -= difference.signum();
+                made_progress = true;
+            }
+        }
+    }
+    // keep the percentages in line with the new sizes so that they hold when the panes are
+    // later resized
+    let mut position = split_geoms
+        .first()
+        .map(|g| match children_split_direction {
+            SplitDirection::Vertical => g.x,
+            SplitDirection::Horizontal => g.y,
+        })
+        .unwrap_or(0);
+    let mut total_size = 0;
+    for (geom, original_size) in split_geoms.iter_mut().zip(original_sizes) {
+        match children_split_direction {
+            SplitDirection::Vertical => geom.x = position,
+            SplitDirection::Horizontal => geom.y = position,
+        }
+        let dimension = split_dimension_mut(geom, children_split_direction);
+        let size = dimension.as_usize();
+        if let Some(percent) = dimension.as_percent() {
+            if size != original_size && original_size > 0 {
+                dimension.set_percent(percent * size as f64 / original_size as f64);
+            }
+        }
+        position += size;
+        total_size += size;
+    }
+    total_size
+}
+
 fn adjust_geoms_for_rounding_errors(
     total_pane_size: usize,
     split_geoms: &mut Vec<PaneGeom>,
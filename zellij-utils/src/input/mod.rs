@@ -50,6 +50,7 @@ mod not_wasm {
             web_sharing: None,
             currently_marking_pane_group: None,
             is_web_client: None,
+            is_read_only: None,
             web_server_ip: None,
             web_server_port: None,
             web_server_capability: None,
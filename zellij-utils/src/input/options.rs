@@ -220,6 +220,35 @@ pub struct Options {
     #[serde(default)]
     pub advanced_mouse_actions: Option<bool>,
 
+    /// Whether to forward notifications sent by panes (OSC 9 / OSC 777) to the terminal emulator
+    /// zellij is running in
+    /// default is false
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub forward_pane_notifications: Option<bool>,
+
+    /// Whether to ask the server to send only the cells that changed on every render instead of
+    /// the full escape sequences (useful over slow connections)
+    /// default is false
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub diff_rendering: Option<bool>,
+
+    /// The maximum size of the linear memory of each plugin in megabytes, can be overridden per
+    /// plugin alias with the `_max_memory_mb` configuration key
+    /// default is 0 (unlimited)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub plugin_max_memory_mb: Option<usize>,
+
+    /// The maximum time in milliseconds a plugin may spend in a single update, render or pipe
+    /// call before it is stopped, can be overridden per plugin alias with the
+    /// `_execution_timeout_ms` configuration key
+    /// default is 10000, 0 means unlimited
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub plugin_execution_timeout_ms: Option<u64>,
+
     // these are intentionally excluded from the CLI options as they must be specified in the
     // configuration file
     pub web_server_ip: Option<IpAddr>,
@@ -315,6 +344,14 @@ impl Options {
         let show_startup_tips = other.show_startup_tips.or(self.show_startup_tips);
         let show_release_notes = other.show_release_notes.or(self.show_release_notes);
         let advanced_mouse_actions = other.advanced_mouse_actions.or(self.advanced_mouse_actions);
+        let forward_pane_notifications = other
+            .forward_pane_notifications
+            .or(self.forward_pane_notifications);
+        let diff_rendering = other.diff_rendering.or(self.diff_rendering);
+        let plugin_max_memory_mb = other.plugin_max_memory_mb.or(self.plugin_max_memory_mb);
+        let plugin_execution_timeout_ms = other
+            .plugin_execution_timeout_ms
+            .or(self.plugin_execution_timeout_ms);
         let web_server_ip = other.web_server_ip.or(self.web_server_ip);
         let web_server_port = other.web_server_port.or(self.web_server_port);
         let web_server_cert = other
@@ -362,6 +399,10 @@ impl Options {
             show_startup_tips,
             show_release_notes,
             advanced_mouse_actions,
+            forward_pane_notifications,
+            diff_rendering,
+            plugin_max_memory_mb,
+            plugin_execution_timeout_ms,
             web_server_ip,
             web_server_port,
             web_server_cert,
@@ -432,6 +473,14 @@ impl Options {
         let show_startup_tips = other.show_startup_tips.or(self.show_startup_tips);
         let show_release_notes = other.show_release_notes.or(self.show_release_notes);
         let advanced_mouse_actions = other.advanced_mouse_actions.or(self.advanced_mouse_actions);
+        let forward_pane_notifications = other
+            .forward_pane_notifications
+            .or(self.forward_pane_notifications);
+        let diff_rendering = other.diff_rendering.or(self.diff_rendering);
+        let plugin_max_memory_mb = other.plugin_max_memory_mb.or(self.plugin_max_memory_mb);
+        let plugin_execution_timeout_ms = other
+            .plugin_execution_timeout_ms
+            .or(self.plugin_execution_timeout_ms);
         let web_server_ip = other.web_server_ip.or(self.web_server_ip);
         let web_server_port = other.web_server_port.or(self.web_server_port);
         let web_server_cert = other
@@ -479,6 +528,10 @@ impl Options {
             show_startup_tips,
             show_release_notes,
             advanced_mouse_actions,
+            forward_pane_notifications,
+            diff_rendering,
+            plugin_max_memory_mb,
+            plugin_execution_timeout_ms,
             web_server_ip,
             web_server_port,
             web_server_cert,
@@ -556,6 +609,10 @@ impl From<CliOptions> for Options {
             show_startup_tips: opts.show_startup_tips,
             show_release_notes: opts.show_release_notes,
             advanced_mouse_actions: opts.advanced_mouse_actions,
+            forward_pane_notifications: opts.forward_pane_notifications,
+            diff_rendering: opts.diff_rendering,
+            plugin_max_memory_mb: opts.plugin_max_memory_mb,
+            plugin_execution_timeout_ms: opts.plugin_execution_timeout_ms,
             web_server_ip: opts.web_server_ip,
             web_server_port: opts.web_server_port,
             web_server_cert: opts.web_server_cert,
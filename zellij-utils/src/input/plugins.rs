@@ -43,6 +43,9 @@ pub struct PluginConfig {
     pub userspace_configuration: PluginUserConfiguration,
     /// plugin initial working directory
     pub initial_cwd: Option<PathBuf>,
+    /// Resource limits for this plugin
+    #[serde(default)]
+    pub limits: PluginLimits,
 }
 
 impl PluginConfig {
@@ -54,6 +57,7 @@ impl PluginConfig {
                 location: run_plugin.location.clone(),
                 userspace_configuration: run_plugin.configuration.clone(),
                 initial_cwd: run_plugin.initial_cwd.clone(),
+                limits: PluginLimits::default(),
             }),
             RunPluginLocation::Zellij(tag) => {
                 let tag = tag.to_string();
@@ -67,6 +71,7 @@ impl PluginConfig {
                     || tag == "about"
                     || tag == "share"
                     || tag == "multiple-select"
+                    || tag == "command-palette"
                 {
                     Some(PluginConfig {
                         path: PathBuf::from(&tag),
@@ -75,6 +80,7 @@ impl PluginConfig {
                             .ok()?,
                         userspace_configuration: run_plugin.configuration.clone(),
                         initial_cwd: run_plugin.initial_cwd.clone(),
+                        limits: PluginLimits::default(),
                     })
                 } else {
                     None
@@ -86,6 +92,7 @@ impl PluginConfig {
                 location: run_plugin.location.clone(),
                 userspace_configuration: run_plugin.configuration.clone(),
                 initial_cwd: run_plugin.initial_cwd.clone(),
+                limits: PluginLimits::default(),
             }),
         }
     }
@@ -191,6 +198,90 @@ impl PluginConfig {
     }
 }
 
+/// Resource limits for a running plugin, a limit of `None` means it is not limited
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct PluginLimits {
+    /// The maximum size of the plugin's linear memory, in megabytes
+    pub max_memory_mb: Option<usize>,
+    /// The maximum duration of a single `update`, `render` or `pipe` call, in milliseconds
+    pub execution_timeout_ms: Option<u64>,
+}
+
+impl PluginLimits {
+    pub fn new(max_memory_mb: Option<usize>, execution_timeout_ms: Option<u64>) -> Self {
+        // 0 means no limit
+        PluginLimits {
+            max_memory_mb: max_memory_mb.filter(|m| *m > 0),
+            execution_timeout_ms: execution_timeout_ms.filter(|e| *e > 0),
+        }
+    }
+    /// Overrides these limits with the ones set in the plugin's alias definition
+    pub fn with_overrides(mut self, overrides: &PluginLimitOverrides) -> Self {
+        if let Some(max_memory_mb) = overrides.max_memory_mb {
+            self.max_memory_mb = Some(max_memory_mb).filter(|m| *m > 0);
+        }
+        if let Some(execution_timeout_ms) = overrides.execution_timeout_ms {
+            self.execution_timeout_ms = Some(execution_timeout_ms).filter(|e| *e > 0);
+        }
+        self
+    }
+}
+
+/// Per-plugin overrides of the global `PluginLimits`, set with the `_max_memory_mb` and
+/// `_execution_timeout_ms` properties of a plugin alias in the `plugins` block of the
+/// configuration. A value of `Some(0)` removes the limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct PluginLimitOverrides {
+    pub max_memory_mb: Option<usize>,
+    pub execution_timeout_ms: Option<u64>,
+}
+
+impl From<PluginLimits> for PluginLimitOverrides {
+    fn from(limits: PluginLimits) -> Self {
+        // overrides that result in exactly these limits
+        PluginLimitOverrides {
+            max_memory_mb: Some(limits.max_memory_mb.unwrap_or(0)),
+            execution_timeout_ms: Some(limits.execution_timeout_ms.unwrap_or(0)),
+        }
+    }
+}
+
+pub const PLUGIN_LOCK_FILE_NAME: &str = "plugins.lock";
+
+/// The sha256 hashes of remote plugins, keyed by their url. These are recorded in a lockfile next
+/// to the configuration file by `zellij plugin lock` and verified whenever the plugin is loaded.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PluginLock {
+    pub hashes: BTreeMap<String, String>,
+}
+
+impl PluginLock {
+    pub fn insert(&mut self, url: String, sha256: String) {
+        self.hashes.insert(url, sha256);
+    }
+    pub fn sha256(&self, url: &str) -> Option<&String> {
+        self.hashes.get(url)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+    pub fn path_for_config_file(config_file: &Path) -> PathBuf {
+        config_file.with_file_name(PLUGIN_LOCK_FILE_NAME)
+    }
+    /// The sha256 hash a remote plugin is pinned to, either explicitly with a `sha256` attribute
+    /// or by the lockfile
+    pub fn pinned_sha256(&self, run_plugin: &RunPlugin) -> Option<String> {
+        match &run_plugin.location {
+            RunPluginLocation::Remote(url) => run_plugin
+                .sha256
+                .as_ref()
+                .or_else(|| self.sha256(url))
+                .cloned(),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Error, Debug, PartialEq)]
 pub enum PluginsConfigError {
     #[error("Duplication in plugin tag names is not allowed: '{}'", String::from(.0.clone()))]
@@ -201,4 +292,6 @@ pub enum PluginsConfigError {
     InvalidUrlScheme(Url),
     #[error("Could not find plugin at the path: '{0:?}'")]
     InvalidPluginLocation(PathBuf),
+    #[error("Only remote (http(s):) plugins can be pinned to a sha256 hash, '{0}' is not remote")]
+    PinnedLocalPlugin(String),
 }
@@ -409,6 +409,86 @@ fn layout_with_panes_in_different_mixed_split_sizes() {
     assert_eq!(layout, expected_layout);
 }
 
+#[test]
+fn layout_with_pane_size_bounds() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="sidebar" min_size=20 max_size="30%"
+            pane size="20%" min_size=10
+            sidebar max_size="40%"
+            pane max_size=5
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let expected_layout = Layout {
+        template: Some((
+            TiledPaneLayout {
+                children: vec![
+                    TiledPaneLayout {
+                        split_size: Some(SplitSize::Percent(20)),
+                        min_size: Some(SplitSize::Fixed(10)),
+                        ..Default::default()
+                    },
+                    TiledPaneLayout {
+                        min_size: Some(SplitSize::Fixed(20)),
+                        max_size: Some(SplitSize::Percent(40)),
+                        ..Default::default()
+                    },
+                    TiledPaneLayout {
+                        max_size: Some(SplitSize::Fixed(5)),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            vec![],
+        )),
+        ..Default::default()
+    };
+    assert_eq!(layout, expected_layout);
+}
+
+#[test]
+fn pane_min_size_larger_than_max_size_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            pane min_size=20 max_size=10
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "min_size cannot be larger than max_size");
+}
+
+#[test]
+fn pane_size_bounds_are_honored_when_positioning_panes() {
+    let kdl_layout = r#"
+        layout {
+            pane size="10%" min_size=30
+            pane
+            pane max_size="20%"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let mut space = crate::pane_size::PaneGeom::default();
+    space.rows.set_inner(100);
+    space.cols.set_inner(100);
+    let positions = layout
+        .template
+        .unwrap()
+        .0
+        .position_panes_in_space(&space, None, false, false)
+        .unwrap();
+    let rows_and_y: Vec<(usize, usize)> = positions
+        .iter()
+        .map(|(_, geom)| (geom.rows.as_usize(), geom.y))
+        .collect();
+    assert_eq!(
+        rows_and_y,
+        vec![(30, 0), (50, 30), (20, 80)],
+        "panes are kept within their min and max sizes"
+    );
+}
+
 #[test]
 fn layout_with_command_panes() {
     let kdl_layout = r#"
@@ -1938,6 +2018,103 @@ fn can_load_swap_layouts_from_a_different_file() {
     assert_snapshot!(format!("{:#?}", layout));
 }
 
+#[test]
+fn swap_layouts_with_viewport_constraints() {
+    let kdl_layout = r#"
+        layout {
+            pane
+            swap_tiled_layout name="narrow" {
+                tab max_columns=120 {
+                    pane
+                    pane
+                }
+            }
+            swap_tiled_layout name="ultrawide" {
+                tab max_panes=3 min_columns=200 min_rows=40 {
+                    pane split_direction="vertical" {
+                        pane
+                        pane
+                        pane
+                    }
+                }
+            }
+            swap_floating_layout {
+                floating_panes max_rows=30 {
+                    pane
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let narrow_constraints: Vec<&LayoutConstraint> =
+        layout.swap_tiled_layouts[0].0.keys().collect();
+    assert_eq!(
+        narrow_constraints,
+        vec![&LayoutConstraint::MaxColumns(120)],
+        "single viewport constraint"
+    );
+    let ultrawide_constraints: Vec<&LayoutConstraint> =
+        layout.swap_tiled_layouts[1].0.keys().collect();
+    assert_eq!(
+        ultrawide_constraints,
+        vec![&LayoutConstraint::All(vec![
+            LayoutConstraint::MaxPanes(3),
+            LayoutConstraint::MinColumns(200),
+            LayoutConstraint::MinRows(40),
+        ])],
+        "viewport constraints combined with a pane count constraint"
+    );
+    let floating_constraints: Vec<&LayoutConstraint> =
+        layout.swap_floating_layouts[0].0.keys().collect();
+    assert_eq!(
+        floating_constraints,
+        vec![&LayoutConstraint::MaxRows(30)],
+        "viewport constraint on floating panes"
+    );
+}
+
+#[test]
+fn swap_layout_min_columns_larger_than_max_columns_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            pane
+            swap_tiled_layout {
+                tab min_columns=200 max_columns=100 {
+                    pane
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(
+        layout.is_err(),
+        "min_columns cannot be larger than max_columns"
+    );
+}
+
+#[test]
+fn layout_constraints_match_pane_count_and_display_area() {
+    let constraint = LayoutConstraint::All(vec![
+        LayoutConstraint::MinPanes(2),
+        LayoutConstraint::MinColumns(200),
+        LayoutConstraint::MaxRows(50),
+    ]);
+    let ultrawide = crate::pane_size::Size {
+        rows: 50,
+        cols: 250,
+    };
+    let laptop = crate::pane_size::Size {
+        rows: 50,
+        cols: 120,
+    };
+    assert!(constraint.fits_pane_count(2));
+    assert!(!constraint.fits_pane_count(1));
+    assert!(constraint.fits_display_area(&ultrawide));
+    assert!(!constraint.fits_display_area(&laptop));
+    assert!(LayoutConstraint::MaxPanes(1).fits_display_area(&laptop));
+    assert!(LayoutConstraint::MaxColumns(120).fits_pane_count(10));
+}
+
 #[test]
 fn can_define_stacked_children_for_pane_node() {
     let kdl_layout = r#"
@@ -2242,3 +2419,473 @@ fn env_var_missing() {
     let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
     assert!(layout.is_err(), "invalid env var lookup should fail");
 }
+
+#[test]
+fn env_variables_are_inherited_by_child_panes() {
+    let kdl_layout = r#"
+        layout {
+            tab {
+                env {
+                    RUST_LOG "info"
+                    DATABASE_URL "postgres://localhost/dev"
+                }
+                pane split_direction="vertical" {
+                    env {
+                        RUST_LOG "debug"
+                    }
+                    pane command="cargo" {
+                        args "run"
+                    }
+                    pane command="psql" {
+                        env {
+                            DATABASE_URL "postgres://localhost/test"
+                            PGCONNECT_TIMEOUT 10
+                        }
+                    }
+                }
+                pane
+                floating_panes {
+                    pane command="htop"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn env_variables_in_pane_template_are_overridden_by_consumer() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="server" {
+                command "cargo"
+                env {
+                    RUST_LOG "info"
+                    PROFILE "dev"
+                }
+            }
+            server {
+                env {
+                    RUST_LOG "trace"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn env_variable_without_value_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            pane command="cargo" {
+                env {
+                    RUST_LOG
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "env variables must have a value");
+}
+
+#[test]
+fn env_on_a_shell_pane_is_passed_to_the_shell() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                env {
+                    RUST_LOG "debug"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let mut expected_env = BTreeMap::new();
+    expected_env.insert("RUST_LOG".to_owned(), "debug".to_owned());
+    assert_eq!(
+        layout.template.unwrap().0.children[0].run,
+        Some(Run::Shell(None, expected_env)),
+        "the shell is spawned with the env of its pane"
+    );
+}
+
+#[test]
+fn env_on_an_edit_pane_is_passed_to_the_editor() {
+    let kdl_layout = r#"
+        layout {
+            pane edit="src/main.rs" {
+                env {
+                    RUST_LOG "debug"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let mut expected_env = BTreeMap::new();
+    expected_env.insert("RUST_LOG".to_owned(), "debug".to_owned());
+    assert_eq!(
+        layout.template.unwrap().0.children[0].run,
+        Some(Run::EditFile(
+            PathBuf::from("src/main.rs"),
+            None,
+            None,
+            expected_env
+        )),
+        "the editor is spawned with the env of its pane"
+    );
+}
+
+#[test]
+fn env_on_a_plugin_pane_is_an_error_pointing_at_the_env() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="zellij:tab-bar"
+                env {
+                    RUST_LOG "debug"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    match layout {
+        Err(ConfigError::KdlError(kdl_error)) => {
+            assert_eq!(
+                kdl_error.offset,
+                kdl_layout.find("env"),
+                "error points at the env node"
+            );
+        },
+        layout => panic!("expected a layout error, got: {:?}", layout),
+    }
+}
+
+#[test]
+fn env_on_a_floating_plugin_pane_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            floating_panes {
+                pane {
+                    plugin location="zellij:tab-bar"
+                    env {
+                        RUST_LOG "debug"
+                    }
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "plugins cannot receive env variables");
+}
+
+#[test]
+fn layout_params_are_interpolated_from_defaults_and_args() {
+    let kdl_layout = r#"
+        layout {
+            params {
+                service default="api"
+                port
+            }
+            tab name="${service}" cwd="/srv/${service}" {
+                pane command="cargo" name="${service} on ${port}" {
+                    args "run" "--bin" "${service}" "--" "--port=${port}"
+                }
+                pane name="${not_a_param}" // left as is
+            }
+        }
+    "#;
+    let mut layout_args = BTreeMap::new();
+    layout_args.insert("port".to_owned(), "8080".to_owned());
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        layout_args,
+        None,
+    )
+    .unwrap();
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn layout_args_override_param_defaults() {
+    let kdl_layout = r#"
+        layout {
+            params {
+                service default="api"
+            }
+            pane command="${service}"
+        }
+    "#;
+    let mut layout_args = BTreeMap::new();
+    layout_args.insert("service".to_owned(), "billing".to_owned());
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        layout_args,
+        None,
+    )
+    .unwrap();
+    let run = layout
+        .template
+        .as_ref()
+        .and_then(|(t, _)| t.children[0].run.clone());
+    match run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(run_command.command, PathBuf::from("billing"))
+        },
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
+#[test]
+fn missing_required_layout_param_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            params {
+                service
+            }
+            pane command="${service}"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    match layout {
+        Err(ConfigError::KdlError(kdl_error)) => {
+            assert_eq!(
+                kdl_error.offset,
+                kdl_layout.find("service"),
+                "error points at the param node"
+            );
+        },
+        layout => panic!("expected a layout error, got: {:?}", layout),
+    }
+}
+
+#[test]
+fn layout_params_are_interpolated_once() {
+    let kdl_layout = r#"
+        layout {
+            params {
+                first default="${second}"
+                second default="second_value"
+            }
+            pane name="${first} ${second} ${HOME}"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let pane_name = layout
+        .template
+        .as_ref()
+        .and_then(|(t, _)| t.children[0].name.clone());
+    assert_eq!(
+        pane_name,
+        Some("${second} second_value ${HOME}".to_owned()),
+        "values of params are not interpolated again and undeclared placeholders are left as is"
+    );
+}
+
+#[test]
+fn undeclared_layout_arg_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            pane
+        }
+    "#;
+    let mut layout_args = BTreeMap::new();
+    layout_args.insert("service".to_owned(), "billing".to_owned());
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        layout_args,
+        None,
+    );
+    assert!(layout.is_err(), "layout args must be declared as params");
+}
+
+fn layout_fixture(file_name: &str) -> (String, String) {
+    // (path_to_layout, stringified_layout)
+    let path_to_layout = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src/input/unit/fixtures/layouts")
+        .join(file_name);
+    let stringified_layout = std::fs::read_to_string(&path_to_layout).unwrap();
+    (path_to_layout.display().to_string(), stringified_layout)
+}
+
+#[test]
+fn templates_and_swap_layouts_are_included_from_other_files() {
+    let (path_to_layout, kdl_layout) = layout_fixture("layout-with-include.kdl");
+    let layout = Layout::from_kdl(&kdl_layout, Some(path_to_layout), None, None).unwrap();
+    assert_eq!(layout.swap_tiled_layouts.len(), 1, "nested includes are resolved");
+    assert_snapshot!(format!("{:#?}", layout));
+}
+
+#[test]
+fn includes_are_looked_up_in_the_layout_dir() {
+    let kdl_layout = r#"
+        layout {
+            include "common.kdl"
+            tab {
+                logs
+            }
+        }
+    "#;
+    let layout_dir =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/input/unit/fixtures/layouts");
+    let layout = Layout::from_kdl_with_args(
+        kdl_layout,
+        None,
+        None,
+        None,
+        BTreeMap::new(),
+        Some(layout_dir),
+    )
+    .unwrap();
+    assert_eq!(layout.swap_tiled_layouts.len(), 1, "nested includes are resolved");
+}
+
+#[test]
+fn swap_layout_files_can_include_other_files() {
+    let (path_to_layout, _kdl_layout) = layout_fixture("layout-with-include.kdl");
+    let kdl_layout = "layout { pane; }";
+    let kdl_swap_layout = r#"include "swap-layouts.kdl""#;
+    let layout = Layout::from_kdl(
+        kdl_layout,
+        Some(path_to_layout),
+        Some(("layout-with-include.swap.kdl", kdl_swap_layout)),
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        layout.swap_tiled_layouts.len(),
+        1,
+        "includes are resolved relative to the layout"
+    );
+}
+
+#[test]
+fn include_cycle_is_an_error() {
+    let (path_to_layout, kdl_layout) = layout_fixture("include-cycle-first.kdl");
+    let layout = Layout::from_kdl(&kdl_layout, Some(path_to_layout), None, None);
+    assert!(
+        format!("{:?}", layout).contains("Include cycle detected"),
+        "include cycles are detected"
+    );
+}
+
+#[test]
+fn missing_include_is_an_error_pointing_at_the_include_directive() {
+    let kdl_layout = r#"
+        layout {
+            include "i_do_not_exist.kdl"
+            pane
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    match layout {
+        Err(ConfigError::KdlError(kdl_error)) => {
+            assert_eq!(
+                kdl_error.offset,
+                kdl_layout.find("include"),
+                "error points at the include directive"
+            );
+        },
+        layout => panic!("expected a layout error, got: {:?}", layout),
+    }
+}
+
+#[test]
+fn run_plugins_differing_only_in_saved_state_or_limits_hash_the_same() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    fn hash_of(run_plugin: &RunPlugin) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        run_plugin.hash(&mut hasher);
+        hasher.finish()
+    }
+    let run_plugin = RunPlugin::from_url("zellij:tab-bar").unwrap();
+    let run_plugin_with_state_and_limits = RunPlugin {
+        saved_state: Some("saved state".to_owned()),
+        limit_overrides: crate::input::plugins::PluginLimitOverrides {
+            max_memory_mb: Some(10),
+            execution_timeout_ms: Some(100),
+        },
+        ..run_plugin.clone()
+    };
+    assert_eq!(run_plugin, run_plugin_with_state_and_limits);
+    assert_eq!(
+        hash_of(&run_plugin),
+        hash_of(&run_plugin_with_state_and_limits)
+    );
+}
+
+#[test]
+fn remote_plugins_can_be_pinned_to_a_sha256() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="https://example.com/plugin.wasm" {
+                    sha256 "abc"
+                    some_key "some_value"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let run_plugin = layout.template.unwrap().0.children[0]
+        .run
+        .as_ref()
+        .and_then(|run| run.get_run_plugin())
+        .unwrap();
+    assert_eq!(run_plugin.sha256, Some("abc".to_owned()));
+    assert!(
+        run_plugin.configuration.inner().get("sha256").is_none(),
+        "the sha256 is not passed to the plugin as configuration"
+    );
+}
+
+#[test]
+fn only_remote_plugins_can_be_pinned_to_a_sha256() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="zellij:tab-bar" sha256="abc"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "only remote plugins can be pinned");
+}
+
+#[test]
+fn plugins_can_be_watched_for_changes() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                plugin location="file:/path/to/my-plugin.wasm" watch_for_changes=true
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let run_plugin = layout.template.unwrap().0.children[0]
+        .run
+        .as_ref()
+        .and_then(|run| run.get_run_plugin())
+        .unwrap();
+    assert!(run_plugin.watches_for_changes());
+    assert!(
+        run_plugin
+            .configuration
+            .inner()
+            .get("watch_for_changes")
+            .is_none(),
+        "watching for changes is not passed to the plugin as configuration"
+    );
+}
@@ -83,6 +83,8 @@ pub enum ClientToServerMsg {
     BackgroundColor(String),
     ForegroundColor(String),
     ColorRegisters(Vec<(usize, String)>),
+    TerminalSupportsKittyGraphics,
+    EnableDiffRendering,
     TerminalResize(Size),
     NewClient(
         ClientAttributes,
@@ -102,6 +104,7 @@ pub enum ClientToServerMsg {
         Option<usize>,       // tab position to focus
         Option<(u32, bool)>, // (pane_id, is_plugin) => pane id to focus
         bool,                // is_web_client
+        bool,                // is_read_only
     ),
     Action(Action, Option<u32>, Option<ClientId>), // u32 is the terminal id
     Key(KeyWithModifier, Vec<u8>, bool),           // key, raw_bytes, is_kitty_keyboard_protocol
@@ -118,6 +121,7 @@ pub enum ClientToServerMsg {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ServerToClientMsg {
     Render(String),
+    RenderDiff(RenderDiff),
     UnblockInputThread,
     Exit(ExitReason),
     Connected,
@@ -132,6 +136,50 @@ pub enum ServerToClientMsg {
     RenamedSession(String), // String -> new session name
 }
 
+/// A render sent to clients that opted into diff rendering, instead of the full VTE string it
+/// contains only the cells that changed since the previous render sent to that client
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderDiff {
+    pub pre_vte: String, // raw instructions to be written before the changed cells
+    pub lines: Vec<RenderDiffLine>,
+    pub post_vte: String, // raw instructions to be written after the changed cells
+}
+
+impl RenderDiff {
+    /// The escape sequences to write to the terminal for this render.
+    ///
+    /// Every line is written as is at its own position, so this does not need any state about
+    /// previous renders.
+    pub fn to_vte(&self) -> String {
+        let mut vte_output = self.pre_vte.clone();
+        for line in &self.lines {
+            // go to the start of the line and reset the styles, the spans' styles are changes
+            // relative to the previous span on the same line
+            vte_output.push_str(&format!("\u{1b}[{};{}H\u{1b}[m", line.y + 1, line.x + 1));
+            for span in &line.spans {
+                vte_output.push_str(&span.styles);
+                vte_output.push_str(&span.text);
+            }
+        }
+        vte_output.push_str(&self.post_vte);
+        vte_output
+    }
+}
+
+/// A run of changed cells on a single line of the client's terminal
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderDiffLine {
+    pub x: usize,
+    pub y: usize,
+    pub spans: Vec<RenderDiffSpan>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderDiffSpan {
+    pub styles: String, // the style changes (SGR and OSC 8) to apply before the text
+    pub text: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ExitReason {
     Normal,
@@ -1,3 +1,4 @@
+use crate::home::default_layout_dir;
 use crate::input::{
     command::RunCommand,
     config::ConfigError,
@@ -41,6 +42,9 @@ pub struct KdlLayoutParser<'a> {
     default_tab_template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>, KdlNode)>,
     new_tab_template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>)>,
     file_name: Option<PathBuf>,
+    layout_args: BTreeMap<String, String>,
+    layout_params: BTreeMap<String, String>,
+    layout_dir: Option<PathBuf>, // where included layouts are looked for if not next to this one
 }
 
 impl<'a> KdlLayoutParser<'a> {
@@ -57,8 +61,19 @@ impl<'a> KdlLayoutParser<'a> {
             new_tab_template: None,
             global_cwd,
             file_name: file_name.map(|f| PathBuf::from(f)),
+            layout_args: BTreeMap::new(),
+            layout_params: BTreeMap::new(),
+            layout_dir: None,
         }
     }
+    pub fn with_layout_args(mut self, layout_args: BTreeMap<String, String>) -> Self {
+        self.layout_args = layout_args;
+        self
+    }
+    pub fn with_layout_dir(mut self, layout_dir: Option<PathBuf>) -> Self {
+        self.layout_dir = layout_dir;
+        self
+    }
     fn is_a_reserved_word(&self, word: &str) -> bool {
         // note that it's important that none of these words happens to also be a config property,
         // otherwise they might collide
@@ -80,23 +95,31 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "focus"
             || word == "name"
             || word == "size"
+            || word == "min_size"
+            || word == "max_size"
             || word == "cwd"
             || word == "split_direction"
             || word == "swap_tiled_layout"
             || word == "swap_floating_layout"
             || word == "hide_floating_panes"
             || word == "contents_file"
+            || word == "scrollback_file"
+            || word == "params"
+            || word == "include"
     }
     fn is_a_valid_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
             || property_name == "focus"
             || property_name == "name"
             || property_name == "size"
+            || property_name == "min_size"
+            || property_name == "max_size"
             || property_name == "plugin"
             || property_name == "command"
             || property_name == "edit"
             || property_name == "cwd"
             || property_name == "args"
+            || property_name == "env"
             || property_name == "close_on_exit"
             || property_name == "start_suspended"
             || property_name == "split_direction"
@@ -106,6 +129,7 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "expanded"
             || property_name == "exclude_from_sync"
             || property_name == "contents_file"
+            || property_name == "scrollback_file"
     }
     fn is_a_valid_floating_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
@@ -116,6 +140,7 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "edit"
             || property_name == "cwd"
             || property_name == "args"
+            || property_name == "env"
             || property_name == "close_on_exit"
             || property_name == "start_suspended"
             || property_name == "x"
@@ -124,6 +149,7 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "height"
             || property_name == "pinned"
             || property_name == "contents_file"
+            || property_name == "scrollback_file"
     }
     fn is_a_valid_tab_property(&self, property_name: &str) -> bool {
         property_name == "focus"
@@ -135,12 +161,21 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "max_panes"
             || property_name == "min_panes"
             || property_name == "exact_panes"
+            || property_name == "min_columns"
+            || property_name == "max_columns"
+            || property_name == "min_rows"
+            || property_name == "max_rows"
             || property_name == "hide_floating_panes"
     }
     pub fn is_a_reserved_plugin_property(property_name: &str) -> bool {
         property_name == "location"
             || property_name == "_allow_exec_host_cmd"
             || property_name == "path"
+            || property_name == "_saved_state"
+            || property_name == "_max_memory_mb"
+            || property_name == "_execution_timeout_ms"
+            || property_name == "sha256"
+            || property_name == "watch_for_changes"
     }
     fn assert_legal_node_name(&self, name: &str, kdl_node: &KdlNode) -> Result<(), ConfigError> {
         if name.contains(char::is_whitespace) {
@@ -211,33 +246,45 @@ impl<'a> KdlLayoutParser<'a> {
         Ok(())
     }
     fn parse_split_size(&self, kdl_node: &KdlNode) -> Result<Option<SplitSize>, ConfigError> {
-        if let Some(size) = kdl_get_string_property_or_child_value!(kdl_node, "size") {
+        self.parse_split_size_property(kdl_node, "size")
+    }
+    fn parse_split_size_property(
+        &self,
+        kdl_node: &KdlNode,
+        property_name: &str,
+    ) -> Result<Option<SplitSize>, ConfigError> {
+        if let Some(size) = kdl_get_string_property_or_child_value!(kdl_node, property_name) {
             match SplitSize::from_str(size) {
                 Ok(size) => Ok(Some(size)),
                 Err(_e) => Err(kdl_parsing_error!(
                     format!(
-                        "size should be a fixed number (eg. 1) or a quoted percent (eg. \"50%\")"
+                        "{} should be a fixed number (eg. 1) or a quoted percent (eg. \"50%\")",
+                        property_name
                     ),
                     kdl_node
                 )),
             }
-        } else if let Some(size) = kdl_get_int_property_or_child_value!(kdl_node, "size") {
+        } else if let Some(size) = kdl_get_int_property_or_child_value!(kdl_node, property_name) {
             if size == 0 {
                 return Err(kdl_parsing_error!(
-                    format!("size should be greater than 0"),
+                    format!("{} should be greater than 0", property_name),
                     kdl_node
                 ));
             }
             Ok(Some(SplitSize::Fixed(size as usize)))
-        } else if let Some(node) = kdl_property_or_child_value_node!(kdl_node, "size") {
+        } else if let Some(node) = kdl_property_or_child_value_node!(kdl_node, property_name) {
             Err(kdl_parsing_error!(
-                format!("size should be a fixed number (eg. 1) or a quoted percent (eg. \"50%\")"),
+                format!(
+                    "{} should be a fixed number (eg. 1) or a quoted percent (eg. \"50%\")",
+                    property_name
+                ),
                 node
             ))
-        } else if let Some(node) = kdl_child_with_name!(kdl_node, "size") {
+        } else if let Some(node) = kdl_child_with_name!(kdl_node, property_name) {
             Err(kdl_parsing_error!(
                 format!(
-                    "size cannot be bare, it should have a value (eg. 'size 1', or 'size \"50%\"')"
+                    "{} cannot be bare, it should have a value (eg. '{} 1', or '{} \"50%\"')",
+                    property_name, property_name, property_name
                 ),
                 node
             ))
@@ -245,6 +292,25 @@ impl<'a> KdlLayoutParser<'a> {
             Ok(None)
         }
     }
+    fn parse_size_bounds(
+        &self,
+        kdl_node: &KdlNode,
+    ) -> Result<(Option<SplitSize>, Option<SplitSize>), ConfigError> {
+        let min_size = self.parse_split_size_property(kdl_node, "min_size")?;
+        let max_size = self.parse_split_size_property(kdl_node, "max_size")?;
+        let min_is_larger_than_max = match (min_size, max_size) {
+            (Some(SplitSize::Fixed(min)), Some(SplitSize::Fixed(max))) => min > max,
+            (Some(SplitSize::Percent(min)), Some(SplitSize::Percent(max))) => min > max,
+            _ => false,
+        };
+        if min_is_larger_than_max {
+            return Err(kdl_parsing_error!(
+                format!("min_size cannot be larger than max_size"),
+                kdl_node
+            ));
+        }
+        Ok((min_size, max_size))
+    }
     fn parse_percent_or_fixed(
         &self,
         kdl_node: &KdlNode,
@@ -322,6 +388,13 @@ impl<'a> KdlLayoutParser<'a> {
         let initial_cwd =
             kdl_get_string_property_or_child_value!(&plugin_block, "cwd").map(|s| PathBuf::from(s));
         let cwd = self.cwd_prefix(initial_cwd.as_ref())?;
+        let saved_state = kdl_get_string_property_or_child_value!(&plugin_block, "_saved_state")
+            .map(|s| s.to_owned());
+        let sha256 =
+            kdl_get_string_property_or_child_value!(&plugin_block, "sha256").map(|s| s.to_owned());
+        let watch_for_changes =
+            kdl_get_bool_property_or_child_value!(&plugin_block, "watch_for_changes")
+                .unwrap_or(false);
         let run_plugin_or_alias = RunPluginOrAlias::from_url(
             &string_url,
             &Some(configuration.inner().clone()),
@@ -335,7 +408,17 @@ impl<'a> KdlLayoutParser<'a> {
                 url_node.span().len(),
             )
         })?
-        .with_initial_cwd(cwd);
+        .with_initial_cwd(cwd)
+        .with_saved_state(saved_state)
+        .with_watch_for_changes(watch_for_changes)
+        .with_sha256(sha256)
+        .map_err(|e| {
+            ConfigError::new_layout_kdl_error(
+                e.to_string(),
+                plugin_block.span().offset(),
+                plugin_block.span().len(),
+            )
+        })?;
         Ok(Some(Run::Plugin(run_plugin_or_alias)))
     }
     pub fn parse_plugin_user_configuration(
@@ -403,6 +486,57 @@ impl<'a> KdlLayoutParser<'a> {
             None => Ok(None),
         }
     }
+    fn parse_env(
+        &self,
+        kdl_node: &KdlNode,
+    ) -> Result<Option<BTreeMap<String, String>>, ConfigError> {
+        match kdl_get_child!(kdl_node, "env") {
+            Some(kdl_env) => {
+                let mut env = BTreeMap::new();
+                if let Some(env_variables) = kdl_children_nodes!(kdl_env) {
+                    for env_variable in env_variables {
+                        let name = kdl_name!(env_variable);
+                        let value = kdl_first_entry_as_string!(env_variable)
+                            .map(|s| s.to_string())
+                            .or_else(|| {
+                                kdl_first_entry_as_i64!(env_variable).map(|i| i.to_string())
+                            })
+                            .or_else(|| {
+                                kdl_first_entry_as_bool!(env_variable).map(|b| b.to_string())
+                            })
+                            .ok_or(kdl_parsing_error!(
+                                format!(
+                                    "env variable {} should have a value (eg. {} \"value\")",
+                                    name, name
+                                ),
+                                env_variable
+                            ))?;
+                        env.insert(name.to_string(), value);
+                    }
+                }
+                Ok(Some(env))
+            },
+            None => Ok(None),
+        }
+    }
+    fn assert_env_is_usable(
+        &self,
+        env: &Option<BTreeMap<String, String>>,
+        run: &Option<Run>,
+        kdl_node: &KdlNode,
+    ) -> Result<(), ConfigError> {
+        // commands, shells and editors are spawned with the env variables of their pane, plugins
+        // cannot receive them
+        if env.is_some() && matches!(run, Some(Run::Plugin(..))) {
+            let env_node = kdl_get_child!(kdl_node, "env").unwrap_or(kdl_node);
+            return Err(ConfigError::new_layout_kdl_error(
+                "env cannot be set for plugin panes".into(),
+                env_node.span().offset(),
+                env_node.span().len(),
+            ));
+        }
+        Ok(())
+    }
     fn cwd_prefix(&self, tab_cwd: Option<&PathBuf>) -> Result<Option<PathBuf>, ConfigError> {
         Ok(match (&self.global_cwd, tab_cwd) {
             (Some(global_cwd), Some(tab_cwd)) => Some(global_cwd.join(tab_cwd)),
@@ -458,10 +592,13 @@ impl<'a> KdlLayoutParser<'a> {
                 hold_on_start,
                 ..Default::default()
             }))),
-            (None, Some(edit), Some(cwd)) => {
-                Ok(Some(Run::EditFile(cwd.join(edit), None, Some(cwd))))
-            },
-            (None, Some(edit), None) => Ok(Some(Run::EditFile(edit, None, None))),
+            (None, Some(edit), Some(cwd)) => Ok(Some(Run::EditFile(
+                cwd.join(edit),
+                None,
+                Some(cwd),
+                BTreeMap::new(),
+            ))),
+            (None, Some(edit), None) => Ok(Some(Run::EditFile(edit, None, None, BTreeMap::new()))),
             (Some(_command), Some(_edit), _) => Err(ConfigError::new_layout_kdl_error(
                 "cannot have both a command and an edit instruction for the same pane".into(),
                 pane_node.span().offset(),
@@ -534,8 +671,12 @@ impl<'a> KdlLayoutParser<'a> {
             kdl_get_bool_property_or_child_value_with_error!(kdl_node, "exclude_from_sync");
         let contents_file =
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "contents_file");
+        let scrollback_file =
+            kdl_get_string_property_or_child_value_with_error!(kdl_node, "scrollback_file");
         let split_size = self.parse_split_size(kdl_node)?;
+        let (min_size, max_size) = self.parse_size_bounds(kdl_node)?;
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let env = self.parse_env(kdl_node)?;
         let children_split_direction = self.parse_split_direction(kdl_node)?;
         let (external_children_index, children) = match kdl_children_nodes!(kdl_node) {
             Some(children) => {
@@ -564,6 +705,7 @@ impl<'a> KdlLayoutParser<'a> {
             ));
         }
         self.assert_no_mixed_children_and_properties(kdl_node)?;
+        self.assert_env_is_usable(&env, &run, kdl_node)?;
         let pane_initial_contents = contents_file.and_then(|contents_file| {
             self.file_name
                 .as_ref()
@@ -572,11 +714,19 @@ impl<'a> KdlLayoutParser<'a> {
                     std::fs::read_to_string(parent_folder.join(contents_file)).ok()
                 })
         });
-        Ok(TiledPaneLayout {
+        let pane_scrollback_file = scrollback_file.and_then(|scrollback_file| {
+            self.file_name
+                .as_ref()
+                .and_then(|f| f.parent())
+                .map(|parent_folder| parent_folder.join(scrollback_file))
+        });
+        let mut pane_layout = TiledPaneLayout {
             borderless: borderless.unwrap_or_default(),
             focus,
             name,
             split_size,
+            min_size,
+            max_size,
             run,
             children_split_direction,
             external_children_index,
@@ -585,8 +735,13 @@ impl<'a> KdlLayoutParser<'a> {
             children_are_stacked,
             is_expanded_in_stack,
             pane_initial_contents,
+            pane_scrollback_file,
             ..Default::default()
-        })
+        };
+        if let Some(env) = env {
+            pane_layout.inherit_env_in_layout(&env);
+        }
+        Ok(pane_layout)
     }
     fn parse_floating_pane_node(
         &self,
@@ -598,12 +753,22 @@ impl<'a> KdlLayoutParser<'a> {
         let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
         let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
         let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
-        let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let mut run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let env = self.parse_env(kdl_node)?;
+        self.assert_env_is_usable(&env, &run, kdl_node)?;
+        match run.as_mut() {
+            Some(run) => run.add_env(env),
+            None => {
+                run = env.and_then(|env| Run::env_for_shell(&env));
+            },
+        }
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
         let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
             .map(|name| name.to_string());
         let contents_file =
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "contents_file");
+        let scrollback_file =
+            kdl_get_string_property_or_child_value_with_error!(kdl_node, "scrollback_file");
         self.assert_no_mixed_children_and_properties(kdl_node)?;
         let pane_initial_contents = contents_file.and_then(|contents_file| {
             self.file_name
@@ -613,6 +778,12 @@ impl<'a> KdlLayoutParser<'a> {
                     std::fs::read_to_string(parent_folder.join(contents_file)).ok()
                 })
         });
+        let pane_scrollback_file = scrollback_file.and_then(|scrollback_file| {
+            self.file_name
+                .as_ref()
+                .and_then(|f| f.parent())
+                .map(|parent_folder| parent_folder.join(scrollback_file))
+        });
         Ok(FloatingPaneLayout {
             name,
             height,
@@ -623,6 +794,7 @@ impl<'a> KdlLayoutParser<'a> {
             focus,
             pinned,
             pane_initial_contents,
+            pane_scrollback_file,
             ..Default::default()
         })
     }
@@ -669,8 +841,12 @@ impl<'a> KdlLayoutParser<'a> {
     ) -> Result<Option<usize>, ConfigError> {
         // Option<external_children_index>
         if let Some(pane_child_nodes) = kdl_children_nodes!(kdl_node) {
-            for (i, child) in pane_child_nodes.iter().enumerate() {
-                if kdl_name!(child) == "children" {
+            let mut pane_count = 0;
+            for child in pane_child_nodes.iter() {
+                if kdl_name!(child) == "pane" || self.pane_templates.contains_key(kdl_name!(child))
+                {
+                    pane_count += 1;
+                } else if kdl_name!(child) == "children" {
                     if let Some(grand_children) = kdl_children_nodes!(child) {
                         let grand_children: Vec<&str> =
                             grand_children.iter().map(|g| kdl_name!(g)).collect();
@@ -685,7 +861,7 @@ impl<'a> KdlLayoutParser<'a> {
                             ));
                         }
                     }
-                    return Ok(Some(i));
+                    return Ok(Some(pane_count));
                 }
             }
         }
@@ -716,7 +892,9 @@ impl<'a> KdlLayoutParser<'a> {
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
                 let split_size = self.parse_split_size(kdl_node)?;
+                let (min_size, max_size) = self.parse_size_bounds(kdl_node)?;
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 let exclude_from_sync =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "exclude_from_sync");
 
@@ -746,6 +924,13 @@ impl<'a> KdlLayoutParser<'a> {
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
                 };
+                pane_template.add_env(env.clone());
+                self.assert_env_is_usable(&env, &pane_template.run, kdl_node)?;
+                if let Some(env) = env {
+                    for child in pane_template.children.iter_mut() {
+                        child.inherit_env_in_layout(&env);
+                    }
+                }
                 if let Some(borderless) = borderless {
                     pane_template.borderless = borderless;
                 }
@@ -761,6 +946,12 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(split_size) = split_size {
                     pane_template.split_size = Some(split_size);
                 }
+                if let Some(min_size) = min_size {
+                    pane_template.min_size = Some(min_size);
+                }
+                if let Some(max_size) = max_size {
+                    pane_template.max_size = Some(max_size);
+                }
                 if let Some(index_of_children) = pane_template.external_children_index {
                     pane_template.children.insert(
                         index_of_children,
@@ -822,6 +1013,7 @@ impl<'a> KdlLayoutParser<'a> {
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 self.assert_no_bare_attributes_in_pane_node_with_template(
                     &run,
                     &pane_template.run,
@@ -831,6 +1023,7 @@ impl<'a> KdlLayoutParser<'a> {
                     kdl_node,
                 )?;
                 pane_template.run = Run::merge(&pane_template.run, &run);
+                self.assert_env_is_usable(&env, &pane_template.run, kdl_node)?;
                 if let Some(pane_template_run_command) = pane_template.run.as_mut() {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
@@ -838,6 +1031,7 @@ impl<'a> KdlLayoutParser<'a> {
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
                 };
+                pane_template.add_env(env);
                 if let Some(focus) = focus {
                     pane_template.focus = Some(focus);
                 }
@@ -876,6 +1070,7 @@ impl<'a> KdlLayoutParser<'a> {
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 self.assert_no_bare_attributes_in_pane_node_with_template(
                     &run,
                     &pane_template.run,
@@ -885,6 +1080,7 @@ impl<'a> KdlLayoutParser<'a> {
                     kdl_node,
                 )?;
                 pane_template.run = Run::merge(&pane_template.run, &run);
+                self.assert_env_is_usable(&env, &pane_template.run, kdl_node)?;
                 if let Some(pane_template_run_command) = pane_template.run.as_mut() {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
@@ -892,6 +1088,7 @@ impl<'a> KdlLayoutParser<'a> {
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
                 };
+                pane_template.add_env(env);
                 if let Some(focus) = focus {
                     pane_template.focus = Some(focus);
                 }
@@ -949,6 +1146,7 @@ impl<'a> KdlLayoutParser<'a> {
         let is_expanded_in_stack =
             kdl_get_bool_property_or_child_value_with_error!(kdl_node, "expanded");
         let split_size = self.parse_split_size(kdl_node)?;
+        let (min_size, max_size) = self.parse_size_bounds(kdl_node)?;
         let split_direction =
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "split_direction");
         let has_children_nodes = self.has_child_nodes(kdl_node);
@@ -962,6 +1160,8 @@ impl<'a> KdlLayoutParser<'a> {
 
         let has_pane_properties = borderless.is_some()
             || split_size.is_some()
+            || min_size.is_some()
+            || max_size.is_some()
             || split_direction.is_some()
             || children_are_stacked.is_some()
             || is_expanded_in_stack.is_some()
@@ -987,6 +1187,7 @@ impl<'a> KdlLayoutParser<'a> {
         let is_expanded_in_stack =
             kdl_get_bool_property_or_child_value_with_error!(kdl_node, "expanded");
         let split_size = self.parse_split_size(kdl_node)?;
+        let (min_size, max_size) = self.parse_size_bounds(kdl_node)?;
         let split_direction =
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "split_direction");
         let has_children_nodes = self.has_child_nodes(kdl_node);
@@ -1000,6 +1201,8 @@ impl<'a> KdlLayoutParser<'a> {
 
         let has_pane_properties = borderless.is_some()
             || split_size.is_some()
+            || min_size.is_some()
+            || max_size.is_some()
             || split_direction.is_some()
             || children_are_stacked.is_some()
             || is_expanded_in_stack.is_some()
@@ -1021,6 +1224,12 @@ impl<'a> KdlLayoutParser<'a> {
             if split_size.is_some() {
                 pane_properties.push("split_size");
             }
+            if min_size.is_some() {
+                pane_properties.push("min_size");
+            }
+            if max_size.is_some() {
+                pane_properties.push("max_size");
+            }
             if split_direction.is_some() {
                 pane_properties.push("split_direction");
             }
@@ -1070,44 +1279,48 @@ impl<'a> KdlLayoutParser<'a> {
         self.assert_legal_template_name(&template_name, kdl_node)?;
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let env = self.parse_env(kdl_node)?;
 
         let is_floating = self.differentiate_pane_and_floating_pane_template(&kdl_node)?;
         let can_be_either_floating_or_tiled =
             self.has_only_neutral_pane_template_properties(&kdl_node)?;
         if can_be_either_floating_or_tiled {
             self.assert_valid_pane_or_floating_pane_properties(kdl_node)?;
+            self.assert_env_is_usable(&env, &run, kdl_node)?;
+            let mut pane_template = TiledPaneLayout {
+                focus,
+                run,
+                ..Default::default()
+            };
+            pane_template.add_env(env);
             self.pane_templates.insert(
                 template_name,
-                (
-                    PaneOrFloatingPane::Either(TiledPaneLayout {
-                        focus,
-                        run,
-                        ..Default::default()
-                    }),
-                    kdl_node.clone(),
-                ),
+                (PaneOrFloatingPane::Either(pane_template), kdl_node.clone()),
             );
         } else if is_floating {
             self.assert_valid_floating_pane_properties(kdl_node)?;
+            self.assert_env_is_usable(&env, &run, kdl_node)?;
             // floating pane properties
             let height = self.parse_percent_or_fixed(kdl_node, "height", false)?;
             let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
             let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
             let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
             let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
+            let mut pane_template = FloatingPaneLayout {
+                focus,
+                run,
+                height,
+                width,
+                x,
+                y,
+                pinned,
+                ..Default::default()
+            };
+            pane_template.add_env(env);
             self.pane_templates.insert(
                 template_name,
                 (
-                    PaneOrFloatingPane::FloatingPane(FloatingPaneLayout {
-                        focus,
-                        run,
-                        height,
-                        width,
-                        x,
-                        y,
-                        pinned,
-                        ..Default::default()
-                    }),
+                    PaneOrFloatingPane::FloatingPane(pane_template),
                     kdl_node.clone(),
                 ),
             );
@@ -1123,31 +1336,39 @@ impl<'a> KdlLayoutParser<'a> {
                 kdl_get_bool_property_or_child_value_with_error!(kdl_node, "expanded")
                     .unwrap_or(false);
             let split_size = self.parse_split_size(kdl_node)?;
+            let (min_size, max_size) = self.parse_size_bounds(kdl_node)?;
             let children_split_direction = self.parse_split_direction(kdl_node)?;
-            let (external_children_index, pane_parts) = match kdl_children_nodes!(kdl_node) {
+            let (external_children_index, mut pane_parts) = match kdl_children_nodes!(kdl_node) {
                 Some(children) => {
                     self.parse_child_pane_nodes_for_pane(&children, children_are_stacked)?
                 },
                 None => (None, vec![]),
             };
+            self.assert_env_is_usable(&env, &run, kdl_node)?;
+            if let Some(env) = env.as_ref() {
+                for pane_part in pane_parts.iter_mut() {
+                    pane_part.inherit_env_in_layout(env);
+                }
+            }
             self.assert_no_mixed_children_and_properties(kdl_node)?;
+            let mut pane_template = TiledPaneLayout {
+                borderless: borderless.unwrap_or_default(),
+                focus,
+                split_size,
+                min_size,
+                max_size,
+                run,
+                children_split_direction,
+                external_children_index,
+                children: pane_parts,
+                children_are_stacked,
+                is_expanded_in_stack,
+                ..Default::default()
+            };
+            pane_template.add_env(env);
             self.pane_templates.insert(
                 template_name,
-                (
-                    PaneOrFloatingPane::Pane(TiledPaneLayout {
-                        borderless: borderless.unwrap_or_default(),
-                        focus,
-                        split_size,
-                        run,
-                        children_split_direction,
-                        external_children_index,
-                        children: pane_parts,
-                        children_are_stacked,
-                        is_expanded_in_stack,
-                        ..Default::default()
-                    }),
-                    kdl_node.clone(),
-                ),
+                (PaneOrFloatingPane::Pane(pane_template), kdl_node.clone()),
             );
         }
 
@@ -1170,6 +1391,7 @@ impl<'a> KdlLayoutParser<'a> {
         let tab_name =
             kdl_get_string_property_or_child_value!(kdl_node, "name").map(|s| s.to_string());
         let tab_cwd = self.parse_path(kdl_node, "cwd")?;
+        let tab_env = self.parse_env(kdl_node)?;
         let is_focused = kdl_get_bool_property_or_child_value!(kdl_node, "focus").unwrap_or(false);
         let hide_floating_panes =
             kdl_get_bool_property_or_child_value!(kdl_node, "hide_floating_panes").unwrap_or(false);
@@ -1198,6 +1420,12 @@ impl<'a> KdlLayoutParser<'a> {
                 floating_pane.add_cwd_to_layout(&cwd_prefix);
             }
         }
+        if let Some(tab_env) = tab_env {
+            pane_layout.inherit_env_in_layout(&tab_env);
+            for floating_pane in child_floating_panes.iter_mut() {
+                floating_pane.inherit_env(&tab_env);
+            }
+        }
         Ok((is_focused, tab_name, pane_layout, child_floating_panes))
     }
     fn parse_child_pane_nodes_for_tab(
@@ -1222,6 +1450,9 @@ impl<'a> KdlLayoutParser<'a> {
                 )?);
             } else if kdl_name!(child) == "floating_panes" {
                 self.populate_floating_pane_children(child, child_floating_panes)?;
+            } else if kdl_name!(child) == "env" {
+                // parsed separately and applied to all the panes in the tab
+                continue;
             } else if self.is_a_valid_tab_property(kdl_name!(child)) {
                 return Err(ConfigError::new_layout_kdl_error(
                     format!("Tab property '{}' must be placed on the tab title line and not in the child braces", kdl_name!(child)),
@@ -1249,7 +1480,7 @@ impl<'a> KdlLayoutParser<'a> {
         // usize is external_children_index
         let mut external_children_index = None;
         let mut nodes = vec![];
-        for (i, child) in children.iter().enumerate() {
+        for child in children.iter() {
             if kdl_name!(child) == "pane" {
                 nodes.push(self.parse_pane_node(child, is_part_of_stack)?);
             } else if kdl_name!(child) == "children" {
@@ -1267,7 +1498,9 @@ impl<'a> KdlLayoutParser<'a> {
                         ));
                     }
                 }
-                external_children_index = Some(i);
+                // property nodes (eg. args or env) can also be children, so we count only the
+                // panes that come before this one
+                external_children_index = Some(nodes.len());
             } else if let Some((pane_template, pane_template_kdl_node)) =
                 self.pane_templates.get(kdl_name!(child)).cloned()
             {
@@ -1572,6 +1805,7 @@ impl<'a> KdlLayoutParser<'a> {
         let tab_name =
             kdl_get_string_property_or_child_value!(kdl_node, "name").map(|s| s.to_string());
         let tab_cwd = self.parse_path(kdl_node, "cwd")?;
+        let tab_env = self.parse_env(kdl_node)?;
         let is_focused = kdl_get_bool_property_or_child_value!(kdl_node, "focus").unwrap_or(false);
         let children_split_direction = self.parse_split_direction(kdl_node)?;
         match kdl_children_nodes!(kdl_node) {
@@ -1607,6 +1841,12 @@ impl<'a> KdlLayoutParser<'a> {
                 floating_pane.add_cwd_to_layout(&cwd_prefix);
             }
         }
+        if let Some(tab_env) = tab_env {
+            tab_layout.inherit_env_in_layout(&tab_env);
+            for floating_pane in tab_template_floating_panes.iter_mut() {
+                floating_pane.inherit_env(&tab_env);
+            }
+        }
         tab_layout.external_children_index = None;
         Ok((
             is_focused,
@@ -1701,6 +1941,9 @@ impl<'a> KdlLayoutParser<'a> {
                 } else if kdl_name!(child) == "floating_panes" {
                     children_index_offset += 1;
                     self.populate_floating_pane_children(child, &mut tab_floating_children)?;
+                } else if kdl_name!(child) == "env" {
+                    // parsed separately and applied to all the panes in the tab_template
+                    children_index_offset += 1;
                 } else if self.is_a_valid_tab_property(kdl_name!(child)) {
                     return Err(ConfigError::new_layout_kdl_error(
                         format!("Tab property '{}' must be placed on the tab_template title line and not in the child braces", kdl_name!(child)),
@@ -1716,6 +1959,14 @@ impl<'a> KdlLayoutParser<'a> {
                 }
             }
         }
+        if let Some(env) = self.parse_env(kdl_node)? {
+            for tab_child in tab_children.iter_mut() {
+                tab_child.inherit_env_in_layout(&env);
+            }
+            for floating_child in tab_floating_children.iter_mut() {
+                floating_child.inherit_env(&env);
+            }
+        }
         Ok((
             TiledPaneLayout {
                 children_split_direction,
@@ -1979,59 +2230,83 @@ impl<'a> KdlLayoutParser<'a> {
         Ok(())
     }
     fn parse_constraint(&mut self, layout_node: &KdlNode) -> Result<LayoutConstraint, ConfigError> {
-        if let Some(max_panes) = kdl_get_string_property_or_child_value!(layout_node, "max_panes") {
-            return Err(kdl_parsing_error!(
-                format!(
-                    "max_panes should be a fixed number (eg. 1) and not a quoted string (\"{}\")",
-                    max_panes
-                ),
-                layout_node
-            ));
-        };
-        if let Some(min_panes) = kdl_get_string_property_or_child_value!(layout_node, "min_panes") {
-            return Err(kdl_parsing_error!(
-                format!(
-                    "min_panes should be a fixed number (eg. 1) and not a quoted string (\"{}\")",
-                    min_panes
-                ),
-                layout_node
-            ));
-        };
-        if let Some(exact_panes) =
-            kdl_get_string_property_or_child_value!(layout_node, "exact_panes")
-        {
-            return Err(kdl_parsing_error!(
-                format!(
-                    "exact_panes should be a fixed number (eg. 1) and not a quoted string (\"{}\")",
-                    exact_panes,
-                ),
-                layout_node
-            ));
-        };
+        for constraint_name in [
+            "max_panes",
+            "min_panes",
+            "exact_panes",
+            "min_columns",
+            "max_columns",
+            "min_rows",
+            "max_rows",
+        ] {
+            if let Some(value) =
+                kdl_get_string_property_or_child_value!(layout_node, constraint_name)
+            {
+                return Err(kdl_parsing_error!(
+                    format!(
+                        "{} should be a fixed number (eg. 1) and not a quoted string (\"{}\")",
+                        constraint_name, value
+                    ),
+                    layout_node
+                ));
+            };
+        }
         let max_panes = kdl_get_int_property_or_child_value!(layout_node, "max_panes");
         let min_panes = kdl_get_int_property_or_child_value!(layout_node, "min_panes");
         let exact_panes = kdl_get_int_property_or_child_value!(layout_node, "exact_panes");
-        let mut constraint_count = 0;
-        let mut constraint = None;
+        let min_columns = kdl_get_int_property_or_child_value!(layout_node, "min_columns");
+        let max_columns = kdl_get_int_property_or_child_value!(layout_node, "max_columns");
+        let min_rows = kdl_get_int_property_or_child_value!(layout_node, "min_rows");
+        let max_rows = kdl_get_int_property_or_child_value!(layout_node, "max_rows");
+        let mut constraints = vec![];
         if let Some(max_panes) = max_panes {
-            constraint_count += 1;
-            constraint = Some(LayoutConstraint::MaxPanes(max_panes as usize));
+            constraints.push(LayoutConstraint::MaxPanes(max_panes as usize));
         }
         if let Some(min_panes) = min_panes {
-            constraint_count += 1;
-            constraint = Some(LayoutConstraint::MinPanes(min_panes as usize));
+            constraints.push(LayoutConstraint::MinPanes(min_panes as usize));
         }
         if let Some(exact_panes) = exact_panes {
-            constraint_count += 1;
-            constraint = Some(LayoutConstraint::ExactPanes(exact_panes as usize));
+            constraints.push(LayoutConstraint::ExactPanes(exact_panes as usize));
         }
-        if constraint_count > 1 {
+        if constraints.len() > 1 {
             return Err(kdl_parsing_error!(
                 format!("cannot have more than one constraint (eg. max_panes + min_panes)'"),
                 layout_node
             ));
         }
-        Ok(constraint.unwrap_or(LayoutConstraint::NoConstraint))
+        if let (Some(min_columns), Some(max_columns)) = (min_columns, max_columns) {
+            if min_columns > max_columns {
+                return Err(kdl_parsing_error!(
+                    format!("min_columns cannot be larger than max_columns"),
+                    layout_node
+                ));
+            }
+        }
+        if let (Some(min_rows), Some(max_rows)) = (min_rows, max_rows) {
+            if min_rows > max_rows {
+                return Err(kdl_parsing_error!(
+                    format!("min_rows cannot be larger than max_rows"),
+                    layout_node
+                ));
+            }
+        }
+        if let Some(min_columns) = min_columns {
+            constraints.push(LayoutConstraint::MinColumns(min_columns as usize));
+        }
+        if let Some(max_columns) = max_columns {
+            constraints.push(LayoutConstraint::MaxColumns(max_columns as usize));
+        }
+        if let Some(min_rows) = min_rows {
+            constraints.push(LayoutConstraint::MinRows(min_rows as usize));
+        }
+        if let Some(max_rows) = max_rows {
+            constraints.push(LayoutConstraint::MaxRows(max_rows as usize));
+        }
+        match constraints.len() {
+            0 => Ok(LayoutConstraint::NoConstraint),
+            1 => Ok(constraints.remove(0)),
+            _ => Ok(LayoutConstraint::All(constraints)),
+        }
     }
     fn populate_one_swap_tiled_layout(
         &self,
@@ -2319,12 +2594,274 @@ impl<'a> KdlLayoutParser<'a> {
         };
         Ok(())
     }
+    fn is_an_includable_node(&self, node_name: &str) -> bool {
+        node_name == "pane_template"
+            || node_name == "tab_template"
+            || node_name == "default_tab_template"
+            || node_name == "new_tab_template"
+            || node_name == "swap_tiled_layout"
+            || node_name == "swap_floating_layout"
+    }
+    fn resolve_layout_includes(&self, kdl_layout: &mut KdlDocument) -> Result<(), ConfigError> {
+        let layout_node = kdl_layout
+            .nodes_mut()
+            .iter_mut()
+            .find(|n| kdl_name!(n) == "layout");
+        if let Some(layout_children) = layout_node.and_then(|l| l.children_mut().as_mut()) {
+            self.resolve_includes(layout_children.nodes_mut())?;
+        }
+        Ok(())
+    }
+    fn resolve_includes(&self, kdl_nodes: &mut Vec<KdlNode>) -> Result<(), ConfigError> {
+        // swap layout files are resolved relative to the layout they belong to, since they are
+        // placed next to it
+        let base_dir = self
+            .file_name
+            .as_ref()
+            .and_then(|f| f.parent())
+            .map(|p| p.to_path_buf());
+        let mut include_stack = vec![];
+        if let Some(file_name) = self.file_name.as_ref().and_then(|f| f.canonicalize().ok()) {
+            include_stack.push(file_name);
+        }
+        let mut resolved_nodes = vec![];
+        for kdl_node in kdl_nodes.drain(..) {
+            if kdl_name!(kdl_node) == "include" {
+                // errors in included files (and in the files they include) point at the include
+                // directive, since this is the file being reported on
+                let error_span = kdl_node.span().clone();
+                self.populate_included_nodes(
+                    &kdl_node,
+                    base_dir.as_ref(),
+                    &mut include_stack,
+                    &error_span,
+                    &mut resolved_nodes,
+                )?;
+            } else {
+                resolved_nodes.push(kdl_node);
+            }
+        }
+        *kdl_nodes = resolved_nodes;
+        Ok(())
+    }
+    fn populate_included_nodes(
+        &self,
+        include_node: &KdlNode,
+        base_dir: Option<&PathBuf>,
+        include_stack: &mut Vec<PathBuf>,
+        error_span: &miette::SourceSpan,
+        included_nodes: &mut Vec<KdlNode>,
+    ) -> Result<(), ConfigError> {
+        let include_error = |message: String| {
+            ConfigError::new_layout_kdl_error(message, error_span.offset(), error_span.len())
+        };
+        let include_path = kdl_first_entry_as_string!(include_node).ok_or_else(|| {
+            include_error(
+                "include should have the path to a layout file (eg. include \"common.kdl\")".into(),
+            )
+        })?;
+        let path_to_include = base_dir
+            .map(|base_dir| base_dir.join(include_path))
+            .into_iter()
+            .chain(
+                self.layout_dir
+                    .clone()
+                    .or_else(default_layout_dir)
+                    .map(|layout_dir| layout_dir.join(include_path)),
+            )
+            .find(|path| path.is_file())
+            .ok_or_else(|| {
+                include_error(format!("Could not find included layout: {}", include_path))
+            })?;
+        let canonical_path = path_to_include
+            .canonicalize()
+            .unwrap_or_else(|_| path_to_include.clone());
+        if include_stack.contains(&canonical_path) {
+            let include_chain: Vec<String> = include_stack
+                .iter()
+                .chain(std::iter::once(&canonical_path))
+                .map(|p| p.display().to_string())
+                .collect();
+            return Err(include_error(format!(
+                "Include cycle detected: {}",
+                include_chain.join(" -> ")
+            )));
+        }
+        let raw_included_layout = std::fs::read_to_string(&path_to_include).map_err(|e| {
+            include_error(format!(
+                "Failed to read included layout {}: {}",
+                path_to_include.display(),
+                e
+            ))
+        })?;
+        let included_layout: KdlDocument = raw_included_layout.parse().map_err(|e| {
+            include_error(format!(
+                "Failed to parse included layout {}: {}",
+                path_to_include.display(),
+                e
+            ))
+        })?;
+        include_stack.push(canonical_path);
+        let included_base_dir = path_to_include.parent().map(|p| p.to_path_buf());
+        let mut nodes_to_include: Vec<&KdlNode> = vec![];
+        for node in included_layout.nodes() {
+            if kdl_name!(node) == "layout" {
+                // included files can be full layouts, in which case we only take their templates
+                // and swap layouts
+                nodes_to_include.extend(kdl_children_nodes!(node).into_iter().flatten());
+            } else {
+                nodes_to_include.push(node);
+            }
+        }
+        for node in nodes_to_include {
+            if kdl_name!(node) == "include" {
+                self.populate_included_nodes(
+                    node,
+                    included_base_dir.as_ref(),
+                    include_stack,
+                    error_span,
+                    included_nodes,
+                )?;
+            } else if self.is_an_includable_node(kdl_name!(node)) {
+                let mut node = node.clone();
+                set_span_recursively(&mut node, error_span);
+                included_nodes.push(node);
+            }
+        }
+        include_stack.pop();
+        Ok(())
+    }
+    fn populate_layout_params(&mut self, kdl_layout: &KdlDocument) -> Result<(), ConfigError> {
+        let layout_node = kdl_layout.nodes().iter().find(|n| kdl_name!(n) == "layout");
+        let params_node = layout_node.and_then(|l| kdl_get_child!(l, "params"));
+        let mut layout_params = BTreeMap::new();
+        if let Some(params) = params_node.and_then(|p| kdl_children_nodes!(p)) {
+            for param in params {
+                let param_name = kdl_name!(param);
+                let mut default_value = None;
+                for entry in param.entries() {
+                    match entry.name().map(|n| n.value()) {
+                        Some("default") => {
+                            default_value = entry
+                                .value()
+                                .as_string()
+                                .map(|s| s.to_owned())
+                                .or_else(|| entry.value().as_i64().map(|i| i.to_string()))
+                                .or_else(|| entry.value().as_bool().map(|b| b.to_string()));
+                        },
+                        _ => {
+                            return Err(ConfigError::new_layout_kdl_error(
+                                format!(
+                                    "Unknown property for layout parameter '{}', parameters can only have a default (eg. {} default=\"value\")",
+                                    param_name, param_name
+                                ),
+                                entry.span().offset(),
+                                entry.span().len(),
+                            ));
+                        },
+                    }
+                }
+                match self.layout_args.get(param_name).cloned().or(default_value) {
+                    Some(value) => {
+                        layout_params.insert(param_name.to_owned(), value);
+                    },
+                    None => {
+                        return Err(ConfigError::new_layout_kdl_error(
+                            format!(
+                                "Missing required layout parameter '{}', pass it with --layout-arg {}=<value>",
+                                param_name, param_name
+                            ),
+                            param.span().offset(),
+                            param.span().len(),
+                        ));
+                    },
+                }
+            }
+        }
+        if let Some(unknown_arg) = self
+            .layout_args
+            .keys()
+            .find(|arg| !layout_params.contains_key(*arg))
+        {
+            let error_span = params_node
+                .map(|p| p.span())
+                .or_else(|| layout_node.map(|l| l.span()))
+                .unwrap_or_else(|| kdl_layout.span());
+            return Err(ConfigError::new_layout_kdl_error(
+                format!(
+                    "Unknown layout parameter '{}', it should be declared in the params block of the layout",
+                    unknown_arg
+                ),
+                error_span.offset(),
+                error_span.len(),
+            ));
+        }
+        self.layout_params = layout_params;
+        Ok(())
+    }
+    fn interpolate_layout_params(&self, kdl_nodes: &mut [KdlNode]) {
+        // replaces ${param_name} in all string values with the value of the layout parameter,
+        // placeholders that are not declared parameters are left as is so that they can still be
+        // expanded as environment variables (eg. cwd "${HOME}/foo")
+        if self.layout_params.is_empty() {
+            return;
+        }
+        for kdl_node in kdl_nodes.iter_mut() {
+            for entry in kdl_node.entries_mut() {
+                let interpolated = entry.value().as_string().and_then(|value| {
+                    let interpolated = self.interpolate_layout_params_in_string(value);
+                    if interpolated != value {
+                        Some(interpolated)
+                    } else {
+                        None
+                    }
+                });
+                if let Some(interpolated) = interpolated {
+                    let interpolated = KdlValue::String(interpolated);
+                    entry.set_value_repr(interpolated.to_string());
+                    entry.set_value(interpolated);
+                }
+            }
+            if let Some(children) = kdl_node.children_mut() {
+                self.interpolate_layout_params(children.nodes_mut());
+            }
+        }
+    }
+    fn interpolate_layout_params_in_string(&self, value: &str) -> String {
+        // a single pass, so that a parameter whose value contains a ${placeholder} is not
+        // interpolated again
+        let mut interpolated = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(placeholder_start) = rest.find("${") {
+            interpolated.push_str(&rest[..placeholder_start]);
+            let placeholder = &rest[placeholder_start..];
+            let param = placeholder.find('}').and_then(|placeholder_end| {
+                self.layout_params
+                    .get(&placeholder[2..placeholder_end])
+                    .map(|param_value| (placeholder_end, param_value))
+            });
+            match param {
+                Some((placeholder_end, param_value)) => {
+                    interpolated.push_str(param_value);
+                    rest = &placeholder[placeholder_end + 1..];
+                },
+                None => {
+                    interpolated.push_str("${");
+                    rest = &placeholder[2..];
+                },
+            }
+        }
+        interpolated.push_str(rest);
+        interpolated
+    }
     pub fn parse_external_swap_layouts(
         &mut self,
         raw_swap_layouts: &str,
         mut existing_layout: Layout,
     ) -> Result<Layout, ConfigError> {
-        let kdl_swap_layout: KdlDocument = raw_swap_layouts.parse()?;
+        let mut kdl_swap_layout: KdlDocument = raw_swap_layouts.parse()?;
+        self.resolve_includes(kdl_swap_layout.nodes_mut())?;
+        self.interpolate_layout_params(kdl_swap_layout.nodes_mut());
         let mut swap_tiled_layouts = vec![];
         let mut swap_floating_layouts = vec![];
 
@@ -2368,7 +2905,10 @@ impl<'a> KdlLayoutParser<'a> {
         Ok(existing_layout)
     }
     pub fn parse(&mut self) -> Result<Layout, ConfigError> {
-        let kdl_layout: KdlDocument = self.raw_layout.parse()?;
+        let mut kdl_layout: KdlDocument = self.raw_layout.parse()?;
+        self.resolve_layout_includes(&mut kdl_layout)?;
+        self.populate_layout_params(&kdl_layout)?;
+        self.interpolate_layout_params(kdl_layout.nodes_mut());
         let layout_node = kdl_layout
             .nodes()
             .iter()
@@ -2469,3 +3009,16 @@ impl<'a> KdlLayoutParser<'a> {
         }
     }
 }
+
+fn set_span_recursively(kdl_node: &mut KdlNode, span: &miette::SourceSpan) {
+    kdl_node.set_span(span.clone());
+    for entry in kdl_node.entries_mut() {
+        entry.set_span(span.clone());
+    }
+    if let Some(children) = kdl_node.children_mut() {
+        children.set_span(span.clone());
+        for child in children.nodes_mut() {
+            set_span_recursively(child, span);
+        }
+    }
+}
@@ -13,7 +13,7 @@ use crate::input::layout::{
 };
 use crate::input::options::{Clipboard, OnForceClose, Options};
 use crate::input::permission::{GrantedPermission, PermissionCache};
-use crate::input::plugins::PluginAliases;
+use crate::input::plugins::{PluginAliases, PluginLimitOverrides, PluginLock};
 use crate::input::theme::{FrameConfig, Theme, Themes, UiConfig};
 use crate::input::web_client::WebClientConfig;
 use kdl_layout_parser::KdlLayoutParser;
@@ -52,6 +52,10 @@ macro_rules! parse_kdl_action_arguments {
                 "ScrollDown" => Ok(Action::ScrollDown),
                 "ScrollToBottom" => Ok(Action::ScrollToBottom),
                 "ScrollToTop" => Ok(Action::ScrollToTop),
+                "ScrollToPreviousPrompt" => Ok(Action::ScrollToPreviousPrompt),
+                "ScrollToNextPrompt" => Ok(Action::ScrollToNextPrompt),
+                "CopyLastCommandOutput" => Ok(Action::CopyLastCommandOutput),
+                "EditLastCommandOutput" => Ok(Action::EditLastCommandOutput),
                 "PageScrollUp" => Ok(Action::PageScrollUp),
                 "PageScrollDown" => Ok(Action::PageScrollDown),
                 "HalfPageScrollUp" => Ok(Action::HalfPageScrollUp),
@@ -665,6 +669,10 @@ impl Action {
             Action::ScrollDown => Some(KdlNode::new("ScrollDown")),
             Action::ScrollToBottom => Some(KdlNode::new("ScrollToBottom")),
             Action::ScrollToTop => Some(KdlNode::new("ScrollToTop")),
+            Action::ScrollToPreviousPrompt => Some(KdlNode::new("ScrollToPreviousPrompt")),
+            Action::ScrollToNextPrompt => Some(KdlNode::new("ScrollToNextPrompt")),
+            Action::CopyLastCommandOutput => Some(KdlNode::new("CopyLastCommandOutput")),
+            Action::EditLastCommandOutput => Some(KdlNode::new("EditLastCommandOutput")),
             Action::PageScrollUp => Some(KdlNode::new("PageScrollUp")),
             Action::PageScrollDown => Some(KdlNode::new("PageScrollDown")),
             Action::HalfPageScrollUp => Some(KdlNode::new("HalfPageScrollUp")),
@@ -1154,6 +1162,20 @@ impl Action {
                 }
                 Some(node)
             },
+            Action::PluginCommand { name, args, .. } => {
+                let mut node = KdlNode::new("PluginCommand");
+                node.push(name.clone());
+                if !args.is_empty() {
+                    let mut node_children = KdlDocument::new();
+                    for (arg_name, arg_value) in args {
+                        let mut arg_node = KdlNode::new(arg_name.clone());
+                        arg_node.push(arg_value.clone());
+                        node_children.nodes_mut().push(arg_node);
+                    }
+                    node.set_children(node_children);
+                }
+                Some(node)
+            },
             Action::TogglePanePinned => Some(KdlNode::new("TogglePanePinned")),
             Action::TogglePaneInGroup => Some(KdlNode::new("TogglePaneInGroup")),
             Action::ToggleGroupMarking => Some(KdlNode::new("ToggleGroupMarking")),
@@ -1357,6 +1379,18 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "ScrollToTop" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "ScrollToPreviousPrompt" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ScrollToNextPrompt" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "CopyLastCommandOutput" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "EditLastCommandOutput" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "PageScrollUp" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -1500,21 +1534,22 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                     .clone()
                     .or_else(|| get_layout_dir(find_default_config_dir()));
                 let (path_to_raw_layout, raw_layout, swap_layouts) =
-                    Layout::stringified_from_path_or_default(layout.as_ref(), layout_dir).map_err(
-                        |e| {
-                            ConfigError::new_kdl_error(
-                                format!("Failed to load layout: {}", e),
-                                kdl_action.span().offset(),
-                                kdl_action.span().len(),
-                            )
-                        },
-                    )?;
+                    Layout::stringified_from_path_or_default(layout.as_ref(), layout_dir.clone())
+                        .map_err(|e| {
+                        ConfigError::new_kdl_error(
+                            format!("Failed to load layout: {}", e),
+                            kdl_action.span().offset(),
+                            kdl_action.span().len(),
+                        )
+                    })?;
 
-                let layout = Layout::from_str(
+                let layout = Layout::from_kdl_with_args(
                     &raw_layout,
-                    path_to_raw_layout,
+                    Some(path_to_raw_layout),
                     swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())),
                     cwd.clone(),
+                    BTreeMap::new(),
+                    layout_dir,
                 )
                 .map_err(|e| {
                     ConfigError::new_kdl_error(
@@ -1819,6 +1854,42 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                     plugin_id: None,
                 })
             },
+            "PluginCommand" => {
+                let arguments = action_arguments.iter().copied();
+                let mut args = kdl_arguments_that_are_strings(arguments)?;
+                if args.is_empty() {
+                    return Err(ConfigError::new_kdl_error(
+                        "PluginCommand needs the name of the command to invoke".into(),
+                        kdl_action.span().offset(),
+                        kdl_action.span().len(),
+                    ));
+                }
+                let name = args.remove(0);
+                let mut command_args = BTreeMap::new();
+                if let Some(command_metadata) = action_children.iter().next() {
+                    for arg_node in command_metadata.nodes() {
+                        let arg_name = kdl_name!(arg_node);
+                        let arg_value =
+                            kdl_child_string_value_for_entry(command_metadata, arg_name)
+                                .ok_or_else(|| {
+                                    ConfigError::new_kdl_error(
+                                        format!(
+                                            "Value of argument '{}' must be a string",
+                                            arg_name
+                                        ),
+                                        arg_node.span().offset(),
+                                        arg_node.span().len(),
+                                    )
+                                })?;
+                        command_args.insert(arg_name.to_owned(), arg_value.to_owned());
+                    }
+                }
+                Ok(Action::PluginCommand {
+                    name,
+                    args: command_args,
+                    pipe_id: None,
+                })
+            },
             "MessagePluginId" => {
                 let arguments = action_arguments.iter().copied();
                 let mut args = kdl_arguments_that_are_digits(arguments)?;
@@ -2377,6 +2448,17 @@ impl Options {
         let advanced_mouse_actions =
             kdl_property_first_arg_as_bool_or_error!(kdl_options, "advanced_mouse_actions")
                 .map(|(v, _)| v);
+        let forward_pane_notifications =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "forward_pane_notifications")
+                .map(|(v, _)| v);
+        let diff_rendering =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "diff_rendering").map(|(v, _)| v);
+        let plugin_max_memory_mb =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "plugin_max_memory_mb")
+                .map(|(v, _)| v as usize);
+        let plugin_execution_timeout_ms =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "plugin_execution_timeout_ms")
+                .map(|(v, _)| v as u64);
         let web_server_ip =
             match kdl_property_first_arg_as_string_or_error!(kdl_options, "web_server_ip") {
                 Some((string, entry)) => Some(IpAddr::from_str(string).map_err(|_| {
@@ -2437,6 +2519,10 @@ impl Options {
             show_startup_tips,
             show_release_notes,
             advanced_mouse_actions,
+            forward_pane_notifications,
+            diff_rendering,
+            plugin_max_memory_mb,
+            plugin_execution_timeout_ms,
             web_server_ip,
             web_server_port,
             web_server_cert,
@@ -3483,6 +3569,119 @@ impl Options {
             None
         }
     }
+    fn forward_pane_notifications_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}",
+            " ",
+            "// Whether to forward notifications sent by panes (OSC 9 / OSC 777) to the terminal",
+            "// emulator zellij is running in",
+            "// default is false",
+        );
+
+        let create_node = |node_value: bool| -> KdlNode {
+            let mut node = KdlNode::new("forward_pane_notifications");
+            node.push(KdlValue::Bool(node_value));
+            node
+        };
+        if let Some(forward_pane_notifications) = self.forward_pane_notifications {
+            let mut node = create_node(forward_pane_notifications);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(true);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn diff_rendering_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}",
+            " ",
+            "// Whether to ask the server to send only the cells that changed on every render",
+            "// instead of the full escape sequences (useful over slow connections)",
+            "// default is false",
+        );
+
+        let create_node = |node_value: bool| -> KdlNode {
+            let mut node = KdlNode::new("diff_rendering");
+            node.push(KdlValue::Bool(node_value));
+            node
+        };
+        if let Some(diff_rendering) = self.diff_rendering {
+            let mut node = create_node(diff_rendering);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(true);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn plugin_max_memory_mb_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}",
+            " ",
+            "// The maximum size of the linear memory of each plugin in megabytes, can be overridden",
+            "// per plugin alias with the _max_memory_mb configuration key",
+            "// default is 0 (unlimited)",
+        );
+
+        let create_node = |node_value: usize| -> KdlNode {
+            let mut node = KdlNode::new("plugin_max_memory_mb");
+            node.push(KdlValue::Base10(node_value as i64));
+            node
+        };
+        if let Some(plugin_max_memory_mb) = self.plugin_max_memory_mb {
+            let mut node = create_node(plugin_max_memory_mb);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(512);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn plugin_execution_timeout_ms_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// The maximum time in milliseconds a plugin may spend in a single update, render or pipe",
+            "// call before it is stopped, can be overridden per plugin alias with the",
+            "// _execution_timeout_ms configuration key",
+            "// default is 10000, 0 means unlimited",
+        );
+
+        let create_node = |node_value: u64| -> KdlNode {
+            let mut node = KdlNode::new("plugin_execution_timeout_ms");
+            node.push(KdlValue::Base10(node_value as i64));
+            node
+        };
+        if let Some(plugin_execution_timeout_ms) = self.plugin_execution_timeout_ms {
+            let mut node = create_node(plugin_execution_timeout_ms);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(10000);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
     fn advanced_mouse_actions_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
         let comment_text = format!(
             "{}\n{}\n{}",
@@ -3712,6 +3911,22 @@ impl Options {
         if let Some(advanced_mouse_actions) = self.advanced_mouse_actions_to_kdl(add_comments) {
             nodes.push(advanced_mouse_actions);
         }
+        if let Some(forward_pane_notifications) =
+            self.forward_pane_notifications_to_kdl(add_comments)
+        {
+            nodes.push(forward_pane_notifications);
+        }
+        if let Some(diff_rendering) = self.diff_rendering_to_kdl(add_comments) {
+            nodes.push(diff_rendering);
+        }
+        if let Some(plugin_max_memory_mb) = self.plugin_max_memory_mb_to_kdl(add_comments) {
+            nodes.push(plugin_max_memory_mb);
+        }
+        if let Some(plugin_execution_timeout_ms) =
+            self.plugin_execution_timeout_ms_to_kdl(add_comments)
+        {
+            nodes.push(plugin_execution_timeout_ms);
+        }
         if let Some(web_server_ip) = self.web_server_ip_to_kdl(add_comments) {
             nodes.push(web_server_ip);
         }
@@ -3734,7 +3949,26 @@ impl Layout {
         raw_swap_layouts: Option<(&str, &str)>, // raw_swap_layouts swap_layouts_file_name
         cwd: Option<PathBuf>,
     ) -> Result<Self, ConfigError> {
-        let mut kdl_layout_parser = KdlLayoutParser::new(raw_layout, cwd, file_name.clone());
+        Layout::from_kdl_with_args(
+            raw_layout,
+            file_name,
+            raw_swap_layouts,
+            cwd,
+            BTreeMap::new(),
+            None,
+        )
+    }
+    pub fn from_kdl_with_args(
+        raw_layout: &str,
+        file_name: Option<String>,
+        raw_swap_layouts: Option<(&str, &str)>, // raw_swap_layouts swap_layouts_file_name
+        cwd: Option<PathBuf>,
+        layout_args: BTreeMap<String, String>, // values for the layout's params block
+        layout_dir: Option<PathBuf>,           // where included layouts are looked for
+    ) -> Result<Self, ConfigError> {
+        let mut kdl_layout_parser = KdlLayoutParser::new(raw_layout, cwd, file_name.clone())
+            .with_layout_args(layout_args)
+            .with_layout_dir(layout_dir);
         let layout = kdl_layout_parser.parse().map_err(|e| match e {
             ConfigError::KdlError(kdl_error) => ConfigError::KdlError(kdl_error.add_src(
                 file_name.unwrap_or_else(|| "N/A".to_owned()),
@@ -4267,9 +4501,39 @@ impl PluginAliases {
                     let initial_cwd =
                         kdl_get_string_property_or_child_value!(alias_definition, "cwd")
                             .map(|s| PathBuf::from(s));
+                    let limit_overrides = PluginLimitOverrides {
+                        max_memory_mb: kdl_get_int_property_or_child_value!(
+                            alias_definition,
+                            "_max_memory_mb"
+                        )
+                        .map(|m| m.max(0) as usize),
+                        execution_timeout_ms: kdl_get_int_property_or_child_value!(
+                            alias_definition,
+                            "_execution_timeout_ms"
+                        )
+                        .map(|e| e.max(0) as u64),
+                    };
+                    let sha256 =
+                        kdl_get_string_property_or_child_value!(alias_definition, "sha256")
+                            .map(|s| s.to_owned());
+                    let watch_for_changes = kdl_get_bool_property_or_child_value!(
+                        alias_definition,
+                        "watch_for_changes"
+                    )
+                    .unwrap_or(false);
                     let run_plugin = RunPlugin::from_url(string_url)?
                         .with_configuration(configuration.inner().clone())
-                        .with_initial_cwd(initial_cwd);
+                        .with_initial_cwd(initial_cwd)
+                        .with_limit_overrides(limit_overrides)
+                        .with_watch_for_changes(watch_for_changes)
+                        .with_sha256(sha256)
+                        .map_err(|e| {
+                            ConfigError::new_kdl_error(
+                                e.to_string(),
+                                alias_definition.span().offset(),
+                                alias_definition.span().len(),
+                            )
+                        })?;
                     aliases.insert(alias_name.to_owned(), run_plugin);
                 }
             }
@@ -4293,6 +4557,30 @@ impl PluginAliases {
                 cwd_node.push(cwd.display().to_string());
                 plugin_alias_children.nodes_mut().push(cwd_node);
             }
+            if plugin_alias.watch_for_changes {
+                has_children = true;
+                let mut node = KdlNode::new("watch_for_changes");
+                node.push(KdlValue::Bool(true));
+                plugin_alias_children.nodes_mut().push(node);
+            }
+            if let Some(sha256) = &plugin_alias.sha256 {
+                has_children = true;
+                let mut node = KdlNode::new("sha256");
+                node.push(sha256.to_owned());
+                plugin_alias_children.nodes_mut().push(node);
+            }
+            if let Some(max_memory_mb) = plugin_alias.limit_overrides.max_memory_mb {
+                has_children = true;
+                let mut node = KdlNode::new("_max_memory_mb");
+                node.push(KdlValue::Base10(max_memory_mb as i64));
+                plugin_alias_children.nodes_mut().push(node);
+            }
+            if let Some(execution_timeout_ms) = plugin_alias.limit_overrides.execution_timeout_ms {
+                has_children = true;
+                let mut node = KdlNode::new("_execution_timeout_ms");
+                node.push(KdlValue::Base10(execution_timeout_ms as i64));
+                plugin_alias_children.nodes_mut().push(node);
+            }
             let configuration = plugin_alias.configuration.inner();
             if !configuration.is_empty() {
                 has_children = true;
@@ -4403,6 +4691,11 @@ fn load_plugins_from_kdl(
             let configuration = KdlLayoutParser::parse_plugin_user_configuration(&plugin_block)?;
             let cwd = kdl_get_string_property_or_child_value!(&plugin_block, "cwd")
                 .map(|s| PathBuf::from(s));
+            let sha256 = kdl_get_string_property_or_child_value!(&plugin_block, "sha256")
+                .map(|s| s.to_owned());
+            let watch_for_changes =
+                kdl_get_bool_property_or_child_value!(&plugin_block, "watch_for_changes")
+                    .unwrap_or(false);
             let run_plugin_or_alias = RunPluginOrAlias::from_url(
                 &string_url,
                 &Some(configuration.inner().clone()),
@@ -4416,7 +4709,16 @@ fn load_plugins_from_kdl(
                     url_node.span().len(),
                 )
             })?
-            .with_initial_cwd(cwd);
+            .with_initial_cwd(cwd)
+            .with_watch_for_changes(watch_for_changes)
+            .with_sha256(sha256)
+            .map_err(|e| {
+                ConfigError::new_kdl_error(
+                    e.to_string(),
+                    plugin_block.span().offset(),
+                    plugin_block.span().len(),
+                )
+            })?;
             load_plugins.insert(run_plugin_or_alias);
         }
     }
@@ -4827,6 +5129,63 @@ impl PermissionCache {
     }
 }
 
+impl PluginLock {
+    pub fn from_string(raw_string: &str) -> Result<Self, ConfigError> {
+        let kdl_document: KdlDocument = raw_string.parse()?;
+        let mut plugin_lock = PluginLock::default();
+        for node in kdl_document.nodes() {
+            if kdl_name!(node) != "plugin" {
+                continue;
+            }
+            let location = kdl_get_string_property_or_child_value!(node, "location");
+            let sha256 = kdl_get_string_property_or_child_value!(node, "sha256");
+            match (location, sha256) {
+                (Some(location), Some(sha256)) => {
+                    plugin_lock.insert(location.to_owned(), sha256.to_owned());
+                },
+                _ => {
+                    return Err(ConfigError::new_kdl_error(
+                        "Locked plugins must have a location and a sha256".into(),
+                        node.span().offset(),
+                        node.span().len(),
+                    ));
+                },
+            }
+        }
+        Ok(plugin_lock)
+    }
+    pub fn from_path(path_to_lock_file: &PathBuf) -> Result<Self, ConfigError> {
+        let raw_string = match std::fs::read_to_string(path_to_lock_file) {
+            Ok(raw_string) => raw_string,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(PluginLock::default());
+            },
+            Err(e) => return Err(ConfigError::IoPath(e, path_to_lock_file.clone())),
+        };
+        PluginLock::from_string(&raw_string).map_err(|e| match e {
+            ConfigError::KdlError(kdl_error) => ConfigError::KdlError(
+                kdl_error.add_src(path_to_lock_file.display().to_string(), raw_string),
+            ),
+            e => e,
+        })
+    }
+    pub fn to_string(&self) -> String {
+        let mut kdl_document = KdlDocument::new();
+        for (location, sha256) in &self.hashes {
+            let mut node = KdlNode::new("plugin");
+            node.insert("location", location.as_str());
+            node.insert("sha256", sha256.as_str());
+            kdl_document.nodes_mut().push(node);
+        }
+        format!(
+            "{}\n{}\n{}",
+            "// Generated by `zellij plugin lock`, pins the remote plugins referenced in the",
+            "// configuration to the sha256 hash of their contents",
+            kdl_document
+        )
+    }
+}
+
 impl SessionInfo {
     pub fn from_string(raw_session_info: &str, current_session_name: &str) -> Result<Self, String> {
         let kdl_document: KdlDocument = raw_session_info
@@ -5105,6 +5464,7 @@ impl TabInfo {
             display_area_columns,
             selectable_tiled_panes_count,
             selectable_floating_panes_count,
+            has_unread_notifications: false, // we don't serialize this
         })
     }
     pub fn encode_to_kdl(&self) -> KdlDocument {
@@ -5309,6 +5669,7 @@ impl PaneInfo {
         let terminal_command = optional_string_node!("terminal_command");
         let plugin_url = optional_string_node!("plugin_url");
         let is_selectable = bool_node!("is_selectable");
+        let cwd = optional_string_node!("cwd").map(PathBuf::from);
 
         let pane_info = PaneInfo {
             id,
@@ -5334,6 +5695,7 @@ impl PaneInfo {
             plugin_url,
             is_selectable,
             index_in_pane_group: Default::default(), // we don't serialize this
+            cwd,
         };
         Ok((tab_position, pane_info))
     }
@@ -5394,6 +5756,9 @@ impl PaneInfo {
             string_node!("plugin_url", plugin_url.to_string());
         }
         bool_node!("is_selectable", self.is_selectable);
+        if let Some(cwd) = &self.cwd {
+            string_node!("cwd", cwd.display().to_string());
+        }
         kdl_doucment
     }
 }
@@ -5482,6 +5847,7 @@ fn serialize_and_deserialize_session_info_with_data() {
             plugin_url: None,
             is_selectable: true,
             index_in_pane_group: Default::default(), // we don't serialize this
+            cwd: None,
         },
         PaneInfo {
             id: 1,
@@ -5507,6 +5873,7 @@ fn serialize_and_deserialize_session_info_with_data() {
             plugin_url: Some("i_am_a_fake_plugin".to_owned()),
             is_selectable: true,
             index_in_pane_group: Default::default(), // we don't serialize this
+            cwd: None,
         },
     ];
     let mut panes = HashMap::new();
@@ -5531,6 +5898,7 @@ fn serialize_and_deserialize_session_info_with_data() {
                 display_area_columns: 10,
                 selectable_tiled_panes_count: 10,
                 selectable_floating_panes_count: 10,
+                has_unread_notifications: false, // we don't serialize this
             },
             TabInfo {
                 position: 1,
@@ -5549,6 +5917,7 @@ fn serialize_and_deserialize_session_info_with_data() {
                 display_area_columns: 10,
                 selectable_tiled_panes_count: 10,
                 selectable_floating_panes_count: 10,
+                has_unread_notifications: false, // we don't serialize this
             },
         ],
         panes: PaneManifest { panes },
@@ -5848,6 +6217,57 @@ fn keybinds_to_string_with_all_actions() {
     insta::assert_snapshot!(serialized.to_string());
 }
 
+#[test]
+fn keybinds_with_plugin_commands_to_string() {
+    let fake_config = r#"
+        keybinds clear-defaults=true {
+            normal {
+                bind "Alt n" { PluginCommand "open-note" { title "todo"; folder "work"; }; }
+                bind "Alt r" { PluginCommand "refresh"; }
+            }
+        }"#;
+    let document: KdlDocument = fake_config.parse().unwrap();
+    let deserialized = Keybinds::from_kdl(
+        document.get("keybinds").unwrap(),
+        Default::default(),
+        &Default::default(),
+    )
+    .unwrap();
+    let open_note_action = deserialized.get_actions_for_key_in_mode(
+        &InputMode::Normal,
+        &KeyWithModifier::new(BareKey::Char('n')).with_alt_modifier(),
+    );
+    assert_eq!(
+        open_note_action,
+        Some(&vec![Action::PluginCommand {
+            name: "open-note".to_owned(),
+            args: BTreeMap::from([
+                ("folder".to_owned(), "work".to_owned()),
+                ("title".to_owned(), "todo".to_owned()),
+            ]),
+            pipe_id: None,
+        }]),
+        "PluginCommand parsed with its arguments"
+    );
+    let clear_defaults = true;
+    let serialized = Keybinds::to_kdl(&deserialized, clear_defaults);
+    let deserialized_from_serialized = Keybinds::from_kdl(
+        serialized
+            .to_string()
+            .parse::<KdlDocument>()
+            .unwrap()
+            .get("keybinds")
+            .unwrap(),
+        Default::default(),
+        &Default::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        deserialized, deserialized_from_serialized,
+        "Deserialized serialized config equals original config"
+    );
+}
+
 #[test]
 fn keybinds_to_string_with_shared_modes() {
     let fake_config = r#"
@@ -6217,6 +6637,26 @@ fn plugins_to_string_with_file_and_web() {
     insta::assert_snapshot!(serialized.to_string());
 }
 
+#[test]
+fn plugin_lock_to_string() {
+    let mut plugin_lock = PluginLock::default();
+    plugin_lock.insert(
+        "https://example.com/first.wasm".to_owned(),
+        "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08".to_owned(),
+    );
+    plugin_lock.insert(
+        "https://example.com/second.wasm".to_owned(),
+        "60303ae22b998861bce3b28f33eec1be758a213c86c93c076dbe9f558c11c752".to_owned(),
+    );
+    let serialized = plugin_lock.to_string();
+    let deserialized = PluginLock::from_string(&serialized).unwrap();
+    assert_eq!(
+        plugin_lock, deserialized,
+        "Deserialized serialized lockfile equals original lockfile"
+    );
+    insta::assert_snapshot!(serialized);
+}
+
 #[test]
 fn ui_config_to_string() {
     let fake_config = r##"
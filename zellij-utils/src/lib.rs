@@ -18,6 +18,8 @@ pub mod windows_utils;
 
 // The following modules can't be used when targeting wasm
 #[cfg(not(target_family = "wasm"))]
+pub mod asciicast;
+#[cfg(not(target_family = "wasm"))]
 pub mod channels; // Requires async_std
 #[cfg(not(target_family = "wasm"))]
 pub mod common_path;
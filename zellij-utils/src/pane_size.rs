@@ -10,7 +10,7 @@ use crate::position::Position;
 
 /// Contains the position and size of a [`Pane`], or more generally of any terminal, measured
 /// in character rows and columns.
-#[derive(Clone, Copy, Default, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
 pub struct PaneGeom {
     pub x: usize,
     pub y: usize,
@@ -19,11 +19,12 @@ pub struct PaneGeom {
     pub stacked: Option<usize>,          // usize - stack id
     pub is_pinned: bool,                 // only relevant to floating panes
     pub logical_position: Option<usize>, // relevant when placing this pane in a layout
+    pub size_bounds: SizeBounds,         // only relevant to tiled panes
 }
 
 impl PartialEq for PaneGeom {
     fn eq(&self, other: &Self) -> bool {
-        // compare all except is_pinned
+        // compare all except is_pinned, logical_position and size_bounds
         // NOTE: Keep this in sync with what the `Hash` trait impl does.
         self.x == other.x
             && self.y == other.y
@@ -46,6 +47,67 @@ impl std::hash::Hash for PaneGeom {
 
 impl Eq for PaneGeom {}
 
+impl std::fmt::Debug for PaneGeom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // size_bounds are left out when empty, since most panes do not have any
+        let mut debug_struct = f.debug_struct("PaneGeom");
+        debug_struct
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .field("rows", &self.rows)
+            .field("cols", &self.cols)
+            .field("stacked", &self.stacked)
+            .field("is_pinned", &self.is_pinned)
+            .field("logical_position", &self.logical_position);
+        if !self.size_bounds.is_empty() {
+            debug_struct.field("size_bounds", &self.size_bounds);
+        }
+        debug_struct.finish()
+    }
+}
+
+/// Optional lower and upper limits to the size of a tiled pane, on top of its fixed or percent
+/// [`Dimension`]s. Percentages are relative to the full size of the screen in that direction.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SizeBounds {
+    pub min_rows: Option<SplitSize>,
+    pub max_rows: Option<SplitSize>,
+    pub min_cols: Option<SplitSize>,
+    pub max_cols: Option<SplitSize>,
+}
+
+impl SizeBounds {
+    pub fn is_empty(&self) -> bool {
+        self.min_rows.is_none()
+            && self.max_rows.is_none()
+            && self.min_cols.is_none()
+            && self.max_cols.is_none()
+    }
+    pub fn min_rows(&self, full_rows: usize) -> Option<usize> {
+        self.min_rows.map(|s| s.to_fixed(full_rows))
+    }
+    pub fn max_rows(&self, full_rows: usize) -> Option<usize> {
+        self.max_rows.map(|s| s.to_fixed(full_rows))
+    }
+    pub fn min_cols(&self, full_cols: usize) -> Option<usize> {
+        self.min_cols.map(|s| s.to_fixed(full_cols))
+    }
+    pub fn max_cols(&self, full_cols: usize) -> Option<usize> {
+        self.max_cols.map(|s| s.to_fixed(full_cols))
+    }
+    pub fn clamp_rows(&self, rows: usize, full_rows: usize) -> usize {
+        clamp_to_bounds(rows, self.min_rows(full_rows), self.max_rows(full_rows))
+    }
+    pub fn clamp_cols(&self, cols: usize, full_cols: usize) -> usize {
+        clamp_to_bounds(cols, self.min_cols(full_cols), self.max_cols(full_cols))
+    }
+}
+
+fn clamp_to_bounds(size: usize, min: Option<usize>, max: Option<usize>) -> usize {
+    let size = max.map(|max| size.min(max)).unwrap_or(size);
+    min.map(|min| size.max(min)).unwrap_or(size)
+}
+
 #[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Viewport {
     pub x: usize,
@@ -5,16 +5,16 @@ pub use super::generated_api::api::{
         LaunchOrFocusPluginPayload, MouseEventPayload as ProtobufMouseEventPayload,
         MovePanePayload, MoveTabDirection as ProtobufMoveTabDirection,
         NameAndValue as ProtobufNameAndValue, NewFloatingPanePayload, NewPanePayload,
-        NewPluginPanePayload, NewTiledPanePayload, PaneIdAndShouldFloat,
+        NewPluginPanePayload, NewTiledPanePayload, PaneIdAndShouldFloat, PluginCommandPayload,
         PluginConfiguration as ProtobufPluginConfiguration, Position as ProtobufPosition,
-        RunCommandAction as ProtobufRunCommandAction, ScrollAtPayload,
+        RecordingPayload, RunCommandAction as ProtobufRunCommandAction, ScrollAtPayload,
         SearchDirection as ProtobufSearchDirection, SearchOption as ProtobufSearchOption,
         SwitchToModePayload, WriteCharsPayload, WritePayload,
     },
     input_mode::InputMode as ProtobufInputMode,
     resize::{Resize as ProtobufResize, ResizeDirection as ProtobufResizeDirection},
 };
-use crate::data::{Direction, InputMode, ResizeStrategy};
+use crate::data::{Direction, InputMode, PaneId, ResizeStrategy};
 use crate::errors::prelude::*;
 use crate::input::actions::Action;
 use crate::input::actions::{SearchDirection, SearchOption};
@@ -724,6 +724,57 @@ impl TryFrom<ProtobufAction> for Action {
                 Some(_) => Err("NewStackedPane should not have a payload"),
                 None => Ok(Action::NewStackedPane(None, None)),
             },
+            Some(ProtobufActionName::ScrollToPreviousPrompt) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("ScrollToPreviousPrompt should not have a payload"),
+                    None => Ok(Action::ScrollToPreviousPrompt),
+                }
+            },
+            Some(ProtobufActionName::ScrollToNextPrompt) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("ScrollToNextPrompt should not have a payload"),
+                    None => Ok(Action::ScrollToNextPrompt),
+                }
+            },
+            Some(ProtobufActionName::CopyLastCommandOutput) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("CopyLastCommandOutput should not have a payload"),
+                    None => Ok(Action::CopyLastCommandOutput),
+                }
+            },
+            Some(ProtobufActionName::EditLastCommandOutput) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("EditLastCommandOutput should not have a payload"),
+                    None => Ok(Action::EditLastCommandOutput),
+                }
+            },
+            Some(ProtobufActionName::PluginCommand) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::PluginCommandPayload(payload)) => Ok(Action::PluginCommand {
+                    name: payload.name,
+                    args: payload
+                        .args
+                        .into_iter()
+                        .map(|name_and_value| (name_and_value.name, name_and_value.value))
+                        .collect(),
+                    pipe_id: payload.pipe_id,
+                }),
+                _ => Err("Wrong payload for Action::PluginCommand"),
+            },
+            Some(ProtobufActionName::StartRecording) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::StartRecordingPayload(payload)) => {
+                    Ok(Action::StartRecording(
+                        PathBuf::from(&payload.path),
+                        recorded_pane_id(&payload),
+                    ))
+                },
+                _ => Err("Wrong payload for Action::StartRecording"),
+            },
+            Some(ProtobufActionName::StopRecording) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::StopRecordingPayload(payload)) => {
+                    Ok(Action::StopRecording(recorded_pane_id(&payload)))
+                },
+                _ => Err("Wrong payload for Action::StopRecording"),
+            },
             _ => Err("Unknown Action"),
         }
     }
@@ -1262,6 +1313,53 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::NewStackedPane as i32,
                 optional_payload: None,
             }),
+            Action::ScrollToPreviousPrompt => Ok(ProtobufAction {
+                name: ProtobufActionName::ScrollToPreviousPrompt as i32,
+                optional_payload: None,
+            }),
+            Action::ScrollToNextPrompt => Ok(ProtobufAction {
+                name: ProtobufActionName::ScrollToNextPrompt as i32,
+                optional_payload: None,
+            }),
+            Action::CopyLastCommandOutput => Ok(ProtobufAction {
+                name: ProtobufActionName::CopyLastCommandOutput as i32,
+                optional_payload: None,
+            }),
+            Action::EditLastCommandOutput => Ok(ProtobufAction {
+                name: ProtobufActionName::EditLastCommandOutput as i32,
+                optional_payload: None,
+            }),
+            Action::PluginCommand {
+                name,
+                args,
+                pipe_id,
+            } => Ok(ProtobufAction {
+                name: ProtobufActionName::PluginCommand as i32,
+                optional_payload: Some(OptionalPayload::PluginCommandPayload(
+                    PluginCommandPayload {
+                        name,
+                        args: args
+                            .into_iter()
+                            .map(|(name, value)| ProtobufNameAndValue { name, value })
+                            .collect(),
+                        pipe_id,
+                    },
+                )),
+            }),
+            Action::StartRecording(path, pane_id) => Ok(ProtobufAction {
+                name: ProtobufActionName::StartRecording as i32,
+                optional_payload: Some(OptionalPayload::StartRecordingPayload(recording_payload(
+                    path.display().to_string(),
+                    pane_id,
+                ))),
+            }),
+            Action::StopRecording(pane_id) => Ok(ProtobufAction {
+                name: ProtobufActionName::StopRecording as i32,
+                optional_payload: Some(OptionalPayload::StopRecordingPayload(recording_payload(
+                    String::new(),
+                    pane_id,
+                ))),
+            }),
             Action::NoOp
             | Action::Confirm
             | Action::NewInPlacePane(..)
@@ -1271,6 +1369,8 @@ impl TryFrom<Action> for ProtobufAction {
             | Action::DumpLayout
             | Action::CliPipe { .. }
             | Action::ListClients
+            | Action::ListSearchMatches
+            | Action::SearchJumpToMatch(..)
             | Action::StackPanes(..)
             | Action::ChangeFloatingPaneCoordinates(..)
             | Action::SkipConfirm(..) => Err("Unsupported action"),
@@ -1278,6 +1378,31 @@ impl TryFrom<Action> for ProtobufAction {
     }
 }
 
+fn recording_payload(path: String, pane_id: Option<PaneId>) -> RecordingPayload {
+    RecordingPayload {
+        path,
+        terminal_pane_id: match pane_id {
+            Some(PaneId::Terminal(terminal_pane_id)) => Some(terminal_pane_id),
+            _ => None,
+        },
+        plugin_pane_id: match pane_id {
+            Some(PaneId::Plugin(plugin_pane_id)) => Some(plugin_pane_id),
+            _ => None,
+        },
+    }
+}
+
+fn recorded_pane_id(recording_payload: &RecordingPayload) -> Option<PaneId> {
+    match (
+        recording_payload.terminal_pane_id,
+        recording_payload.plugin_pane_id,
+    ) {
+        (Some(terminal_pane_id), _) => Some(PaneId::Terminal(terminal_pane_id)),
+        (None, Some(plugin_pane_id)) => Some(PaneId::Plugin(plugin_pane_id)),
+        (None, None) => None,
+    }
+}
+
 impl TryFrom<ProtobufSearchOption> for SearchOption {
     type Error = &'static str;
     fn try_from(protobuf_search_option: ProtobufSearchOption) -> Result<Self, &'static str> {
@@ -1285,6 +1410,7 @@ impl TryFrom<ProtobufSearchOption> for SearchOption {
             ProtobufSearchOption::CaseSensitivity => Ok(SearchOption::CaseSensitivity),
             ProtobufSearchOption::WholeWord => Ok(SearchOption::WholeWord),
             ProtobufSearchOption::Wrap => Ok(SearchOption::Wrap),
+            ProtobufSearchOption::Regex => Ok(SearchOption::Regex),
         }
     }
 }
@@ -1296,6 +1422,7 @@ impl TryFrom<SearchOption> for ProtobufSearchOption {
             SearchOption::CaseSensitivity => Ok(ProtobufSearchOption::CaseSensitivity),
             SearchOption::WholeWord => Ok(ProtobufSearchOption::WholeWord),
             SearchOption::Wrap => Ok(ProtobufSearchOption::Wrap),
+            SearchOption::Regex => Ok(ProtobufSearchOption::Regex),
         }
     }
 }
@@ -1357,6 +1484,11 @@ impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
             .and_then(|d| d.try_into().ok());
         let hold_on_close = protobuf_run_command_action.hold_on_close;
         let hold_on_start = protobuf_run_command_action.hold_on_start;
+        let env = protobuf_run_command_action
+            .env
+            .into_iter()
+            .map(|name_and_value| (name_and_value.name, name_and_value.value))
+            .collect();
         Ok(RunCommandAction {
             command,
             args,
@@ -1364,6 +1496,7 @@ impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
             direction,
             hold_on_close,
             hold_on_start,
+            env,
             ..Default::default()
         })
     }
@@ -1381,6 +1514,11 @@ impl TryFrom<RunCommandAction> for ProtobufRunCommandAction {
         });
         let hold_on_close = run_command_action.hold_on_close;
         let hold_on_start = run_command_action.hold_on_start;
+        let env = run_command_action
+            .env
+            .into_iter()
+            .map(|(name, value)| ProtobufNameAndValue { name, value })
+            .collect();
         Ok(ProtobufRunCommandAction {
             command,
             args,
@@ -1389,6 +1527,7 @@ impl TryFrom<RunCommandAction> for ProtobufRunCommandAction {
             hold_on_close,
             hold_on_start,
             pane_name: None,
+            env,
         })
     }
 }
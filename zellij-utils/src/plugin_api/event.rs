@@ -2,14 +2,18 @@ pub use super::generated_api::api::{
     action::{Action as ProtobufAction, Position as ProtobufPosition},
     event::{
         event::Payload as ProtobufEventPayload, ClientInfo as ProtobufClientInfo,
-        ClientTabHistory as ProtobufClientTabHistory, CopyDestination as ProtobufCopyDestination,
+        ClientTabHistory as ProtobufClientTabHistory, CommandArgSpec as ProtobufCommandArgSpec,
+        CommandSpec as ProtobufCommandSpec, CopyDestination as ProtobufCopyDestination,
         Event as ProtobufEvent, EventNameList as ProtobufEventNameList,
         EventType as ProtobufEventType, FileMetadata as ProtobufFileMetadata,
+        FileSystemChange as ProtobufFileSystemChange,
         InputModeKeybinds as ProtobufInputModeKeybinds, KeyBind as ProtobufKeyBind,
         LayoutInfo as ProtobufLayoutInfo, ModeUpdatePayload as ProtobufModeUpdatePayload,
         PaneId as ProtobufPaneId, PaneInfo as ProtobufPaneInfo,
         PaneManifest as ProtobufPaneManifest, PaneType as ProtobufPaneType,
-        PluginInfo as ProtobufPluginInfo, ResurrectableSession as ProtobufResurrectableSession,
+        PluginInfo as ProtobufPluginInfo, RegisteredCommand as ProtobufRegisteredCommand,
+        ResurrectableSession as ProtobufResurrectableSession,
+        ScrollbackSearchMatch as ProtobufScrollbackSearchMatch,
         SessionManifest as ProtobufSessionManifest, TabInfo as ProtobufTabInfo,
         WebServerStatusPayload as ProtobufWebServerStatusPayload, WebSharing as ProtobufWebSharing,
         *,
@@ -20,9 +24,11 @@ pub use super::generated_api::api::{
 };
 #[allow(hidden_glob_reexports)]
 use crate::data::{
-    ClientInfo, CopyDestination, Event, EventType, FileMetadata, InputMode, KeyWithModifier,
-    LayoutInfo, ModeInfo, Mouse, PaneId, PaneInfo, PaneManifest, PermissionStatus,
-    PluginCapabilities, PluginInfo, SessionInfo, Style, TabInfo, WebServerStatus, WebSharing,
+    ClientInfo, CommandArgSpec, CommandSpec, CopyDestination, Event, EventType, FileMetadata,
+    FileSystemChange, InputMode, KeyWithModifier, LayoutInfo, ModeInfo, Mouse, PaneId, PaneInfo,
+    PaneManifest, PaneScrollback, PermissionStatus, PluginCapabilities, PluginInfo,
+    RegisteredCommand, ScrollbackSearchMatch, SessionInfo, Style, TabInfo, WebServerStatus,
+    WebSharing,
 };
 
 use crate::errors::prelude::*;
@@ -387,6 +393,127 @@ impl TryFrom<ProtobufEvent> for Event {
                 },
                 _ => Err("Malformed payload for the InterceptedKeyPress Event"),
             },
+            Some(ProtobufEventType::PaneNotification) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneNotificationPayload(pane_notification_payload)) => {
+                    let pane_id = pane_notification_payload
+                        .pane_id
+                        .ok_or("Malformed payload for the PaneNotification Event")?;
+                    Ok(Event::PaneNotification {
+                        pane_id: PaneId::try_from(pane_id)?,
+                        title: pane_notification_payload.title,
+                        body: pane_notification_payload.body,
+                    })
+                },
+                _ => Err("Malformed payload for the PaneNotification Event"),
+            },
+            Some(ProtobufEventType::PaneScrollbackSearchResult) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneScrollbackSearchResultPayload(
+                    pane_scrollback_search_result_payload,
+                )) => {
+                    let pane_id = pane_scrollback_search_result_payload
+                        .pane_id
+                        .ok_or("Malformed payload for the PaneScrollbackSearchResult Event")?;
+                    Ok(Event::PaneScrollbackSearchResult {
+                        pane_id: PaneId::try_from(pane_id)?,
+                        pattern: pane_scrollback_search_result_payload.pattern,
+                        matches: pane_scrollback_search_result_payload
+                            .matches
+                            .into_iter()
+                            .map(|m| m.into())
+                            .collect(),
+                    })
+                },
+                _ => Err("Malformed payload for the PaneScrollbackSearchResult Event"),
+            },
+            Some(ProtobufEventType::PaneScrollback) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneScrollbackPayload(pane_scrollback_payload)) => {
+                    let pane_id = pane_scrollback_payload
+                        .pane_id
+                        .ok_or("Malformed payload for the PaneScrollback Event")?;
+                    Ok(Event::PaneScrollback {
+                        pane_id: PaneId::try_from(pane_id)?,
+                        scrollback: PaneScrollback {
+                            lines: pane_scrollback_payload.lines,
+                            first_line_index: pane_scrollback_payload.first_line_index as usize,
+                            total_lines: pane_scrollback_payload.total_lines as usize,
+                            with_styles: pane_scrollback_payload.with_styles,
+                        },
+                    })
+                },
+                _ => Err("Malformed payload for the PaneScrollback Event"),
+            },
+            Some(ProtobufEventType::PaneContentChanged) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneContentChangedPayload(
+                    pane_content_changed_payload,
+                )) => {
+                    let pane_id = pane_content_changed_payload
+                        .pane_id
+                        .ok_or("Malformed payload for the PaneContentChanged Event")?;
+                    Ok(Event::PaneContentChanged(PaneId::try_from(pane_id)?))
+                },
+                _ => Err("Malformed payload for the PaneContentChanged Event"),
+            },
+            Some(ProtobufEventType::PluginCallResult) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PluginCallResultPayload(plugin_call_result_payload)) => {
+                    let result = match (
+                        plugin_call_result_payload.response,
+                        plugin_call_result_payload.error,
+                    ) {
+                        (_, Some(error)) => Err(error),
+                        (Some(response), None) => Ok(response),
+                        (None, None) => {
+                            return Err("Malformed payload for the PluginCallResult Event")
+                        },
+                    };
+                    Ok(Event::PluginCallResult(
+                        plugin_call_result_payload.call_id,
+                        result,
+                    ))
+                },
+                _ => Err("Malformed payload for the PluginCallResult Event"),
+            },
+            Some(ProtobufEventType::WatchedPathsChanged) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::WatchedPathsChangedPayload(
+                    watched_paths_changed_payload,
+                )) => {
+                    let mut changes = vec![];
+                    for watched_path_change in watched_paths_changed_payload.changes {
+                        let change = ProtobufFileSystemChange::from_i32(watched_path_change.change)
+                            .ok_or(
+                                "Malformed file system change for the WatchedPathsChanged Event",
+                            )?
+                            .into();
+                        changes.push((PathBuf::from(watched_path_change.path), change));
+                    }
+                    Ok(Event::WatchedPathsChanged(
+                        watched_paths_changed_payload.watch_id,
+                        changes,
+                    ))
+                },
+                _ => Err("Malformed payload for the WatchedPathsChanged Event"),
+            },
+            Some(ProtobufEventType::WatchPathsFailed) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::WatchPathsFailedPayload(watch_paths_failed_payload)) => {
+                    Ok(Event::WatchPathsFailed(
+                        watch_paths_failed_payload.watch_id,
+                        watch_paths_failed_payload.error,
+                    ))
+                },
+                _ => Err("Malformed payload for the WatchPathsFailed Event"),
+            },
+            Some(ProtobufEventType::RegisteredCommandsUpdate) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::RegisteredCommandsUpdatePayload(
+                    registered_commands_update_payload,
+                )) => {
+                    let mut registered_commands = vec![];
+                    for registered_command in registered_commands_update_payload.registered_commands
+                    {
+                        registered_commands.push(registered_command.try_into()?);
+                    }
+                    Ok(Event::RegisteredCommandsUpdate(registered_commands))
+                },
+                _ => Err("Malformed payload for the RegisteredCommandsUpdate Event"),
+            },
             None => Err("Unknown Protobuf Event"),
         }
     }
@@ -403,6 +530,7 @@ impl TryFrom<ProtobufClientInfo> for ClientInfo {
                 .try_into()?,
             protobuf_client_info.running_command,
             protobuf_client_info.is_current_client,
+            protobuf_client_info.is_read_only,
         ))
     }
 }
@@ -415,6 +543,7 @@ impl TryFrom<ClientInfo> for ProtobufClientInfo {
             pane_id: Some(client_info.pane_id.try_into()?),
             running_command: client_info.running_command,
             is_current_client: client_info.is_current_client,
+            is_read_only: client_info.is_read_only,
         })
     }
 }
@@ -781,6 +910,105 @@ impl TryFrom<Event> for ProtobufEvent {
                 name: ProtobufEventType::InterceptedKeyPress as i32,
                 payload: Some(event::Payload::KeyPayload(key.try_into()?)),
             }),
+            Event::PaneNotification {
+                pane_id,
+                title,
+                body,
+            } => Ok(ProtobufEvent {
+                name: ProtobufEventType::PaneNotification as i32,
+                payload: Some(event::Payload::PaneNotificationPayload(
+                    PaneNotificationPayload {
+                        pane_id: Some(pane_id.try_into()?),
+                        title,
+                        body,
+                    },
+                )),
+            }),
+            Event::PaneScrollbackSearchResult {
+                pane_id,
+                pattern,
+                matches,
+            } => Ok(ProtobufEvent {
+                name: ProtobufEventType::PaneScrollbackSearchResult as i32,
+                payload: Some(event::Payload::PaneScrollbackSearchResultPayload(
+                    PaneScrollbackSearchResultPayload {
+                        pane_id: Some(pane_id.try_into()?),
+                        pattern,
+                        matches: matches.into_iter().map(|m| m.into()).collect(),
+                    },
+                )),
+            }),
+            Event::PaneScrollback {
+                pane_id,
+                scrollback,
+            } => Ok(ProtobufEvent {
+                name: ProtobufEventType::PaneScrollback as i32,
+                payload: Some(event::Payload::PaneScrollbackPayload(
+                    PaneScrollbackPayload {
+                        pane_id: Some(pane_id.try_into()?),
+                        lines: scrollback.lines,
+                        first_line_index: scrollback.first_line_index as u32,
+                        total_lines: scrollback.total_lines as u32,
+                        with_styles: scrollback.with_styles,
+                    },
+                )),
+            }),
+            Event::PaneContentChanged(pane_id) => Ok(ProtobufEvent {
+                name: ProtobufEventType::PaneContentChanged as i32,
+                payload: Some(event::Payload::PaneContentChangedPayload(
+                    PaneContentChangedPayload {
+                        pane_id: Some(pane_id.try_into()?),
+                    },
+                )),
+            }),
+            Event::PluginCallResult(call_id, result) => {
+                let (response, error) = match result {
+                    Ok(response) => (Some(response), None),
+                    Err(error) => (None, Some(error)),
+                };
+                Ok(ProtobufEvent {
+                    name: ProtobufEventType::PluginCallResult as i32,
+                    payload: Some(event::Payload::PluginCallResultPayload(
+                        PluginCallResultPayload {
+                            call_id,
+                            response,
+                            error,
+                        },
+                    )),
+                })
+            },
+            Event::RegisteredCommandsUpdate(registered_commands) => Ok(ProtobufEvent {
+                name: ProtobufEventType::RegisteredCommandsUpdate as i32,
+                payload: Some(event::Payload::RegisteredCommandsUpdatePayload(
+                    RegisteredCommandsUpdatePayload {
+                        registered_commands: registered_commands
+                            .into_iter()
+                            .map(|registered_command| registered_command.into())
+                            .collect(),
+                    },
+                )),
+            }),
+            Event::WatchedPathsChanged(watch_id, changes) => Ok(ProtobufEvent {
+                name: ProtobufEventType::WatchedPathsChanged as i32,
+                payload: Some(event::Payload::WatchedPathsChangedPayload(
+                    WatchedPathsChangedPayload {
+                        watch_id,
+                        changes: changes
+                            .into_iter()
+                            .map(|(path, change)| WatchedPathChange {
+                                path: path.display().to_string(),
+                                change: ProtobufFileSystemChange::from(change) as i32,
+                            })
+                            .collect(),
+                    },
+                )),
+            }),
+            Event::WatchPathsFailed(watch_id, error) => Ok(ProtobufEvent {
+                name: ProtobufEventType::WatchPathsFailed as i32,
+                payload: Some(event::Payload::WatchPathsFailedPayload(
+                    WatchPathsFailedPayload { watch_id, error },
+                )),
+            }),
         }
     }
 }
@@ -1134,6 +1362,7 @@ impl TryFrom<ProtobufPaneInfo> for PaneInfo {
                     )
                 })
                 .collect(),
+            cwd: protobuf_pane_info.cwd.map(PathBuf::from),
         })
     }
 }
@@ -1177,6 +1406,7 @@ impl TryFrom<PaneInfo> for ProtobufPaneInfo {
                     index: index as u32,
                 })
                 .collect(),
+            cwd: pane_info.cwd.map(|cwd| cwd.display().to_string()),
         })
     }
 }
@@ -1206,6 +1436,7 @@ impl TryFrom<ProtobufTabInfo> for TabInfo {
             selectable_tiled_panes_count: protobuf_tab_info.selectable_tiled_panes_count as usize,
             selectable_floating_panes_count: protobuf_tab_info.selectable_floating_panes_count
                 as usize,
+            has_unread_notifications: protobuf_tab_info.has_unread_notifications,
         })
     }
 }
@@ -1234,6 +1465,7 @@ impl TryFrom<TabInfo> for ProtobufTabInfo {
             display_area_columns: tab_info.display_area_columns as u32,
             selectable_tiled_panes_count: tab_info.selectable_tiled_panes_count as u32,
             selectable_floating_panes_count: tab_info.selectable_floating_panes_count as u32,
+            has_unread_notifications: tab_info.has_unread_notifications,
         })
     }
 }
@@ -1294,6 +1526,7 @@ impl TryFrom<ProtobufModeUpdatePayload> for ModeInfo {
         let currently_marking_pane_group =
             protobuf_mode_update_payload.currently_marking_pane_group;
         let is_web_client = protobuf_mode_update_payload.is_web_client;
+        let is_read_only = protobuf_mode_update_payload.is_read_only;
 
         let web_server_ip = protobuf_mode_update_payload
             .web_server_ip
@@ -1319,6 +1552,7 @@ impl TryFrom<ProtobufModeUpdatePayload> for ModeInfo {
             web_sharing,
             currently_marking_pane_group,
             is_web_client,
+            is_read_only,
             web_server_ip,
             web_server_port,
             web_server_capability,
@@ -1343,6 +1577,7 @@ impl TryFrom<ModeInfo> for ProtobufModeUpdatePayload {
         let web_sharing = mode_info.web_sharing.map(|w| w as i32);
         let currently_marking_pane_group = mode_info.currently_marking_pane_group;
         let is_web_client = mode_info.is_web_client;
+        let is_read_only = mode_info.is_read_only;
         let web_server_ip = mode_info.web_server_ip.map(|i| format!("{}", i));
         let web_server_port = mode_info.web_server_port.map(|p| p as u32);
         let web_server_capability = mode_info.web_server_capability;
@@ -1386,6 +1621,7 @@ impl TryFrom<ModeInfo> for ProtobufModeUpdatePayload {
             web_server_ip,
             web_server_port,
             web_server_capability,
+            is_read_only,
         })
     }
 }
@@ -1459,6 +1695,14 @@ impl TryFrom<ProtobufEventType> for EventType {
             ProtobufEventType::BeforeClose => EventType::BeforeClose,
             ProtobufEventType::FailedToStartWebServer => EventType::FailedToStartWebServer,
             ProtobufEventType::InterceptedKeyPress => EventType::InterceptedKeyPress,
+            ProtobufEventType::PaneNotification => EventType::PaneNotification,
+            ProtobufEventType::PaneScrollbackSearchResult => EventType::PaneScrollbackSearchResult,
+            ProtobufEventType::PaneScrollback => EventType::PaneScrollback,
+            ProtobufEventType::PaneContentChanged => EventType::PaneContentChanged,
+            ProtobufEventType::PluginCallResult => EventType::PluginCallResult,
+            ProtobufEventType::WatchedPathsChanged => EventType::WatchedPathsChanged,
+            ProtobufEventType::RegisteredCommandsUpdate => EventType::RegisteredCommandsUpdate,
+            ProtobufEventType::WatchPathsFailed => EventType::WatchPathsFailed,
         })
     }
 }
@@ -1502,10 +1746,102 @@ impl TryFrom<EventType> for ProtobufEventType {
             EventType::BeforeClose => ProtobufEventType::BeforeClose,
             EventType::FailedToStartWebServer => ProtobufEventType::FailedToStartWebServer,
             EventType::InterceptedKeyPress => ProtobufEventType::InterceptedKeyPress,
+            EventType::PaneNotification => ProtobufEventType::PaneNotification,
+            EventType::PaneScrollbackSearchResult => ProtobufEventType::PaneScrollbackSearchResult,
+            EventType::PaneScrollback => ProtobufEventType::PaneScrollback,
+            EventType::PaneContentChanged => ProtobufEventType::PaneContentChanged,
+            EventType::PluginCallResult => ProtobufEventType::PluginCallResult,
+            EventType::WatchedPathsChanged => ProtobufEventType::WatchedPathsChanged,
+            EventType::RegisteredCommandsUpdate => ProtobufEventType::RegisteredCommandsUpdate,
+            EventType::WatchPathsFailed => ProtobufEventType::WatchPathsFailed,
+        })
+    }
+}
+
+impl From<ProtobufCommandSpec> for CommandSpec {
+    fn from(protobuf_command_spec: ProtobufCommandSpec) -> CommandSpec {
+        CommandSpec {
+            name: protobuf_command_spec.name,
+            description: protobuf_command_spec.description,
+            args: protobuf_command_spec
+                .args
+                .into_iter()
+                .map(|arg| CommandArgSpec {
+                    name: arg.name,
+                    description: arg.description,
+                    required: arg.required,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<CommandSpec> for ProtobufCommandSpec {
+    fn from(command_spec: CommandSpec) -> ProtobufCommandSpec {
+        ProtobufCommandSpec {
+            name: command_spec.name,
+            description: command_spec.description,
+            args: command_spec
+                .args
+                .into_iter()
+                .map(|arg| ProtobufCommandArgSpec {
+                    name: arg.name,
+                    description: arg.description,
+                    required: arg.required,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<ProtobufRegisteredCommand> for RegisteredCommand {
+    type Error = &'static str;
+    fn try_from(
+        protobuf_registered_command: ProtobufRegisteredCommand,
+    ) -> Result<Self, &'static str> {
+        Ok(RegisteredCommand {
+            plugin_id: protobuf_registered_command.plugin_id,
+            plugin_url: protobuf_registered_command.plugin_url,
+            command: protobuf_registered_command
+                .command
+                .ok_or("Registered command without a command spec")?
+                .into(),
         })
     }
 }
 
+impl From<RegisteredCommand> for ProtobufRegisteredCommand {
+    fn from(registered_command: RegisteredCommand) -> ProtobufRegisteredCommand {
+        ProtobufRegisteredCommand {
+            plugin_id: registered_command.plugin_id,
+            plugin_url: registered_command.plugin_url,
+            command: Some(registered_command.command.into()),
+        }
+    }
+}
+
+impl From<ProtobufFileSystemChange> for FileSystemChange {
+    fn from(protobuf_file_system_change: ProtobufFileSystemChange) -> FileSystemChange {
+        match protobuf_file_system_change {
+            ProtobufFileSystemChange::Create => FileSystemChange::Create,
+            ProtobufFileSystemChange::Read => FileSystemChange::Read,
+            ProtobufFileSystemChange::Update => FileSystemChange::Update,
+            ProtobufFileSystemChange::Delete => FileSystemChange::Delete,
+        }
+    }
+}
+
+impl From<FileSystemChange> for ProtobufFileSystemChange {
+    fn from(file_system_change: FileSystemChange) -> ProtobufFileSystemChange {
+        match file_system_change {
+            FileSystemChange::Create => ProtobufFileSystemChange::Create,
+            FileSystemChange::Read => ProtobufFileSystemChange::Read,
+            FileSystemChange::Update => ProtobufFileSystemChange::Update,
+            FileSystemChange::Delete => ProtobufFileSystemChange::Delete,
+        }
+    }
+}
+
 impl From<ProtobufResurrectableSession> for (String, Duration) {
     fn from(protobuf_resurrectable_session: ProtobufResurrectableSession) -> (String, Duration) {
         (
@@ -1524,6 +1860,26 @@ impl From<(String, Duration)> for ProtobufResurrectableSession {
     }
 }
 
+impl From<ProtobufScrollbackSearchMatch> for ScrollbackSearchMatch {
+    fn from(protobuf_search_match: ProtobufScrollbackSearchMatch) -> ScrollbackSearchMatch {
+        ScrollbackSearchMatch {
+            line_number: protobuf_search_match.line_number as usize,
+            column: protobuf_search_match.column as usize,
+            line: protobuf_search_match.line,
+        }
+    }
+}
+
+impl From<ScrollbackSearchMatch> for ProtobufScrollbackSearchMatch {
+    fn from(search_match: ScrollbackSearchMatch) -> ProtobufScrollbackSearchMatch {
+        ProtobufScrollbackSearchMatch {
+            line_number: search_match.line_number as u32,
+            column: search_match.column as u32,
+            line: search_match.line,
+        }
+    }
+}
+
 impl From<&ProtobufFileMetadata> for Option<FileMetadata> {
     fn from(protobuf_file_metadata: &ProtobufFileMetadata) -> Option<FileMetadata> {
         if protobuf_file_metadata.metadata_is_set {
@@ -1646,6 +2002,7 @@ fn serialize_mode_update_event_with_non_default_values() {
         web_sharing: Some(WebSharing::default()),
         currently_marking_pane_group: Some(false),
         is_web_client: Some(false),
+        is_read_only: Some(false),
         web_server_ip: IpAddr::from_str("127.0.0.1").ok(),
         web_server_port: Some(8082),
         web_server_capability: Some(true),
@@ -1697,6 +2054,7 @@ fn serialize_tab_update_event_with_non_default_values() {
             display_area_columns: 10,
             selectable_tiled_panes_count: 10,
             selectable_floating_panes_count: 10,
+            has_unread_notifications: false,
         },
         TabInfo {
             position: 1,
@@ -1715,6 +2073,7 @@ fn serialize_tab_update_event_with_non_default_values() {
             display_area_columns: 10,
             selectable_tiled_panes_count: 10,
             selectable_floating_panes_count: 10,
+            has_unread_notifications: false,
         },
         TabInfo::default(),
     ]);
@@ -1880,6 +2239,204 @@ fn serialize_custom_message_event() {
     );
 }
 
+#[test]
+fn serialize_pane_notification_event() {
+    use prost::Message;
+    let pane_notification_event = Event::PaneNotification {
+        pane_id: PaneId::Terminal(1),
+        title: Some("Build".to_owned()),
+        body: "Build finished successfully".to_owned(),
+    };
+    let protobuf_event: ProtobufEvent = pane_notification_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_notification_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_pane_scrollback_search_result_event() {
+    use prost::Message;
+    let pane_scrollback_search_result_event = Event::PaneScrollbackSearchResult {
+        pane_id: PaneId::Terminal(1),
+        pattern: "err(or)?".to_owned(),
+        matches: vec![
+            ScrollbackSearchMatch {
+                line_number: 3,
+                column: 0,
+                line: "error: could not compile".to_owned(),
+            },
+            ScrollbackSearchMatch {
+                line_number: 10,
+                column: 4,
+                line: "    err: no such file".to_owned(),
+            },
+        ],
+    };
+    let protobuf_event: ProtobufEvent = pane_scrollback_search_result_event
+        .clone()
+        .try_into()
+        .unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_scrollback_search_result_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_pane_scrollback_event() {
+    use prost::Message;
+    let pane_scrollback_event = Event::PaneScrollback {
+        pane_id: PaneId::Terminal(1),
+        scrollback: PaneScrollback {
+            lines: vec![
+                "error[E0308]: mismatched types".to_owned(),
+                "  --> src/main.rs:4:5".to_owned(),
+            ],
+            first_line_index: 12,
+            total_lines: 40,
+            with_styles: false,
+        },
+    };
+    let protobuf_event: ProtobufEvent = pane_scrollback_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_scrollback_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_pane_content_changed_event() {
+    use prost::Message;
+    let pane_content_changed_event = Event::PaneContentChanged(PaneId::Terminal(3));
+    let protobuf_event: ProtobufEvent = pane_content_changed_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_content_changed_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_plugin_call_result_event() {
+    use prost::Message;
+    for plugin_call_result_event in [
+        Event::PluginCallResult(1, Ok("response".to_owned())),
+        Event::PluginCallResult(2, Err("Plugin zellij:foo is not loaded".to_owned())),
+    ] {
+        let protobuf_event: ProtobufEvent = plugin_call_result_event.clone().try_into().unwrap();
+        let serialized_protobuf_event = protobuf_event.encode_to_vec();
+        let deserialized_protobuf_event: ProtobufEvent =
+            Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+        let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+        assert_eq!(
+            plugin_call_result_event, deserialized_event,
+            "Event properly serialized/deserialized without change"
+        );
+    }
+}
+
+#[test]
+fn serialize_watched_paths_changed_event() {
+    use crate::data::FileSystemChange;
+    use prost::Message;
+    let watched_paths_changed_event = Event::WatchedPathsChanged(
+        3,
+        vec![
+            ("/absolute/path".into(), FileSystemChange::Create),
+            ("/absolute/path/file.md".into(), FileSystemChange::Update),
+            ("/another/path".into(), FileSystemChange::Delete),
+        ],
+    );
+    let protobuf_event: ProtobufEvent = watched_paths_changed_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        watched_paths_changed_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_watch_paths_failed_event() {
+    use prost::Message;
+    let watch_paths_failed_event =
+        Event::WatchPathsFailed(3, "failed to watch /nonexistent/path".to_owned());
+    let protobuf_event: ProtobufEvent = watch_paths_failed_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        watch_paths_failed_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_registered_commands_update_event() {
+    use crate::data::{CommandArgSpec, CommandSpec, RegisteredCommand};
+    use prost::Message;
+    let registered_commands_update_event = Event::RegisteredCommandsUpdate(vec![
+        RegisteredCommand {
+            plugin_id: 1,
+            plugin_url: "zellij:notes".to_owned(),
+            command: CommandSpec {
+                name: "open-note".to_owned(),
+                description: "Open a note by its title".to_owned(),
+                args: vec![
+                    CommandArgSpec {
+                        name: "title".to_owned(),
+                        description: "The title of the note".to_owned(),
+                        required: true,
+                    },
+                    CommandArgSpec {
+                        name: "folder".to_owned(),
+                        description: "".to_owned(),
+                        required: false,
+                    },
+                ],
+            },
+        },
+        RegisteredCommand {
+            plugin_id: 2,
+            plugin_url: "file:/tmp/my-plugin.wasm".to_owned(),
+            command: CommandSpec {
+                name: "refresh".to_owned(),
+                description: "".to_owned(),
+                args: vec![],
+            },
+        },
+    ]);
+    let protobuf_event: ProtobufEvent =
+        registered_commands_update_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        registered_commands_update_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
 #[test]
 fn serialize_file_system_create_event() {
     use prost::Message;
@@ -1988,6 +2545,7 @@ fn serialize_session_update_event_with_non_default_values() {
             display_area_columns: 10,
             selectable_tiled_panes_count: 10,
             selectable_floating_panes_count: 10,
+            has_unread_notifications: false,
         },
         TabInfo {
             position: 1,
@@ -2006,6 +2564,7 @@ fn serialize_session_update_event_with_non_default_values() {
             display_area_columns: 10,
             selectable_tiled_panes_count: 10,
             selectable_floating_panes_count: 10,
+            has_unread_notifications: false,
         },
         TabInfo::default(),
     ];
@@ -2043,6 +2602,7 @@ fn serialize_session_update_event_with_non_default_values() {
             plugin_url: None,
             is_selectable: true,
             index_in_pane_group: index_in_pane_group_1,
+            cwd: Some(PathBuf::from("/home/user/my_project")),
         },
         PaneInfo {
             id: 1,
@@ -2068,6 +2628,7 @@ fn serialize_session_update_event_with_non_default_values() {
             plugin_url: Some("i_am_a_fake_plugin".to_owned()),
             is_selectable: true,
             index_in_pane_group: index_in_pane_group_2,
+            cwd: None,
         },
     ];
     panes.insert(0, panes_list);
@@ -6,6 +6,7 @@ pub mod input_mode;
 pub mod key;
 pub mod message;
 pub mod pipe_message;
+pub mod plugin_call;
 pub mod plugin_command;
 pub mod plugin_ids;
 pub mod plugin_permission;
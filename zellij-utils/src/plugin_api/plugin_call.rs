@@ -0,0 +1,24 @@
+pub use super::generated_api::api::plugin_call::PluginCall as ProtobufPluginCall;
+use crate::data::PluginCall;
+
+impl From<ProtobufPluginCall> for PluginCall {
+    fn from(protobuf_plugin_call: ProtobufPluginCall) -> Self {
+        PluginCall {
+            id: protobuf_plugin_call.id,
+            caller_plugin_id: protobuf_plugin_call.caller_plugin_id,
+            method: protobuf_plugin_call.method,
+            payload: protobuf_plugin_call.payload,
+        }
+    }
+}
+
+impl From<PluginCall> for ProtobufPluginCall {
+    fn from(plugin_call: PluginCall) -> Self {
+        ProtobufPluginCall {
+            id: plugin_call.id,
+            caller_plugin_id: plugin_call.caller_plugin_id,
+            method: plugin_call.method,
+            payload: plugin_call.payload,
+        }
+    }
+}
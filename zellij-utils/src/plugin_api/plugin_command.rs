@@ -4,17 +4,18 @@ pub use super::generated_api::api::{
     input_mode::InputMode as ProtobufInputMode,
     plugin_command::{
         plugin_command::Payload, BreakPanesToNewTabPayload, BreakPanesToTabWithIndexPayload,
-        ChangeFloatingPanesCoordinatesPayload, ChangeHostFolderPayload,
-        ClearScreenForPaneIdPayload, CliPipeOutputPayload, CloseMultiplePanesPayload,
-        CloseTabWithIndexPayload, CommandName, ContextItem,
+        CallPluginPayload, ChangeFloatingPanesCoordinatesPayload, ChangeHostFolderPayload,
+        ClearPluginStorePayload, ClearScreenForPaneIdPayload, CliPipeOutputPayload,
+        CloseMultiplePanesPayload, CloseTabWithIndexPayload, CommandName, ContextItem,
         CreateTokenResponse as ProtobufCreateTokenResponse, CreateTokenResponse,
         EditScrollbackForPaneWithIdPayload, EmbedMultiplePanesPayload, EnvVariable, ExecCmdPayload,
         FixedOrPercent as ProtobufFixedOrPercent,
         FixedOrPercentValue as ProtobufFixedOrPercentValue, FloatMultiplePanesPayload,
         FloatingPaneCoordinates as ProtobufFloatingPaneCoordinates, GenerateWebLoginTokenPayload,
-        GroupAndUngroupPanesPayload, HidePaneWithIdPayload, HighlightAndUnhighlightPanesPayload,
-        HttpVerb as ProtobufHttpVerb, IdAndNewName, KeyToRebind, KeyToUnbind, KillSessionsPayload,
-        ListTokensResponse, LoadNewPluginPayload, MessageToPluginPayload,
+        GetPaneScrollbackPayload, GroupAndUngroupPanesPayload, HidePaneWithIdPayload,
+        HighlightAndUnhighlightPanesPayload, HttpVerb as ProtobufHttpVerb, IdAndNewName,
+        InvokeRegisteredCommandPayload, KeyToRebind, KeyToUnbind, KillSessionsPayload,
+        ListPluginStorePayload, ListTokensResponse, LoadNewPluginPayload, MessageToPluginPayload,
         MovePaneWithPaneIdInDirectionPayload, MovePaneWithPaneIdPayload, MovePayload,
         NewPluginArgs as ProtobufNewPluginArgs, NewTabPayload, NewTabsWithLayoutInfoPayload,
         OpenCommandPaneFloatingNearPluginPayload, OpenCommandPaneInPlaceOfPluginPayload,
@@ -25,16 +26,21 @@ pub use super::generated_api::api::{
         PageScrollDownInPaneIdPayload, PageScrollUpInPaneIdPayload, PaneId as ProtobufPaneId,
         PaneIdAndFloatingPaneCoordinates, PaneType as ProtobufPaneType,
         PluginCommand as ProtobufPluginCommand, PluginMessagePayload, RebindKeysPayload,
-        ReconfigurePayload, ReloadPluginPayload, RenameWebLoginTokenPayload,
-        RenameWebTokenResponse, ReplacePaneWithExistingPanePayload, RequestPluginPermissionPayload,
-        RerunCommandPanePayload, ResizePaneIdWithDirectionPayload, ResizePayload,
-        RevokeAllWebTokensResponse, RevokeTokenResponse, RevokeWebLoginTokenPayload,
-        RunCommandPayload, ScrollDownInPaneIdPayload, ScrollToBottomInPaneIdPayload,
-        ScrollToTopInPaneIdPayload, ScrollUpInPaneIdPayload, SetFloatingPanePinnedPayload,
-        SetSelfMouseSelectionSupportPayload, SetTimeoutPayload, ShowPaneWithIdPayload,
-        StackPanesPayload, SubscribePayload, SwitchSessionPayload, SwitchTabToPayload,
+        ReconfigurePayload, RegisterCommandsPayload, ReloadPluginPayload,
+        RenameWebLoginTokenPayload, RenameWebTokenResponse, ReplacePaneWithExistingPanePayload,
+        RequestPluginPermissionPayload, RerunCommandPanePayload, ResizePaneIdWithDirectionPayload,
+        ResizePayload, RespondToPluginCallPayload, RevokeAllWebTokensResponse, RevokeTokenResponse,
+        RevokeWebLoginTokenPayload, RunActionsPayload, RunCommandPayload,
+        ScrollDownInPaneIdPayload, ScrollToBottomInPaneIdPayload, ScrollToTopInPaneIdPayload,
+        ScrollUpInPaneIdPayload, ScrollbackRange as ProtobufScrollbackRange,
+        ScrollbackRangeType as ProtobufScrollbackRangeType, SearchPaneScrollbackPayload,
+        SetFloatingPanePinnedPayload, SetSelfMouseSelectionSupportPayload, SetTimeoutPayload,
+        ShowPaneWithIdPayload, StackPanesPayload, StoreDeletePayload, StoreGetPayload,
+        StoreGetResponse, StoreListPayload, StoreListResponse, StoreScope as ProtobufStoreScope,
+        StoreSetPayload, SubscribePayload, SwitchSessionPayload, SwitchTabToPayload,
         TogglePaneEmbedOrEjectForPaneIdPayload, TogglePaneIdFullscreenPayload, UnsubscribePayload,
-        WebRequestPayload, WriteCharsToPaneIdPayload, WriteToPaneIdPayload,
+        UnwatchPathsPayload, WatchPathsPayload, WebRequestPayload,
+        WebTokenScope as ProtobufWebTokenScope, WriteCharsToPaneIdPayload, WriteToPaneIdPayload,
     },
     plugin_permission::PermissionType as ProtobufPermissionType,
     resize::ResizeAction as ProtobufResizeAction,
@@ -42,7 +48,8 @@ pub use super::generated_api::api::{
 
 use crate::data::{
     ConnectToSession, FloatingPaneCoordinates, HttpVerb, InputMode, KeyWithModifier,
-    MessageToPlugin, NewPluginArgs, PaneId, PermissionType, PluginCommand,
+    MessageToPlugin, NewPluginArgs, PaneId, PermissionType, PluginCommand, ScrollbackRange,
+    StoreScope, WatchOptions, WebTokenScope,
 };
 use crate::input::actions::Action;
 use crate::input::layout::SplitSize;
@@ -50,6 +57,7 @@ use crate::input::layout::SplitSize;
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::path::PathBuf;
+use std::time::Duration;
 
 impl Into<FloatingPaneCoordinates> for ProtobufFloatingPaneCoordinates {
     fn into(self) -> FloatingPaneCoordinates {
@@ -173,6 +181,88 @@ impl Into<ProtobufHttpVerb> for HttpVerb {
     }
 }
 
+impl Into<WebTokenScope> for ProtobufWebTokenScope {
+    fn into(self) -> WebTokenScope {
+        WebTokenScope {
+            allowed_sessions: self.allowed_sessions,
+            read_only: self.read_only,
+            expires_in: self.expires_in_secs.map(Duration::from_secs),
+        }
+    }
+}
+
+impl Into<ProtobufWebTokenScope> for WebTokenScope {
+    fn into(self) -> ProtobufWebTokenScope {
+        ProtobufWebTokenScope {
+            allowed_sessions: self.allowed_sessions,
+            read_only: self.read_only,
+            expires_in_secs: self.expires_in.map(|e| e.as_secs()),
+        }
+    }
+}
+
+impl TryFrom<ProtobufScrollbackRange> for ScrollbackRange {
+    type Error = &'static str;
+    fn try_from(protobuf_scrollback_range: ProtobufScrollbackRange) -> Result<Self, &'static str> {
+        match ProtobufScrollbackRangeType::from_i32(protobuf_scrollback_range.r#type) {
+            Some(ProtobufScrollbackRangeType::Viewport) => Ok(ScrollbackRange::Viewport),
+            Some(ProtobufScrollbackRangeType::Full) => Ok(ScrollbackRange::Full),
+            Some(ProtobufScrollbackRangeType::Lines) => Ok(ScrollbackRange::Lines {
+                start: protobuf_scrollback_range.start as usize,
+                end: protobuf_scrollback_range.end as usize,
+            }),
+            Some(ProtobufScrollbackRangeType::Last) => Ok(ScrollbackRange::Last(
+                protobuf_scrollback_range.line_count as usize,
+            )),
+            None => Err("Failed to convert ScrollbackRange"),
+        }
+    }
+}
+
+impl Into<ProtobufScrollbackRange> for ScrollbackRange {
+    fn into(self) -> ProtobufScrollbackRange {
+        let mut protobuf_scrollback_range = ProtobufScrollbackRange::default();
+        match self {
+            ScrollbackRange::Viewport => {
+                protobuf_scrollback_range.r#type = ProtobufScrollbackRangeType::Viewport as i32;
+            },
+            ScrollbackRange::Full => {
+                protobuf_scrollback_range.r#type = ProtobufScrollbackRangeType::Full as i32;
+            },
+            ScrollbackRange::Lines { start, end } => {
+                protobuf_scrollback_range.r#type = ProtobufScrollbackRangeType::Lines as i32;
+                protobuf_scrollback_range.start = start as u32;
+                protobuf_scrollback_range.end = end as u32;
+            },
+            ScrollbackRange::Last(line_count) => {
+                protobuf_scrollback_range.r#type = ProtobufScrollbackRangeType::Last as i32;
+                protobuf_scrollback_range.line_count = line_count as u32;
+            },
+        }
+        protobuf_scrollback_range
+    }
+}
+
+impl TryFrom<i32> for StoreScope {
+    type Error = &'static str;
+    fn try_from(protobuf_store_scope: i32) -> Result<Self, &'static str> {
+        match ProtobufStoreScope::from_i32(protobuf_store_scope) {
+            Some(ProtobufStoreScope::Global) => Ok(StoreScope::Global),
+            Some(ProtobufStoreScope::Session) => Ok(StoreScope::Session),
+            None => Err("Failed to convert StoreScope"),
+        }
+    }
+}
+
+impl Into<ProtobufStoreScope> for StoreScope {
+    fn into(self) -> ProtobufStoreScope {
+        match self {
+            StoreScope::Global => ProtobufStoreScope::Global,
+            StoreScope::Session => ProtobufStoreScope::Session,
+        }
+    }
+}
+
 impl TryFrom<ProtobufPaneId> for PaneId {
     type Error = &'static str;
     fn try_from(protobuf_pane_id: ProtobufPaneId) -> Result<Self, &'static str> {
@@ -1679,6 +1769,10 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 Some(Payload::GenerateWebLoginTokenPayload(generate_web_login_token_payload)) => {
                     Ok(PluginCommand::GenerateWebLoginToken(
                         generate_web_login_token_payload.token_label,
+                        generate_web_login_token_payload
+                            .scope
+                            .map(|s| s.into())
+                            .unwrap_or_default(),
                     ))
                 },
                 _ => Err("GenerateWebLoginToken requires a payload"),
@@ -1736,6 +1830,174 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 )),
                 _ => Err("Mismatched payload for ReplacePaneWithExistingPane"),
             },
+            Some(CommandName::SearchPaneScrollback) => match protobuf_plugin_command.payload {
+                Some(Payload::SearchPaneScrollbackPayload(search_pane_scrollback_payload)) => {
+                    Ok(PluginCommand::SearchPaneScrollback(
+                        search_pane_scrollback_payload
+                            .pane_id
+                            .and_then(|p_id| PaneId::try_from(p_id).ok())
+                            .ok_or("Failed to parse SearchPaneScrollbackPayload")?,
+                        search_pane_scrollback_payload.pattern,
+                    ))
+                },
+                _ => Err("Mismatched payload for SearchPaneScrollback"),
+            },
+            Some(CommandName::GetPaneScrollback) => match protobuf_plugin_command.payload {
+                Some(Payload::GetPaneScrollbackPayload(get_pane_scrollback_payload)) => {
+                    Ok(PluginCommand::GetPaneScrollback(
+                        get_pane_scrollback_payload
+                            .pane_id
+                            .and_then(|p_id| PaneId::try_from(p_id).ok())
+                            .ok_or("Failed to parse GetPaneScrollbackPayload")?,
+                        get_pane_scrollback_payload
+                            .range
+                            .and_then(|r| ScrollbackRange::try_from(r).ok())
+                            .ok_or("Failed to parse GetPaneScrollbackPayload")?,
+                        get_pane_scrollback_payload.with_styles,
+                    ))
+                },
+                _ => Err("Mismatched payload for GetPaneScrollback"),
+            },
+            Some(CommandName::StoreGet) => match protobuf_plugin_command.payload {
+                Some(Payload::StoreGetPayload(store_get_payload)) => Ok(PluginCommand::StoreGet(
+                    store_get_payload.key,
+                    StoreScope::try_from(store_get_payload.scope)?,
+                )),
+                _ => Err("Mismatched payload for StoreGet"),
+            },
+            Some(CommandName::StoreSet) => match protobuf_plugin_command.payload {
+                Some(Payload::StoreSetPayload(store_set_payload)) => Ok(PluginCommand::StoreSet(
+                    store_set_payload.key,
+                    store_set_payload.value,
+                    StoreScope::try_from(store_set_payload.scope)?,
+                )),
+                _ => Err("Mismatched payload for StoreSet"),
+            },
+            Some(CommandName::StoreDelete) => match protobuf_plugin_command.payload {
+                Some(Payload::StoreDeletePayload(store_delete_payload)) => {
+                    Ok(PluginCommand::StoreDelete(
+                        store_delete_payload.key,
+                        StoreScope::try_from(store_delete_payload.scope)?,
+                    ))
+                },
+                _ => Err("Mismatched payload for StoreDelete"),
+            },
+            Some(CommandName::StoreList) => match protobuf_plugin_command.payload {
+                Some(Payload::StoreListPayload(store_list_payload)) => Ok(
+                    PluginCommand::StoreList(StoreScope::try_from(store_list_payload.scope)?),
+                ),
+                _ => Err("Mismatched payload for StoreList"),
+            },
+            Some(CommandName::ListPluginStore) => match protobuf_plugin_command.payload {
+                Some(Payload::ListPluginStorePayload(list_plugin_store_payload)) => {
+                    Ok(PluginCommand::ListPluginStore(
+                        list_plugin_store_payload.plugin_location,
+                        StoreScope::try_from(list_plugin_store_payload.scope)?,
+                    ))
+                },
+                _ => Err("Mismatched payload for ListPluginStore"),
+            },
+            Some(CommandName::ClearPluginStore) => match protobuf_plugin_command.payload {
+                Some(Payload::ClearPluginStorePayload(clear_plugin_store_payload)) => Ok(
+                    PluginCommand::ClearPluginStore(clear_plugin_store_payload.plugin_location),
+                ),
+                _ => Err("Mismatched payload for ClearPluginStore"),
+            },
+            Some(CommandName::CallPlugin) => match protobuf_plugin_command.payload {
+                Some(Payload::CallPluginPayload(call_plugin_payload)) => {
+                    Ok(PluginCommand::CallPlugin {
+                        call_id: call_plugin_payload.call_id,
+                        destination: call_plugin_payload.destination,
+                        method: call_plugin_payload.method,
+                        payload: call_plugin_payload.payload,
+                        timeout: call_plugin_payload.timeout_ms.map(Duration::from_millis),
+                    })
+                },
+                _ => Err("Mismatched payload for CallPlugin"),
+            },
+            Some(CommandName::RespondToPluginCall) => match protobuf_plugin_command.payload {
+                Some(Payload::RespondToPluginCallPayload(respond_to_plugin_call_payload)) => {
+                    let result = match (
+                        respond_to_plugin_call_payload.response,
+                        respond_to_plugin_call_payload.error,
+                    ) {
+                        (_, Some(error)) => Err(error),
+                        (Some(response), None) => Ok(response),
+                        (None, None) => return Err("Malformed RespondToPluginCallPayload"),
+                    };
+                    Ok(PluginCommand::RespondToPluginCall(
+                        respond_to_plugin_call_payload.call_id,
+                        result,
+                    ))
+                },
+                _ => Err("Mismatched payload for RespondToPluginCall"),
+            },
+            Some(CommandName::WatchPaths) => match protobuf_plugin_command.payload {
+                Some(Payload::WatchPathsPayload(watch_paths_payload)) => {
+                    Ok(PluginCommand::WatchPaths(
+                        watch_paths_payload.watch_id,
+                        watch_paths_payload
+                            .paths
+                            .into_iter()
+                            .map(PathBuf::from)
+                            .collect(),
+                        WatchOptions {
+                            recursive: watch_paths_payload.recursive,
+                            debounce_ms: watch_paths_payload.debounce_ms,
+                            include_globs: watch_paths_payload.include_globs,
+                            exclude_globs: watch_paths_payload.exclude_globs,
+                        },
+                    ))
+                },
+                _ => Err("Mismatched payload for WatchPaths"),
+            },
+            Some(CommandName::UnwatchPaths) => match protobuf_plugin_command.payload {
+                Some(Payload::UnwatchPathsPayload(unwatch_paths_payload)) => {
+                    Ok(PluginCommand::UnwatchPaths(unwatch_paths_payload.watch_id))
+                },
+                _ => Err("Mismatched payload for UnwatchPaths"),
+            },
+            Some(CommandName::RegisterCommands) => match protobuf_plugin_command.payload {
+                Some(Payload::RegisterCommandsPayload(register_commands_payload)) => {
+                    Ok(PluginCommand::RegisterCommands(
+                        register_commands_payload
+                            .commands
+                            .into_iter()
+                            .map(|command| command.into())
+                            .collect(),
+                    ))
+                },
+                _ => Err("Mismatched payload for RegisterCommands"),
+            },
+            Some(CommandName::ListRegisteredCommands) => match protobuf_plugin_command.payload {
+                Some(_) => Err("ListRegisteredCommands should have no payload, found a payload"),
+                None => Ok(PluginCommand::ListRegisteredCommands),
+            },
+            Some(CommandName::InvokeRegisteredCommand) => match protobuf_plugin_command.payload {
+                Some(Payload::InvokeRegisteredCommandPayload(
+                    invoke_registered_command_payload,
+                )) => Ok(PluginCommand::InvokeRegisteredCommand(
+                    invoke_registered_command_payload.name,
+                    invoke_registered_command_payload
+                        .args
+                        .into_iter()
+                        .map(|arg| (arg.name, arg.value))
+                        .collect(),
+                )),
+                _ => Err("Mismatched payload for InvokeRegisteredCommand"),
+            },
+            Some(CommandName::RunActions) => match protobuf_plugin_command.payload {
+                Some(Payload::RunActionsPayload(run_actions_payload)) => {
+                    Ok(PluginCommand::RunActions(
+                        run_actions_payload
+                            .actions
+                            .into_iter()
+                            .map(|action| action.try_into())
+                            .collect::<Result<Vec<_>, _>>()?,
+                    ))
+                },
+                _ => Err("Mismatched payload for RunActions"),
+            },
             None => Err("Unrecognized plugin command"),
         }
     }
@@ -2843,10 +3105,13 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     )),
                 })
             },
-            PluginCommand::GenerateWebLoginToken(token_label) => Ok(ProtobufPluginCommand {
+            PluginCommand::GenerateWebLoginToken(token_label, scope) => Ok(ProtobufPluginCommand {
                 name: CommandName::GenerateWebLoginToken as i32,
                 payload: Some(Payload::GenerateWebLoginTokenPayload(
-                    GenerateWebLoginTokenPayload { token_label },
+                    GenerateWebLoginTokenPayload {
+                        token_label,
+                        scope: Some(scope.into()),
+                    },
                 )),
             }),
             PluginCommand::RevokeWebLoginToken(token_label) => Ok(ProtobufPluginCommand {
@@ -2888,6 +3153,165 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     )),
                 })
             },
+            PluginCommand::SearchPaneScrollback(pane_id, pattern) => Ok(ProtobufPluginCommand {
+                name: CommandName::SearchPaneScrollback as i32,
+                payload: Some(Payload::SearchPaneScrollbackPayload(
+                    SearchPaneScrollbackPayload {
+                        pane_id: ProtobufPaneId::try_from(pane_id).ok(),
+                        pattern,
+                    },
+                )),
+            }),
+            PluginCommand::GetPaneScrollback(pane_id, range, with_styles) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::GetPaneScrollback as i32,
+                    payload: Some(Payload::GetPaneScrollbackPayload(
+                        GetPaneScrollbackPayload {
+                            pane_id: ProtobufPaneId::try_from(pane_id).ok(),
+                            range: Some(range.into()),
+                            with_styles,
+                        },
+                    )),
+                })
+            },
+            PluginCommand::StoreGet(key, scope) => {
+                let scope: ProtobufStoreScope = scope.into();
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::StoreGet as i32,
+                    payload: Some(Payload::StoreGetPayload(StoreGetPayload {
+                        key,
+                        scope: scope as i32,
+                    })),
+                })
+            },
+            PluginCommand::StoreSet(key, value, scope) => {
+                let scope: ProtobufStoreScope = scope.into();
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::StoreSet as i32,
+                    payload: Some(Payload::StoreSetPayload(StoreSetPayload {
+                        key,
+                        value,
+                        scope: scope as i32,
+                    })),
+                })
+            },
+            PluginCommand::StoreDelete(key, scope) => {
+                let scope: ProtobufStoreScope = scope.into();
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::StoreDelete as i32,
+                    payload: Some(Payload::StoreDeletePayload(StoreDeletePayload {
+                        key,
+                        scope: scope as i32,
+                    })),
+                })
+            },
+            PluginCommand::StoreList(scope) => {
+                let scope: ProtobufStoreScope = scope.into();
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::StoreList as i32,
+                    payload: Some(Payload::StoreListPayload(StoreListPayload {
+                        scope: scope as i32,
+                    })),
+                })
+            },
+            PluginCommand::ListPluginStore(plugin_location, scope) => {
+                let scope: ProtobufStoreScope = scope.into();
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::ListPluginStore as i32,
+                    payload: Some(Payload::ListPluginStorePayload(ListPluginStorePayload {
+                        plugin_location,
+                        scope: scope as i32,
+                    })),
+                })
+            },
+            PluginCommand::ClearPluginStore(plugin_location) => Ok(ProtobufPluginCommand {
+                name: CommandName::ClearPluginStore as i32,
+                payload: Some(Payload::ClearPluginStorePayload(ClearPluginStorePayload {
+                    plugin_location,
+                })),
+            }),
+            PluginCommand::CallPlugin {
+                call_id,
+                destination,
+                method,
+                payload,
+                timeout,
+            } => Ok(ProtobufPluginCommand {
+                name: CommandName::CallPlugin as i32,
+                payload: Some(Payload::CallPluginPayload(CallPluginPayload {
+                    call_id,
+                    destination,
+                    method,
+                    payload,
+                    timeout_ms: timeout.map(|t| t.as_millis() as u64),
+                })),
+            }),
+            PluginCommand::RespondToPluginCall(call_id, result) => {
+                let (response, error) = match result {
+                    Ok(response) => (Some(response), None),
+                    Err(error) => (None, Some(error)),
+                };
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::RespondToPluginCall as i32,
+                    payload: Some(Payload::RespondToPluginCallPayload(
+                        RespondToPluginCallPayload {
+                            call_id,
+                            response,
+                            error,
+                        },
+                    )),
+                })
+            },
+            PluginCommand::WatchPaths(watch_id, paths, watch_options) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::WatchPaths as i32,
+                    payload: Some(Payload::WatchPathsPayload(WatchPathsPayload {
+                        watch_id,
+                        paths: paths.iter().map(|p| p.display().to_string()).collect(),
+                        recursive: watch_options.recursive,
+                        debounce_ms: watch_options.debounce_ms,
+                        include_globs: watch_options.include_globs,
+                        exclude_globs: watch_options.exclude_globs,
+                    })),
+                })
+            },
+            PluginCommand::RegisterCommands(commands) => Ok(ProtobufPluginCommand {
+                name: CommandName::RegisterCommands as i32,
+                payload: Some(Payload::RegisterCommandsPayload(RegisterCommandsPayload {
+                    commands: commands.into_iter().map(|command| command.into()).collect(),
+                })),
+            }),
+            PluginCommand::ListRegisteredCommands => Ok(ProtobufPluginCommand {
+                name: CommandName::ListRegisteredCommands as i32,
+                payload: None,
+            }),
+            PluginCommand::InvokeRegisteredCommand(name, args) => Ok(ProtobufPluginCommand {
+                name: CommandName::InvokeRegisteredCommand as i32,
+                payload: Some(Payload::InvokeRegisteredCommandPayload(
+                    InvokeRegisteredCommandPayload {
+                        name,
+                        args: args
+                            .into_iter()
+                            .map(|(name, value)| ContextItem { name, value })
+                            .collect(),
+                    },
+                )),
+            }),
+            PluginCommand::RunActions(actions) => Ok(ProtobufPluginCommand {
+                name: CommandName::RunActions as i32,
+                payload: Some(Payload::RunActionsPayload(RunActionsPayload {
+                    actions: actions
+                        .into_iter()
+                        .map(|action| action.try_into())
+                        .collect::<Result<Vec<_>, _>>()?,
+                })),
+            }),
+            PluginCommand::UnwatchPaths(watch_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::UnwatchPaths as i32,
+                payload: Some(Payload::UnwatchPathsPayload(UnwatchPathsPayload {
+                    watch_id,
+                })),
+            }),
         }
     }
 }
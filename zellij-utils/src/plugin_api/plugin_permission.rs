@@ -28,6 +28,7 @@ impl TryFrom<ProtobufPermissionType> for PermissionType {
             ProtobufPermissionType::FullHdAccess => Ok(PermissionType::FullHdAccess),
             ProtobufPermissionType::StartWebServer => Ok(PermissionType::StartWebServer),
             ProtobufPermissionType::InterceptInput => Ok(PermissionType::InterceptInput),
+            ProtobufPermissionType::ReadPaneContents => Ok(PermissionType::ReadPaneContents),
         }
     }
 }
@@ -57,6 +58,7 @@ impl TryFrom<PermissionType> for ProtobufPermissionType {
             PermissionType::FullHdAccess => Ok(ProtobufPermissionType::FullHdAccess),
             PermissionType::StartWebServer => Ok(ProtobufPermissionType::StartWebServer),
             PermissionType::InterceptInput => Ok(ProtobufPermissionType::InterceptInput),
+            PermissionType::ReadPaneContents => Ok(ProtobufPermissionType::ReadPaneContents),
         }
     }
 }
@@ -36,6 +36,7 @@ pub struct PaneLayoutManifest {
     pub title: Option<String>,
     pub is_focused: bool,
     pub pane_contents: Option<String>,
+    pub scrollback_file: Option<String>,
 }
 
 pub fn serialize_session_layout(
@@ -163,7 +164,9 @@ fn serialize_tiled_pane(
     pane_contents: &mut BTreeMap<String, String>,
 ) -> KdlNode {
     let (command, args) = extract_command_and_args(&layout.run);
+    let env = extract_env(&layout.run);
     let (plugin, plugin_config) = extract_plugin_and_config(&layout.run);
+    let plugin_saved_state = extract_plugin_saved_state(&layout.run);
     let (edit, _line_number) = extract_edit_and_line_number(&layout.run);
     let cwd = layout.run.as_ref().and_then(|r| r.get_cwd());
     let has_children = layout.external_children_index.is_some() || !layout.children.is_empty();
@@ -176,6 +179,7 @@ fn serialize_tiled_pane(
         cwd,
         layout.focus,
         &layout.pane_initial_contents,
+        &layout.pane_scrollback_file,
         pane_contents,
         has_children,
         &mut tiled_pane_node,
@@ -185,13 +189,20 @@ fn serialize_tiled_pane(
     let has_child_attributes = !layout.children.is_empty()
         || layout.external_children_index.is_some()
         || !args.is_empty()
+        || !env.is_empty()
         || plugin.is_some()
         || command.is_some();
     if has_child_attributes {
         let mut tiled_pane_node_children = KdlDocument::new();
         serialize_args(args, &mut tiled_pane_node_children);
+        serialize_env(env, &mut tiled_pane_node_children);
         serialize_start_suspended(&command, &mut tiled_pane_node_children);
-        serialize_plugin(plugin, plugin_config, &mut tiled_pane_node_children);
+        serialize_plugin(
+            plugin,
+            plugin_config,
+            plugin_saved_state,
+            &mut tiled_pane_node_children,
+        );
         if layout.children.is_empty() && layout.external_children_index.is_some() {
             tiled_pane_node_children
                 .nodes_mut()
@@ -222,6 +233,13 @@ pub fn extract_command_and_args(layout_run: &Option<Run>) -> (Option<String>, Ve
         _ => (None, vec![]),
     }
 }
+pub fn extract_env(layout_run: &Option<Run>) -> BTreeMap<String, String> {
+    match layout_run {
+        Some(Run::Command(run_command)) => run_command.env.clone(),
+        Some(Run::EditFile(_, _, _, env)) | Some(Run::Shell(_, env)) => env.clone(),
+        _ => BTreeMap::new(),
+    }
+}
 pub fn extract_plugin_and_config(
     layout_run: &Option<Run>,
 ) -> (Option<String>, Option<PluginUserConfiguration>) {
@@ -250,10 +268,18 @@ pub fn extract_plugin_and_config(
         _ => (None, None),
     }
 }
+pub fn extract_plugin_saved_state(layout_run: &Option<Run>) -> Option<String> {
+    match &layout_run {
+        Some(Run::Plugin(RunPluginOrAlias::RunPlugin(run_plugin))) => {
+            run_plugin.saved_state.clone()
+        },
+        _ => None,
+    }
+}
 pub fn extract_edit_and_line_number(layout_run: &Option<Run>) -> (Option<String>, Option<usize>) {
     match &layout_run {
         // TODO: line number in layouts?
-        Some(Run::EditFile(path, line_number, _cwd)) => {
+        Some(Run::EditFile(path, line_number, _cwd, _env)) => {
             (Some(path.display().to_string()), line_number.clone())
         },
         _ => (None, None),
@@ -267,6 +293,7 @@ fn serialize_pane_title_and_attributes(
     cwd: Option<PathBuf>,
     focus: Option<bool>,
     initial_pane_contents: &Option<String>,
+    pane_scrollback_file: &Option<PathBuf>,
     pane_contents: &mut BTreeMap<String, String>,
     has_children: bool,
     kdl_node: &mut KdlNode,
@@ -308,6 +335,14 @@ fn serialize_pane_title_and_attributes(
             pane_contents.insert(file_name, initial_pane_contents.clone());
         }
     }
+    if let Some(pane_scrollback_file) = pane_scrollback_file.as_ref() {
+        if command.is_none() && edit.is_none() {
+            kdl_node.entries_mut().push(KdlEntry::new_prop(
+                "scrollback_file",
+                pane_scrollback_file.display().to_string(),
+            ));
+        }
+    }
 }
 
 fn serialize_args(args: Vec<String>, pane_node_children: &mut KdlDocument) {
@@ -320,9 +355,24 @@ fn serialize_args(args: Vec<String>, pane_node_children: &mut KdlDocument) {
     }
 }
 
+fn serialize_env(env: BTreeMap<String, String>, pane_node_children: &mut KdlDocument) {
+    if !env.is_empty() {
+        let mut env_node = KdlNode::new("env");
+        let mut env_node_children = KdlDocument::new();
+        for (key, value) in env {
+            let mut env_variable_node = KdlNode::new(key);
+            env_variable_node.entries_mut().push(KdlEntry::new(value));
+            env_node_children.nodes_mut().push(env_variable_node);
+        }
+        env_node.set_children(env_node_children);
+        pane_node_children.nodes_mut().push(env_node);
+    }
+}
+
 fn serialize_plugin(
     plugin: Option<String>,
     plugin_config: Option<PluginUserConfiguration>,
+    plugin_saved_state: Option<String>,
     pane_node_children: &mut KdlDocument,
 ) {
     if let Some(plugin) = plugin {
@@ -330,17 +380,24 @@ fn serialize_plugin(
         plugin_node
             .entries_mut()
             .push(KdlEntry::new_prop("location", plugin.to_owned()));
-        if let Some(plugin_config) =
-            plugin_config.and_then(|p| if p.inner().is_empty() { None } else { Some(p) })
-        {
+        let plugin_config =
+            plugin_config.and_then(|p| if p.inner().is_empty() { None } else { Some(p) });
+        if plugin_config.is_some() || plugin_saved_state.is_some() {
             let mut plugin_node_children = KdlDocument::new();
-            for (config_key, config_value) in plugin_config.inner() {
+            for (config_key, config_value) in plugin_config.iter().flat_map(|p| p.inner()) {
                 let mut config_node = KdlNode::new(config_key.to_owned());
                 config_node
                     .entries_mut()
                     .push(KdlEntry::new(config_value.to_owned()));
                 plugin_node_children.nodes_mut().push(config_node);
             }
+            if let Some(plugin_saved_state) = plugin_saved_state {
+                let mut saved_state_node = KdlNode::new("_saved_state");
+                saved_state_node
+                    .entries_mut()
+                    .push(KdlEntry::new(plugin_saved_state));
+                plugin_node_children.nodes_mut().push(saved_state_node);
+            }
             plugin_node.set_children(plugin_node_children);
         }
         pane_node_children.nodes_mut().push(plugin_node);
@@ -353,15 +410,9 @@ fn serialize_tiled_layout_attributes(
     kdl_node: &mut KdlNode,
 ) {
     if !ignore_size {
-        match layout.split_size {
-            Some(SplitSize::Fixed(size)) => kdl_node
-                .entries_mut()
-                .push(KdlEntry::new_prop("size", KdlValue::Base10(size as i64))),
-            Some(SplitSize::Percent(size)) => kdl_node
-                .entries_mut()
-                .push(KdlEntry::new_prop("size", format!("{size}%"))),
-            None => (),
-        };
+        serialize_split_size("size", layout.split_size, kdl_node);
+        serialize_split_size("min_size", layout.min_size, kdl_node);
+        serialize_split_size("max_size", layout.max_size, kdl_node);
     }
     if layout.borderless {
         kdl_node
@@ -389,6 +440,18 @@ fn serialize_tiled_layout_attributes(
     }
 }
 
+fn serialize_split_size(name: &str, split_size: Option<SplitSize>, kdl_node: &mut KdlNode) {
+    match split_size {
+        Some(SplitSize::Fixed(size)) => kdl_node
+            .entries_mut()
+            .push(KdlEntry::new_prop(name, KdlValue::Base10(size as i64))),
+        Some(SplitSize::Percent(size)) => kdl_node
+            .entries_mut()
+            .push(KdlEntry::new_prop(name, format!("{size}%"))),
+        None => (),
+    };
+}
+
 fn serialize_floating_layout_attributes(
     layout: &FloatingPaneLayout,
     pane_node_children: &mut KdlDocument,
@@ -530,7 +593,7 @@ fn serialize_swap_tiled_layouts(
                 };
             let mut layout_step_node = KdlNode::new("tab");
             let mut layout_step_node_children = KdlDocument::new();
-            if let Some(layout_constraint_entry) = serialize_layout_constraint(layout_constraint) {
+            for layout_constraint_entry in serialize_layout_constraint(layout_constraint) {
                 layout_step_node.entries_mut().push(layout_constraint_entry);
             }
 
@@ -552,22 +615,24 @@ fn serialize_swap_tiled_layouts(
     }
 }
 
-fn serialize_layout_constraint(layout_constraint: LayoutConstraint) -> Option<KdlEntry> {
-    match layout_constraint {
-        LayoutConstraint::MaxPanes(max_panes) => Some(KdlEntry::new_prop(
-            "max_panes",
-            KdlValue::Base10(max_panes as i64),
-        )),
-        LayoutConstraint::MinPanes(min_panes) => Some(KdlEntry::new_prop(
-            "min_panes",
-            KdlValue::Base10(min_panes as i64),
-        )),
-        LayoutConstraint::ExactPanes(exact_panes) => Some(KdlEntry::new_prop(
-            "exact_panes",
-            KdlValue::Base10(exact_panes as i64),
-        )),
-        LayoutConstraint::NoConstraint => None,
-    }
+fn serialize_layout_constraint(layout_constraint: LayoutConstraint) -> Vec<KdlEntry> {
+    let (name, value) = match layout_constraint {
+        LayoutConstraint::All(layout_constraints) => {
+            return layout_constraints
+                .into_iter()
+                .flat_map(serialize_layout_constraint)
+                .collect();
+        },
+        LayoutConstraint::MaxPanes(max_panes) => ("max_panes", max_panes),
+        LayoutConstraint::MinPanes(min_panes) => ("min_panes", min_panes),
+        LayoutConstraint::ExactPanes(exact_panes) => ("exact_panes", exact_panes),
+        LayoutConstraint::MinColumns(min_columns) => ("min_columns", min_columns),
+        LayoutConstraint::MaxColumns(max_columns) => ("max_columns", max_columns),
+        LayoutConstraint::MinRows(min_rows) => ("min_rows", min_rows),
+        LayoutConstraint::MaxRows(max_rows) => ("max_rows", max_rows),
+        LayoutConstraint::NoConstraint => return vec![],
+    };
+    vec![KdlEntry::new_prop(name, KdlValue::Base10(value as i64))]
 }
 
 fn serialize_swap_floating_layouts(
@@ -588,7 +653,7 @@ fn serialize_swap_floating_layouts(
         for (layout_constraint, floating_panes_layout) in swap_floating_layout.0 {
             let mut layout_step_node = KdlNode::new("floating_panes");
             let mut layout_step_node_children = KdlDocument::new();
-            if let Some(layout_constraint_entry) = serialize_layout_constraint(layout_constraint) {
+            for layout_constraint_entry in serialize_layout_constraint(layout_constraint) {
                 layout_step_node.entries_mut().push(layout_constraint_entry);
             }
 
@@ -644,7 +709,9 @@ fn serialize_floating_pane(
     let mut floating_pane_node = KdlNode::new("pane");
     let mut floating_pane_node_children = KdlDocument::new();
     let (command, args) = extract_command_and_args(&layout.run);
+    let env = extract_env(&layout.run);
     let (plugin, plugin_config) = extract_plugin_and_config(&layout.run);
+    let plugin_saved_state = extract_plugin_saved_state(&layout.run);
     let (edit, _line_number) = extract_edit_and_line_number(&layout.run);
     let cwd = layout.run.as_ref().and_then(|r| r.get_cwd());
     let has_children = false;
@@ -655,6 +722,7 @@ fn serialize_floating_pane(
         cwd,
         layout.focus,
         &layout.pane_initial_contents,
+        &layout.pane_scrollback_file,
         pane_contents,
         has_children,
         &mut floating_pane_node,
@@ -662,7 +730,13 @@ fn serialize_floating_pane(
     serialize_start_suspended(&command, &mut floating_pane_node_children);
     serialize_floating_layout_attributes(&layout, &mut floating_pane_node_children);
     serialize_args(args, &mut floating_pane_node_children);
-    serialize_plugin(plugin, plugin_config, &mut floating_pane_node_children);
+    serialize_env(env, &mut floating_pane_node_children);
+    serialize_plugin(
+        plugin,
+        plugin_config,
+        plugin_saved_state,
+        &mut floating_pane_node_children,
+    );
     floating_pane_node.set_children(floating_pane_node_children);
     floating_pane_node
 }
@@ -709,7 +783,15 @@ fn tiled_pane_layout_from_manifest(
     manifest: Option<&PaneLayoutManifest>,
     split_size: Option<SplitSize>,
 ) -> TiledPaneLayout {
-    let (run, borderless, is_expanded_in_stack, name, focus, pane_initial_contents) = manifest
+    let (
+        run,
+        borderless,
+        is_expanded_in_stack,
+        name,
+        focus,
+        pane_initial_contents,
+        pane_scrollback_file,
+    ) = manifest
         .map(|g| {
             let mut run = g.run.clone();
             if let Some(cwd) = &g.cwd {
@@ -726,9 +808,10 @@ fn tiled_pane_layout_from_manifest(
                 g.title.clone(),
                 Some(g.is_focused),
                 g.pane_contents.clone(),
+                g.scrollback_file.as_ref().map(PathBuf::from),
             )
         })
-        .unwrap_or((None, false, false, None, None, None));
+        .unwrap_or((None, false, false, None, None, None, None));
     TiledPaneLayout {
         split_size,
         run,
@@ -737,6 +820,7 @@ fn tiled_pane_layout_from_manifest(
         name,
         focus,
         pane_initial_contents,
+        pane_scrollback_file,
         ..Default::default()
     }
 }
@@ -801,7 +885,11 @@ fn get_tiled_panes_layout_from_panegeoms(
 
     for (subgeoms, subsplit_size) in new_geoms.iter().zip(new_split_sizes) {
         match get_tiled_panes_layout_from_panegeoms(&subgeoms, subsplit_size) {
-            Some(child) => {
+            Some(mut child) => {
+                let (min_size, max_size) =
+                    get_common_size_bounds(&subgeoms, &children_split_direction);
+                child.min_size = min_size;
+                child.max_size = max_size;
                 children.push(child);
             },
             None => {
@@ -822,6 +910,30 @@ fn get_tiled_panes_layout_from_panegeoms(
     })
 }
 
+fn get_common_size_bounds(
+    geoms: &[PaneLayoutManifest],
+    split_direction: &SplitDirection,
+) -> (Option<SplitSize>, Option<SplitSize>) {
+    // the size bounds of a node in its parent's split direction are the ones shared by all the
+    // panes in it (a logical container passes its bounds on to all of its children)
+    let bounds_in_direction = |g: &PaneLayoutManifest| match split_direction {
+        SplitDirection::Vertical => (g.geom.size_bounds.min_cols, g.geom.size_bounds.max_cols),
+        SplitDirection::Horizontal => (g.geom.size_bounds.min_rows, g.geom.size_bounds.max_rows),
+    };
+    let mut bounds = geoms.iter().map(bounds_in_direction);
+    let first = bounds.next().unwrap_or((None, None));
+    let (mut min_size, mut max_size) = first;
+    for (min, max) in bounds {
+        if min != first.0 {
+            min_size = None;
+        }
+        if max != first.1 {
+            max_size = None;
+        }
+    }
+    (min_size, max_size)
+}
+
 fn get_floating_panes_layout_from_panegeoms(
     manifests: &Vec<PaneLayoutManifest>,
 ) -> Vec<FloatingPaneLayout> {
@@ -843,6 +955,7 @@ fn get_floating_panes_layout_from_panegeoms(
                 focus: Some(m.is_focused),
                 already_running: false,
                 pane_initial_contents: m.pane_contents.clone(),
+                pane_scrollback_file: m.scrollback_file.as_ref().map(PathBuf::from),
                 logical_position: None,
             }
         })
@@ -1372,6 +1485,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1385,6 +1499,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1393,6 +1508,7 @@ mod tests {
                         PathBuf::from("/tmp/\"my/cool cwd/my-file"),
                         None,
                         None,
+                        BTreeMap::new(),
                     )),
                     geom: PaneGeom {
                         x: 0,
@@ -1402,6 +1518,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1418,6 +1535,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1439,6 +1557,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1454,6 +1573,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1471,6 +1591,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1484,6 +1605,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1499,6 +1621,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1513,6 +1636,72 @@ mod tests {
         assert_snapshot!(kdl.0);
     }
     #[test]
+    fn can_serialize_and_resurrect_pane_scrollback_file() {
+        use crate::input::command::RunCommand;
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![
+                PaneLayoutManifest {
+                    scrollback_file: Some("scrollback_1".to_owned()),
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 0,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                        size_bounds: Default::default(),
+                    },
+                    ..Default::default()
+                },
+                PaneLayoutManifest {
+                    // command panes run their command again rather than restore their scrollback
+                    run: Some(Run::Command(RunCommand {
+                        command: PathBuf::from("htop"),
+                        ..Default::default()
+                    })),
+                    scrollback_file: Some("scrollback_2".to_owned()),
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 10,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                        size_bounds: Default::default(),
+                    },
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+        // the scrollback file is looked up next to the resurrected layout
+        let resurrected_layout = Layout::from_kdl(
+            &kdl.0,
+            Some("/tmp/session-layout.kdl".to_owned()),
+            None,
+            None,
+        )
+        .unwrap();
+        let resurrected_panes = &resurrected_layout.tabs[0].1.children;
+        assert_eq!(
+            resurrected_panes[0].pane_scrollback_file,
+            Some(PathBuf::from("/tmp/scrollback_1")),
+            "scrollback file is resurrected"
+        );
+        assert_eq!(
+            resurrected_panes[1].pane_scrollback_file, None,
+            "command pane scrollback file is not serialized"
+        );
+    }
+    #[test]
     fn can_serialize_tab_with_floating_panes() {
         use crate::input::command::RunCommand;
         use crate::input::layout::RunPlugin;
@@ -1530,6 +1719,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1543,6 +1733,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1551,6 +1742,7 @@ mod tests {
                         PathBuf::from("/tmp/\"my/cool cwd/my-file"),
                         None,
                         None,
+                        BTreeMap::new(),
                     )),
                     geom: PaneGeom {
                         x: 0,
@@ -1560,6 +1752,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1576,6 +1769,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1597,6 +1791,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1612,6 +1807,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1629,6 +1825,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1644,6 +1841,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1659,6 +1857,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1688,6 +1887,7 @@ mod tests {
                         stacked: Some(0),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1700,6 +1900,7 @@ mod tests {
                         stacked: Some(0),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1712,6 +1913,7 @@ mod tests {
                         stacked: Some(0),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1738,6 +1940,7 @@ mod tests {
                         stacked: Some(0),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1750,6 +1953,7 @@ mod tests {
                         stacked: Some(0),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1762,6 +1966,7 @@ mod tests {
                         stacked: Some(0),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1774,6 +1979,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1786,6 +1992,7 @@ mod tests {
                         stacked: Some(1),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1798,6 +2005,7 @@ mod tests {
                         stacked: Some(1),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1810,6 +2018,7 @@ mod tests {
                         stacked: Some(1),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1836,6 +2045,7 @@ mod tests {
                         stacked: Some(0),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1848,6 +2058,7 @@ mod tests {
                         stacked: Some(0),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1860,6 +2071,7 @@ mod tests {
                         stacked: Some(0),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1872,6 +2084,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1884,6 +2097,7 @@ mod tests {
                         stacked: Some(1),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1896,6 +2110,7 @@ mod tests {
                         stacked: Some(1),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1908,6 +2123,7 @@ mod tests {
                         stacked: Some(1),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1920,6 +2136,7 @@ mod tests {
                         stacked: Some(2),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1932,6 +2149,7 @@ mod tests {
                         stacked: Some(2),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1944,6 +2162,7 @@ mod tests {
                         stacked: Some(2),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1956,6 +2175,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1968,6 +2188,7 @@ mod tests {
                         stacked: Some(3),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1980,6 +2201,7 @@ mod tests {
                         stacked: Some(3),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -1992,6 +2214,7 @@ mod tests {
                         stacked: Some(3),
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -2017,6 +2240,7 @@ mod tests {
                     stacked: None,
                     is_pinned: false,
                     logical_position: None,
+                    size_bounds: Default::default(),
                 },
                 ..Default::default()
             }],
@@ -2033,6 +2257,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -2045,6 +2270,7 @@ mod tests {
                         stacked: None,
                         is_pinned: false,
                         logical_position: None,
+                        size_bounds: Default::default(),
                     },
                     ..Default::default()
                 },
@@ -2161,6 +2387,232 @@ mod tests {
         let kdl = serialize_session_layout(global_layout_manifest).unwrap();
         assert_snapshot!(kdl.0);
     }
+    #[test]
+    fn can_serialize_swap_layouts_with_display_area_constraints() {
+        let tiled_panes_layout = TiledPaneLayout {
+            children: vec![TiledPaneLayout::default(), TiledPaneLayout::default()],
+            ..Default::default()
+        };
+        let mut default_layout = Layout::default();
+        let mut swap_tiled_layout = BTreeMap::new();
+        swap_tiled_layout.insert(
+            LayoutConstraint::All(vec![
+                LayoutConstraint::MaxPanes(2),
+                LayoutConstraint::MinColumns(200),
+                LayoutConstraint::MaxRows(60),
+            ]),
+            tiled_panes_layout.clone(),
+        );
+        swap_tiled_layout.insert(LayoutConstraint::MaxColumns(120), tiled_panes_layout);
+        let mut swap_floating_layout = BTreeMap::new();
+        swap_floating_layout.insert(
+            LayoutConstraint::MinRows(40),
+            vec![FloatingPaneLayout::default()],
+        );
+        default_layout.swap_tiled_layouts = vec![(swap_tiled_layout, None)];
+        default_layout.swap_floating_layouts = vec![(swap_floating_layout, None)];
+        let default_layout = Box::new(default_layout);
+        let global_layout_manifest = GlobalLayoutManifest {
+            default_layout,
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+    }
+    #[test]
+    fn can_serialize_and_resurrect_plugin_saved_state() {
+        use crate::input::layout::RunPlugin;
+        let mut plugin_configuration = BTreeMap::new();
+        plugin_configuration.insert("key".to_owned(), "val".to_owned());
+        let run_plugin = RunPlugin {
+            saved_state: Some("{\"folder\": \"/tmp/my \\\"cool\\\" folder\"}".to_owned()),
+            ..RunPlugin::from_url("file:/path/to/plugin.wasm")
+                .unwrap()
+                .with_configuration(plugin_configuration.clone())
+        };
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![
+                PaneLayoutManifest {
+                    run: Some(Run::Plugin(RunPluginOrAlias::RunPlugin(run_plugin.clone()))),
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 0,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                        size_bounds: Default::default(),
+                    },
+                    ..Default::default()
+                },
+                PaneLayoutManifest {
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 10,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                        size_bounds: Default::default(),
+                    },
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+        let resurrected_layout = Layout::from_kdl(&kdl.0, None, None, None).unwrap();
+        let resurrected_run_plugin = resurrected_layout.tabs[0].1.children[0]
+            .run
+            .as_ref()
+            .and_then(|r| r.get_run_plugin())
+            .unwrap();
+        assert_eq!(
+            resurrected_run_plugin.saved_state, run_plugin.saved_state,
+            "saved state is resurrected"
+        );
+        assert_eq!(
+            resurrected_run_plugin.configuration.inner(),
+            &plugin_configuration,
+            "saved state is not part of the plugin configuration"
+        );
+    }
+
+    #[test]
+    fn can_serialize_and_resurrect_command_env() {
+        use crate::input::command::RunCommand;
+        let mut env = BTreeMap::new();
+        env.insert("RUST_LOG".to_owned(), "debug".to_owned());
+        env.insert(
+            "DATABASE_URL".to_owned(),
+            "postgres://localhost/dev".to_owned(),
+        );
+        let run_command = RunCommand {
+            command: PathBuf::from("cargo"),
+            args: vec!["run".to_owned()],
+            env: env.clone(),
+            ..Default::default()
+        };
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![
+                PaneLayoutManifest {
+                    run: Some(Run::Command(run_command)),
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 0,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                        size_bounds: Default::default(),
+                    },
+                    ..Default::default()
+                },
+                PaneLayoutManifest {
+                    run: Some(Run::Shell(None, env.clone())),
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 10,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                        size_bounds: Default::default(),
+                    },
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+        let resurrected_layout = Layout::from_kdl(&kdl.0, None, None, None).unwrap();
+        match &resurrected_layout.tabs[0].1.children[0].run {
+            Some(Run::Command(run_command)) => {
+                assert_eq!(run_command.env, env, "env is resurrected")
+            },
+            run => panic!("expected a command pane, got: {:?}", run),
+        }
+        match &resurrected_layout.tabs[0].1.children[1].run {
+            Some(Run::Shell(_cwd, shell_env)) => {
+                assert_eq!(shell_env, &env, "shell env is resurrected")
+            },
+            run => panic!("expected a shell pane, got: {:?}", run),
+        }
+    }
+
+    #[test]
+    fn can_serialize_and_resurrect_pane_size_bounds() {
+        use crate::pane_size::SizeBounds;
+        let mut left_pane_cols = Dimension::percent(50.0);
+        left_pane_cols.set_inner(50);
+        let mut right_pane_cols = Dimension::percent(50.0);
+        right_pane_cols.set_inner(50);
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![
+                PaneLayoutManifest {
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 0,
+                        rows: Dimension::fixed(10),
+                        cols: left_pane_cols,
+                        size_bounds: SizeBounds {
+                            min_cols: Some(SplitSize::Fixed(20)),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                PaneLayoutManifest {
+                    geom: PaneGeom {
+                        x: 50,
+                        y: 0,
+                        rows: Dimension::fixed(10),
+                        cols: right_pane_cols,
+                        size_bounds: SizeBounds {
+                            max_cols: Some(SplitSize::Percent(70)),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+        let resurrected_layout = Layout::from_kdl(&kdl.0, None, None, None).unwrap();
+        let resurrected_panes = &resurrected_layout.tabs[0].1.children[0].children;
+        assert_eq!(
+            resurrected_panes[0].min_size,
+            Some(SplitSize::Fixed(20)),
+            "min size is resurrected"
+        );
+        assert_eq!(
+            resurrected_panes[1].max_size,
+            Some(SplitSize::Percent(70)),
+            "max size is resurrected"
+        );
+    }
 
     // utility functions
     fn parse_panegeom_from_json(data_str: &str) -> PaneGeom {
@@ -2178,6 +2630,7 @@ mod tests {
             stacked: None,
             is_pinned: false,
             logical_position: None,
+            size_bounds: Default::default(),
         }
     }
 
@@ -1,15 +1,17 @@
 // TODO: GATE THIS WHOLE FILE AND RELEVANT DEPS BEHIND web_server_capability
 use crate::consts::ZELLIJ_PROJ_DIR;
-use rusqlite::Connection;
+use crate::data::WebTokenScope;
+use rusqlite::{params, Connection, OptionalExtension};
 use sha2::{Digest, Sha256};
 use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 #[derive(Debug)]
 pub struct TokenInfo {
     pub name: String,
     pub created_at: String,
+    pub scope: WebTokenScope,
 }
 
 #[derive(Debug)]
@@ -78,10 +80,14 @@ fn init_db(conn: &Connection) -> Result<()> {
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             token_hash TEXT UNIQUE NOT NULL,
             name TEXT UNIQUE NOT NULL,
-            created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            allowed_sessions TEXT NOT NULL DEFAULT '[]',
+            read_only BOOLEAN NOT NULL DEFAULT 0,
+            expires_at DATETIME
         )",
         [],
     )?;
+    migrate_tokens_table(conn)?;
 
     conn.execute(
         "CREATE TABLE IF NOT EXISTS session_tokens (
@@ -99,6 +105,44 @@ fn init_db(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+fn migrate_tokens_table(conn: &Connection) -> Result<()> {
+    // databases created before tokens could be scoped lack these columns
+    let mut stmt = conn.prepare("PRAGMA table_info(tokens)")?;
+    let existing_columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    for (column, definition) in [
+        ("allowed_sessions", "TEXT NOT NULL DEFAULT '[]'"),
+        ("read_only", "BOOLEAN NOT NULL DEFAULT 0"),
+        ("expires_at", "DATETIME"),
+    ] {
+        if !existing_columns.iter().any(|c| c == column) {
+            conn.execute(
+                &format!("ALTER TABLE tokens ADD COLUMN {} {}", column, definition),
+                [],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// selects the columns read by scope_from_row, the last one being the seconds the token has left
+const SCOPE_COLUMNS: &str = "tokens.allowed_sessions, tokens.read_only, \
+    MAX(0, strftime('%s', tokens.expires_at) - strftime('%s', 'now'))";
+const TOKEN_NOT_EXPIRED: &str =
+    "(tokens.expires_at IS NULL OR tokens.expires_at > datetime('now'))";
+
+fn scope_from_row(row: &rusqlite::Row, first_column: usize) -> rusqlite::Result<WebTokenScope> {
+    let allowed_sessions = row.get::<_, String>(first_column)?;
+    Ok(WebTokenScope {
+        allowed_sessions: serde_json::from_str(&allowed_sessions).unwrap_or_default(),
+        read_only: row.get::<_, bool>(first_column + 1)?,
+        expires_in: row
+            .get::<_, Option<i64>>(first_column + 2)?
+            .map(|secs| Duration::from_secs(secs.max(0) as u64)),
+    })
+}
+
 fn hash_token(token: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(token.as_bytes());
@@ -106,10 +150,21 @@ fn hash_token(token: &str) -> String {
 }
 
 pub fn create_token(name: Option<String>) -> Result<(String, String)> {
+    create_scoped_token(name, WebTokenScope::default())
+}
+
+pub fn create_scoped_token(name: Option<String>, scope: WebTokenScope) -> Result<(String, String)> {
     let db_path = get_db_path()?;
     let conn = Connection::open(db_path)?;
     init_db(&conn)?;
+    insert_scoped_token(&conn, name, scope)
+}
 
+fn insert_scoped_token(
+    conn: &Connection,
+    name: Option<String>,
+    scope: WebTokenScope,
+) -> Result<(String, String)> {
     let token = Uuid::new_v4().to_string();
     let token_hash = hash_token(&token);
 
@@ -120,9 +175,25 @@ pub fn create_token(name: Option<String>) -> Result<(String, String)> {
         format!("token_{}", count + 1)
     };
 
+    let allowed_sessions =
+        serde_json::to_string(&scope.allowed_sessions).unwrap_or_else(|_| "[]".to_owned());
+    let expires_at = scope.expires_in.map(|expires_in| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        (now + expires_in.as_secs()) as i64
+    });
+
     match conn.execute(
-        "INSERT INTO tokens (token_hash, name) VALUES (?1, ?2)",
-        [&token_hash, &token_name],
+        "INSERT INTO tokens (token_hash, name, allowed_sessions, read_only, expires_at) VALUES (?1, ?2, ?3, ?4, datetime(?5, 'unixepoch'))",
+        params![
+            &token_hash,
+            &token_name,
+            &allowed_sessions,
+            scope.read_only,
+            expires_at
+        ],
     ) {
         Err(rusqlite::Error::SqliteFailure(ffi_error, _))
             if ffi_error.code == rusqlite::ErrorCode::ConstraintViolation =>
@@ -144,7 +215,10 @@ pub fn create_session_token(auth_token: &str, remember_me: bool) -> Result<Strin
     let auth_token_hash = hash_token(auth_token);
 
     let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1",
+        &format!(
+            "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1 AND {}",
+            TOKEN_NOT_EXPIRED
+        ),
         [&auth_token_hash],
         |row| row.get(0),
     )?;
@@ -191,7 +265,10 @@ pub fn validate_session_token(session_token: &str) -> Result<bool> {
     let session_token_hash = hash_token(session_token);
 
     let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM session_tokens WHERE session_token_hash = ?1 AND expires_at > datetime('now')",
+        &format!(
+            "SELECT COUNT(*) FROM session_tokens JOIN tokens ON tokens.token_hash = session_tokens.auth_token_hash WHERE session_tokens.session_token_hash = ?1 AND session_tokens.expires_at > datetime('now') AND {}",
+            TOKEN_NOT_EXPIRED
+        ),
         [&session_token_hash],
         |row| row.get(0),
     )?;
@@ -199,6 +276,31 @@ pub fn validate_session_token(session_token: &str) -> Result<bool> {
     Ok(count > 0)
 }
 
+pub fn get_session_token_scope(session_token: &str) -> Result<Option<WebTokenScope>> {
+    // returns None if the session token (or the auth token it was created with) is not valid
+    let db_path = get_db_path()?;
+    let conn = Connection::open(db_path)?;
+    init_db(&conn)?;
+    session_token_scope(&conn, session_token)
+}
+
+fn session_token_scope(conn: &Connection, session_token: &str) -> Result<Option<WebTokenScope>> {
+    let session_token_hash = hash_token(session_token);
+
+    let scope = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM session_tokens JOIN tokens ON tokens.token_hash = session_tokens.auth_token_hash WHERE session_tokens.session_token_hash = ?1 AND session_tokens.expires_at > datetime('now') AND {}",
+                SCOPE_COLUMNS, TOKEN_NOT_EXPIRED
+            ),
+            [&session_token_hash],
+            |row| scope_from_row(row, 0),
+        )
+        .optional()?;
+
+    Ok(scope)
+}
+
 pub fn cleanup_expired_sessions() -> Result<usize> {
     let db_path = get_db_path()?;
     let conn = Connection::open(db_path)?;
@@ -310,11 +412,15 @@ pub fn list_tokens() -> Result<Vec<TokenInfo>> {
     let conn = Connection::open(db_path)?;
     init_db(&conn)?;
 
-    let mut stmt = conn.prepare("SELECT name, created_at FROM tokens ORDER BY created_at")?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT name, created_at, {} FROM tokens ORDER BY created_at",
+        SCOPE_COLUMNS
+    ))?;
     let rows = stmt.query_map([], |row| {
         Ok(TokenInfo {
             name: row.get::<_, String>(0)?,
             created_at: row.get::<_, String>(1)?,
+            scope: scope_from_row(row, 2)?,
         })
     })?;
 
@@ -341,9 +447,191 @@ pub fn validate_token(token: &str) -> Result<bool> {
     let token_hash = hash_token(token);
 
     let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1",
+        &format!(
+            "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1 AND {}",
+            TOKEN_NOT_EXPIRED
+        ),
         [&token_hash],
         |row| row.get(0),
     )?;
     Ok(count > 0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        init_db(&conn).unwrap();
+        conn
+    }
+
+    fn insert_session_token(conn: &Connection, auth_token: &str, expires_at: &str) -> String {
+        let session_token = Uuid::new_v4().to_string();
+        conn.execute(
+            &format!(
+                "INSERT INTO session_tokens (session_token_hash, auth_token_hash, expires_at) VALUES (?1, ?2, {})",
+                expires_at
+            ),
+            [hash_token(&session_token), hash_token(auth_token)],
+        )
+        .unwrap();
+        session_token
+    }
+
+    #[test]
+    fn session_token_scope_is_the_scope_of_its_auth_token() {
+        let conn = test_db();
+        let scope = WebTokenScope {
+            allowed_sessions: vec!["session-a".to_owned(), "session-b".to_owned()],
+            read_only: true,
+            expires_in: Some(Duration::from_secs(60 * 60)),
+        };
+        let (auth_token, _name) =
+            insert_scoped_token(&conn, Some("scoped".to_owned()), scope).unwrap();
+        let session_token = insert_session_token(&conn, &auth_token, "datetime('now', '+1 hour')");
+
+        let scope = session_token_scope(&conn, &session_token).unwrap().unwrap();
+        assert_eq!(
+            scope.allowed_sessions,
+            vec!["session-a".to_owned(), "session-b".to_owned()]
+        );
+        assert!(scope.read_only);
+        let expires_in = scope.expires_in.unwrap().as_secs();
+        assert!(
+            expires_in > 60 * 59 && expires_in <= 60 * 60,
+            "token should have about an hour left, has {}s",
+            expires_in
+        );
+    }
+
+    #[test]
+    fn unscoped_token_has_default_scope() {
+        let conn = test_db();
+        let (auth_token, _name) =
+            insert_scoped_token(&conn, None, WebTokenScope::default()).unwrap();
+        let session_token = insert_session_token(&conn, &auth_token, "datetime('now', '+1 hour')");
+        assert_eq!(
+            session_token_scope(&conn, &session_token).unwrap(),
+            Some(WebTokenScope::default())
+        );
+    }
+
+    #[test]
+    fn expired_auth_token_has_no_session_token_scope() {
+        let conn = test_db();
+        let (auth_token, name) =
+            insert_scoped_token(&conn, None, WebTokenScope::default()).unwrap();
+        let session_token = insert_session_token(&conn, &auth_token, "datetime('now', '+1 hour')");
+        conn.execute(
+            "UPDATE tokens SET expires_at = datetime('now', '-1 minute') WHERE name = ?1",
+            [&name],
+        )
+        .unwrap();
+        assert_eq!(session_token_scope(&conn, &session_token).unwrap(), None);
+
+        let count: i64 = conn
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM tokens WHERE token_hash = ?1 AND {}",
+                    TOKEN_NOT_EXPIRED
+                ),
+                [hash_token(&auth_token)],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 0, "expired auth token should not be valid");
+    }
+
+    #[test]
+    fn expired_session_token_has_no_scope() {
+        let conn = test_db();
+        let (auth_token, _name) =
+            insert_scoped_token(&conn, None, WebTokenScope::default()).unwrap();
+        let session_token =
+            insert_session_token(&conn, &auth_token, "datetime('now', '-1 minute')");
+        assert_eq!(session_token_scope(&conn, &session_token).unwrap(), None);
+        assert_eq!(session_token_scope(&conn, "not-a-token").unwrap(), None);
+    }
+
+    #[test]
+    fn scope_of_expired_token_reports_zero_time_left() {
+        let conn = test_db();
+        let (_auth_token, name) =
+            insert_scoped_token(&conn, None, WebTokenScope::default()).unwrap();
+        conn.execute(
+            "UPDATE tokens SET expires_at = datetime('now', '-1 hour') WHERE name = ?1",
+            [&name],
+        )
+        .unwrap();
+        let scope = conn
+            .query_row(
+                &format!("SELECT {} FROM tokens WHERE name = ?1", SCOPE_COLUMNS),
+                [&name],
+                |row| scope_from_row(row, 0),
+            )
+            .unwrap();
+        assert_eq!(scope.expires_in, Some(Duration::ZERO));
+        assert!(scope.is_expired());
+    }
+
+    #[test]
+    fn tokens_table_from_before_scopes_is_migrated() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE tokens (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                token_hash TEXT UNIQUE NOT NULL,
+                name TEXT UNIQUE NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO tokens (token_hash, name) VALUES (?1, 'old_token')",
+            [hash_token("old-token")],
+        )
+        .unwrap();
+
+        init_db(&conn).unwrap();
+        // migrating twice should be a no-op
+        init_db(&conn).unwrap();
+
+        let session_token = insert_session_token(&conn, "old-token", "datetime('now', '+1 hour')");
+        assert_eq!(
+            session_token_scope(&conn, &session_token).unwrap(),
+            Some(WebTokenScope::default()),
+            "tokens created before scopes existed should be unrestricted"
+        );
+        insert_scoped_token(
+            &conn,
+            Some("new_token".to_owned()),
+            WebTokenScope {
+                read_only: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn scope_description_lists_restrictions() {
+        assert_eq!(WebTokenScope::default().description(), None);
+        let scope = WebTokenScope {
+            allowed_sessions: vec!["a".to_owned(), "b".to_owned()],
+            read_only: true,
+            expires_in: Some(Duration::from_secs(2 * 60 * 60 + 30)),
+        };
+        assert_eq!(
+            scope.description(),
+            Some("sessions: a, b, read-only, expires in 2h".to_owned())
+        );
+        let expired = WebTokenScope {
+            expires_in: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        assert_eq!(expired.description(), Some("expired".to_owned()));
+    }
+}